@@ -18,5 +18,5 @@ cp target/wasm32-unknown-unknown/release/graphql-loader.opt.wasm packages/rollup
 
 current_version=$(npm pkg get version --json)
 npm pkg set version=${current_version} --json --workspaces
-npm pkg set dependencies.@nitrogql/core=${current_version} --json -w @nitrogql/cli
+npm pkg set dependencies.@nitrogql/core=${current_version} --json -w @nitrogql/cli -w @nitrogql/graphql-loader -w @nitrogql/rollup-plugin
 npx prettier --write "./**/package.json"
\ No newline at end of file
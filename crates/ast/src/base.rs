@@ -5,12 +5,21 @@ use std::fmt::Display;
 use crate::current_file::get_current_file_of_pos;
 
 /// Position in source file.
+/// Columns count characters (Unicode scalar values), not bytes or UTF-16 code units.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Pos {
     /// 0-based line
     pub line: usize,
     /// 0-base column
     pub column: usize,
+    /// 0-based line of the end of the node
+    pub end_line: usize,
+    /// 0-based column of the end of the node (exclusive)
+    pub end_column: usize,
+    /// Byte offset of the start of the node
+    pub offset: usize,
+    /// Byte offset of the end of the node (exclusive)
+    pub end_offset: usize,
     /// file (specified by index)
     pub file: usize,
     /// Flag that indicates that this Pos is not from parsed document, but is a built-in structure.
@@ -18,11 +27,32 @@ pub struct Pos {
 }
 
 impl Pos {
-    /// Generates a non-built-in Pos.
+    /// Generates a non-built-in Pos that points to a single location.
     pub fn new(line: usize, column: usize) -> Self {
         Pos {
             line,
             column,
+            end_line: line,
+            end_column: column,
+            offset: 0,
+            end_offset: 0,
+            file: get_current_file_of_pos(),
+            builtin: false,
+        }
+    }
+
+    /// Generates a non-built-in Pos that spans from (line, column, offset) to (end_line, end_column, end_offset).
+    pub fn new_span(
+        (line, column, offset): (usize, usize, usize),
+        (end_line, end_column, end_offset): (usize, usize, usize),
+    ) -> Self {
+        Pos {
+            line,
+            column,
+            end_line,
+            end_column,
+            offset,
+            end_offset,
             file: get_current_file_of_pos(),
             builtin: false,
         }
@@ -33,10 +63,24 @@ impl Pos {
         Pos {
             line: 0,
             column: 0,
+            end_line: 0,
+            end_column: 0,
+            offset: 0,
+            end_offset: 0,
             file: 0,
             builtin: true,
         }
     }
+
+    /// Returns a Pos that starts at this Pos and ends at the end of given Pos.
+    pub fn with_end(self, end: Pos) -> Self {
+        Pos {
+            end_line: end.end_line,
+            end_column: end.end_column,
+            end_offset: end.end_offset,
+            ..self
+        }
+    }
 }
 
 impl Default for Pos {
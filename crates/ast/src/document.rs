@@ -0,0 +1,41 @@
+use super::{
+    operation::{ExecutableDefinition, ImportComment, OperationDocument},
+    type_system::{TypeSystemDefinitionOrExtension, TypeSystemOrExtensionDocument},
+};
+
+/// Document that may contain both executable definitions and type system definitions.
+#[derive(Clone, Debug)]
+pub struct Document<'a> {
+    /// Import comments found at the top level of the document.
+    pub imports: Vec<ImportComment<'a>>,
+    pub definitions: Vec<Definition<'a>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Definition<'a> {
+    ExecutableDefinition(ExecutableDefinition<'a>),
+    TypeSystemDefinitionOrExtension(TypeSystemDefinitionOrExtension<'a>),
+}
+
+impl<'a> Document<'a> {
+    /// Splits the document into an operation document and a type system document.
+    /// Definitions keep their relative order in each document.
+    pub fn split(self) -> (OperationDocument<'a>, TypeSystemOrExtensionDocument<'a>) {
+        let mut operations = OperationDocument {
+            imports: self.imports,
+            definitions: vec![],
+        };
+        let mut type_system = TypeSystemOrExtensionDocument {
+            definitions: vec![],
+        };
+        for definition in self.definitions {
+            match definition {
+                Definition::ExecutableDefinition(def) => operations.definitions.push(def),
+                Definition::TypeSystemDefinitionOrExtension(def) => {
+                    type_system.definitions.push(def)
+                }
+            }
+        }
+        (operations, type_system)
+    }
+}
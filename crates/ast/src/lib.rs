@@ -1,6 +1,7 @@
 pub mod base;
 mod current_file;
 pub mod directive;
+pub mod document;
 pub mod operation;
 pub mod selection_set;
 pub mod r#type;
@@ -9,5 +10,6 @@ pub mod value;
 pub mod variable;
 
 pub use current_file::set_current_file_of_pos;
+pub use document::Document;
 pub use operation::OperationDocument;
 pub use type_system::{TypeSystemDocument, TypeSystemOrExtensionDocument};
@@ -48,6 +48,8 @@ impl HasPos for ExecutableDefinition<'_> {
 pub struct OperationDefinition<'a> {
     pub position: Pos,
     pub operation_type: OperationType,
+    /// Whether the operation is written as a bare selection set, which is a shorthand for an anonymous query.
+    pub shorthand: bool,
     pub name: Option<Ident<'a>>,
     pub variables_definition: Option<VariablesDefinition<'a>>,
     pub directives: Vec<Directive<'a>>,
@@ -65,7 +67,7 @@ impl HasPos for OperationDefinition<'_> {
 
 impl OperationDefinition<'_> {
     /// Returns Pos for its name.
-    pub fn name_pos(&self) -> NamePos {
+    pub fn name_pos(&self) -> NamePos<'_> {
         match self.name {
             None => NamePos {
                 pos: *self.position(),
@@ -99,5 +101,24 @@ impl HasPos for FragmentDefinition<'_> {
 
 #[derive(Clone, Debug)]
 pub struct OperationDocument<'a> {
+    /// Import comments found at the top level of the document.
+    pub imports: Vec<ImportComment<'a>>,
     pub definitions: Vec<ExecutableDefinition<'a>>,
 }
+
+/// Comment that imports another document, like `#import "./fragments.graphql"`.
+#[derive(Clone, Debug)]
+pub struct ImportComment<'a> {
+    pub position: Pos,
+    /// Path of the imported document as written in the comment.
+    pub path: &'a str,
+}
+
+impl HasPos for ImportComment<'_> {
+    fn position(&self) -> &Pos {
+        &self.position
+    }
+    fn name(&self) -> Option<&str> {
+        None
+    }
+}
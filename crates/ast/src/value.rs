@@ -1,4 +1,7 @@
-use std::{fmt::Display, ops::Deref};
+use std::{
+    fmt::{Display, Write},
+    ops::Deref,
+};
 
 use crate::variable::Variable;
 
@@ -53,8 +56,7 @@ impl Display for Value<'_> {
             }
             Value::IntValue(i) => write!(f, "{}", i.value),
             Value::FloatValue(i) => write!(f, "{}", i.value),
-            // TODO: escaping not implemented for ease
-            Value::StringValue(i) => write!(f, "\"{}\"", i.value),
+            Value::StringValue(i) => write_quoted_string(f, &i.value),
             Value::EnumValue(i) => write!(f, "{}", i.value),
             Value::NullValue(_) => write!(f, "null"),
             Value::Variable(v) => write!(f, "${}", v.name),
@@ -82,6 +84,25 @@ impl Display for Value<'_> {
     }
 }
 
+/// Writes given string as a quoted string literal with special characters escaped.
+fn write_quoted_string(f: &mut std::fmt::Formatter<'_>, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct IntValue<'a> {
     pub position: Pos,
@@ -1,5 +1,4 @@
 use graphql_type_system::{InputValue, OriginalNodeRef, Schema, Text, Type, TypeDefinition};
-use log::warn;
 
 use nitrogql_ast::{
     base::{HasPos, Pos},
@@ -9,10 +8,14 @@ use nitrogql_ast::{
 };
 use nitrogql_semantics::type_system_utils::convert_type;
 
-use super::error::{CheckError, CheckErrorMessage};
+use super::{
+    custom_scalar::{CustomScalarRules, LiteralKind},
+    error::{CheckError, CheckErrorMessage},
+};
 
 pub fn check_directives<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
+    custom_scalars: &CustomScalarRules,
     variables: Option<&VariablesDefinition<'src>>,
     directives: &[Directive<'src>],
     current_position: &'static str,
@@ -51,6 +54,7 @@ pub fn check_directives<'src, S: Text<'src>>(
 
                 check_arguments(
                     definitions,
+                    custom_scalars,
                     variables,
                     d.position,
                     d.name.name,
@@ -67,6 +71,7 @@ pub fn check_directives<'src, S: Text<'src>>(
 #[allow(clippy::too_many_arguments)]
 pub fn check_arguments<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
+    custom_scalars: &CustomScalarRules,
     variables: Option<&VariablesDefinition<'src>>,
     parent_pos: Pos,
     parent_name: &str,
@@ -125,8 +130,31 @@ pub fn check_arguments<'src, S: Text<'src>>(
                             )
                         }
                     }
-                    Some((_, arg_value)) => {
-                        check_value(definitions, variables, arg_value, &arg_def.r#type, result);
+                    Some((arg_name, arg_value)) => {
+                        if let Some(ref reason) = arg_def.deprecation {
+                            result.push(
+                                CheckErrorMessage::DeprecatedArgument {
+                                    name: arg_name.to_string(),
+                                    reason: reason.to_string(),
+                                }
+                                .with_pos(arg_name.position)
+                                .with_additional_info(vec![(
+                                    *arg_def.name.original_node_ref(),
+                                    CheckErrorMessage::DefinitionPos {
+                                        name: arg_def.name.to_string(),
+                                    },
+                                )]),
+                            );
+                        }
+                        check_value(
+                            definitions,
+                            custom_scalars,
+                            variables,
+                            arg_value,
+                            &arg_def.r#type,
+                            arg_def.default_value.is_some(),
+                            result,
+                        );
                         seen_args += 1;
                     }
                 }
@@ -151,62 +179,92 @@ pub fn check_arguments<'src, S: Text<'src>>(
     }
 }
 
+/// Checks given value against expected type.
+/// `has_location_default_value` should be true if the location (argument or input field) has a default value.
 pub fn check_value<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
+    custom_scalars: &CustomScalarRules,
     variables: Option<&VariablesDefinition<'src>>,
     value: &Value<'src>,
     expected_type: &Type<S, Pos>,
+    has_location_default_value: bool,
     result: &mut Vec<CheckError>,
 ) {
+    if let Value::Variable(variable) = value {
+        let Some(v_def) = get_variable_definition(variables, variable) else {
+            // Undefined variables are reported by the operation checker
+            return;
+        };
+        check_variable_usage(
+            v_def,
+            variable,
+            expected_type,
+            has_location_default_value,
+            result,
+        );
+        return;
+    }
     let mut additional_info = vec![];
-    let is_mismatch = 'b: {
-        if let Value::Variable(variable) = value {
-            let Some(v_def) = get_variable_definition(variables, variable) else {
-                result.push(
-                    CheckErrorMessage::UnknownVariable { name: variable.name.to_owned() }
-                    .with_pos(*value.position())
+    let is_mismatch = match expected_type {
+        Type::NonNull(inner) => match value {
+            Value::NullValue(_) => true,
+            Value::Variable(_) => unreachable!(),
+            value => {
+                check_value(
+                    definitions,
+                    custom_scalars,
+                    variables,
+                    value,
+                    inner,
+                    false,
+                    result,
                 );
-                return;
-            };
-            break 'b !check_type_compatibility(&convert_type(&v_def.r#type), expected_type);
-        }
-        match expected_type {
-            Type::NonNull(inner) => match value {
-                Value::NullValue(_) => true,
-                Value::Variable(_) => unreachable!(),
-                value => {
-                    check_value(definitions, variables, value, inner, result);
-                    false
-                }
-            },
-            Type::List(expected_inner) => match value {
-                Value::ListValue(inner) => {
-                    for elem in inner.values.iter() {
-                        check_value(definitions, variables, elem, expected_inner, result);
-                    }
-                    false
+                false
+            }
+        },
+        Type::List(expected_inner) => match value {
+            Value::ListValue(inner) => {
+                for elem in inner.values.iter() {
+                    check_value(
+                        definitions,
+                        custom_scalars,
+                        variables,
+                        elem,
+                        expected_inner,
+                        false,
+                        result,
+                    );
                 }
-                Value::Variable(_) => unreachable!(),
-                _ => true,
-            },
-            Type::Named(expected_name) => {
-                let Some(type_def) = definitions.get_type(expected_name) else {
-                    // unknown type name
-                    result.push(
-                        CheckErrorMessage::TypeSystemError
+                false
+            }
+            Value::Variable(_) => unreachable!(),
+            _ => true,
+        },
+        Type::Named(expected_name) => {
+            let Some(type_def) = definitions.get_type(expected_name) else {
+                // unknown type name
+                result.push(
+                    CheckErrorMessage::TypeSystemError
                         .with_pos(*expected_name.original_node_ref())
                         .with_additional_info(vec![(
                             *expected_name.original_node_ref(),
-                            CheckErrorMessage::UnknownType { name: expected_name.to_string() }
-                        )])
-                    );
-                    return;
-                };
-                let (is_compatible, a) =
-                    is_value_compatible_type_def(definitions, variables, value, type_def, result);
-                additional_info.extend(a);
-                !is_compatible
-            }
+                            CheckErrorMessage::UnknownType {
+                                name: expected_name.to_string(),
+                            },
+                        )]),
+                );
+                return;
+            };
+            let (is_compatible, a) = is_value_compatible_type_def(
+                definitions,
+                custom_scalars,
+                variables,
+                value,
+                type_def,
+                result,
+            );
+            additional_info.extend(a);
+            !is_compatible
         }
     };
     if is_mismatch {
@@ -223,32 +281,17 @@ pub fn check_value<'src, S: Text<'src>>(
 // Note: this function does not consider Value::Variable
 fn is_value_compatible_type_def<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
+    custom_scalars: &CustomScalarRules,
     variables: Option<&VariablesDefinition<'src>>,
     value: &Value<'src>,
     expected_type: &TypeDefinition<S, Pos>,
     result: &mut Vec<CheckError>,
 ) -> (bool, Vec<(Pos, CheckErrorMessage)>) {
     match expected_type {
-        TypeDefinition::Scalar(scalar_def) => {
-            // TODO: better handling of scalar, including custom scalars
-            (
-                match scalar_def.name.as_ref() {
-                    "Boolean" => matches!(value, Value::BooleanValue(_) | Value::NullValue(_)),
-                    "Int" => matches!(value, Value::IntValue(_) | Value::NullValue(_)),
-                    "Float" => matches!(value, Value::FloatValue(_) | Value::NullValue(_)),
-                    "String" => matches!(value, Value::StringValue(_) | Value::NullValue(_)),
-                    "ID" => matches!(value, Value::StringValue(_) | Value::NullValue(_)),
-                    custom_scalar => {
-                        warn!(
-                            "Not checking value compatibility for custom scalar '{}'",
-                            custom_scalar
-                        );
-                        true
-                    }
-                },
-                vec![],
-            )
-        }
+        TypeDefinition::Scalar(scalar_def) => (
+            is_value_compatible_scalar(custom_scalars, value, scalar_def.name.as_ref(), result),
+            vec![],
+        ),
         TypeDefinition::Object(_) | TypeDefinition::Interface(_) | TypeDefinition::Union(_) => {
             // These are never inputs
             (false, vec![])
@@ -257,7 +300,8 @@ fn is_value_compatible_type_def<'src, S: Text<'src>>(
             Value::NullValue(_) => (true, vec![]),
             Value::EnumValue(value) => {
                 let enum_name = value.value;
-                if enum_def.members.iter().all(|v| v.name != enum_name) {
+                let member = enum_def.members.iter().find(|v| v.name == enum_name);
+                let Some(member) = member else {
                     result.push(
                         CheckErrorMessage::UnknownEnumMember {
                             member: enum_name.to_owned(),
@@ -271,6 +315,23 @@ fn is_value_compatible_type_def<'src, S: Text<'src>>(
                             },
                         )]),
                     );
+                    return (true, vec![]);
+                };
+                if let Some(ref reason) = member.deprecation {
+                    result.push(
+                        CheckErrorMessage::DeprecatedEnumValue {
+                            member: enum_name.to_owned(),
+                            r#enum: enum_def.name.to_string(),
+                            reason: reason.to_string(),
+                        }
+                        .with_pos(value.position)
+                        .with_additional_info(vec![(
+                            *member.name.original_node_ref(),
+                            CheckErrorMessage::DefinitionPos {
+                                name: enum_name.to_owned(),
+                            },
+                        )]),
+                    );
                 }
                 (true, vec![])
             }
@@ -311,9 +372,11 @@ fn is_value_compatible_type_def<'src, S: Text<'src>>(
                     Some((_, value)) => {
                         check_value(
                             definitions,
+                            custom_scalars,
                             variables,
                             value,
                             &expected_field.r#type,
+                            expected_field.default_value.is_some(),
                             result,
                         );
                         seen_fields += 1;
@@ -340,6 +403,118 @@ fn is_value_compatible_type_def<'src, S: Text<'src>>(
     }
 }
 
+/// Checks a value given to a scalar type.
+/// Built-in scalars follow input coercion rules of the spec.
+/// Custom scalars are checked only if they have a rule.
+// Note: this function does not consider Value::Variable
+fn is_value_compatible_scalar(
+    custom_scalars: &CustomScalarRules,
+    value: &Value,
+    scalar_name: &str,
+    result: &mut Vec<CheckError>,
+) -> bool {
+    if matches!(value, Value::NullValue(_)) {
+        return true;
+    }
+    match scalar_name {
+        "Boolean" => matches!(value, Value::BooleanValue(_)),
+        "Int" => match value {
+            Value::IntValue(int) => {
+                if int.value.parse::<i32>().is_err() {
+                    result.push(
+                        CheckErrorMessage::ValueOutOfRange {
+                            value: int.value.to_owned(),
+                            r#type: "Int",
+                        }
+                        .with_pos(int.position),
+                    );
+                }
+                true
+            }
+            _ => false,
+        },
+        "Float" => {
+            // Int literals are coerced to Float.
+            let (number, position) = match value {
+                Value::IntValue(int) => (int.value, int.position),
+                Value::FloatValue(float) => (float.value, float.position),
+                _ => return false,
+            };
+            if !number.parse::<f64>().is_ok_and(f64::is_finite) {
+                result.push(
+                    CheckErrorMessage::ValueOutOfRange {
+                        value: number.to_owned(),
+                        r#type: "Float",
+                    }
+                    .with_pos(position),
+                );
+            }
+            true
+        }
+        "String" => matches!(value, Value::StringValue(_)),
+        "ID" => matches!(value, Value::StringValue(_) | Value::IntValue(_)),
+        custom_scalar => {
+            let Some(rule) = custom_scalars.get(custom_scalar) else {
+                return true;
+            };
+            if !LiteralKind::of(value).is_some_and(|kind| rule.accepts(kind)) {
+                return false;
+            }
+            if let Value::StringValue(string) = value {
+                if let Some(pattern) = rule.mismatched_pattern(&string.value) {
+                    result.push(
+                        CheckErrorMessage::ScalarPatternMismatch {
+                            scalar: custom_scalar.to_owned(),
+                            pattern: pattern.to_owned(),
+                        }
+                        .with_pos(string.position),
+                    );
+                }
+            }
+            true
+        }
+    }
+}
+
+/// Checks whether a variable is used in an allowed position.
+fn check_variable_usage<'src, S: Text<'src>>(
+    variable_definition: &VariableDefinition<'src>,
+    variable: &Variable<'src>,
+    expected_type: &Type<S, Pos>,
+    has_location_default_value: bool,
+    result: &mut Vec<CheckError>,
+) {
+    // https://spec.graphql.org/October2021/#IsVariableUsageAllowed()
+    let variable_type = convert_type(&variable_definition.r#type);
+    let is_allowed = match expected_type {
+        Type::NonNull(nullable_expected_type) if !variable_type.is_nonnull() => {
+            let has_non_null_variable_default_value = variable_definition
+                .default_value
+                .as_ref()
+                .is_some_and(|value| !matches!(value, Value::NullValue(_)));
+            (has_non_null_variable_default_value || has_location_default_value)
+                && check_type_compatibility(&variable_type, nullable_expected_type)
+        }
+        _ => check_type_compatibility(&variable_type, expected_type),
+    };
+    if !is_allowed {
+        result.push(
+            CheckErrorMessage::VariableTypeMismatch {
+                name: variable.name.to_owned(),
+                variable_type: variable_type.to_string(),
+                expected_type: expected_type.to_string(),
+            }
+            .with_pos(variable.position)
+            .with_additional_info(vec![(
+                variable_definition.pos,
+                CheckErrorMessage::DefinitionPos {
+                    name: format!("${}", variable.name),
+                },
+            )]),
+        );
+    }
+}
+
 /// Returns true if `value_type` is assignable to `expected_type`.
 fn check_type_compatibility<'src, S: Text<'src>>(
     value_type: &Type<S, Pos>,
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use nitrogql_ast::value::Value;
+
+/// Kind of a literal value in GraphQL documents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LiteralKind {
+    Int,
+    Float,
+    String,
+    Boolean,
+    Enum,
+    List,
+    Object,
+}
+
+impl LiteralKind {
+    /// Returns the kind of given value.
+    /// Returns None for variables and null, which are not literals of any kind.
+    pub fn of(value: &Value) -> Option<LiteralKind> {
+        match value {
+            Value::Variable(_) | Value::NullValue(_) => None,
+            Value::IntValue(_) => Some(LiteralKind::Int),
+            Value::FloatValue(_) => Some(LiteralKind::Float),
+            Value::StringValue(_) => Some(LiteralKind::String),
+            Value::BooleanValue(_) => Some(LiteralKind::Boolean),
+            Value::EnumValue(_) => Some(LiteralKind::Enum),
+            Value::ListValue(_) => Some(LiteralKind::List),
+            Value::ObjectValue(_) => Some(LiteralKind::Object),
+        }
+    }
+}
+
+/// Literals accepted by a custom scalar.
+#[derive(Debug, Clone)]
+pub struct CustomScalarRule {
+    literals: Vec<LiteralKind>,
+    pattern: Option<(String, Regex)>,
+}
+
+impl CustomScalarRule {
+    /// Creates a rule that accepts given kinds of literals.
+    /// If `pattern` is given, string literals must match it as a whole.
+    pub fn new(literals: Vec<LiteralKind>, pattern: Option<&str>) -> Result<Self, regex::Error> {
+        let pattern = pattern
+            .map(|pattern| {
+                Regex::new(&format!("^(?:{pattern})$")).map(|regex| (pattern.to_owned(), regex))
+            })
+            .transpose()?;
+        Ok(CustomScalarRule { literals, pattern })
+    }
+
+    pub fn accepts(&self, kind: LiteralKind) -> bool {
+        self.literals.contains(&kind)
+    }
+
+    /// Returns the pattern if given string does not match it.
+    pub fn mismatched_pattern(&self, value: &str) -> Option<&str> {
+        self.pattern
+            .as_ref()
+            .filter(|(_, regex)| !regex.is_match(value))
+            .map(|(pattern, _)| pattern.as_str())
+    }
+}
+
+/// Rules of custom scalars, keyed by scalar name.
+/// Values given to scalars without a rule are not checked.
+pub type CustomScalarRules = BTreeMap<String, CustomScalarRule>;
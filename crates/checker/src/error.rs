@@ -13,6 +13,9 @@ pub struct CheckError {
 }
 
 impl CheckError {
+    pub fn severity(&self) -> Severity {
+        self.message.severity()
+    }
     pub fn with_additional_info(
         mut self,
         infos: impl IntoIterator<Item = (Pos, CheckErrorMessage)>,
@@ -37,6 +40,10 @@ pub enum CheckErrorMessage {
     RequiredArgumentNotSpecified { name: String },
     #[error("This value does not match expected type '{r#type}' ")]
     TypeMismatch { r#type: String },
+    #[error("Value {value} cannot be represented as type '{r#type}'")]
+    ValueOutOfRange { value: String, r#type: &'static str },
+    #[error("This value does not match the pattern '{pattern}' of scalar '{scalar}'")]
+    ScalarPatternMismatch { scalar: String, pattern: String },
     #[error("Variable '${name}' is not defined")]
     UnknownVariable { name: String },
     #[error("'{member}' is a member of enum {r#enum}")]
@@ -120,6 +127,47 @@ pub enum CheckErrorMessage {
     RecursingFragmentSpread { name: String },
     #[error("Subscription operation must have exactly one root field")]
     SubscriptionMustHaveExactlyOneRootField,
+    #[error("Variable '${name}' of type '{variable_type}' cannot be used where '{expected_type}' is expected")]
+    VariableTypeMismatch {
+        name: String,
+        variable_type: String,
+        expected_type: String,
+    },
+    #[error("Variable '${name}' is never used")]
+    UnusedVariable { name: String },
+    #[error("Fragment '{name}' is never used")]
+    UnusedFragment { name: String },
+    #[error(
+        "Fields '{response_key}' conflict because '{name}' and '{other_name}' are different fields"
+    )]
+    FieldNameConflict {
+        response_key: String,
+        name: String,
+        other_name: String,
+    },
+    #[error("Fields '{response_key}' conflict because they have different arguments")]
+    FieldArgumentsConflict { response_key: String },
+    #[error("Fields '{response_key}' conflict because they return conflicting types '{r#type}' and '{other_type}'")]
+    FieldTypeConflict {
+        response_key: String,
+        r#type: String,
+        other_type: String,
+    },
+    // warnings for operation
+    #[error("Field '{field_name}' of type '{type_name}' is deprecated: {reason}")]
+    DeprecatedField {
+        field_name: String,
+        type_name: String,
+        reason: String,
+    },
+    #[error("Argument '{name}' is deprecated: {reason}")]
+    DeprecatedArgument { name: String, reason: String },
+    #[error("Enum value '{member}' of enum {r#enum} is deprecated: {reason}")]
+    DeprecatedEnumValue {
+        member: String,
+        r#enum: String,
+        reason: String,
+    },
     // Error that should be checked in type system check phase
     #[error("Type system error. This is a bug of checker")]
     TypeSystemError,
@@ -130,6 +178,10 @@ pub enum CheckErrorMessage {
     DefinitionPos { name: String },
     #[error("Root types are defined here")]
     RootTypesAreDefinedHere,
+    #[error("This operation does not define variable '${name}'")]
+    VariableNotDefinedInOperation { name: String },
+    #[error("Conflicting selection of '{response_key}'")]
+    ConflictingFieldPos { response_key: String },
 }
 
 impl CheckErrorMessage {
@@ -140,6 +192,37 @@ impl CheckErrorMessage {
             additional_info: vec![],
         }
     }
+    pub fn severity(&self) -> Severity {
+        match self {
+            CheckErrorMessage::DeprecatedField { .. }
+            | CheckErrorMessage::DeprecatedArgument { .. }
+            | CheckErrorMessage::DeprecatedEnumValue { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Severity of a check result.
+/// Warnings are reported but do not make the check fail.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl From<CheckError> for PositionedError {
@@ -1,9 +1,17 @@
 mod common;
+mod custom_scalar;
 mod error;
+mod lint_error;
 mod operation_checker;
+mod operation_linter;
 mod type_system_checker;
+mod type_system_linter;
 mod types;
 
-pub use error::{CheckError, CheckErrorMessage};
-pub use operation_checker::check_operation_document;
+pub use custom_scalar::{CustomScalarRule, CustomScalarRules, LiteralKind};
+pub use error::{CheckError, CheckErrorMessage, Severity};
+pub use lint_error::{LintError, LintErrorMessage, LintRuleSetting};
+pub use operation_checker::{check_operation_document, check_operation_document_with_fragments};
+pub use operation_linter::{lint_operation_document, OperationLintRule};
 pub use type_system_checker::check_type_system_document;
+pub use type_system_linter::{lint_type_system_document, TypeSystemLintRule};
@@ -0,0 +1,77 @@
+use thiserror::Error;
+
+use nitrogql_ast::{base::Pos, operation::OperationType};
+use nitrogql_error::PositionedError;
+
+use crate::Severity;
+
+/// A lint rule with the severity it is reported with.
+#[derive(Debug, Clone)]
+pub struct LintRuleSetting<Rule> {
+    pub rule: Rule,
+    pub severity: Severity,
+}
+
+/// Problem reported by a lint rule.
+#[derive(Debug)]
+pub struct LintError {
+    /// Id of the rule that reported this.
+    pub rule_id: &'static str,
+    pub severity: Severity,
+    pub position: Pos,
+    pub message: LintErrorMessage,
+}
+
+#[derive(Error, Debug)]
+pub enum LintErrorMessage {
+    // operation rules
+    #[error("{} must be named", operation_type.as_str())]
+    UnnamedOperation { operation_type: OperationType },
+    #[error("Name of {} '{name}' must end with '{suffix}'", operation_type.as_str())]
+    OperationNameSuffix {
+        operation_type: OperationType,
+        name: String,
+        suffix: &'static str,
+    },
+    #[error("Argument '{name}' of a mutation must be passed as a variable")]
+    MutationLiteralArgument { name: String },
+    #[error("Selection depth must not exceed {max_depth}")]
+    SelectionTooDeep { max_depth: usize },
+    #[error("Field '{field_name}' of type '{type_name}' must be selected")]
+    IdFieldNotSelected {
+        field_name: String,
+        type_name: String,
+    },
+    // schema rules
+    #[error("{kind} '{name}' must have a description")]
+    MissingDescription { kind: &'static str, name: String },
+    #[error("Value '{name}' of enum '{enum_name}' must be UPPER_CASE")]
+    EnumValueNotUpperCase { name: String, enum_name: String },
+    #[error("Name of input type '{name}' must end with 'Input'")]
+    InputTypeSuffix { name: String },
+    #[error("Nullable list type '{r#type}' must not have nullable items")]
+    NullableListOfNullableItems { r#type: String },
+    #[error("Deprecation of '{name}' must have a reason")]
+    DeprecationWithoutReason { name: String },
+    #[error("Type '{type_name}' must have field '{field_name}'")]
+    RelayFieldMissing {
+        type_name: String,
+        field_name: &'static str,
+    },
+    #[error("Field '{field_name}' of type '{type_name}' must be {expected}")]
+    RelayFieldInvalidType {
+        type_name: String,
+        field_name: &'static str,
+        expected: &'static str,
+    },
+}
+
+impl From<LintError> for PositionedError {
+    fn from(value: LintError) -> Self {
+        PositionedError::new(
+            anyhow::anyhow!("{} ({})", value.message, value.rule_id),
+            Some(value.position),
+            vec![],
+        )
+    }
+}
@@ -1,16 +1,15 @@
 use nitrogql_ast::selection_set::{Selection, SelectionSet};
-
-use super::fragment_map::FragmentMap;
+use nitrogql_semantics::FragmentRegistry;
 
 pub fn selection_set_has_more_than_one_fields(
-    fragment_map: &FragmentMap,
+    fragment_registry: &FragmentRegistry,
     selection_set: &SelectionSet,
 ) -> bool {
-    selection_set_has_more_than_one_fields_impl(fragment_map, selection_set, &[]) > 1
+    selection_set_has_more_than_one_fields_impl(fragment_registry, selection_set, &[]) > 1
 }
 
 fn selection_set_has_more_than_one_fields_impl(
-    fragment_map: &FragmentMap,
+    fragment_registry: &FragmentRegistry,
     selection_set: &SelectionSet,
     seen_fragments: &[&str],
 ) -> usize {
@@ -25,7 +24,7 @@ fn selection_set_has_more_than_one_fields_impl(
                     // prevent infinite recursions
                     continue;
                 }
-                let fragment_def = fragment_map.get(fragment_spread.fragment_name.name);
+                let fragment_def = fragment_registry.get(fragment_spread.fragment_name.name);
                 match fragment_def {
                     None => {
                         // This should be handled elsewhere
@@ -38,7 +37,7 @@ fn selection_set_has_more_than_one_fields_impl(
                             .chain(vec![fragment_spread.fragment_name.name])
                             .collect();
                         count += selection_set_has_more_than_one_fields_impl(
-                            fragment_map,
+                            fragment_registry,
                             &f.selection_set,
                             &seen_fragments,
                         );
@@ -47,7 +46,7 @@ fn selection_set_has_more_than_one_fields_impl(
             }
             Selection::InlineFragment(inline_fragment) => {
                 count += selection_set_has_more_than_one_fields_impl(
-                    fragment_map,
+                    fragment_registry,
                     &inline_fragment.selection_set,
                     seen_fragments,
                 );
@@ -0,0 +1,358 @@
+//! Implementation of the "Field Selection Merging" validation rule.
+//! https://spec.graphql.org/October2021/#sec-Field-Selection-Merging
+
+use std::{borrow::Cow, collections::HashSet};
+
+use graphql_type_system::{Field, Node, Schema, Text, Type, TypeDefinition};
+use nitrogql_ast::{
+    base::{HasPos, Pos},
+    selection_set::{Field as SelectionField, Selection, SelectionSet},
+    value::{Arguments, Value},
+};
+use nitrogql_semantics::{direct_fields_of_output_type, FragmentRegistry};
+
+use crate::error::{CheckError, CheckErrorMessage};
+
+/// Checker of field selection merging.
+/// One checker should be used for the whole document so that
+/// the same conflict found via different selection sets is reported only once.
+pub struct FieldsCanMergeChecker<'a, 'src, S> {
+    definitions: &'a Schema<S, Pos>,
+    fragment_registry: &'a FragmentRegistry<'a, 'src>,
+    reported_pairs: HashSet<(Pos, Pos)>,
+}
+
+/// A field collected from a selection set, including those from fragments.
+struct CollectedField<'a, 'src, S: Clone> {
+    /// Type in which this field is selected.
+    parent_type: &'a Node<TypeDefinition<S, Pos>, Pos>,
+    field: &'a SelectionField<'src>,
+    /// Definition of this field. None if field is not found in schema.
+    definition: Option<Cow<'a, Field<S, Pos>>>,
+}
+
+impl<'src, S: Clone> CollectedField<'_, 'src, S> {
+    fn response_key(&self) -> &'src str {
+        self.field.alias.unwrap_or(self.field.name).name
+    }
+    fn position(&self) -> Pos {
+        *self
+            .field
+            .alias
+            .as_ref()
+            .unwrap_or(&self.field.name)
+            .position()
+    }
+}
+
+impl<'a, 'src, S: Text<'src>> FieldsCanMergeChecker<'a, 'src, S> {
+    pub fn new(
+        definitions: &'a Schema<S, Pos>,
+        fragment_registry: &'a FragmentRegistry<'a, 'src>,
+    ) -> Self {
+        FieldsCanMergeChecker {
+            definitions,
+            fragment_registry,
+            reported_pairs: HashSet::new(),
+        }
+    }
+
+    /// Checks given selection set and all selection sets nested in it.
+    /// Fragment spreads are not followed as fragment definitions are checked separately.
+    pub fn check_selection_set(
+        &mut self,
+        parent_type: &'a Node<TypeDefinition<S, Pos>, Pos>,
+        selection_set: &'a SelectionSet<'src>,
+        result: &mut Vec<CheckError>,
+    ) {
+        let fields = self.collect_fields(parent_type, selection_set);
+        for (idx, field1) in fields.iter().enumerate() {
+            for field2 in fields.iter().skip(idx + 1) {
+                if field1.response_key() == field2.response_key() {
+                    self.find_conflict(field1, field2, false, result);
+                }
+            }
+        }
+
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    let Some(ref selection_set) = field.selection_set else {
+                        continue;
+                    };
+                    let Some(field_type) = self.field_type(parent_type, field) else {
+                        continue;
+                    };
+                    self.check_selection_set(field_type, selection_set, result);
+                }
+                Selection::FragmentSpread(_) => {}
+                Selection::InlineFragment(inline_fragment) => {
+                    let parent_type = match inline_fragment.type_condition {
+                        None => Some(parent_type),
+                        Some(ref type_cond) => self.definitions.get_type(type_cond.name),
+                    };
+                    if let Some(parent_type) = parent_type {
+                        self.check_selection_set(
+                            parent_type,
+                            &inline_fragment.selection_set,
+                            result,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks whether two fields with the same response key can be merged.
+    fn find_conflict(
+        &mut self,
+        field1: &CollectedField<'a, 'src, S>,
+        field2: &CollectedField<'a, 'src, S>,
+        parents_mutually_exclusive: bool,
+        result: &mut Vec<CheckError>,
+    ) {
+        if std::ptr::eq(field1.field, field2.field) {
+            // Same field reached via the same fragment twice
+            return;
+        }
+        let (Some(definition1), Some(definition2)) = (&field1.definition, &field2.definition)
+        else {
+            // This should be checked elsewhere
+            return;
+        };
+        let pos1 = field1.position();
+        let pos2 = field2.position();
+        if self.reported_pairs.contains(&(pos1, pos2))
+            || self.reported_pairs.contains(&(pos2, pos1))
+        {
+            return;
+        }
+        let response_key = field1.response_key();
+        // When parent types are different object types, these fields are never selected at the same time.
+        let mutually_exclusive = parents_mutually_exclusive
+            || (field1.parent_type.name() != field2.parent_type.name()
+                && field1.parent_type.as_object().is_some()
+                && field2.parent_type.as_object().is_some());
+
+        let conflict = if !mutually_exclusive && field1.field.name.name != field2.field.name.name {
+            Some(CheckErrorMessage::FieldNameConflict {
+                response_key: response_key.to_owned(),
+                name: field1.field.name.to_string(),
+                other_name: field2.field.name.to_string(),
+            })
+        } else if !mutually_exclusive
+            && !arguments_are_identical(
+                field1.field.arguments.as_ref(),
+                field2.field.arguments.as_ref(),
+            )
+        {
+            Some(CheckErrorMessage::FieldArgumentsConflict {
+                response_key: response_key.to_owned(),
+            })
+        } else if self.types_conflict(&definition1.r#type, &definition2.r#type) {
+            Some(CheckErrorMessage::FieldTypeConflict {
+                response_key: response_key.to_owned(),
+                r#type: definition1.r#type.to_string(),
+                other_type: definition2.r#type.to_string(),
+            })
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            self.reported_pairs.insert((pos1, pos2));
+            result.push(conflict.with_pos(pos1).with_additional_info(vec![(
+                pos2,
+                CheckErrorMessage::ConflictingFieldPos {
+                    response_key: response_key.to_owned(),
+                },
+            )]));
+            return;
+        }
+
+        // Check subfields of both fields as if they were merged
+        let (Some(selection_set1), Some(selection_set2)) =
+            (&field1.field.selection_set, &field2.field.selection_set)
+        else {
+            return;
+        };
+        let (Some(type1), Some(type2)) = (
+            self.definitions
+                .get_type(definition1.r#type.unwrapped().inner_ref()),
+            self.definitions
+                .get_type(definition2.r#type.unwrapped().inner_ref()),
+        ) else {
+            return;
+        };
+        let subfields1 = self.collect_fields(type1, selection_set1);
+        let subfields2 = self.collect_fields(type2, selection_set2);
+        for subfield1 in subfields1.iter() {
+            for subfield2 in subfields2.iter() {
+                if subfield1.response_key() == subfield2.response_key() {
+                    self.find_conflict(subfield1, subfield2, mutually_exclusive, result);
+                }
+            }
+        }
+    }
+
+    /// Checks whether given types have different response shapes.
+    /// Composite types are not compared here; their subfields are compared separately.
+    fn types_conflict(&self, type1: &Type<S, Pos>, type2: &Type<S, Pos>) -> bool {
+        match (type1, type2) {
+            (Type::NonNull(inner1), Type::NonNull(inner2)) => {
+                self.types_conflict(inner1.as_inner(), inner2.as_inner())
+            }
+            (Type::NonNull(_), _) | (_, Type::NonNull(_)) => true,
+            (Type::List(inner1), Type::List(inner2)) => {
+                self.types_conflict(inner1.as_inner(), inner2.as_inner())
+            }
+            (Type::List(_), _) | (_, Type::List(_)) => true,
+            (Type::Named(name1), Type::Named(name2)) => {
+                let is_leaf_type = |name: &str| {
+                    self.definitions.get_type(name).is_some_and(|def| {
+                        matches!(**def, TypeDefinition::Scalar(_) | TypeDefinition::Enum(_))
+                    })
+                };
+                (is_leaf_type(name1.inner_ref()) || is_leaf_type(name2.inner_ref()))
+                    && name1.inner_ref() != name2.inner_ref()
+            }
+        }
+    }
+
+    /// Collects fields in given selection set, expanding fragment spreads and inline fragments.
+    fn collect_fields(
+        &self,
+        parent_type: &'a Node<TypeDefinition<S, Pos>, Pos>,
+        selection_set: &'a SelectionSet<'src>,
+    ) -> Vec<CollectedField<'a, 'src, S>> {
+        let mut fields = vec![];
+        self.collect_fields_impl(parent_type, selection_set, &[], &mut fields);
+        fields
+    }
+
+    fn collect_fields_impl(
+        &self,
+        parent_type: &'a Node<TypeDefinition<S, Pos>, Pos>,
+        selection_set: &'a SelectionSet<'src>,
+        seen_fragments: &[&str],
+        fields: &mut Vec<CollectedField<'a, 'src, S>>,
+    ) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    let definition = direct_fields_of_output_type(parent_type).and_then(|fields| {
+                        fields
+                            .into_iter()
+                            .find(|def| def.name.inner_ref() == &field.name.name)
+                    });
+                    fields.push(CollectedField {
+                        parent_type,
+                        field,
+                        definition,
+                    });
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if seen_fragments.contains(&fragment_spread.fragment_name.name) {
+                        // prevent infinite recursions
+                        continue;
+                    }
+                    let Some(fragment) = self
+                        .fragment_registry
+                        .get(fragment_spread.fragment_name.name)
+                    else {
+                        // This should be handled elsewhere
+                        continue;
+                    };
+                    let Some(fragment_condition) =
+                        self.definitions.get_type(fragment.type_condition.name)
+                    else {
+                        continue;
+                    };
+                    let seen_fragments: Vec<_> = seen_fragments
+                        .iter()
+                        .copied()
+                        .chain(vec![fragment_spread.fragment_name.name])
+                        .collect();
+                    self.collect_fields_impl(
+                        fragment_condition,
+                        &fragment.selection_set,
+                        &seen_fragments,
+                        fields,
+                    );
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    let parent_type = match inline_fragment.type_condition {
+                        None => parent_type,
+                        Some(ref type_cond) => {
+                            let Some(type_cond) = self.definitions.get_type(type_cond.name) else {
+                                continue;
+                            };
+                            type_cond
+                        }
+                    };
+                    self.collect_fields_impl(
+                        parent_type,
+                        &inline_fragment.selection_set,
+                        seen_fragments,
+                        fields,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the type of given field selected in parent_type.
+    fn field_type(
+        &self,
+        parent_type: &'a Node<TypeDefinition<S, Pos>, Pos>,
+        field: &SelectionField<'src>,
+    ) -> Option<&'a Node<TypeDefinition<S, Pos>, Pos>> {
+        let fields = direct_fields_of_output_type(parent_type)?;
+        let definition = fields
+            .iter()
+            .find(|def| def.name.inner_ref() == &field.name.name)?;
+        self.definitions
+            .get_type(definition.r#type.unwrapped().inner_ref())
+    }
+}
+
+/// Checks whether two sets of arguments are identical.
+fn arguments_are_identical(arguments1: Option<&Arguments>, arguments2: Option<&Arguments>) -> bool {
+    let arguments1 = arguments1.map_or(&[][..], |args| &args.arguments);
+    let arguments2 = arguments2.map_or(&[][..], |args| &args.arguments);
+    arguments1.len() == arguments2.len()
+        && arguments1.iter().all(|(name1, value1)| {
+            arguments2.iter().any(|(name2, value2)| {
+                name1.name == name2.name && values_are_identical(value1, value2)
+            })
+        })
+}
+
+/// Checks whether two values are identical, ignoring their positions.
+fn values_are_identical(value1: &Value, value2: &Value) -> bool {
+    match (value1, value2) {
+        (Value::Variable(v1), Value::Variable(v2)) => v1.name == v2.name,
+        (Value::IntValue(v1), Value::IntValue(v2)) => v1.value == v2.value,
+        (Value::FloatValue(v1), Value::FloatValue(v2)) => v1.value == v2.value,
+        (Value::StringValue(v1), Value::StringValue(v2)) => v1.value == v2.value,
+        (Value::BooleanValue(v1), Value::BooleanValue(v2)) => v1.value == v2.value,
+        (Value::NullValue(_), Value::NullValue(_)) => true,
+        (Value::EnumValue(v1), Value::EnumValue(v2)) => v1.value == v2.value,
+        (Value::ListValue(v1), Value::ListValue(v2)) => {
+            v1.values.len() == v2.values.len()
+                && v1
+                    .values
+                    .iter()
+                    .zip(v2.values.iter())
+                    .all(|(v1, v2)| values_are_identical(v1, v2))
+        }
+        (Value::ObjectValue(v1), Value::ObjectValue(v2)) => {
+            v1.fields.len() == v2.fields.len()
+                && v1.fields.iter().all(|(key1, v1)| {
+                    v2.fields
+                        .iter()
+                        .any(|(key2, v2)| key1.name == key2.name && values_are_identical(v1, v2))
+                })
+        }
+        _ => false,
+    }
+}
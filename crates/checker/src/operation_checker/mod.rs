@@ -1,4 +1,4 @@
-use std::borrow::{Borrow, Cow};
+use std::{borrow::{Borrow, Cow}, collections::HashSet};
 
 use graphql_type_system::{Schema, RootTypes, OriginalNodeRef, TypeDefinition, Field, Node, Text};
 use nitrogql_ast::{
@@ -10,23 +10,39 @@ use nitrogql_ast::{
         variable::VariablesDefinition
 };
 
-use self::{fragment_map::{generate_fragment_map, FragmentMap}, count_selection_set_fields::selection_set_has_more_than_one_fields};
+use self::{count_selection_set_fields::selection_set_has_more_than_one_fields, fields_can_merge::FieldsCanMergeChecker, operation_usage::{collect_operation_usage, OperationUsage}};
 
-use super::{error::{CheckError, CheckErrorMessage, TypeKind}, common::{check_directives, check_arguments}, types::inout_kind_of_type};
-use nitrogql_semantics::{direct_fields_of_output_type};
+use super::{error::{CheckError, CheckErrorMessage, TypeKind}, common::{check_directives, check_arguments}, custom_scalar::CustomScalarRules, types::inout_kind_of_type};
+use nitrogql_semantics::{direct_fields_of_output_type, FragmentRegistry};
 
 #[cfg(test)]
 mod tests;
-mod fragment_map;
 mod count_selection_set_fields;
+mod fields_can_merge;
+mod operation_usage;
 
 pub fn check_operation_document<'src>(
     definitions: &Schema<Cow<'src, str>, Pos>,
     document: &OperationDocument<'src>,
+) -> Vec<CheckError> {
+    check_operation_document_with_fragments(definitions, document, &FragmentRegistry::new(), &CustomScalarRules::new())
+}
+
+/// Checks an operation document.
+/// Fragments in `fragments` (typically defined in other documents) can also be spread from this document.
+/// Values given to custom scalars are checked against `custom_scalars`.
+pub fn check_operation_document_with_fragments<'a, 'src>(
+    definitions: &Schema<Cow<'src, str>, Pos>,
+    document: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    custom_scalars: &CustomScalarRules,
 ) -> Vec<CheckError> {
     let mut result = vec![];
 
-    let fragment_map = generate_fragment_map(document);
+    let mut fragment_registry = FragmentRegistry::from_document(document);
+    fragment_registry.extend(fragments);
+    let mut fields_can_merge_checker = FieldsCanMergeChecker::new(definitions, &fragment_registry);
+    let mut used_fragments = HashSet::new();
 
     let operation_num = document
         .definitions
@@ -55,7 +71,7 @@ pub fn check_operation_document<'src>(
                             .take(idx)
                             .find(|other| match other {
                                 ExecutableDefinition::OperationDefinition(def) => {
-                                    def.name.map_or(false, |n| n.name == name.name)
+                                    def.name.is_some_and(|n| n.name == name.name)
                                 }
                                 ExecutableDefinition::FragmentDefinition(_) => false,
                             });
@@ -73,7 +89,9 @@ pub fn check_operation_document<'src>(
                     }
                 }
 
-                check_operation(definitions, &fragment_map, op, &mut result);
+                let usage = collect_operation_usage(&fragment_registry, op);
+                check_operation(definitions, custom_scalars, &fragment_registry, &mut fields_can_merge_checker, &usage, op, &mut result);
+                used_fragments.extend(usage.fragments);
             }
             ExecutableDefinition::FragmentDefinition(def) => {
                 // Find other one with same name
@@ -94,19 +112,45 @@ pub fn check_operation_document<'src>(
                         }
                         .with_pos(def.name.position),
                     );
+                } else if let Some(other) = fragments.get(def.name.name).filter(|other| other.position.file != def.position.file) {
+                    // Fragment of the same name is defined in another document
+                    result.push(
+                        CheckErrorMessage::DuplicateFragmentName {
+                            other_position: other.position,
+                        }
+                        .with_pos(def.name.position),
+                    );
                 }
 
-                check_fragment_definition(definitions, def, &mut result);
+                check_fragment_definition(definitions, &mut fields_can_merge_checker, def, &mut result);
+            }
+        }
+    }
+    // Documents without operations are allowed to define fragments for use in other documents.
+    if operation_num > 0 {
+        for (name, fragment) in document.definitions.iter().filter_map(|def| match def {
+            ExecutableDefinition::FragmentDefinition(fragment) => Some((fragment.name.name, fragment)),
+            ExecutableDefinition::OperationDefinition(_) => None,
+        }) {
+            // Fragments spread from other documents are also considered used.
+            if !used_fragments.contains(name) && !fragments.is_spread(name) {
+                result.push(
+                    CheckErrorMessage::UnusedFragment { name: name.to_owned() }
+                    .with_pos(fragment.name.position)
+                );
             }
         }
     }
     result
 }
 
-fn check_operation<'src, S: Text<'src>>(
-    definitions: &Schema<S, Pos>,
-    fragment_map: &FragmentMap<'_, 'src>,
-    op: &OperationDefinition<'src>,
+fn check_operation<'a, 'src, S: Text<'src>>(
+    definitions: &'a Schema<S, Pos>,
+    custom_scalars: &CustomScalarRules,
+    fragment_registry: &FragmentRegistry<'_, 'src>,
+    fields_can_merge_checker: &mut FieldsCanMergeChecker<'a, 'src, S>,
+    usage: &OperationUsage<'_, 'src>,
+    op: &'a OperationDefinition<'src>,
     result: &mut Vec<CheckError>,
 ) {
     let root_type = {
@@ -141,6 +185,7 @@ fn check_operation<'src, S: Text<'src>>(
         root_type
     };
     check_directives(definitions,
+        custom_scalars,
         op.variables_definition.as_ref(),
          &op.directives, match op.operation_type {
         OperationType::Query => "QUERY",
@@ -150,9 +195,10 @@ fn check_operation<'src, S: Text<'src>>(
     if let Some(ref variables_definition) = op.variables_definition {
         check_variables_definition(definitions, variables_definition, result);
     }
+    check_variable_usage(op, usage, result);
     if op.operation_type == OperationType::Subscription {
         // Single root field check
-        if selection_set_has_more_than_one_fields(fragment_map, &op.selection_set) {
+        if selection_set_has_more_than_one_fields(fragment_registry, &op.selection_set) {
             result.push(
                 CheckErrorMessage::SubscriptionMustHaveExactlyOneRootField
                 .with_pos(op.position)
@@ -162,13 +208,15 @@ fn check_operation<'src, S: Text<'src>>(
     let seen_fragments = vec![];
     check_selection_set(
         definitions,
-        fragment_map,
+        custom_scalars,
+        fragment_registry,
         &seen_fragments,
         op.variables_definition.as_ref(),
         root_type,
         &op.selection_set,
         result,
     );
+    fields_can_merge_checker.check_selection_set(root_type, &op.selection_set, result);
 }
 
 fn operation_type_from_root_types<T>(
@@ -182,9 +230,10 @@ fn operation_type_from_root_types<T>(
     }
 }
 
-fn check_fragment_definition<'src, S: Text<'src>>(
-    definitions: &Schema<S, Pos>,
-    op: &FragmentDefinition<'src>,
+fn check_fragment_definition<'a, 'src, S: Text<'src>>(
+    definitions: &'a Schema<S, Pos>,
+    fields_can_merge_checker: &mut FieldsCanMergeChecker<'a, 'src, S>,
+    op: &'a FragmentDefinition<'src>,
     result: &mut Vec<CheckError>,
 ) {
     let target = definitions.get_type(op.type_condition.name);
@@ -207,7 +256,9 @@ fn check_fragment_definition<'src, S: Text<'src>>(
                 (*target.original_node_ref(), CheckErrorMessage::DefinitionPos { name: (*target.name()).to_string() })
             ])
         );
+        return;
     }
+    fields_can_merge_checker.check_selection_set(target, &op.selection_set, result);
 
 }
 
@@ -246,9 +297,49 @@ fn check_variables_definition<'src, S: Text<'src>>(
     }
 }
 
+fn check_variable_usage<'src>(
+    op: &OperationDefinition<'src>,
+    usage: &OperationUsage<'_, 'src>,
+    result: &mut Vec<CheckError>,
+) {
+    let variable_definitions = op.variables_definition.as_ref().map_or(&[][..], |v| &v.definitions);
+    let mut reported_positions = vec![];
+    for variable_usage in usage.variables.iter() {
+        let variable = variable_usage.variable;
+        if variable_definitions.iter().any(|def| def.name.name == variable.name) {
+            continue;
+        }
+        if reported_positions.contains(&variable.position) {
+            continue;
+        }
+        reported_positions.push(variable.position);
+        let error = CheckErrorMessage::UnknownVariable { name: variable.name.to_owned() }
+            .with_pos(variable.position);
+        result.push(
+            if variable_usage.in_fragment {
+                error.with_additional_info(vec![
+                    (op.position, CheckErrorMessage::VariableNotDefinedInOperation { name: variable.name.to_owned() })
+                ])
+            } else {
+                error
+            }
+        );
+    }
+    for def in variable_definitions.iter() {
+        if usage.variables.iter().all(|u| u.variable.name != def.name.name) {
+            result.push(
+                CheckErrorMessage::UnusedVariable { name: def.name.name.to_owned() }
+                .with_pos(def.pos)
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn check_selection_set<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
-    fragment_map: &FragmentMap<'_, 'src>,
+    custom_scalars: &CustomScalarRules,
+    fragment_registry: &FragmentRegistry<'_, 'src>,
     seen_fragments: &[&str],
     variables: Option<&VariablesDefinition<'src>>,
     root_type: &Node<TypeDefinition<S, Pos>, Pos>,
@@ -276,7 +367,8 @@ fn check_selection_set<'src, S: Text<'src>>(
             Selection::Field(field_selection) => {
                 check_selection_field(
                     definitions,
-                    fragment_map,
+                    custom_scalars,
+                    fragment_registry,
                     seen_fragments,
                     variables,
                     *root_type.original_node_ref(),
@@ -288,10 +380,10 @@ fn check_selection_set<'src, S: Text<'src>>(
                 
             }
             Selection::FragmentSpread(fragment_spread) => {
-                check_fragment_spread(definitions, fragment_map, seen_fragments, variables, root_type, fragment_spread, result);
+                check_fragment_spread(definitions, custom_scalars, fragment_registry, seen_fragments, variables, root_type, fragment_spread, result);
             },
             Selection::InlineFragment(inline_fragment) => {
-                check_inline_fragment(definitions, fragment_map, seen_fragments, variables, root_type, inline_fragment, result);
+                check_inline_fragment(definitions, custom_scalars, fragment_registry, seen_fragments, variables, root_type, inline_fragment, result);
             }
         }
     }
@@ -300,7 +392,8 @@ fn check_selection_set<'src, S: Text<'src>>(
 #[allow(clippy::too_many_arguments)]
 fn check_selection_field<'src, S: Text<'src>, F: Borrow<Field<S, Pos>>>(
     definitions: &Schema<S, Pos>,
-    fragment_map: &FragmentMap<'_, 'src>,
+    custom_scalars: &CustomScalarRules,
+    fragment_registry: &FragmentRegistry<'_, 'src>,
     seen_fragments: &[&str],
     variables: Option<&VariablesDefinition<'src>>,
     root_type_pos: Pos,
@@ -328,10 +421,25 @@ fn check_selection_field<'src, S: Text<'src>, F: Borrow<Field<S, Pos>>>(
         );
         return;
     };
+    if let Some(ref reason) = target_field.deprecation {
+        result.push(
+            CheckErrorMessage::DeprecatedField {
+                field_name: selection_name.to_owned(),
+                type_name: root_type_name.to_owned(),
+                reason: reason.to_string(),
+            }.with_pos(field_selection.name.position)
+            .with_additional_info(vec![
+                (*target_field.name.original_node_ref(), CheckErrorMessage::DefinitionPos {
+                    name: selection_name.to_owned(),
+                })
+            ])
+        );
+    }
 
-    check_directives(definitions, variables, &field_selection.directives, "FIELD", result);
+    check_directives(definitions, custom_scalars, variables, &field_selection.directives, "FIELD", result);
     check_arguments(
         definitions,
+        custom_scalars,
         variables,
         field_selection.name.position,
         field_selection.name.name,
@@ -348,7 +456,7 @@ fn check_selection_field<'src, S: Text<'src>, F: Borrow<Field<S, Pos>>>(
         };
 
     if let Some(ref selection_set) = field_selection.selection_set {
-        check_selection_set(definitions, fragment_map, seen_fragments, variables, target_field_type, selection_set, result);
+        check_selection_set(definitions, custom_scalars, fragment_registry, seen_fragments, variables, target_field_type, selection_set, result);
     } else {
         // No selection set
         if direct_fields_of_output_type(target_field_type).is_some() {
@@ -358,9 +466,11 @@ fn check_selection_field<'src, S: Text<'src>, F: Borrow<Field<S, Pos>>>(
 
 }
 
+#[allow(clippy::too_many_arguments)]
 fn check_fragment_spread<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
-    fragment_map: &FragmentMap<'_, 'src>,
+    custom_scalars: &CustomScalarRules,
+    fragment_registry: &FragmentRegistry<'_, 'src>,
     seen_fragments: &[&str],
     variables: Option<&VariablesDefinition<'src>>,
     root_type: &Node<TypeDefinition<S, Pos>, Pos>,
@@ -377,7 +487,7 @@ fn check_fragment_spread<'src, S: Text<'src>>(
     }
     let seen_fragments: Vec<&str> = seen_fragments.iter().copied().chain(vec![fragment_spread.fragment_name.name]).collect();
     let seen_fragments = &seen_fragments;
-    let Some(target) = fragment_map.get(fragment_spread.fragment_name.name) else {
+    let Some(target) = fragment_registry.get(fragment_spread.fragment_name.name) else {
         result.push(
             CheckErrorMessage::UnknownFragment { name: fragment_spread.fragment_name.to_string() }
             .with_pos(fragment_spread.fragment_name.position)
@@ -390,7 +500,8 @@ fn check_fragment_spread<'src, S: Text<'src>>(
     };
     check_fragment_spread_core(
         definitions,
-        fragment_map,
+        custom_scalars,
+        fragment_registry,
         seen_fragments,
         variables,
         root_type,
@@ -401,9 +512,11 @@ fn check_fragment_spread<'src, S: Text<'src>>(
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn check_inline_fragment<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
-    fragment_map: &FragmentMap<'_, 'src>,
+    custom_scalars: &CustomScalarRules,
+    fragment_registry: &FragmentRegistry<'_, 'src>,
     seen_fragments: &[&str],
     variables: Option<&VariablesDefinition<'src>>,
     root_type: &Node<TypeDefinition<S, Pos>, Pos>,
@@ -412,7 +525,7 @@ fn check_inline_fragment<'src, S: Text<'src>>(
 ) {
     match inline_fragment.type_condition {
         None => {
-            check_selection_set(definitions, fragment_map, seen_fragments, variables, root_type, &inline_fragment.selection_set, result);
+            check_selection_set(definitions, custom_scalars, fragment_registry, seen_fragments, variables, root_type, &inline_fragment.selection_set, result);
         }
         Some(ref type_cond) => {
             let Some(type_cond_definition) = definitions.get_type(type_cond.name) else {
@@ -424,7 +537,8 @@ fn check_inline_fragment<'src, S: Text<'src>>(
             };
         check_fragment_spread_core(
             definitions,
-            fragment_map,
+            custom_scalars,
+            fragment_registry,
             seen_fragments,
             variables,
             root_type,
@@ -440,7 +554,8 @@ fn check_inline_fragment<'src, S: Text<'src>>(
 #[allow(clippy::too_many_arguments)]
 fn check_fragment_spread_core<'src, S: Text<'src>>(
     definitions: &Schema<S, Pos>,
-    fragment_map: &FragmentMap<'_, 'src>,
+    custom_scalars: &CustomScalarRules,
+    fragment_registry: &FragmentRegistry<'_, 'src>,
     seen_fragments: &[&str],
     variables: Option<&VariablesDefinition<'src>>,
     root_type: &Node<TypeDefinition<S, Pos>, Pos>,
@@ -623,7 +738,7 @@ fn check_fragment_spread_core<'src, S: Text<'src>>(
         }
         _ => {}
     }
-    check_selection_set(definitions, fragment_map, seen_fragments, variables, fragment_condition, fragment_selection_set, result);
+    check_selection_set(definitions, custom_scalars, fragment_registry, seen_fragments, variables, fragment_condition, fragment_selection_set, result);
 }
 
 fn kind_of_type_definition<S, D>(definition: &TypeDefinition<S, D>) -> TypeKind {
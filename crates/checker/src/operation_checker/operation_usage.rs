@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use nitrogql_ast::{
+    directive::Directive,
+    operation::OperationDefinition,
+    selection_set::{Selection, SelectionSet},
+    value::{Arguments, Value},
+    variable::Variable,
+};
+use nitrogql_semantics::FragmentRegistry;
+
+/// Variables and fragments used by an operation, including those used through fragment spreads.
+pub struct OperationUsage<'a, 'src> {
+    /// Variables used in the operation.
+    pub variables: Vec<VariableUsage<'a, 'src>>,
+    /// Names of fragments spread in the operation.
+    pub fragments: HashSet<&'a str>,
+}
+
+pub struct VariableUsage<'a, 'src> {
+    pub variable: &'a Variable<'src>,
+    /// Whether this usage is inside a fragment definition.
+    pub in_fragment: bool,
+}
+
+pub fn collect_operation_usage<'a, 'src>(
+    fragment_registry: &FragmentRegistry<'a, 'src>,
+    operation: &'a OperationDefinition<'src>,
+) -> OperationUsage<'a, 'src> {
+    let mut usage = OperationUsage {
+        variables: vec![],
+        fragments: HashSet::new(),
+    };
+    collect_directives(&operation.directives, false, &mut usage);
+    collect_selection_set(
+        fragment_registry,
+        &operation.selection_set,
+        false,
+        &mut usage,
+    );
+    usage
+}
+
+fn collect_selection_set<'a, 'src>(
+    fragment_registry: &FragmentRegistry<'a, 'src>,
+    selection_set: &'a SelectionSet<'src>,
+    in_fragment: bool,
+    usage: &mut OperationUsage<'a, 'src>,
+) {
+    for selection in selection_set.selections.iter() {
+        collect_directives(selection.directives(), in_fragment, usage);
+        match selection {
+            Selection::Field(field) => {
+                collect_arguments(field.arguments.as_ref(), in_fragment, usage);
+                if let Some(ref selection_set) = field.selection_set {
+                    collect_selection_set(fragment_registry, selection_set, in_fragment, usage);
+                }
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if !usage.fragments.insert(fragment_spread.fragment_name.name) {
+                    // Already visited (this also prevents infinite recursions)
+                    continue;
+                }
+                if let Some(fragment) = fragment_registry.get(fragment_spread.fragment_name.name) {
+                    collect_directives(&fragment.directives, true, usage);
+                    collect_selection_set(fragment_registry, &fragment.selection_set, true, usage);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                collect_selection_set(
+                    fragment_registry,
+                    &inline_fragment.selection_set,
+                    in_fragment,
+                    usage,
+                );
+            }
+        }
+    }
+}
+
+fn collect_directives<'a, 'src>(
+    directives: &'a [Directive<'src>],
+    in_fragment: bool,
+    usage: &mut OperationUsage<'a, 'src>,
+) {
+    for directive in directives {
+        collect_arguments(directive.arguments.as_ref(), in_fragment, usage);
+    }
+}
+
+fn collect_arguments<'a, 'src>(
+    arguments: Option<&'a Arguments<'src>>,
+    in_fragment: bool,
+    usage: &mut OperationUsage<'a, 'src>,
+) {
+    for (_, value) in arguments.into_iter().flatten() {
+        collect_value(value, in_fragment, usage);
+    }
+}
+
+fn collect_value<'a, 'src>(
+    value: &'a Value<'src>,
+    in_fragment: bool,
+    usage: &mut OperationUsage<'a, 'src>,
+) {
+    match value {
+        Value::Variable(variable) => {
+            usage.variables.push(VariableUsage {
+                variable,
+                in_fragment,
+            });
+        }
+        Value::ListValue(list) => {
+            for value in list.values.iter() {
+                collect_value(value, in_fragment, usage);
+            }
+        }
+        Value::ObjectValue(object) => {
+            for (_, value) in object.fields.iter() {
+                collect_value(value, in_fragment, usage);
+            }
+        }
+        _ => {}
+    }
+}
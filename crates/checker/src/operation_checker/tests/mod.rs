@@ -351,6 +351,36 @@ mod selection_set {
         assert_debug_snapshot!(check_operation_document(&schema, &doc))
     }
 
+    #[test]
+    fn query_shorthand() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            {
+                user { id name }
+                foo2
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn query_shorthand_with_other_operation() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            { foo }
+            query q1 { foo }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
     #[test]
     fn duplicated_selected_field() {
         let schema = type_system();
@@ -917,7 +947,844 @@ mod fragments {
     }
 }
 
-fn parse_to_type_system_document(source: &str) -> TypeSystemDocument {
+mod field_selection_merging {
+    use std::borrow::Cow;
+
+    use graphql_type_system::Schema;
+    use insta::assert_debug_snapshot;
+    use nitrogql_semantics::ast_to_type_system;
+
+    use crate::operation_checker::check_operation_document;
+    use nitrogql_ast::base::Pos;
+    use nitrogql_parser::parse_operation_document;
+
+    use super::parse_to_type_system_document;
+
+    fn type_system() -> Schema<Cow<'static, str>, Pos> {
+        let doc = parse_to_type_system_document(
+            "
+            type Query {
+                foo(x: Int, y: Int): Int!
+                bar: Int!
+                user(id: ID!): User
+                node: Node
+                pet: Pet
+            }
+            interface Node {
+                id: ID!
+            }
+            type User implements Node {
+                id: ID!
+                name: String!
+                nickname: String
+                friends: [User!]!
+            }
+            type Dog {
+                name: String!
+                age: Int
+            }
+            type Cat {
+                name: String
+                age: String
+            }
+            union Pet = Dog | Cat
+        ",
+        );
+        ast_to_type_system(&doc)
+    }
+
+    #[test]
+    fn same_field_can_merge() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($id: ID!) {
+                foo(x: 1, y: 2)
+                foo(y: 2, x: 1)
+                user(id: $id) { id }
+                user(id: $id) { name }
+                alias: bar
+                alias: bar
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn different_fields_conflict() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                a: foo
+                a: bar
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn different_arguments_conflict() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($id: ID!) {
+                a: foo(x: 1)
+                a: foo(x: 2)
+                b: foo(x: 1)
+                b: foo
+                user(id: $id) { id }
+                user(id: \"1\") { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn nested_conflict() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\") {
+                    friends { n: name }
+                }
+                user(id: \"1\") {
+                    friends { n: nickname }
+                }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn conflict_through_fragments() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                a: foo
+                ...F
+                node {
+                    ... on User { id: name }
+                }
+            }
+            fragment F on Query {
+                a: bar
+                ...G
+            }
+            fragment G on Query {
+                a: foo(x: 1)
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn mutually_exclusive_parents() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                pet {
+                    ... on Dog { name }
+                    ... on Cat { name }
+                    ... on Dog { x: name }
+                    ... on Cat { x: age }
+                }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn different_response_shapes_conflict() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                pet {
+                    ... on Dog { age }
+                    ... on Cat { age }
+                }
+                node {
+                    ... on User { friends { id } }
+                    friends: id
+                }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+}
+
+mod usage {
+    use std::borrow::Cow;
+
+    use graphql_type_system::Schema;
+    use insta::assert_debug_snapshot;
+    use nitrogql_semantics::ast_to_type_system;
+
+    use crate::operation_checker::check_operation_document;
+    use nitrogql_ast::base::Pos;
+    use nitrogql_parser::parse_operation_document;
+
+    use super::parse_to_type_system_document;
+
+    fn type_system() -> Schema<Cow<'static, str>, Pos> {
+        let doc = parse_to_type_system_document(
+            "
+            directive @dir_bool(bool: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+            type Query {
+                user(id: ID!): User
+                users(filter: UserFilter): [User!]!
+            }
+            type User {
+                id: ID!
+                name: String!
+                friends(first: Int): [User!]!
+            }
+            input UserFilter {
+                ids: [ID!]
+                name: String
+            }
+        ",
+        );
+        ast_to_type_system(&doc)
+    }
+
+    #[test]
+    fn unused_variables() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($id: ID!, $unused: Int, $name: String, $b: Boolean!, $first: Int) {
+                user(id: $id) { ...F }
+                users(filter: { ids: [$id], name: $name }) {
+                    ... @dir_bool(bool: $b) { id }
+                }
+            }
+            fragment F on User {
+                friends(first: $first) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn undefined_variables_in_fragments() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query q1($id: ID!) {
+                user(id: $id) { ...F ...F }
+            }
+            query q2($id: ID!, $first: Int) {
+                user(id: $id) { ...F }
+            }
+            fragment F on User {
+                ...G
+            }
+            fragment G on User {
+                friends(first: $first) {
+                    id @dir_bool(bool: $b)
+                }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn unused_fragments() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\") { ...F }
+            }
+            fragment F on User {
+                ...G
+            }
+            fragment G on User {
+                id
+            }
+            fragment H on User {
+                ...I
+            }
+            fragment I on User {
+                id
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn fragment_only_document() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            fragment F on User {
+                id
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+}
+
+mod variable_positions {
+    use std::borrow::Cow;
+
+    use graphql_type_system::Schema;
+    use insta::assert_debug_snapshot;
+    use nitrogql_semantics::ast_to_type_system;
+
+    use crate::operation_checker::check_operation_document;
+    use nitrogql_ast::base::Pos;
+    use nitrogql_parser::parse_operation_document;
+
+    use super::parse_to_type_system_document;
+
+    fn type_system() -> Schema<Cow<'static, str>, Pos> {
+        let doc = parse_to_type_system_document(
+            "
+            type Query {
+                user(id: ID!): User
+                users(ids: [ID!]!, first: Int! = 10): [User!]!
+                search(filter: SearchFilter): [User!]!
+            }
+            type User {
+                id: ID!
+            }
+            input SearchFilter {
+                ids: [ID!]
+                name: String!
+                limit: Int! = 10
+            }
+        ",
+        );
+        ast_to_type_system(&doc)
+    }
+
+    #[test]
+    fn nullability() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($id: ID, $id2: ID!, $id3: ID = \"1\", $id4: ID = null) {
+                a: user(id: $id) { id }
+                b: user(id: $id2) { id }
+                c: user(id: $id3) { id }
+                d: user(id: $id4) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn lists() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($ids: [ID!]!, $ids2: [ID]!, $ids3: ID!, $id: ID, $id2: ID!) {
+                a: users(ids: $ids) { id }
+                b: users(ids: $ids2) { id }
+                c: users(ids: $ids3) { id }
+                d: users(ids: [$id, $id2]) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn location_default_value() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($first: Int, $limit: Int, $name: String) {
+                users(ids: [], first: $first) { id }
+                search(filter: { name: $name, limit: $limit }) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+
+    #[test]
+    fn nested_input_fields() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query($ids: [ID], $name: String!, $limit: String) {
+                search(filter: { ids: $ids, name: $name, limit: $limit }) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc))
+    }
+}
+
+mod cross_file_fragments {
+    use std::borrow::Cow;
+
+    use graphql_type_system::Schema;
+    use insta::assert_debug_snapshot;
+    use nitrogql_semantics::{ast_to_type_system, FragmentRegistry};
+
+    use crate::{
+        custom_scalar::CustomScalarRules, operation_checker::check_operation_document_with_fragments,
+    };
+    use nitrogql_ast::{base::Pos, set_current_file_of_pos, OperationDocument};
+    use nitrogql_parser::parse_operation_document;
+
+    use super::parse_to_type_system_document;
+
+    fn type_system() -> Schema<Cow<'static, str>, Pos> {
+        let doc = parse_to_type_system_document(
+            "
+            type Query {
+                user(id: ID!): User
+            }
+            type User {
+                id: ID!
+                name: String!
+                friends(first: Int): [User!]!
+            }
+        ",
+        );
+        ast_to_type_system(&doc)
+    }
+
+    fn parse_other_file(source: &str) -> OperationDocument<'_> {
+        set_current_file_of_pos(1);
+        let doc = parse_operation_document(source).unwrap();
+        set_current_file_of_pos(0);
+        doc
+    }
+
+    #[test]
+    fn spread_fragment_from_other_file() {
+        let schema = type_system();
+        let other = parse_other_file(
+            "
+            fragment F on User {
+                id
+                ...G
+            }
+            fragment G on User {
+                name
+            }
+        ",
+        );
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\") { ...F id: name }
+            }
+        ",
+        )
+        .unwrap();
+        let mut fragments = FragmentRegistry::from_document(&other);
+        fragments.add_document(&doc);
+
+        assert_debug_snapshot!(check_operation_document_with_fragments(
+            &schema,
+            &doc,
+            &fragments,
+            &CustomScalarRules::new()
+        ))
+    }
+
+    #[test]
+    fn undefined_variable_in_fragment_from_other_file() {
+        let schema = type_system();
+        let other = parse_other_file(
+            "
+            fragment F on User {
+                friends(first: $first) { id }
+            }
+        ",
+        );
+        let doc = parse_operation_document(
+            "
+            query q1($first: Int) {
+                user(id: \"1\") { ...F }
+            }
+            query q2 {
+                user(id: \"1\") { ...F ...Unknown }
+            }
+        ",
+        )
+        .unwrap();
+        let mut fragments = FragmentRegistry::from_document(&other);
+        fragments.add_document(&doc);
+
+        assert_debug_snapshot!(check_operation_document_with_fragments(
+            &schema,
+            &doc,
+            &fragments,
+            &CustomScalarRules::new()
+        ))
+    }
+
+    #[test]
+    fn duplicate_fragment_name_across_files() {
+        let schema = type_system();
+        let other = parse_other_file(
+            "
+            fragment F on User {
+                id
+            }
+        ",
+        );
+        let doc = parse_operation_document(
+            "
+            fragment F on User {
+                name
+            }
+        ",
+        )
+        .unwrap();
+        let mut fragments = FragmentRegistry::from_document(&other);
+        fragments.add_document(&doc);
+
+        assert_debug_snapshot!(check_operation_document_with_fragments(
+            &schema,
+            &doc,
+            &fragments,
+            &CustomScalarRules::new()
+        ))
+    }
+
+    #[test]
+    fn fragment_used_from_other_file() {
+        let schema = type_system();
+        let other = parse_other_file(
+            "
+            query {
+                user(id: \"1\") { ...F }
+            }
+        ",
+        );
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\") { id }
+            }
+            fragment F on User {
+                id
+            }
+            fragment G on User {
+                name
+            }
+        ",
+        )
+        .unwrap();
+        let mut fragments = FragmentRegistry::from_document(&other);
+        fragments.add_document(&doc);
+
+        assert_debug_snapshot!(check_operation_document_with_fragments(
+            &schema,
+            &doc,
+            &fragments,
+            &CustomScalarRules::new()
+        ))
+    }
+}
+
+mod deprecation {
+    use std::borrow::Cow;
+
+    use graphql_type_system::Schema;
+    use insta::assert_debug_snapshot;
+    use nitrogql_semantics::ast_to_type_system;
+
+    use crate::{operation_checker::check_operation_document, Severity};
+    use nitrogql_ast::base::Pos;
+    use nitrogql_parser::parse_operation_document;
+
+    use super::parse_to_type_system_document;
+
+    fn type_system() -> Schema<Cow<'static, str>, Pos> {
+        let doc = parse_to_type_system_document(
+            "
+            type Query {
+                user(id: ID!, name: String @deprecated(reason: \"Use id\")): User
+                users(order: Order): [User!]!
+            }
+            type User {
+                id: ID!
+                name: String! @deprecated
+                fullName: String!
+            }
+            enum Order {
+                ASC
+                DESC
+                RANDOM @deprecated(reason: \"Not stable\")
+            }
+        ",
+        );
+        ast_to_type_system(&doc)
+    }
+
+    #[test]
+    fn no_deprecated_usage() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\") { id fullName }
+                users(order: ASC) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert!(check_operation_document(&schema, &doc).is_empty());
+    }
+
+    #[test]
+    fn deprecated_field() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\") { id name }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc));
+    }
+
+    #[test]
+    fn deprecated_argument() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\", name: \"foo\") { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc));
+    }
+
+    #[test]
+    fn deprecated_enum_value() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                users(order: RANDOM) { id }
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc));
+    }
+
+    #[test]
+    fn deprecation_is_warning() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                user(id: \"1\", name: \"foo\") { name }
+                users(order: RANDOM) { id unknown }
+            }
+        ",
+        )
+        .unwrap();
+
+        let severities = check_operation_document(&schema, &doc)
+            .iter()
+            .map(|err| err.severity())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            severities,
+            vec![
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Error,
+            ]
+        );
+    }
+}
+
+mod scalar_values {
+    use std::borrow::Cow;
+
+    use graphql_type_system::Schema;
+    use insta::assert_debug_snapshot;
+    use nitrogql_semantics::{ast_to_type_system, FragmentRegistry};
+
+    use crate::{
+        custom_scalar::{CustomScalarRule, CustomScalarRules, LiteralKind},
+        operation_checker::{check_operation_document, check_operation_document_with_fragments},
+    };
+    use nitrogql_ast::base::Pos;
+    use nitrogql_parser::parse_operation_document;
+
+    use super::parse_to_type_system_document;
+
+    fn type_system() -> Schema<Cow<'static, str>, Pos> {
+        let doc = parse_to_type_system_document(
+            "
+            scalar DateTime
+            scalar JSON
+            type Query {
+                int(value: Int): Int
+                float(value: Float): Float
+                id(value: ID): ID
+                dateTime(value: DateTime): DateTime
+                json(value: JSON): JSON
+            }
+        ",
+        );
+        ast_to_type_system(&doc)
+    }
+
+    fn custom_scalars() -> CustomScalarRules {
+        let mut rules = CustomScalarRules::new();
+        rules.insert(
+            "DateTime".to_owned(),
+            CustomScalarRule::new(
+                vec![LiteralKind::String],
+                Some(r"\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z"),
+            )
+            .unwrap(),
+        );
+        rules
+    }
+
+    #[test]
+    fn built_in_coercion() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                a: int(value: 2147483647)
+                b: int(value: -2147483648)
+                c: float(value: 1)
+                d: float(value: 1.5e10)
+                e: id(value: 123)
+                f: id(value: \"abc\")
+            }
+        ",
+        )
+        .unwrap();
+
+        assert!(check_operation_document(&schema, &doc).is_empty());
+    }
+
+    #[test]
+    fn built_in_mismatch() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                a: int(value: 2147483648)
+                b: int(value: 1.0)
+                c: float(value: 1e400)
+                d: float(value: \"1\")
+                e: id(value: 1.5)
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document(&schema, &doc));
+    }
+
+    #[test]
+    fn unconfigured_custom_scalar() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                a: dateTime(value: 1)
+                b: json(value: { key: [1, \"a\"] })
+            }
+        ",
+        )
+        .unwrap();
+
+        assert!(check_operation_document(&schema, &doc).is_empty());
+    }
+
+    #[test]
+    fn custom_scalar() {
+        let schema = type_system();
+        let doc = parse_operation_document(
+            "
+            query {
+                a: dateTime(value: \"2023-05-01T12:00:00Z\")
+                b: dateTime(value: null)
+                c: dateTime(value: 1683000000)
+                d: dateTime(value: \"2023-05-01\")
+                e: json(value: { key: [1, \"a\"] })
+            }
+        ",
+        )
+        .unwrap();
+
+        assert_debug_snapshot!(check_operation_document_with_fragments(
+            &schema,
+            &doc,
+            &FragmentRegistry::new(),
+            &custom_scalars()
+        ));
+    }
+}
+
+fn parse_to_type_system_document(source: &str) -> TypeSystemDocument<'_> {
     let mut doc = parse_type_system_document(source).unwrap();
     doc.extend(generate_builtins());
     let doc = resolve_extensions(doc).unwrap();
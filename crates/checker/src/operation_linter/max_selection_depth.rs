@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use nitrogql_ast::{
+    base::Pos,
+    operation::OperationDocument,
+    selection_set::{Selection, SelectionSet},
+};
+use nitrogql_semantics::FragmentRegistry;
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{operations, RuleResult};
+
+/// Reports fields nested deeper than `max_depth`. Root fields are at depth 1.
+/// Depth is counted through fragment spreads.
+pub fn max_selection_depth<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    max_depth: usize,
+    result: &mut RuleResult,
+) {
+    let mut checker = DepthChecker {
+        fragments,
+        max_depth,
+        fragment_stack: vec![],
+        reported: HashSet::new(),
+        result,
+    };
+    for op in operations(document) {
+        checker.check_selection_set(&op.selection_set, 1);
+    }
+}
+
+struct DepthChecker<'a, 'src, 'b> {
+    fragments: &'b FragmentRegistry<'a, 'src>,
+    max_depth: usize,
+    /// Fragments being expanded, to avoid infinite recursion.
+    fragment_stack: Vec<&'a str>,
+    /// A field in a fragment is reported only once.
+    reported: HashSet<Pos>,
+    result: &'b mut RuleResult,
+}
+
+impl<'a, 'src> DepthChecker<'a, 'src, '_> {
+    fn check_selection_set(&mut self, selection_set: &'a SelectionSet<'src>, depth: usize) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    if depth > self.max_depth {
+                        if self.reported.insert(field.name.position) {
+                            self.result.push((
+                                field.name.position,
+                                LintErrorMessage::SelectionTooDeep {
+                                    max_depth: self.max_depth,
+                                },
+                            ));
+                        }
+                        continue;
+                    }
+                    if let Some(ref selection_set) = field.selection_set {
+                        self.check_selection_set(selection_set, depth + 1);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.fragments.get(spread.fragment_name.name) else {
+                        continue;
+                    };
+                    // Recursing fragments are reported by the checker.
+                    if self.fragment_stack.contains(&fragment.name.name) {
+                        continue;
+                    }
+                    self.fragment_stack.push(fragment.name.name);
+                    self.check_selection_set(&fragment.selection_set, depth);
+                    self.fragment_stack.pop();
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.check_selection_set(&fragment.selection_set, depth);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+
+use graphql_type_system::Schema;
+use nitrogql_ast::{
+    base::Pos,
+    operation::{ExecutableDefinition, OperationDefinition, OperationDocument},
+};
+use nitrogql_semantics::FragmentRegistry;
+
+use crate::lint_error::{LintError, LintErrorMessage, LintRuleSetting};
+
+use self::{
+    max_selection_depth::max_selection_depth,
+    no_mutation_literal_arguments::no_mutation_literal_arguments,
+    operation_names::{named_operations, operation_name_suffix},
+    require_id_field::require_id_field,
+};
+
+mod max_selection_depth;
+mod no_mutation_literal_arguments;
+mod operation_names;
+mod require_id_field;
+#[cfg(test)]
+mod tests;
+
+/// Lint rule for operation documents.
+#[derive(Debug, Clone)]
+pub enum OperationLintRule {
+    /// Operations must be named.
+    NamedOperations,
+    /// Operation names must end with their operation type, such as `Query`.
+    OperationNameSuffix,
+    /// Arguments in mutations must be passed as variables.
+    NoMutationLiteralArguments,
+    /// Selection sets must not be nested deeper than `max_depth`.
+    MaxSelectionDepth { max_depth: usize },
+    /// `field_name` must be selected whenever the selected type has it.
+    RequireIdField { field_name: String },
+}
+
+impl OperationLintRule {
+    /// Id of the rule, as used in config files.
+    pub fn id(&self) -> &'static str {
+        match self {
+            OperationLintRule::NamedOperations => "named-operations",
+            OperationLintRule::OperationNameSuffix => "operation-name-suffix",
+            OperationLintRule::NoMutationLiteralArguments => "no-mutation-literal-arguments",
+            OperationLintRule::MaxSelectionDepth { .. } => "max-selection-depth",
+            OperationLintRule::RequireIdField { .. } => "require-id-field",
+        }
+    }
+}
+
+/// Lints an operation document with given rules.
+/// Fragments in `fragments` (typically defined in other documents) are followed from fragment spreads.
+pub fn lint_operation_document<'a, 'src>(
+    definitions: &Schema<Cow<'src, str>, Pos>,
+    document: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    rules: &[LintRuleSetting<OperationLintRule>],
+) -> Vec<LintError> {
+    let mut fragment_registry = FragmentRegistry::from_document(document);
+    fragment_registry.extend(fragments);
+
+    let mut result = vec![];
+    for setting in rules {
+        let mut found = vec![];
+        match setting.rule {
+            OperationLintRule::NamedOperations => named_operations(document, &mut found),
+            OperationLintRule::OperationNameSuffix => operation_name_suffix(document, &mut found),
+            OperationLintRule::NoMutationLiteralArguments => {
+                no_mutation_literal_arguments(document, &fragment_registry, &mut found)
+            }
+            OperationLintRule::MaxSelectionDepth { max_depth } => {
+                max_selection_depth(document, &fragment_registry, max_depth, &mut found)
+            }
+            OperationLintRule::RequireIdField { ref field_name } => require_id_field(
+                definitions,
+                document,
+                &fragment_registry,
+                field_name,
+                &mut found,
+            ),
+        }
+        result.extend(found.into_iter().map(|(position, message)| LintError {
+            rule_id: setting.rule.id(),
+            severity: setting.severity,
+            position,
+            message,
+        }));
+    }
+    result.sort_by_key(|error| (error.position.file, error.position.offset));
+    result
+}
+
+/// Iterates over operations in given document.
+fn operations<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+) -> impl Iterator<Item = &'a OperationDefinition<'src>> {
+    document.definitions.iter().filter_map(|def| match def {
+        ExecutableDefinition::OperationDefinition(op) => Some(op),
+        ExecutableDefinition::FragmentDefinition(_) => None,
+    })
+}
+
+/// Problems found by one rule.
+type RuleResult = Vec<(Pos, LintErrorMessage)>;
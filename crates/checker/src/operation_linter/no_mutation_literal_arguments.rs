@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use nitrogql_ast::{
+    base::HasPos,
+    operation::{OperationDocument, OperationType},
+    selection_set::{Selection, SelectionSet},
+    value::Value,
+};
+use nitrogql_semantics::FragmentRegistry;
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{operations, RuleResult};
+
+/// Reports arguments in mutations that are not passed as variables.
+/// Fields in fragments spread from mutations are also checked.
+pub fn no_mutation_literal_arguments<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    result: &mut RuleResult,
+) {
+    // Each fragment is checked once even if it is spread from multiple mutations.
+    let mut seen_fragments = HashSet::new();
+    for op in operations(document) {
+        if op.operation_type == OperationType::Mutation {
+            check_selection_set(&op.selection_set, fragments, &mut seen_fragments, result);
+        }
+    }
+}
+
+fn check_selection_set<'a, 'src>(
+    selection_set: &'a SelectionSet<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    seen_fragments: &mut HashSet<&'a str>,
+    result: &mut RuleResult,
+) {
+    for selection in selection_set.selections.iter() {
+        match selection {
+            Selection::Field(field) => {
+                for (name, value) in field.arguments.iter().flat_map(|args| &args.arguments) {
+                    if !matches!(value, Value::Variable(_)) {
+                        result.push((
+                            *value.position(),
+                            LintErrorMessage::MutationLiteralArgument {
+                                name: name.name.to_owned(),
+                            },
+                        ));
+                    }
+                }
+                if let Some(ref selection_set) = field.selection_set {
+                    check_selection_set(selection_set, fragments, seen_fragments, result);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let Some(fragment) = fragments.get(spread.fragment_name.name) else {
+                    continue;
+                };
+                if seen_fragments.insert(fragment.name.name) {
+                    check_selection_set(&fragment.selection_set, fragments, seen_fragments, result);
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                check_selection_set(&fragment.selection_set, fragments, seen_fragments, result);
+            }
+        }
+    }
+}
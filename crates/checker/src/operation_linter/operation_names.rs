@@ -0,0 +1,43 @@
+use nitrogql_ast::operation::{OperationDocument, OperationType};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{operations, RuleResult};
+
+/// Reports operations without a name.
+pub fn named_operations(document: &OperationDocument, result: &mut RuleResult) {
+    for op in operations(document) {
+        if op.name.is_none() {
+            result.push((
+                op.position,
+                LintErrorMessage::UnnamedOperation {
+                    operation_type: op.operation_type,
+                },
+            ));
+        }
+    }
+}
+
+/// Reports operations whose name does not end with the operation type.
+pub fn operation_name_suffix(document: &OperationDocument, result: &mut RuleResult) {
+    for op in operations(document) {
+        let Some(name) = op.name else {
+            continue;
+        };
+        let suffix = match op.operation_type {
+            OperationType::Query => "Query",
+            OperationType::Mutation => "Mutation",
+            OperationType::Subscription => "Subscription",
+        };
+        if !name.name.ends_with(suffix) {
+            result.push((
+                name.position,
+                LintErrorMessage::OperationNameSuffix {
+                    operation_type: op.operation_type,
+                    name: name.name.to_owned(),
+                    suffix,
+                },
+            ));
+        }
+    }
+}
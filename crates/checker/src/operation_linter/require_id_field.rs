@@ -0,0 +1,197 @@
+use std::borrow::Cow;
+
+use graphql_type_system::{Schema, TypeDefinition};
+use nitrogql_ast::{
+    base::Pos,
+    operation::{ExecutableDefinition, OperationDocument, OperationType},
+    selection_set::{Selection, SelectionSet},
+};
+use nitrogql_semantics::{direct_fields_of_output_type, FragmentRegistry};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::RuleResult;
+
+/// Reports selection sets that do not select `field_name` although the selected type has it.
+/// Selection sets in fragment definitions are checked where they are defined.
+pub fn require_id_field<'a, 'src>(
+    definitions: &'a Schema<Cow<'src, str>, Pos>,
+    document: &'a OperationDocument<'src>,
+    fragments: &'a FragmentRegistry<'a, 'src>,
+    field_name: &'a str,
+    result: &mut RuleResult,
+) {
+    let checker = IdFieldChecker {
+        definitions,
+        fragments,
+        field_name,
+    };
+    for def in document.definitions.iter() {
+        match def {
+            ExecutableDefinition::OperationDefinition(op) => {
+                let root_types = definitions.root_types().unwrap_or_default();
+                let root_type_name = match op.operation_type {
+                    OperationType::Query => root_types.query_type,
+                    OperationType::Mutation => root_types.mutation_type,
+                    OperationType::Subscription => root_types.subscription_type,
+                };
+                let Some(root_type) = definitions.get_type(&root_type_name) else {
+                    continue;
+                };
+                checker.check_selection_set(
+                    root_type,
+                    &op.selection_set,
+                    op.position,
+                    false,
+                    result,
+                );
+            }
+            ExecutableDefinition::FragmentDefinition(fragment) => {
+                let Some(target_type) = definitions.get_type(fragment.type_condition.name) else {
+                    continue;
+                };
+                checker.check_selection_set(
+                    target_type,
+                    &fragment.selection_set,
+                    fragment.name.position,
+                    false,
+                    result,
+                );
+            }
+        }
+    }
+}
+
+struct IdFieldChecker<'a, 'src> {
+    definitions: &'a Schema<Cow<'src, str>, Pos>,
+    fragments: &'a FragmentRegistry<'a, 'src>,
+    field_name: &'a str,
+}
+
+impl<'a, 'src> IdFieldChecker<'a, 'src> {
+    /// Checks given selection set of `parent_type`.
+    /// `selected_outside` should be true if the field is already selected by an enclosing selection set.
+    fn check_selection_set(
+        &self,
+        parent_type: &'a TypeDefinition<Cow<'src, str>, Pos>,
+        selection_set: &'a SelectionSet<'src>,
+        report_position: Pos,
+        selected_outside: bool,
+        result: &mut RuleResult,
+    ) {
+        let Some(fields) = direct_fields_of_output_type(parent_type) else {
+            return;
+        };
+        let selected =
+            selected_outside || self.selects_field(parent_type.name(), selection_set, &mut vec![]);
+        if !selected && fields.iter().any(|field| field.name == self.field_name) {
+            result.push((
+                report_position,
+                LintErrorMessage::IdFieldNotSelected {
+                    field_name: self.field_name.to_owned(),
+                    type_name: parent_type.name().to_string(),
+                },
+            ));
+        }
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    let Some(ref selection_set) = field.selection_set else {
+                        continue;
+                    };
+                    let Some(field_type) = fields
+                        .iter()
+                        .find(|f| f.name == field.name.name)
+                        .and_then(|f| self.definitions.get_type(f.r#type.unwrapped()))
+                    else {
+                        continue;
+                    };
+                    self.check_selection_set(
+                        field_type,
+                        selection_set,
+                        field.name.position,
+                        false,
+                        result,
+                    );
+                }
+                // Fragments are checked at their definitions.
+                Selection::FragmentSpread(_) => {}
+                Selection::InlineFragment(fragment) => {
+                    let target_type = match fragment.type_condition {
+                        None => Some(parent_type),
+                        Some(ref type_condition) => self
+                            .definitions
+                            .get_type(type_condition.name)
+                            .map(|ty| &**ty),
+                    };
+                    let Some(target_type) = target_type else {
+                        continue;
+                    };
+                    // Selections in the enclosing set that apply to the target type also count.
+                    let selected_outside = selected_outside
+                        || self.selects_field(target_type.name(), selection_set, &mut vec![]);
+                    self.check_selection_set(
+                        target_type,
+                        &fragment.selection_set,
+                        fragment.position,
+                        selected_outside,
+                        result,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns whether the field is selected for `type_name` in given selection set without an alias.
+    /// Fragments that always apply to `type_name` are looked into.
+    fn selects_field(
+        &self,
+        type_name: &str,
+        selection_set: &'a SelectionSet<'src>,
+        seen_fragments: &mut Vec<&'a str>,
+    ) -> bool {
+        selection_set
+            .selections
+            .iter()
+            .any(|selection| match selection {
+                Selection::Field(field) => {
+                    field.name.name == self.field_name
+                        && field
+                            .alias
+                            .is_none_or(|alias| alias.name == self.field_name)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.fragments.get(spread.fragment_name.name) else {
+                        return false;
+                    };
+                    if !self.always_applies(fragment.type_condition.name, type_name)
+                        || seen_fragments.contains(&fragment.name.name)
+                    {
+                        return false;
+                    }
+                    seen_fragments.push(fragment.name.name);
+                    self.selects_field(type_name, &fragment.selection_set, seen_fragments)
+                }
+                Selection::InlineFragment(fragment) => {
+                    fragment.type_condition.is_none_or(|type_condition| {
+                        self.always_applies(type_condition.name, type_name)
+                    }) && self.selects_field(type_name, &fragment.selection_set, seen_fragments)
+                }
+            })
+    }
+
+    /// Returns whether a fragment with given type condition always applies to `type_name`.
+    fn always_applies(&self, type_condition: &str, type_name: &str) -> bool {
+        if type_condition == type_name {
+            return true;
+        }
+        let interfaces = match self.definitions.get_type(type_name).map(|ty| &**ty) {
+            Some(TypeDefinition::Object(def)) => &def.interfaces,
+            Some(TypeDefinition::Interface(def)) => &def.interfaces,
+            _ => return false,
+        };
+        interfaces
+            .iter()
+            .any(|interface| *interface == type_condition)
+    }
+}
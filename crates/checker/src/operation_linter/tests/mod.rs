@@ -0,0 +1,204 @@
+use std::borrow::Cow;
+
+use graphql_builtins::generate_builtins;
+use graphql_type_system::Schema;
+use insta::assert_debug_snapshot;
+use nitrogql_ast::base::Pos;
+use nitrogql_parser::{parse_operation_document, parse_type_system_document};
+use nitrogql_semantics::{ast_to_type_system, resolve_extensions, FragmentRegistry};
+
+use crate::{
+    lint_error::{LintError, LintRuleSetting},
+    operation_linter::{lint_operation_document, OperationLintRule},
+    Severity,
+};
+
+fn type_system() -> Schema<Cow<'static, str>, Pos> {
+    let mut doc = parse_type_system_document(
+        "
+        type Query {
+            me: User!
+            user(id: ID!): User
+            search(text: String!): [SearchResult!]!
+        }
+        type Mutation {
+            updateUser(id: ID!, input: UserInput!): User
+        }
+        input UserInput {
+            name: String
+        }
+        interface Node {
+            id: ID!
+        }
+        type User implements Node {
+            id: ID!
+            name: String!
+            friends: [User!]!
+            posts: [Post!]!
+        }
+        type Post implements Node {
+            id: ID!
+            title: String!
+            author: User!
+        }
+        type Tag {
+            name: String!
+        }
+        union SearchResult = User | Post | Tag
+    ",
+    )
+    .unwrap();
+    doc.extend(generate_builtins());
+    let doc = resolve_extensions(doc).unwrap();
+    ast_to_type_system(&doc)
+}
+
+fn lint(source: &str, rule: OperationLintRule) -> Vec<LintError> {
+    let schema = type_system();
+    let doc = parse_operation_document(source).unwrap();
+    lint_operation_document(
+        &schema,
+        &doc,
+        &FragmentRegistry::new(),
+        &[LintRuleSetting {
+            rule,
+            severity: Severity::Error,
+        }],
+    )
+}
+
+#[test]
+fn named_operations() {
+    let errors = lint(
+        "
+        query { me { id } }
+        query MeQuery { me { id } }
+        ",
+        OperationLintRule::NamedOperations,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn operation_name_suffix() {
+    let errors = lint(
+        "
+        query GetMe { me { id } }
+        query MeQuery { me { id } }
+        mutation UpdateUser($id: ID!, $input: UserInput!) {
+            updateUser(id: $id, input: $input) { id }
+        }
+        mutation UpdateUserMutation($id: ID!, $input: UserInput!) {
+            updateUser(id: $id, input: $input) { id }
+        }
+        ",
+        OperationLintRule::OperationNameSuffix,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn no_mutation_literal_arguments() {
+    let errors = lint(
+        "
+        query UserQuery { user(id: \"1\") { id } }
+        mutation UpdateMutation($input: UserInput!) {
+            updateUser(id: \"1\", input: $input) { ...F }
+        }
+        fragment F on User {
+            friends { id }
+            posts { id author { id } }
+        }
+        mutation OtherMutation($id: ID!) {
+            updateUser(id: $id, input: { name: \"foo\" }) { id }
+        }
+        ",
+        OperationLintRule::NoMutationLiteralArguments,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn max_selection_depth() {
+    let errors = lint(
+        "
+        query ShallowQuery { me { id friends { id } } }
+        query DeepQuery {
+            me {
+                friends {
+                    id
+                    friends { id }
+                    ...F
+                }
+            }
+        }
+        fragment F on User {
+            posts { id }
+        }
+        ",
+        OperationLintRule::MaxSelectionDepth { max_depth: 3 },
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn require_id_field() {
+    let errors = lint(
+        "
+        query MeQuery {
+            me { name friends { id name } posts { title } }
+            aliased: me { myId: id }
+            search(text: \"foo\") {
+                ... on Node { id }
+                ... on User { name }
+                ... on Post { author { ...UserId } }
+                ... on Tag { name }
+            }
+        }
+        fragment UserId on User {
+            id
+        }
+        fragment UserName on User {
+            name
+        }
+        ",
+        OperationLintRule::RequireIdField {
+            field_name: "id".to_owned(),
+        },
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn rule_id_and_severity() {
+    let schema = type_system();
+    let doc = parse_operation_document("query Me { me { name } }").unwrap();
+    let errors = lint_operation_document(
+        &schema,
+        &doc,
+        &FragmentRegistry::new(),
+        &[
+            LintRuleSetting {
+                rule: OperationLintRule::OperationNameSuffix,
+                severity: Severity::Warning,
+            },
+            LintRuleSetting {
+                rule: OperationLintRule::RequireIdField {
+                    field_name: "id".to_owned(),
+                },
+                severity: Severity::Error,
+            },
+        ],
+    );
+    let errors = errors
+        .iter()
+        .map(|error| (error.rule_id, error.severity))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            ("operation-name-suffix", Severity::Warning),
+            ("require-id-field", Severity::Error),
+        ]
+    );
+}
@@ -13,6 +13,7 @@ use self::{
 
 use super::{
     common::check_directives,
+    custom_scalar::CustomScalarRules,
     error::{CheckError, CheckErrorMessage},
     types::inout_kind_of_type,
 };
@@ -23,6 +24,9 @@ mod interfaces;
 #[cfg(test)]
 mod tests;
 
+/// Values in type system documents are checked only against built-in scalars.
+static NO_CUSTOM_SCALARS: CustomScalarRules = CustomScalarRules::new();
+
 /// Checks for invalid type system definition document.
 pub fn check_type_system_document(document: &TypeSystemDocument) -> Vec<CheckError> {
     let definition_map = generate_definition_map(document);
@@ -71,6 +75,7 @@ pub fn check_type_system_document(document: &TypeSystemDocument) -> Vec<CheckErr
 fn check_schema(d: &SchemaDefinition, definitions: &DefinitionMap, result: &mut Vec<CheckError>) {
     check_directives(
         &definitions.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &d.directives,
         "SCHEMA",
@@ -103,6 +108,7 @@ fn check_scalar(
     }
     check_directives(
         &definition_map.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &scalar.directives,
         "SCALAR",
@@ -120,6 +126,7 @@ fn check_object(
     }
     check_directives(
         &definitions.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &object.directives,
         "OBJECT",
@@ -199,6 +206,7 @@ fn check_interface(
     }
     check_directives(
         &definitions.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &interface.directives,
         "INTERFACE",
@@ -224,7 +232,7 @@ fn check_interface(
             &definitions.type_system,
             f.r#type.unwrapped_type().name.name,
         )
-        .map_or(false, |k| !k.is_output_type())
+        .is_some_and(|k| !k.is_output_type())
         {
             result.push(
                 CheckErrorMessage::NoInputType {
@@ -272,6 +280,7 @@ fn check_union(
     }
     check_directives(
         &definitions.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &union.directives,
         "UNION",
@@ -325,6 +334,7 @@ fn check_enum(
     }
     check_directives(
         &definitions.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &enum_def.directives,
         "ENUM",
@@ -345,6 +355,7 @@ fn check_enum(
         }
         check_directives(
             &definitions.type_system,
+            &NO_CUSTOM_SCALARS,
             None,
             &v.directives,
             "ENUM_VALUE",
@@ -363,6 +374,7 @@ fn check_input_object(
     }
     check_directives(
         &definitions.type_system,
+        &NO_CUSTOM_SCALARS,
         None,
         &input.directives,
         "INPUT_OBJECT",
@@ -386,6 +398,7 @@ fn check_input_object(
         }
         check_directives(
             &definitions.type_system,
+            &NO_CUSTOM_SCALARS,
             None,
             &f.directives,
             "INPUT_FIELD_DEFINITION",
@@ -465,6 +478,7 @@ fn check_arguments_definition(
 
         check_directives(
             &definitions.type_system,
+            &NO_CUSTOM_SCALARS,
             None,
             &v.directives,
             "ARGUMENT_DEFINITION",
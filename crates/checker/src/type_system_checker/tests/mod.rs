@@ -28,6 +28,10 @@ mod directives {
                 position: Pos {
                     line: 1,
                     column: 8,
+                    end_line: 1,
+                    end_column: 86,
+                    offset: 9,
+                    end_offset: 87,
                     file: 0,
                     builtin: false,
                 },
@@ -63,6 +67,10 @@ mod directives {
                 position: Pos {
                     line: 1,
                     column: 8,
+                    end_line: 2,
+                    end_column: 8,
+                    offset: 9,
+                    end_offset: 66,
                     file: 0,
                     builtin: false,
                 },
@@ -75,6 +83,10 @@ mod directives {
                 position: Pos {
                     line: 6,
                     column: 8,
+                    end_line: 7,
+                    end_column: 8,
+                    offset: 148,
+                    end_offset: 213,
                     file: 0,
                     builtin: false,
                 },
@@ -106,6 +118,10 @@ mod directives {
                 position: Pos {
                     line: 1,
                     column: 19,
+                    end_line: 1,
+                    end_column: 27,
+                    offset: 20,
+                    end_offset: 28,
                     file: 0,
                     builtin: false,
                 },
@@ -135,6 +151,10 @@ mod directives {
                 position: Pos {
                     line: 1,
                     column: 26,
+                    end_line: 1,
+                    end_column: 32,
+                    offset: 27,
+                    end_offset: 33,
                     file: 0,
                     builtin: false,
                 },
@@ -164,6 +184,10 @@ mod directives {
                 position: Pos {
                     line: 1,
                     column: 41,
+                    end_line: 1,
+                    end_column: 45,
+                    offset: 42,
+                    end_offset: 46,
                     file: 0,
                     builtin: false,
                 },
@@ -202,6 +226,10 @@ mod directives {
                 position: Pos {
                     line: 2,
                     column: 18,
+                    end_line: 2,
+                    end_column: 24,
+                    offset: 46,
+                    end_offset: 52,
                     file: 0,
                     builtin: false,
                 },
@@ -214,6 +242,10 @@ mod directives {
                 position: Pos {
                     line: 3,
                     column: 18,
+                    end_line: 3,
+                    end_column: 29,
+                    offset: 72,
+                    end_offset: 83,
                     file: 0,
                     builtin: false,
                 },
@@ -226,6 +258,10 @@ mod directives {
                 position: Pos {
                     line: 4,
                     column: 18,
+                    end_line: 4,
+                    end_column: 25,
+                    offset: 103,
+                    end_offset: 110,
                     file: 0,
                     builtin: false,
                 },
@@ -278,6 +314,10 @@ mod schemas {
                 position: Pos {
                     line: 2,
                     column: 20,
+                    end_line: 2,
+                    end_column: 25,
+                    offset: 54,
+                    end_offset: 59,
                     file: 0,
                     builtin: false,
                 },
@@ -319,6 +359,10 @@ mod schemas {
                 position: Pos {
                     line: 2,
                     column: 15,
+                    end_line: 2,
+                    end_column: 20,
+                    offset: 66,
+                    end_offset: 71,
                     file: 0,
                     builtin: false,
                 },
@@ -354,6 +398,10 @@ mod scalars {
                 position: Pos {
                     line: 1,
                     column: 19,
+                    end_line: 1,
+                    end_column: 24,
+                    offset: 20,
+                    end_offset: 25,
                     file: 0,
                     builtin: false,
                 },
@@ -378,6 +426,10 @@ mod scalars {
                 position: Pos {
                     line: 2,
                     column: 19,
+                    end_line: 3,
+                    end_column: 8,
+                    offset: 70,
+                    end_offset: 83,
                     file: 0,
                     builtin: false,
                 },
@@ -417,6 +469,10 @@ mod objects {
                 position: Pos {
                     line: 1,
                     column: 17,
+                    end_line: 1,
+                    end_column: 25,
+                    offset: 18,
+                    end_offset: 26,
                     file: 0,
                     builtin: false,
                 },
@@ -445,6 +501,10 @@ mod objects {
                 position: Pos {
                     line: 3,
                     column: 16,
+                    end_line: 3,
+                    end_column: 19,
+                    offset: 72,
+                    end_offset: 75,
                     file: 0,
                     builtin: false,
                 },
@@ -474,6 +534,10 @@ mod objects {
                 position: Pos {
                     line: 2,
                     column: 16,
+                    end_line: 2,
+                    end_column: 21,
+                    offset: 43,
+                    end_offset: 48,
                     file: 0,
                     builtin: false,
                 },
@@ -501,6 +565,10 @@ mod objects {
                 position: Pos {
                     line: 2,
                     column: 31,
+                    end_line: 2,
+                    end_column: 36,
+                    offset: 58,
+                    end_offset: 63,
                     file: 0,
                     builtin: false,
                 },
@@ -539,6 +607,10 @@ mod objects {
                 position: Pos {
                     line: 2,
                     column: 23,
+                    end_line: 2,
+                    end_column: 28,
+                    offset: 50,
+                    end_offset: 55,
                     file: 0,
                     builtin: false,
                 },
@@ -549,6 +621,10 @@ mod objects {
                 position: Pos {
                     line: 3,
                     column: 16,
+                    end_line: 3,
+                    end_column: 22,
+                    offset: 88,
+                    end_offset: 94,
                     file: 0,
                     builtin: false,
                 },
@@ -561,6 +637,10 @@ mod objects {
                 position: Pos {
                     line: 4,
                     column: 34,
+                    end_line: 4,
+                    end_column: 37,
+                    offset: 138,
+                    end_offset: 141,
                     file: 0,
                     builtin: false,
                 },
@@ -573,6 +653,10 @@ mod objects {
                 position: Pos {
                     line: 5,
                     column: 28,
+                    end_line: 5,
+                    end_column: 34,
+                    offset: 186,
+                    end_offset: 192,
                     file: 0,
                     builtin: false,
                 },
@@ -601,6 +685,10 @@ mod objects {
                 position: Pos {
                     line: 2,
                     column: 28,
+                    end_line: 2,
+                    end_column: 33,
+                    offset: 55,
+                    end_offset: 60,
                     file: 0,
                     builtin: false,
                 },
@@ -650,6 +738,10 @@ mod objects {
                 position: Pos {
                     line: 10,
                     column: 16,
+                    end_line: 10,
+                    end_column: 20,
+                    offset: 277,
+                    end_offset: 281,
                     file: 0,
                     builtin: false,
                 },
@@ -662,6 +754,10 @@ mod objects {
                 position: Pos {
                     line: 8,
                     column: 17,
+                    end_line: 8,
+                    end_column: 23,
+                    offset: 200,
+                    end_offset: 206,
                     file: 0,
                     builtin: false,
                 },
@@ -675,6 +771,10 @@ mod objects {
                 position: Pos {
                     line: 13,
                     column: 16,
+                    end_line: 13,
+                    end_column: 19,
+                    offset: 360,
+                    end_offset: 363,
                     file: 0,
                     builtin: false,
                 },
@@ -688,6 +788,10 @@ mod objects {
                 position: Pos {
                     line: 13,
                     column: 20,
+                    end_line: 13,
+                    end_column: 32,
+                    offset: 364,
+                    end_offset: 376,
                     file: 0,
                     builtin: false,
                 },
@@ -700,6 +804,10 @@ mod objects {
                 position: Pos {
                     line: 16,
                     column: 20,
+                    end_line: 16,
+                    end_column: 23,
+                    offset: 471,
+                    end_offset: 474,
                     file: 0,
                     builtin: false,
                 },
@@ -712,6 +820,10 @@ mod objects {
                 position: Pos {
                     line: 19,
                     column: 35,
+                    end_line: 19,
+                    end_column: 39,
+                    offset: 583,
+                    end_offset: 587,
                     file: 0,
                     builtin: false,
                 },
@@ -724,6 +836,10 @@ mod objects {
                 position: Pos {
                     line: 19,
                     column: 16,
+                    end_line: 19,
+                    end_column: 19,
+                    offset: 564,
+                    end_offset: 567,
                     file: 0,
                     builtin: false,
                 },
@@ -761,6 +877,10 @@ mod interfaces {
                 position: Pos {
                     line: 1,
                     column: 22,
+                    end_line: 1,
+                    end_column: 35,
+                    offset: 23,
+                    end_offset: 36,
                     file: 0,
                     builtin: false,
                 },
@@ -788,6 +908,10 @@ mod interfaces {
                 position: Pos {
                     line: 3,
                     column: 16,
+                    end_line: 3,
+                    end_column: 19,
+                    offset: 77,
+                    end_offset: 80,
                     file: 0,
                     builtin: false,
                 },
@@ -817,6 +941,10 @@ mod interfaces {
                 position: Pos {
                     line: 2,
                     column: 16,
+                    end_line: 2,
+                    end_column: 21,
+                    offset: 53,
+                    end_offset: 58,
                     file: 0,
                     builtin: false,
                 },
@@ -857,6 +985,10 @@ mod interfaces {
                 position: Pos {
                     line: 7,
                     column: 36,
+                    end_line: 7,
+                    end_column: 44,
+                    offset: 246,
+                    end_offset: 254,
                     file: 0,
                     builtin: false,
                 },
@@ -892,6 +1024,10 @@ mod interfaces {
                 position: Pos {
                     line: 2,
                     column: 23,
+                    end_line: 2,
+                    end_column: 28,
+                    offset: 55,
+                    end_offset: 60,
                     file: 0,
                     builtin: false,
                 },
@@ -902,6 +1038,10 @@ mod interfaces {
                 position: Pos {
                     line: 3,
                     column: 16,
+                    end_line: 3,
+                    end_column: 22,
+                    offset: 93,
+                    end_offset: 99,
                     file: 0,
                     builtin: false,
                 },
@@ -914,6 +1054,10 @@ mod interfaces {
                 position: Pos {
                     line: 4,
                     column: 34,
+                    end_line: 4,
+                    end_column: 37,
+                    offset: 143,
+                    end_offset: 146,
                     file: 0,
                     builtin: false,
                 },
@@ -926,6 +1070,10 @@ mod interfaces {
                 position: Pos {
                     line: 5,
                     column: 28,
+                    end_line: 5,
+                    end_column: 34,
+                    offset: 191,
+                    end_offset: 197,
                     file: 0,
                     builtin: false,
                 },
@@ -975,6 +1123,10 @@ mod interfaces {
                 position: Pos {
                     line: 10,
                     column: 16,
+                    end_line: 10,
+                    end_column: 20,
+                    offset: 282,
+                    end_offset: 286,
                     file: 0,
                     builtin: false,
                 },
@@ -987,6 +1139,10 @@ mod interfaces {
                 position: Pos {
                     line: 8,
                     column: 22,
+                    end_line: 8,
+                    end_column: 28,
+                    offset: 205,
+                    end_offset: 211,
                     file: 0,
                     builtin: false,
                 },
@@ -1000,6 +1156,10 @@ mod interfaces {
                 position: Pos {
                     line: 13,
                     column: 16,
+                    end_line: 13,
+                    end_column: 19,
+                    offset: 370,
+                    end_offset: 373,
                     file: 0,
                     builtin: false,
                 },
@@ -1013,6 +1173,10 @@ mod interfaces {
                 position: Pos {
                     line: 13,
                     column: 20,
+                    end_line: 13,
+                    end_column: 32,
+                    offset: 374,
+                    end_offset: 386,
                     file: 0,
                     builtin: false,
                 },
@@ -1025,6 +1189,10 @@ mod interfaces {
                 position: Pos {
                     line: 16,
                     column: 20,
+                    end_line: 16,
+                    end_column: 23,
+                    offset: 486,
+                    end_offset: 489,
                     file: 0,
                     builtin: false,
                 },
@@ -1037,6 +1205,10 @@ mod interfaces {
                 position: Pos {
                     line: 19,
                     column: 35,
+                    end_line: 19,
+                    end_column: 39,
+                    offset: 603,
+                    end_offset: 607,
                     file: 0,
                     builtin: false,
                 },
@@ -1049,6 +1221,10 @@ mod interfaces {
                 position: Pos {
                     line: 19,
                     column: 16,
+                    end_line: 19,
+                    end_column: 19,
+                    offset: 584,
+                    end_offset: 587,
                     file: 0,
                     builtin: false,
                 },
@@ -1077,6 +1253,10 @@ mod interfaces {
                 position: Pos {
                     line: 1,
                     column: 35,
+                    end_line: 1,
+                    end_column: 36,
+                    offset: 36,
+                    end_offset: 37,
                     file: 0,
                     builtin: false,
                 },
@@ -1113,6 +1293,10 @@ mod unions {
                 position: Pos {
                     line: 1,
                     column: 18,
+                    end_line: 1,
+                    end_column: 21,
+                    offset: 19,
+                    end_offset: 22,
                     file: 0,
                     builtin: false,
                 },
@@ -1139,6 +1323,10 @@ mod unions {
                 position: Pos {
                     line: 2,
                     column: 22,
+                    end_line: 2,
+                    end_column: 27,
+                    offset: 77,
+                    end_offset: 82,
                     file: 0,
                     builtin: false,
                 },
@@ -1167,6 +1355,10 @@ mod unions {
                 position: Pos {
                     line: 1,
                     column: 32,
+                    end_line: 1,
+                    end_column: 33,
+                    offset: 33,
+                    end_offset: 34,
                     file: 0,
                     builtin: false,
                 },
@@ -1199,6 +1391,10 @@ mod unions {
                 position: Pos {
                     line: 1,
                     column: 25,
+                    end_line: 1,
+                    end_column: 28,
+                    offset: 26,
+                    end_offset: 29,
                     file: 0,
                     builtin: false,
                 },
@@ -1211,6 +1407,10 @@ mod unions {
                 position: Pos {
                     line: 1,
                     column: 37,
+                    end_line: 1,
+                    end_column: 42,
+                    offset: 38,
+                    end_offset: 43,
                     file: 0,
                     builtin: false,
                 },
@@ -1223,6 +1423,10 @@ mod unions {
                 position: Pos {
                     line: 1,
                     column: 45,
+                    end_line: 1,
+                    end_column: 49,
+                    offset: 46,
+                    end_offset: 50,
                     file: 0,
                     builtin: false,
                 },
@@ -1235,6 +1439,10 @@ mod unions {
                 position: Pos {
                     line: 1,
                     column: 52,
+                    end_line: 1,
+                    end_column: 57,
+                    offset: 53,
+                    end_offset: 58,
                     file: 0,
                     builtin: false,
                 },
@@ -1270,6 +1478,10 @@ mod enums {
                 position: Pos {
                     line: 1,
                     column: 17,
+                    end_line: 1,
+                    end_column: 20,
+                    offset: 18,
+                    end_offset: 21,
                     file: 0,
                     builtin: false,
                 },
@@ -1300,6 +1512,10 @@ mod enums {
                 position: Pos {
                     line: 4,
                     column: 21,
+                    end_line: 4,
+                    end_column: 23,
+                    offset: 140,
+                    end_offset: 142,
                     file: 0,
                     builtin: false,
                 },
@@ -1312,6 +1528,10 @@ mod enums {
                 position: Pos {
                     line: 6,
                     column: 18,
+                    end_line: 7,
+                    end_column: 16,
+                    offset: 188,
+                    end_offset: 207,
                     file: 0,
                     builtin: false,
                 },
@@ -1349,6 +1569,10 @@ mod input_objects {
                 position: Pos {
                     line: 1,
                     column: 18,
+                    end_line: 1,
+                    end_column: 23,
+                    offset: 19,
+                    end_offset: 24,
                     file: 0,
                     builtin: false,
                 },
@@ -1380,6 +1604,10 @@ mod input_objects {
                 position: Pos {
                     line: 4,
                     column: 26,
+                    end_line: 4,
+                    end_column: 28,
+                    offset: 185,
+                    end_offset: 187,
                     file: 0,
                     builtin: false,
                 },
@@ -1392,6 +1620,10 @@ mod input_objects {
                 position: Pos {
                     line: 5,
                     column: 28,
+                    end_line: 6,
+                    end_column: 16,
+                    offset: 220,
+                    end_offset: 239,
                     file: 0,
                     builtin: false,
                 },
@@ -1422,6 +1654,10 @@ mod input_objects {
                 position: Pos {
                     line: 2,
                     column: 16,
+                    end_line: 2,
+                    end_column: 23,
+                    offset: 43,
+                    end_offset: 50,
                     file: 0,
                     builtin: false,
                 },
@@ -1432,6 +1668,10 @@ mod input_objects {
                 position: Pos {
                     line: 4,
                     column: 16,
+                    end_line: 4,
+                    end_column: 22,
+                    offset: 102,
+                    end_offset: 108,
                     file: 0,
                     builtin: false,
                 },
@@ -1469,6 +1709,10 @@ mod input_objects {
                 position: Pos {
                     line: 3,
                     column: 24,
+                    end_line: 3,
+                    end_column: 30,
+                    offset: 80,
+                    end_offset: 86,
                     file: 0,
                     builtin: false,
                 },
@@ -1481,6 +1725,10 @@ mod input_objects {
                 position: Pos {
                     line: 4,
                     column: 23,
+                    end_line: 4,
+                    end_column: 30,
+                    offset: 111,
+                    end_offset: 118,
                     file: 0,
                     builtin: false,
                 },
@@ -1494,7 +1742,7 @@ mod input_objects {
     }
 }
 
-fn parse_to_type_system_document(source: &str) -> TypeSystemDocument {
+fn parse_to_type_system_document(source: &str) -> TypeSystemDocument<'_> {
     use graphql_builtins::generate_builtins;
 
     let mut doc = parse_type_system_document(source).unwrap();
@@ -0,0 +1,83 @@
+use nitrogql_ast::{
+    directive::Directive,
+    type_system::{TypeDefinition, TypeSystemDocument},
+    value::Value,
+};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{directive_definitions, output_fields, type_definitions, RuleResult};
+
+/// Reports `@deprecated` directives without a non-empty reason.
+pub fn deprecation_reason(document: &TypeSystemDocument, result: &mut RuleResult) {
+    for def in type_definitions(document) {
+        let type_name = def.name().name;
+        for field in output_fields(def) {
+            let field_name = format!("{}.{}", type_name, field.name.name);
+            check_directives(&field_name, &field.directives, result);
+            for arg in field.arguments.iter().flat_map(|args| &args.input_values) {
+                check_directives(
+                    &format!("{}({}:)", field_name, arg.name.name),
+                    &arg.directives,
+                    result,
+                );
+            }
+        }
+        match def {
+            TypeDefinition::InputObject(def) => {
+                for field in def.fields.iter() {
+                    check_directives(
+                        &format!("{}.{}", type_name, field.name.name),
+                        &field.directives,
+                        result,
+                    );
+                }
+            }
+            TypeDefinition::Enum(def) => {
+                for value in def.values.iter() {
+                    check_directives(
+                        &format!("{}.{}", type_name, value.name.name),
+                        &value.directives,
+                        result,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    for def in directive_definitions(document) {
+        for arg in def.arguments.iter().flat_map(|args| &args.input_values) {
+            check_directives(
+                &format!("@{}({}:)", def.name.name, arg.name.name),
+                &arg.directives,
+                result,
+            );
+        }
+    }
+}
+
+fn check_directives(name: &str, directives: &[Directive], result: &mut RuleResult) {
+    for directive in directives {
+        if directive.name.name != "deprecated" {
+            continue;
+        }
+        let reason = directive.arguments.iter().find_map(|args| {
+            args.arguments
+                .iter()
+                .find(|(arg_name, _)| arg_name.name == "reason")
+                .map(|(_, value)| value)
+        });
+        let has_reason = matches!(
+            reason,
+            Some(Value::StringValue(reason)) if !reason.value.trim().is_empty()
+        );
+        if !has_reason {
+            result.push((
+                directive.position,
+                LintErrorMessage::DeprecationWithoutReason {
+                    name: name.to_owned(),
+                },
+            ));
+        }
+    }
+}
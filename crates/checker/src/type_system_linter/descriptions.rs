@@ -0,0 +1,83 @@
+use nitrogql_ast::{
+    type_system::{ArgumentsDefinition, TypeDefinition, TypeSystemDocument},
+    value::StringValue,
+};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{directive_definitions, output_fields, type_definitions, RuleResult};
+
+/// Reports types, fields and arguments without a description.
+pub fn require_description(document: &TypeSystemDocument, result: &mut RuleResult) {
+    for def in type_definitions(document) {
+        let (description, name) = match def {
+            TypeDefinition::Scalar(def) => (&def.description, &def.name),
+            TypeDefinition::Object(def) => (&def.description, &def.name),
+            TypeDefinition::Interface(def) => (&def.description, &def.name),
+            TypeDefinition::Union(def) => (&def.description, &def.name),
+            TypeDefinition::Enum(def) => (&def.description, &def.name),
+            TypeDefinition::InputObject(def) => (&def.description, &def.name),
+        };
+        if !has_description(description) {
+            result.push((
+                name.position,
+                LintErrorMessage::MissingDescription {
+                    kind: "Type",
+                    name: name.name.to_owned(),
+                },
+            ));
+        }
+        for field in output_fields(def) {
+            let field_name = format!("{}.{}", name.name, field.name.name);
+            if !has_description(&field.description) {
+                result.push((
+                    field.name.position,
+                    LintErrorMessage::MissingDescription {
+                        kind: "Field",
+                        name: field_name.clone(),
+                    },
+                ));
+            }
+            check_arguments(&field_name, &field.arguments, result);
+        }
+        if let TypeDefinition::InputObject(def) = def {
+            for field in def.fields.iter() {
+                if !has_description(&field.description) {
+                    result.push((
+                        field.name.position,
+                        LintErrorMessage::MissingDescription {
+                            kind: "Input field",
+                            name: format!("{}.{}", name.name, field.name.name),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+    for def in directive_definitions(document) {
+        check_arguments(&format!("@{}", def.name.name), &def.arguments, result);
+    }
+}
+
+fn check_arguments(owner: &str, arguments: &Option<ArgumentsDefinition>, result: &mut RuleResult) {
+    let Some(arguments) = arguments else {
+        return;
+    };
+    for arg in arguments.input_values.iter() {
+        if !has_description(&arg.description) {
+            result.push((
+                arg.name.position,
+                LintErrorMessage::MissingDescription {
+                    kind: "Argument",
+                    name: format!("{}({}:)", owner, arg.name.name),
+                },
+            ));
+        }
+    }
+}
+
+fn has_description(description: &Option<StringValue>) -> bool {
+    description
+        .as_ref()
+        .is_some_and(|description| !description.value.trim().is_empty())
+}
@@ -0,0 +1,121 @@
+use nitrogql_ast::{
+    base::{HasPos, Pos},
+    type_system::{
+        DirectiveDefinition, FieldDefinition, TypeDefinition, TypeSystemDefinition,
+        TypeSystemDocument,
+    },
+};
+
+use crate::lint_error::{LintError, LintErrorMessage, LintRuleSetting};
+
+use self::{
+    deprecation_reason::deprecation_reason,
+    descriptions::require_description,
+    naming::{enum_values_upper_case, input_type_suffix},
+    nullable_list::no_nullable_list_of_nullable_items,
+    relay_connection::relay_connection,
+};
+
+mod deprecation_reason;
+mod descriptions;
+mod naming;
+mod nullable_list;
+mod relay_connection;
+#[cfg(test)]
+mod tests;
+
+/// Lint rule for type system documents.
+#[derive(Debug, Clone)]
+pub enum TypeSystemLintRule {
+    /// Types, fields and arguments must have a description.
+    RequireDescription,
+    /// Enum values must be UPPER_CASE.
+    EnumValuesUpperCase,
+    /// Names of input object types must end with `Input`.
+    InputTypeSuffix,
+    /// Nullable list types must not have nullable items.
+    NoNullableListOfNullableItems,
+    /// `@deprecated` must be given a non-empty reason.
+    DeprecationReason,
+    /// Connection types must follow the Relay Cursor Connections Specification.
+    RelayConnection,
+}
+
+impl TypeSystemLintRule {
+    /// Id of the rule, as used in config files.
+    pub fn id(&self) -> &'static str {
+        match self {
+            TypeSystemLintRule::RequireDescription => "require-description",
+            TypeSystemLintRule::EnumValuesUpperCase => "enum-values-upper-case",
+            TypeSystemLintRule::InputTypeSuffix => "input-type-suffix",
+            TypeSystemLintRule::NoNullableListOfNullableItems => {
+                "no-nullable-list-of-nullable-items"
+            }
+            TypeSystemLintRule::DeprecationReason => "deprecation-reason",
+            TypeSystemLintRule::RelayConnection => "relay-connection",
+        }
+    }
+}
+
+/// Lints a type system document with given rules.
+/// The document should have its extensions resolved.
+/// Built-in definitions are not linted.
+pub fn lint_type_system_document(
+    document: &TypeSystemDocument,
+    rules: &[LintRuleSetting<TypeSystemLintRule>],
+) -> Vec<LintError> {
+    let mut result = vec![];
+    for setting in rules {
+        let mut found = vec![];
+        match setting.rule {
+            TypeSystemLintRule::RequireDescription => require_description(document, &mut found),
+            TypeSystemLintRule::EnumValuesUpperCase => enum_values_upper_case(document, &mut found),
+            TypeSystemLintRule::InputTypeSuffix => input_type_suffix(document, &mut found),
+            TypeSystemLintRule::NoNullableListOfNullableItems => {
+                no_nullable_list_of_nullable_items(document, &mut found)
+            }
+            TypeSystemLintRule::DeprecationReason => deprecation_reason(document, &mut found),
+            TypeSystemLintRule::RelayConnection => relay_connection(document, &mut found),
+        }
+        result.extend(found.into_iter().map(|(position, message)| LintError {
+            rule_id: setting.rule.id(),
+            severity: setting.severity,
+            position,
+            message,
+        }));
+    }
+    result.sort_by_key(|error| (error.position.file, error.position.offset));
+    result
+}
+
+/// Iterates over type definitions in given document, except built-in ones.
+fn type_definitions<'a, 'src>(
+    document: &'a TypeSystemDocument<'src>,
+) -> impl Iterator<Item = &'a TypeDefinition<'src>> {
+    document.definitions.iter().filter_map(|def| match def {
+        TypeSystemDefinition::TypeDefinition(def) if !def.position().builtin => Some(def),
+        _ => None,
+    })
+}
+
+/// Iterates over directive definitions in given document, except built-in ones.
+fn directive_definitions<'a, 'src>(
+    document: &'a TypeSystemDocument<'src>,
+) -> impl Iterator<Item = &'a DirectiveDefinition<'src>> {
+    document.definitions.iter().filter_map(|def| match def {
+        TypeSystemDefinition::DirectiveDefinition(def) if !def.position.builtin => Some(def),
+        _ => None,
+    })
+}
+
+/// Returns fields of given type if it is an object or interface type.
+fn output_fields<'a, 'src>(def: &'a TypeDefinition<'src>) -> &'a [FieldDefinition<'src>] {
+    match def {
+        TypeDefinition::Object(def) => &def.fields,
+        TypeDefinition::Interface(def) => &def.fields,
+        _ => &[],
+    }
+}
+
+/// Problems found by one rule.
+type RuleResult = Vec<(Pos, LintErrorMessage)>;
@@ -0,0 +1,47 @@
+use nitrogql_ast::type_system::{TypeDefinition, TypeSystemDocument};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{type_definitions, RuleResult};
+
+/// Reports enum values that are not UPPER_CASE.
+pub fn enum_values_upper_case(document: &TypeSystemDocument, result: &mut RuleResult) {
+    for def in type_definitions(document) {
+        let TypeDefinition::Enum(def) = def else {
+            continue;
+        };
+        for value in def.values.iter() {
+            let is_upper_case = value
+                .name
+                .name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+            if !is_upper_case {
+                result.push((
+                    value.name.position,
+                    LintErrorMessage::EnumValueNotUpperCase {
+                        name: value.name.name.to_owned(),
+                        enum_name: def.name.name.to_owned(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Reports input object types whose name does not end with `Input`.
+pub fn input_type_suffix(document: &TypeSystemDocument, result: &mut RuleResult) {
+    for def in type_definitions(document) {
+        let TypeDefinition::InputObject(def) = def else {
+            continue;
+        };
+        if !def.name.name.ends_with("Input") {
+            result.push((
+                def.name.position,
+                LintErrorMessage::InputTypeSuffix {
+                    name: def.name.name.to_owned(),
+                },
+            ));
+        }
+    }
+}
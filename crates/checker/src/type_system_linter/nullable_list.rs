@@ -0,0 +1,48 @@
+use nitrogql_ast::{
+    r#type::Type,
+    type_system::{TypeDefinition, TypeSystemDocument},
+};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{directive_definitions, output_fields, type_definitions, RuleResult};
+
+/// Reports list types where both the list and its items are nullable.
+pub fn no_nullable_list_of_nullable_items(document: &TypeSystemDocument, result: &mut RuleResult) {
+    for def in type_definitions(document) {
+        for field in output_fields(def) {
+            check_type(&field.r#type, result);
+            for arg in field.arguments.iter().flat_map(|args| &args.input_values) {
+                check_type(&arg.r#type, result);
+            }
+        }
+        if let TypeDefinition::InputObject(def) = def {
+            for field in def.fields.iter() {
+                check_type(&field.r#type, result);
+            }
+        }
+    }
+    for def in directive_definitions(document) {
+        for arg in def.arguments.iter().flat_map(|args| &args.input_values) {
+            check_type(&arg.r#type, result);
+        }
+    }
+}
+
+fn check_type(ty: &Type, result: &mut RuleResult) {
+    match ty {
+        Type::Named(_) => {}
+        Type::NonNull(inner) => check_type(&inner.r#type, result),
+        Type::List(list) => {
+            if !list.r#type.is_nonnull() {
+                result.push((
+                    list.position,
+                    LintErrorMessage::NullableListOfNullableItems {
+                        r#type: ty.to_string(),
+                    },
+                ));
+            }
+            check_type(&list.r#type, result);
+        }
+    }
+}
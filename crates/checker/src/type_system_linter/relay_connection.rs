@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use nitrogql_ast::{
+    r#type::Type,
+    type_system::{
+        FieldDefinition, ObjectTypeDefinition, TypeDefinition, TypeSystemDefinition,
+        TypeSystemDocument,
+    },
+};
+
+use crate::lint_error::LintErrorMessage;
+
+use super::{type_definitions, RuleResult};
+
+type TypeMap<'a, 'src> = HashMap<&'src str, &'a TypeDefinition<'src>>;
+
+/// Checks that connection types, their edge types and `PageInfo` follow the Relay Cursor Connections Specification.
+/// Object types whose name ends with `Connection` are considered connection types.
+pub fn relay_connection(document: &TypeSystemDocument, result: &mut RuleResult) {
+    let types: TypeMap = document
+        .definitions
+        .iter()
+        .filter_map(|def| match def {
+            TypeSystemDefinition::TypeDefinition(def) => Some((def.name().name, def)),
+            _ => None,
+        })
+        .collect();
+
+    let mut edge_types = vec![];
+    for def in type_definitions(document) {
+        let TypeDefinition::Object(def) = def else {
+            continue;
+        };
+        if !def.name.name.ends_with("Connection") {
+            continue;
+        }
+        if let Some(edges) = required_field(def, "edges", result) {
+            let edge_type = match strip_nonnull(&edges.r#type) {
+                Type::List(list) => match strip_nonnull(&list.r#type) {
+                    Type::Named(name) => match types.get(name.name.name) {
+                        Some(TypeDefinition::Object(edge_type)) => Some(edge_type),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            };
+            match edge_type {
+                Some(edge_type) => {
+                    if !edge_types
+                        .iter()
+                        .any(|ty: &&ObjectTypeDefinition| ty.name.name == edge_type.name.name)
+                    {
+                        edge_types.push(edge_type);
+                    }
+                }
+                None => result.push((
+                    edges.name.position,
+                    LintErrorMessage::RelayFieldInvalidType {
+                        type_name: def.name.name.to_owned(),
+                        field_name: "edges",
+                        expected: "a list of edge objects",
+                    },
+                )),
+            }
+        }
+        if let Some(page_info) = required_field(def, "pageInfo", result) {
+            if !is_non_null_named(&page_info.r#type, "PageInfo") {
+                result.push((
+                    page_info.name.position,
+                    LintErrorMessage::RelayFieldInvalidType {
+                        type_name: def.name.name.to_owned(),
+                        field_name: "pageInfo",
+                        expected: "'PageInfo!'",
+                    },
+                ));
+            }
+        }
+    }
+
+    for edge_type in edge_types {
+        if let Some(node) = required_field(edge_type, "node", result) {
+            if matches!(strip_nonnull(&node.r#type), Type::List(_)) {
+                result.push((
+                    node.name.position,
+                    LintErrorMessage::RelayFieldInvalidType {
+                        type_name: edge_type.name.name.to_owned(),
+                        field_name: "node",
+                        expected: "a non-list type",
+                    },
+                ));
+            }
+        }
+        check_scalar_field(edge_type, "cursor", &types, result);
+    }
+
+    if let Some(TypeDefinition::Object(page_info)) = types.get("PageInfo") {
+        for field_name in ["hasPreviousPage", "hasNextPage"] {
+            let Some(field) = required_field(page_info, field_name, result) else {
+                continue;
+            };
+            if !is_non_null_named(&field.r#type, "Boolean") {
+                result.push((
+                    field.name.position,
+                    LintErrorMessage::RelayFieldInvalidType {
+                        type_name: page_info.name.name.to_owned(),
+                        field_name,
+                        expected: "'Boolean!'",
+                    },
+                ));
+            }
+        }
+        for field_name in ["startCursor", "endCursor"] {
+            check_scalar_field(page_info, field_name, &types, result);
+        }
+    }
+}
+
+/// Finds a field of given object type. Reports if the field does not exist.
+fn required_field<'a, 'src>(
+    def: &'a ObjectTypeDefinition<'src>,
+    field_name: &'static str,
+    result: &mut RuleResult,
+) -> Option<&'a FieldDefinition<'src>> {
+    let field = def
+        .fields
+        .iter()
+        .find(|field| field.name.name == field_name);
+    if field.is_none() {
+        result.push((
+            def.name.position,
+            LintErrorMessage::RelayFieldMissing {
+                type_name: def.name.name.to_owned(),
+                field_name,
+            },
+        ));
+    }
+    field
+}
+
+/// Checks that given object type has a field of a scalar type.
+fn check_scalar_field(
+    def: &ObjectTypeDefinition,
+    field_name: &'static str,
+    types: &TypeMap,
+    result: &mut RuleResult,
+) {
+    let Some(field) = required_field(def, field_name, result) else {
+        return;
+    };
+    let is_scalar = match strip_nonnull(&field.r#type) {
+        Type::Named(name) => matches!(types.get(name.name.name), Some(TypeDefinition::Scalar(_))),
+        _ => false,
+    };
+    if !is_scalar {
+        result.push((
+            field.name.position,
+            LintErrorMessage::RelayFieldInvalidType {
+                type_name: def.name.name.to_owned(),
+                field_name,
+                expected: "a scalar type",
+            },
+        ));
+    }
+}
+
+fn strip_nonnull<'a, 'src>(ty: &'a Type<'src>) -> &'a Type<'src> {
+    match ty {
+        Type::NonNull(inner) => &inner.r#type,
+        _ => ty,
+    }
+}
+
+fn is_non_null_named(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::NonNull(inner) => {
+            matches!(&inner.r#type, Type::Named(named) if named.name.name == name)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,183 @@
+use graphql_builtins::generate_builtins;
+use insta::assert_debug_snapshot;
+use nitrogql_parser::parse_type_system_document;
+use nitrogql_semantics::resolve_extensions;
+
+use crate::{
+    lint_error::{LintError, LintRuleSetting},
+    type_system_linter::{lint_type_system_document, TypeSystemLintRule},
+    Severity,
+};
+
+fn lint(source: &str, rule: TypeSystemLintRule) -> Vec<LintError> {
+    let mut doc = parse_type_system_document(source).unwrap();
+    doc.extend(generate_builtins());
+    let doc = resolve_extensions(doc).unwrap();
+    lint_type_system_document(
+        &doc,
+        &[LintRuleSetting {
+            rule,
+            severity: Severity::Error,
+        }],
+    )
+}
+
+#[test]
+fn require_description() {
+    let errors = lint(
+        r#"
+        "Root query type."
+        type Query {
+            "Looks up a user."
+            user(
+                "Id of the user."
+                id: ID!
+                includeDeleted: Boolean
+            ): User
+        }
+        type User {
+            ""
+            name: String!
+        }
+        "Input to update a user."
+        input UserInput {
+            name: String
+        }
+        "Marks a field as requiring a role."
+        directive @auth(role: String!) on FIELD_DEFINITION
+        "#,
+        TypeSystemLintRule::RequireDescription,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn enum_values_upper_case() {
+    let errors = lint(
+        "
+        enum Role {
+            ADMIN
+            SUPER_USER_2
+            guest
+            readOnly
+        }
+        ",
+        TypeSystemLintRule::EnumValuesUpperCase,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn input_type_suffix() {
+    let errors = lint(
+        "
+        input UserInput {
+            name: String
+        }
+        input UserFilter {
+            name: String
+        }
+        ",
+        TypeSystemLintRule::InputTypeSuffix,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn no_nullable_list_of_nullable_items() {
+    let errors = lint(
+        "
+        type Query {
+            ok1: [String!]
+            ok2: [String]!
+            ok3: [[String!]!]
+            bad1: [String]
+            bad2(ids: [ID]): [[String!]]
+        }
+        input FilterInput {
+            tags: [String]
+        }
+        ",
+        TypeSystemLintRule::NoNullableListOfNullableItems,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn deprecation_reason() {
+    let errors = lint(
+        r#"
+        type Query {
+            ok: String @deprecated(reason: "Use 'other' instead.")
+            noReason: String @deprecated
+            emptyReason: String @deprecated(reason: "")
+            field(arg: String @deprecated): String
+        }
+        enum Role {
+            ADMIN
+            GUEST @deprecated
+        }
+        "#,
+        TypeSystemLintRule::DeprecationReason,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn relay_connection() {
+    let errors = lint(
+        "
+        type Query {
+            users: UserConnection!
+            posts: PostConnection!
+        }
+        type User {
+            id: ID!
+        }
+        type Post {
+            id: ID!
+        }
+        type UserConnection {
+            edges: [UserEdge!]!
+            pageInfo: PageInfo!
+        }
+        type UserEdge {
+            node: User!
+            cursor: String!
+        }
+        type PostConnection {
+            edges: [Post!]
+            pageInfo: PageInfo
+        }
+        type BrokenConnection {
+            edges: [BrokenEdge]
+        }
+        type BrokenEdge {
+            node: [Post]
+            cursor: Post
+        }
+        type PageInfo {
+            hasNextPage: Boolean!
+            hasPreviousPage: Boolean
+            startCursor: String
+        }
+        ",
+        TypeSystemLintRule::RelayConnection,
+    );
+    assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn builtins_are_not_linted() {
+    let errors = lint(
+        r#"
+        "Root query type."
+        type Query {
+            "Greeting."
+            hello: String!
+        }
+        "#,
+        TypeSystemLintRule::RequireDescription,
+    );
+    assert!(errors.is_empty());
+}
@@ -1,13 +1,27 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet, path::PathBuf};
 
 use log::{debug, info};
 
 use graphql_builtins::generate_builtins;
-use nitrogql_checker::{check_operation_document, check_type_system_document};
+use nitrogql_checker::{
+    check_operation_document_with_fragments, check_type_system_document, lint_operation_document,
+    lint_type_system_document, CheckError, CustomScalarRules, Severity,
+};
 use nitrogql_error::Result;
-use nitrogql_semantics::{ast_to_type_system, resolve_extensions};
+use nitrogql_semantics::{ast_to_type_system, resolve_extensions, FragmentRegistry};
 
-use crate::{context::LoadedSchema, output::InputFileKind};
+use nitrogql_ast::{
+    base::{HasPos, Pos},
+    operation::{ExecutableDefinition, OperationDocument},
+    type_system::{TypeSystemDocument, TypeSystemOrExtensionDocument},
+};
+
+use crate::{
+    context::{CliConfig, LoadedSchema},
+    custom_scalar::custom_scalar_rules,
+    lint::{operation_lint_rules, type_system_lint_rules},
+    output::{CliOutput, InputFileKind, OperationErrors},
+};
 
 use super::{error::CliError, CliContext};
 
@@ -16,53 +30,112 @@ pub fn run_check(context: CliContext) -> Result<CliContext> {
     match context {
         CliContext::SchemaUnresolved {
             schema,
+            next_schema,
             operations,
             file_store,
             config,
             output,
         } => {
             output.command_run("check".to_owned());
-            let loaded_schema = {
-                match schema {
-                    LoadedSchema::GraphQL(mut document) => {
-                        document.extend(generate_builtins());
-                        let resolved = resolve_extensions(document)?;
-                        let errors = check_type_system_document(&resolved);
-
-                        if !errors.is_empty() {
-                            output
-                                .extend(errors.into_iter().map(|err| (InputFileKind::Schema, err)));
-                            return Err(CliError::CommandNotSuccessful("check".into()).into());
-                        }
-                        LoadedSchema::GraphQL(resolved)
-                    }
-                    LoadedSchema::Introspection(schema) => LoadedSchema::Introspection(schema),
-                }
+            let custom_scalars = custom_scalar_rules(&config.config)?;
+            let loaded_schema = resolve_schema(schema, output, "check")?;
+            let schema_lint_errors = match loaded_schema {
+                LoadedSchema::GraphQL(ref document) => lint_type_system_document(
+                    document,
+                    &type_system_lint_rules(&config.config.lint.rules),
+                ),
+                // Introspection results are not owned by the project, so they are not linted.
+                LoadedSchema::Introspection(_) => vec![],
             };
             let schema =
                 loaded_schema.map_into(|doc| Cow::Owned(ast_to_type_system(doc)), Cow::Borrowed);
+            let mut fragments = FragmentRegistry::new();
+            for (_, doc, _) in operations.iter() {
+                fragments.add_document(doc);
+            }
             let errors = operations
                 .iter()
-                .flat_map(|(_, doc, file_by_index)| {
-                    check_operation_document(&schema, doc)
-                        .into_iter()
-                        .map(move |err| (err, file_by_index))
+                .filter(|(_, _, file_by_index)| config.is_affected(*file_by_index))
+                .flat_map(|(_, doc, _)| {
+                    check_operation_document_with_fragments(
+                        &schema,
+                        doc,
+                        &fragments,
+                        &custom_scalars,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let lint_rules = operation_lint_rules(&config.config.lint.rules);
+            // Rules that follow fragment spreads report a fragment shared by several documents once per document.
+            let mut reported = HashSet::new();
+            let lint_errors = operations
+                .iter()
+                .filter(|(_, _, file_by_index)| config.is_affected(*file_by_index))
+                .flat_map(|(_, doc, _)| {
+                    lint_operation_document(&schema, doc, &fragments, &lint_rules)
                 })
+                .filter(|err| reported.insert((err.rule_id, err.position)))
                 .collect::<Vec<_>>();
-            if errors.is_empty() {
-                info!("Check succeeded");
-                eprintln!("'check' finished");
-            } else {
-                output.extend(
-                    errors
-                        .into_iter()
-                        .map(|(err, _)| (InputFileKind::Operation, err)),
-                );
+            let has_errors = errors.iter().any(|err| err.severity() == Severity::Error)
+                || schema_lint_errors
+                    .iter()
+                    .chain(lint_errors.iter())
+                    .any(|err| err.severity == Severity::Error);
+            let has_next_schema_errors = match next_schema {
+                Some(ref next_schema) => {
+                    // Errors that also occur with the current schema are not reported again.
+                    let current_errors = errors
+                        .iter()
+                        .map(|err| (err.position, err.message.to_string()))
+                        .collect::<HashSet<_>>();
+                    let next_schema_errors = check_next_schema(
+                        next_schema.clone(),
+                        &operations,
+                        &fragments,
+                        &custom_scalars,
+                        &current_errors,
+                        &config,
+                        output,
+                    )?;
+                    let has_errors = !next_schema_errors.is_empty();
+                    output.next_schema_errors(next_schema_errors);
+                    has_errors
+                }
+                None => false,
+            };
+            // Warnings are reported even if the check succeeds.
+            output.extend(
+                schema_lint_errors
+                    .into_iter()
+                    .map(|err| (InputFileKind::Schema, err)),
+            );
+            output.extend(
+                errors
+                    .into_iter()
+                    .map(|err| (InputFileKind::Operation, err)),
+            );
+            output.extend(
+                lint_errors
+                    .into_iter()
+                    .map(|err| (InputFileKind::Operation, err)),
+            );
+            if has_errors || has_next_schema_errors {
                 return Err(CliError::CommandNotSuccessful("check".into()).into());
             }
+            let warning_count = output.warning_count();
+            if let Some(max) = config.max_warnings.filter(|max| warning_count > *max) {
+                return Err(CliError::TooManyWarnings {
+                    count: warning_count,
+                    max,
+                }
+                .into());
+            }
+            info!("Check succeeded");
+            eprintln!("'check' finished");
 
             Ok(CliContext::SchemaResolved {
                 schema: loaded_schema,
+                next_schema,
                 operations,
                 file_store,
                 config,
@@ -75,3 +148,145 @@ pub fn run_check(context: CliContext) -> Result<CliContext> {
         .into()),
     }
 }
+
+/// Resolves extensions in the schema and checks it.
+/// Errors and warnings in the schema are added to the output.
+/// Fails only if there are errors.
+pub fn resolve_schema<'src>(
+    schema: LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>,
+    output: &mut CliOutput,
+    command_name: &str,
+) -> Result<LoadedSchema<'src, TypeSystemDocument<'src>>> {
+    match schema {
+        LoadedSchema::GraphQL(mut document) => {
+            document.extend(generate_builtins());
+            let resolved = resolve_extensions(document)?;
+            let errors = check_type_system_document(&resolved);
+            let has_errors = errors.iter().any(|err| err.severity() == Severity::Error);
+
+            output.extend(errors.into_iter().map(|err| (InputFileKind::Schema, err)));
+            if has_errors {
+                return Err(CliError::CommandNotSuccessful(command_name.into()).into());
+            }
+            Ok(LoadedSchema::GraphQL(resolved))
+        }
+        LoadedSchema::Introspection(schema) => Ok(LoadedSchema::Introspection(schema)),
+    }
+}
+
+/// Checks operations against the next schema.
+/// Returns errors not in `current_errors`, grouped by operation.
+/// Warnings are not included.
+fn check_next_schema<'src>(
+    next_schema: LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>,
+    operations: &[(PathBuf, OperationDocument<'src>, usize)],
+    fragments: &FragmentRegistry<'_, 'src>,
+    custom_scalars: &CustomScalarRules,
+    current_errors: &HashSet<(Pos, String)>,
+    config: &CliConfig,
+    output: &mut CliOutput,
+) -> Result<Vec<OperationErrors>> {
+    let next_schema = resolve_schema(next_schema, output, "check")?;
+    let next_schema =
+        next_schema.map_into(|doc| Cow::Owned(ast_to_type_system(doc)), Cow::Borrowed);
+    Ok(operations
+        .iter()
+        .filter(|(_, _, file_by_index)| config.is_affected(*file_by_index))
+        .flat_map(|(_, doc, _)| {
+            let new_errors = check_operation_document_with_fragments(
+                &next_schema,
+                doc,
+                fragments,
+                custom_scalars,
+            )
+            .into_iter()
+            .filter(|err| {
+                err.severity() == Severity::Error
+                    && !current_errors.contains(&(err.position, err.message.to_string()))
+            })
+            .collect();
+            group_by_definition(doc, new_errors)
+        })
+        .collect())
+}
+
+/// Groups errors by the operation or fragment that contains them.
+fn group_by_definition(doc: &OperationDocument, errors: Vec<CheckError>) -> Vec<OperationErrors> {
+    let mut groups = doc
+        .definitions
+        .iter()
+        .map(|def| OperationErrors {
+            operation: definition_label(def),
+            position: *def.position(),
+            errors: vec![],
+        })
+        .collect::<Vec<_>>();
+    for error in errors {
+        let definition_index = doc
+            .definitions
+            .iter()
+            .position(|def| contains(def.position(), &error.position));
+        match definition_index {
+            Some(index) => groups[index].errors.push(error),
+            None => groups.push(OperationErrors {
+                operation: "document".to_owned(),
+                position: error.position,
+                errors: vec![error],
+            }),
+        }
+    }
+    groups.retain(|group| !group.errors.is_empty());
+    groups
+}
+
+/// Returns a human-readable label of given definition, such as `query 'GetUser'`.
+fn definition_label(def: &ExecutableDefinition) -> String {
+    match def {
+        ExecutableDefinition::OperationDefinition(op) => match op.name {
+            Some(name) => format!("{} '{}'", op.operation_type.as_str(), name.name),
+            None => format!("anonymous {}", op.operation_type.as_str()),
+        },
+        ExecutableDefinition::FragmentDefinition(fragment) => {
+            format!("fragment '{}'", fragment.name.name)
+        }
+    }
+}
+
+fn contains(outer: &Pos, inner: &Pos) -> bool {
+    outer.file == inner.file && outer.offset <= inner.offset && inner.end_offset <= outer.end_offset
+}
+
+/// Runs given function with the resolved schema.
+/// Context is passed through unchanged so that other commands can follow.
+pub fn with_resolved_schema<'src>(
+    mut context: CliContext<'src>,
+    command_name: &str,
+    f: impl FnOnce(
+        &LoadedSchema<'src, TypeSystemDocument<'src>>,
+        &CliConfig,
+        &mut CliOutput,
+    ) -> Result<()>,
+) -> Result<CliContext<'src>> {
+    match context {
+        CliContext::SchemaUnresolved {
+            ref schema,
+            ref config,
+            ref mut output,
+            ..
+        } => {
+            output.command_run(command_name.to_owned());
+            let schema = resolve_schema(schema.clone(), output, command_name)?;
+            f(&schema, config, output)?;
+        }
+        CliContext::SchemaResolved {
+            ref schema,
+            ref config,
+            ref mut output,
+            ..
+        } => {
+            output.command_run(command_name.to_owned());
+            f(schema, config, output)?;
+        }
+    }
+    Ok(context)
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, path::PathBuf, str::FromStr};
+use std::{borrow::Cow, collections::HashSet, path::PathBuf, str::FromStr};
 
 use graphql_type_system::Schema;
 use nitrogql_ast::{
@@ -7,11 +7,13 @@ use nitrogql_ast::{
     type_system::{TypeSystemDocument, TypeSystemOrExtensionDocument},
 };
 use nitrogql_config_file::Config;
+use nitrogql_utils::FileStore;
 use thiserror::Error;
 
-use crate::{file_store::FileStore, output::CliOutput};
+use crate::output::CliOutput;
 
 #[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
 pub enum LoadedSchema<'src, Gql> {
     GraphQL(Gql),
     Introspection(Schema<Cow<'src, str>, Pos>),
@@ -34,24 +36,46 @@ pub enum CliContext<'src> {
     SchemaUnresolved {
         config: CliConfig,
         schema: LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>,
+        next_schema: Option<LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>>,
         operations: Vec<(PathBuf, OperationDocument<'src>, usize)>,
-        file_store: &'src FileStore,
+        file_store: &'src FileStore<'src>,
         output: &'src mut CliOutput,
     },
     SchemaResolved {
         config: CliConfig,
         schema: LoadedSchema<'src, TypeSystemDocument<'src>>,
+        next_schema: Option<LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>>,
         operations: Vec<(PathBuf, OperationDocument<'src>, usize)>,
-        file_store: &'src FileStore,
+        file_store: &'src FileStore<'src>,
         output: &'src mut CliOutput,
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CliConfig {
     /// Root directory for other paths.
     pub root_dir: PathBuf,
     pub config: Config,
+    /// Paths to the schema to compare with the current schema.
+    pub next_schema: Vec<String>,
+    /// Maximum number of warnings allowed before the 'check' command fails.
+    /// Warnings never fail the command if not set.
+    pub max_warnings: Option<usize>,
+    /// If true, the 'format' command only reports files that are not formatted.
+    pub format_check: bool,
+    /// If set, only operation files of these indices are checked and generated,
+    /// and generation of schema output is skipped.
+    /// Other operation files are still used for resolving fragments.
+    pub affected_operations: Option<HashSet<usize>>,
+}
+
+impl CliConfig {
+    /// Returns whether given operation file should be processed.
+    pub fn is_affected(&self, file_index: usize) -> bool {
+        self.affected_operations
+            .as_ref()
+            .is_none_or(|files| files.contains(&file_index))
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -0,0 +1,35 @@
+use nitrogql_checker::{CustomScalarRule, CustomScalarRules, LiteralKind};
+use nitrogql_config_file::{Config, ScalarLiteralKind};
+
+use crate::error::CliError;
+
+/// Compiles rules of custom scalars declared in given config.
+pub fn custom_scalar_rules(config: &Config) -> Result<CustomScalarRules, CliError> {
+    config
+        .custom_scalars
+        .iter()
+        .map(|(name, scalar)| {
+            let literals = scalar.literals.iter().copied().map(literal_kind).collect();
+            let rule =
+                CustomScalarRule::new(literals, scalar.pattern.as_deref()).map_err(|err| {
+                    CliError::InvalidCustomScalarPattern {
+                        name: name.clone(),
+                        message: err.to_string(),
+                    }
+                })?;
+            Ok((name.clone(), rule))
+        })
+        .collect()
+}
+
+fn literal_kind(kind: ScalarLiteralKind) -> LiteralKind {
+    match kind {
+        ScalarLiteralKind::Int => LiteralKind::Int,
+        ScalarLiteralKind::Float => LiteralKind::Float,
+        ScalarLiteralKind::String => LiteralKind::String,
+        ScalarLiteralKind::Boolean => LiteralKind::Boolean,
+        ScalarLiteralKind::Enum => LiteralKind::Enum,
+        ScalarLiteralKind::List => LiteralKind::List,
+        ScalarLiteralKind::Object => LiteralKind::Object,
+    }
+}
@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+use log::{debug, info};
+
+use graphql_type_system::Schema;
+use nitrogql_ast::{base::Pos, type_system::TypeSystemDocument};
+use nitrogql_error::Result;
+use nitrogql_schema_diff::{diff_schemas, ChangeSeverity};
+use nitrogql_semantics::ast_to_type_system;
+
+use crate::{
+    check::{resolve_schema, with_resolved_schema},
+    context::LoadedSchema,
+    error::CliError,
+};
+
+use super::CliContext;
+
+/// Compares the schema with the next schema and reports changes.
+/// Fails if any breaking change is found.
+pub fn run_diff(context: CliContext) -> Result<CliContext> {
+    debug!("Comparing schemas");
+    let (CliContext::SchemaUnresolved {
+        ref next_schema, ..
+    }
+    | CliContext::SchemaResolved {
+        ref next_schema, ..
+    }) = context;
+    let Some(next_schema) = next_schema.clone() else {
+        return Err(CliError::OptionRequired {
+            option: String::from("--next-schema"),
+            command: String::from("diff"),
+        }
+        .into());
+    };
+    let context = with_resolved_schema(context, "diff", |schema, _, output| {
+        let next_schema = resolve_schema(next_schema, output, "diff")?;
+        let old_schema = to_type_system(schema);
+        let new_schema = to_type_system(&next_schema);
+        let mut changes = diff_schemas(&old_schema, &new_schema);
+        // Schemas loaded from introspection do not have meaningful positions.
+        for change in changes.iter_mut() {
+            if let LoadedSchema::Introspection(_) = schema {
+                change.old_position = None;
+            }
+            if let LoadedSchema::Introspection(_) = next_schema {
+                change.new_position = None;
+            }
+        }
+        let has_breaking_change = changes
+            .iter()
+            .any(|change| change.severity == ChangeSeverity::Breaking);
+        output.extend(changes);
+        if has_breaking_change {
+            return Err(CliError::CommandNotSuccessful("diff".into()).into());
+        }
+        Ok(())
+    })?;
+    info!("Diff succeeded");
+    eprintln!("'diff' finished");
+    Ok(context)
+}
+
+fn to_type_system<'a, 'src>(
+    schema: &'a LoadedSchema<'src, TypeSystemDocument<'src>>,
+) -> Cow<'a, Schema<Cow<'src, str>, Pos>> {
+    schema.map_into(|doc| Cow::Owned(ast_to_type_system(doc)), Cow::Borrowed)
+}
@@ -24,6 +24,12 @@ pub enum CliError {
     FailedToCalculateSourceMapFileName { path: PathBuf },
     #[error("{0}")]
     GlobError(String),
+    #[error("Failed to load imported file '{path}': {message}")]
+    FailedToLoadImport { path: PathBuf, message: String },
     #[error("Command not successful: {0}")]
     CommandNotSuccessful(String),
+    #[error("Found {count} warnings, which exceeds the maximum of {max}")]
+    TooManyWarnings { count: usize, max: usize },
+    #[error("Invalid pattern for custom scalar '{name}': {message}")]
+    InvalidCustomScalarPattern { name: String, message: String },
 }
@@ -0,0 +1,99 @@
+use std::fs;
+
+use log::{debug, info};
+
+use nitrogql_ast::set_current_file_of_pos;
+use nitrogql_error::Result;
+use nitrogql_parser::{is_embedding_source, parse_operation_document, parse_type_system_document};
+use nitrogql_printer::{
+    format_operation_document, format_type_system_document, GraphQLFormatterOptions,
+};
+use nitrogql_utils::FileKind;
+
+use crate::{
+    error::CliError,
+    output::{FormatResult, InputFileKind},
+};
+
+use super::CliContext;
+
+/// Formats schema and operation files.
+/// In check mode, files are not rewritten and the command fails if any file is not formatted.
+pub fn run_format(mut context: CliContext) -> Result<CliContext> {
+    debug!("Formatting");
+    let (CliContext::SchemaUnresolved {
+        ref config,
+        file_store,
+        ref mut output,
+        ..
+    }
+    | CliContext::SchemaResolved {
+        ref config,
+        file_store,
+        ref mut output,
+        ..
+    }) = context;
+    output.command_run("format".to_owned());
+    let options = GraphQLFormatterOptions {
+        indent_width: config.config.format.indent_width,
+        line_width: config.config.format.line_width,
+        sort_definitions: config.config.format.sort_definitions,
+    };
+
+    // All files are formatted before any file is written,
+    // so that a parse error leaves every file untouched.
+    let mut formatted_files = vec![];
+    for (file_idx, (path, source, kind)) in file_store.iter() {
+        set_current_file_of_pos(file_idx);
+        let (kind, formatted) = match kind {
+            FileKind::Schema => {
+                // Introspection results are not GraphQL documents.
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    continue;
+                }
+                let document = parse_type_system_document(source)?;
+                (
+                    InputFileKind::Schema,
+                    format_type_system_document(source, &document, &options),
+                )
+            }
+            FileKind::Operation => {
+                // Documents embedded in TypeScript and JavaScript sources are left as they are.
+                if is_embedding_source(path) {
+                    continue;
+                }
+                let document = parse_operation_document(source)?;
+                (
+                    InputFileKind::Operation,
+                    format_operation_document(source, &document, &options),
+                )
+            }
+            _ => continue,
+        };
+        if formatted == source {
+            continue;
+        }
+        formatted_files.push((kind, path, formatted));
+    }
+
+    let mut files = vec![];
+    for (kind, path, formatted) in formatted_files {
+        if !config.format_check {
+            info!("formatting {}", path.to_string_lossy());
+            fs::write(path, formatted)?;
+        }
+        files.push((kind, path.to_owned()));
+    }
+
+    let has_unformatted_files = config.format_check && !files.is_empty();
+    output.format_result(FormatResult {
+        check: config.format_check,
+        files,
+    });
+    if has_unformatted_files {
+        return Err(CliError::CommandNotSuccessful("format".into()).into());
+    }
+    info!("Format succeeded");
+    eprintln!("'format' finished");
+    Ok(context)
+}
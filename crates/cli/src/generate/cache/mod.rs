@@ -0,0 +1,242 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use log::debug;
+use nitrogql_config_file::Config;
+use nitrogql_utils::{FileKind, FileStore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::source_map_file_path;
+
+#[cfg(test)]
+mod tests;
+
+/// Location of the manifest file relative to the root directory.
+const MANIFEST_PATH: &str = "node_modules/.cache/nitrogql/generate-manifest.json";
+
+/// On-disk record of generated files.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    version: String,
+    #[serde(rename = "optionsHash")]
+    options_hash: String,
+    #[serde(rename = "schemaHash")]
+    schema_hash: String,
+    /// Mapping from output file path to its entry.
+    outputs: BTreeMap<String, OutputEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OutputEntry {
+    /// Operation file from which the output is generated. None for schema output.
+    source: Option<String>,
+    /// Hash of inputs that affect the output.
+    #[serde(rename = "inputHash")]
+    input_hash: String,
+    /// Hash of the output file and its source map.
+    #[serde(rename = "outputHash")]
+    output_hash: String,
+}
+
+/// Cache of generated files, keyed by content hashes of inputs.
+pub struct GenerateCache {
+    manifest_path: PathBuf,
+    /// Whether entries in the previous manifest can be reused.
+    valid: bool,
+    previous: Manifest,
+    current: Manifest,
+}
+
+impl GenerateCache {
+    /// Loads the manifest from previous run.
+    pub fn load(root_dir: &Path, options_hash: String, schema_hash: String) -> Self {
+        let manifest_path = root_dir.join(MANIFEST_PATH);
+        let previous = fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Manifest>(&content).ok())
+            .unwrap_or_default();
+        let valid = previous.version == env!("CARGO_PKG_VERSION")
+            && previous.options_hash == options_hash
+            && previous.schema_hash == schema_hash;
+        debug!(
+            "Generate cache is {}",
+            if valid { "valid" } else { "invalid" }
+        );
+        // Outputs not generated in this run are kept as is.
+        // If the cache is invalid, they are kept only for removing stale outputs.
+        let outputs = previous
+            .outputs
+            .iter()
+            .map(|(path, entry)| {
+                let mut entry = entry.clone();
+                if !valid {
+                    entry.input_hash.clear();
+                }
+                (path.clone(), entry)
+            })
+            .collect();
+        Self {
+            manifest_path,
+            valid,
+            previous,
+            current: Manifest {
+                version: env!("CARGO_PKG_VERSION").to_owned(),
+                options_hash,
+                schema_hash,
+                outputs,
+            },
+        }
+    }
+
+    /// Hash of schema files given at load time.
+    pub fn schema_hash(&self) -> &str {
+        &self.current.schema_hash
+    }
+
+    /// Returns whether the output generated from the same inputs is already on disk.
+    pub fn is_fresh(&self, output_path: &Path, input_hash: &str) -> bool {
+        if !self.valid {
+            return false;
+        }
+        let Some(entry) = self.previous.outputs.get(&*output_path.to_string_lossy()) else {
+            return false;
+        };
+        entry.input_hash == input_hash
+            && hash_output_files(output_path).is_some_and(|hash| hash == entry.output_hash)
+    }
+
+    /// Records a generated output.
+    pub fn record(
+        &mut self,
+        output_path: &Path,
+        source: Option<&Path>,
+        input_hash: String,
+        output_hash: String,
+    ) {
+        self.current.outputs.insert(
+            output_path.to_string_lossy().into_owned(),
+            OutputEntry {
+                source: source.map(|source| source.to_string_lossy().into_owned()),
+                input_hash,
+                output_hash,
+            },
+        );
+    }
+
+    /// Deletes outputs whose source operation file no longer exists.
+    pub fn remove_stale_outputs(&mut self) -> io::Result<()> {
+        let stale_outputs = self
+            .current
+            .outputs
+            .iter()
+            .filter(|(_, entry)| {
+                entry
+                    .source
+                    .as_ref()
+                    .is_some_and(|source| !Path::new(source).exists())
+            })
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        for output_path in stale_outputs {
+            let output_path = PathBuf::from(output_path);
+            debug!("Removing {}", output_path.to_string_lossy());
+            remove_file_if_exists(&output_path)?;
+            if let Ok(source_map_path) = source_map_file_path(&output_path) {
+                remove_file_if_exists(&source_map_path)?;
+            }
+            self.current.outputs.remove(&*output_path.to_string_lossy());
+        }
+        Ok(())
+    }
+
+    /// Writes the manifest to disk.
+    pub fn save(self) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(&self.current)?;
+        if let Some(parent_dir) = self.manifest_path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        write_if_changed(&self.manifest_path, &content)
+    }
+}
+
+/// Calculates a hex-encoded SHA-256 hash of given parts.
+pub fn hash_parts<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        // Length prefix keeps boundaries of parts unambiguous.
+        hasher.update(part.len().to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Calculates a hash of config options that affect outputs.
+/// Globs of operation documents are included, as they decide which fragments outputs can use.
+pub fn options_hash(config: &Config) -> String {
+    let operations = &config.operations;
+    let config = &config.generate;
+    let scalar_types = config.scalar_types.iter().collect::<BTreeMap<_, _>>();
+    let resolver_parent_types = config
+        .resolver_parent_types
+        .iter()
+        .collect::<BTreeMap<_, _>>();
+    hash_parts([format!(
+        "{:?}",
+        (
+            config.mode,
+            &config.schema_output,
+            &config.schema_module_specifier,
+            scalar_types,
+            config.default_export_for_operation,
+            &config.name,
+            config.emit_schema_runtime,
+            &config.resolvers_output,
+            resolver_parent_types,
+            &config.resolver_context_type,
+            &config.persisted_documents_output,
+            operations,
+        )
+    )
+    .as_str()])
+}
+
+/// Calculates a hash of all schema files.
+pub fn schema_hash(file_store: &FileStore) -> String {
+    let schema_files = file_store
+        .iter()
+        .filter(|(_, (_, _, kind))| *kind == FileKind::Schema)
+        .map(|(_, (path, content, _))| (path.to_string_lossy(), content))
+        .collect::<Vec<_>>();
+    hash_parts(
+        schema_files
+            .iter()
+            .flat_map(|(path, content)| [path.as_ref(), *content]),
+    )
+}
+
+/// Calculates a hash of an output file and its source map on disk.
+fn hash_output_files(output_path: &Path) -> Option<String> {
+    let output = fs::read_to_string(output_path).ok()?;
+    let source_map = fs::read_to_string(source_map_file_path(output_path).ok()?).ok()?;
+    Some(hash_parts([output.as_str(), source_map.as_str()]))
+}
+
+/// Writes a file unless it already has the same content.
+pub fn write_if_changed(path: &Path, content: &str) -> io::Result<()> {
+    if fs::read(path).is_ok_and(|current| current == content.as_bytes()) {
+        debug!("Skipping unchanged {}", path.to_string_lossy());
+        return Ok(());
+    }
+    fs::write(path, content)
+}
+
+fn remove_file_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use nitrogql_config_file::Config;
+
+use super::{hash_parts, options_hash, write_if_changed, GenerateCache};
+
+/// Creates an empty temporary directory for a test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("nitrogql-cli-cache-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes an output file with its source map and returns the hash to record.
+fn write_output(output_path: &Path, content: &str) -> String {
+    let source_map = format!("map of {content}");
+    fs::write(output_path, content).unwrap();
+    fs::write(output_path.with_extension("ts.map"), &source_map).unwrap();
+    hash_parts([content, source_map.as_str()])
+}
+
+#[test]
+fn unchanged_run() {
+    let dir = temp_dir("unchanged");
+    let source_path = dir.join("a.graphql");
+    let output_path = dir.join("a.d.graphql.ts");
+    fs::write(&source_path, "query A { me }").unwrap();
+    let options = options_hash(&Config::default());
+
+    let mut cache = GenerateCache::load(&dir, options.clone(), "schema".to_owned());
+    assert!(!cache.is_fresh(&output_path, "input"));
+    let output_hash = write_output(&output_path, "output");
+    cache.record(
+        &output_path,
+        Some(&source_path),
+        "input".to_owned(),
+        output_hash,
+    );
+    cache.save().unwrap();
+
+    let cache = GenerateCache::load(&dir, options, "schema".to_owned());
+    assert!(cache.is_fresh(&output_path, "input"));
+    assert!(!cache.is_fresh(&output_path, "changed input"));
+    // Outputs edited on disk are generated again.
+    fs::write(&output_path, "edited").unwrap();
+    assert!(!cache.is_fresh(&output_path, "input"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn changed_option() {
+    let dir = temp_dir("changed-option");
+    let output_path = dir.join("a.d.graphql.ts");
+    let config = Config::default();
+    let options = options_hash(&config);
+
+    let mut cache = GenerateCache::load(&dir, options.clone(), "schema".to_owned());
+    let output_hash = write_output(&output_path, "output");
+    cache.record(&output_path, None, "input".to_owned(), output_hash);
+    cache.save().unwrap();
+
+    let mut generate_config = config.clone();
+    generate_config.generate.default_export_for_operation =
+        !config.generate.default_export_for_operation;
+    let mut documents_config = config.clone();
+    documents_config.operations = vec!["src/**/*.graphql".to_owned()];
+    for changed_config in [generate_config, documents_config] {
+        let changed_options = options_hash(&changed_config);
+        assert_ne!(changed_options, options);
+        let cache = GenerateCache::load(&dir, changed_options, "schema".to_owned());
+        assert!(!cache.is_fresh(&output_path, "input"));
+    }
+    let cache = GenerateCache::load(&dir, options, "changed schema".to_owned());
+    assert!(!cache.is_fresh(&output_path, "input"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn removed_source_file() {
+    let dir = temp_dir("removed-source");
+    let kept_source = dir.join("a.graphql");
+    let removed_source = dir.join("b.graphql");
+    let kept_output = dir.join("a.d.graphql.ts");
+    let removed_output = dir.join("b.d.graphql.ts");
+    fs::write(&kept_source, "query A { me }").unwrap();
+    let options = options_hash(&Config::default());
+
+    let mut cache = GenerateCache::load(&dir, options.clone(), "schema".to_owned());
+    for (source, output) in [
+        (&kept_source, &kept_output),
+        (&removed_source, &removed_output),
+    ] {
+        let output_hash = write_output(output, "output");
+        cache.record(output, Some(source), "input".to_owned(), output_hash);
+    }
+    cache.remove_stale_outputs().unwrap();
+    cache.save().unwrap();
+
+    assert!(kept_output.exists());
+    assert!(!removed_output.exists());
+    assert!(!removed_output.with_extension("ts.map").exists());
+    let cache = GenerateCache::load(&dir, options, "schema".to_owned());
+    assert!(cache.is_fresh(&kept_output, "input"));
+    assert!(!cache
+        .previous
+        .outputs
+        .contains_key(&*removed_output.to_string_lossy()));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn write_only_changed_content() {
+    let dir = temp_dir("write-if-changed");
+    let path = dir.join("output.ts");
+    fs::write(&path, "content").unwrap();
+    let old_time = SystemTime::now() - Duration::from_secs(60);
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(old_time)
+        .unwrap();
+
+    write_if_changed(&path, "content").unwrap();
+    assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), old_time);
+    write_if_changed(&path, "new content").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new content");
+    assert_ne!(fs::metadata(&path).unwrap().modified().unwrap(), old_time);
+    fs::remove_dir_all(&dir).unwrap();
+}
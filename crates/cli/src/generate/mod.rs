@@ -0,0 +1,527 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::debug;
+use nitrogql_ast::operation::ExecutableDefinition;
+use nitrogql_semantics::{ast_to_type_system, type_system_to_ast, FragmentRegistry};
+
+use crate::context::LoadedSchema;
+use crate::error::CliError;
+use crate::output::{CliOutput, OutputFileKind};
+use nitrogql_config_file::GenerateMode;
+use nitrogql_error::Result;
+use nitrogql_parser::is_embedding_source;
+use nitrogql_printer::{
+    print_types_for_operation_document, OperationTypePrinterOptions, ResolverTypePrinter,
+    ResolverTypePrinterOptions, SchemaTypePrinter, SchemaTypePrinterOptions,
+};
+use nitrogql_utils::{clone_into, relative_path, FileKind, FileStore};
+use sourcemap_writer::{print_source_map_json, SourceWriter, SourceWriterBuffers};
+
+use self::cache::{hash_parts, options_hash, schema_hash, write_if_changed, GenerateCache};
+use self::persisted_documents::write_persisted_documents;
+use super::{check::run_check, context::CliContext};
+
+mod cache;
+mod persisted_documents;
+
+pub fn run_generate(mut context: CliContext) -> Result<CliContext> {
+    if let CliContext::SchemaUnresolved { .. } = context {
+        // Seems like check is not run
+        context = run_check(context)?;
+    }
+    match context {
+        CliContext::SchemaUnresolved { .. } => panic!("Something went wrong"),
+        CliContext::SchemaResolved {
+            schema,
+            next_schema,
+            operations,
+            config,
+            file_store,
+            output,
+        } => {
+            output.command_run("generate".to_owned());
+            if config.config.generate.schema_output.is_none()
+                && config.config.generate.schema_module_specifier.is_none()
+            {
+                return Err(CliError::OptionRequired {
+                    option: String::from("schemaOutput"),
+                    command: String::from("generate"),
+                }
+                .into());
+            }
+            let schema_output = config
+                .config
+                .generate
+                .schema_output
+                .as_ref()
+                .map(|schema_output| config.root_dir.join(schema_output));
+            // If output contains runtime, prevent .d.ts from being generated
+            if config.config.generate.emit_schema_runtime
+                && schema_output
+                    .as_ref()
+                    .and_then(|schema_output| schema_output.file_name())
+                    .is_some_and(|name| name.to_string_lossy().ends_with(".d.ts"))
+            {
+                return Err(CliError::CannotEmitRuntimeToDts.into());
+            }
+            let mut cache = GenerateCache::load(
+                &config.root_dir,
+                options_hash(&config.config),
+                schema_hash(file_store),
+            );
+            let schema_output_to_generate = schema_output
+                .as_ref()
+                .filter(|_| config.affected_operations.is_none());
+            if let Some(schema_output) = schema_output_to_generate {
+                let input_hash = cache.schema_hash().to_owned();
+                if cache.is_fresh(schema_output, &input_hash) {
+                    debug!("Schema output is up to date");
+                    report_generated_file(
+                        output,
+                        OutputFileKind::SchemaTypeDefinition,
+                        schema_output,
+                    )?;
+                } else {
+                    debug!("Processing schema");
+                    let file_map = FileMap {
+                        file_store,
+                        file_indices: file_store
+                            .iter()
+                            .map(|(idx, (_, _, kind))| {
+                                if kind == FileKind::Schema {
+                                    idx
+                                } else {
+                                    usize::MAX
+                                }
+                            })
+                            .collect(),
+                    };
+
+                    let mut options = SchemaTypePrinterOptions {
+                        emit_schema_runtime: config.config.generate.emit_schema_runtime,
+                        ..SchemaTypePrinterOptions::default()
+                    };
+                    options.scalar_types.extend(
+                        config
+                            .config
+                            .generate
+                            .scalar_types
+                            .iter()
+                            .map(|(key, value)| (key.to_owned(), value.to_owned())),
+                    );
+
+                    let mut writer = SourceWriter::new();
+                    writer.set_file_index_mapper(file_map.file_indices.clone());
+                    let mut printer = SchemaTypePrinter::new(options, &mut writer);
+
+                    match schema {
+                        LoadedSchema::GraphQL(ref schema) => {
+                            printer.print_document(schema)?;
+                        }
+                        LoadedSchema::Introspection(ref schema) => {
+                            let ast = type_system_to_ast(schema);
+                            printer.print_document(&ast)?;
+                        }
+                    }
+
+                    let buffers = writer.into_buffers();
+                    let output_hash = write_file_and_sourcemap(
+                        &file_map,
+                        output,
+                        OutputFileKind::SchemaTypeDefinition,
+                        schema_output,
+                        buffers,
+                    )?;
+                    cache.record(schema_output, None, input_hash, output_hash);
+                }
+            }
+
+            let resolvers_output = config
+                .config
+                .generate
+                .resolvers_output
+                .as_ref()
+                .map(|resolvers_output| config.root_dir.join(resolvers_output))
+                .filter(|_| config.affected_operations.is_none());
+            if let Some(ref resolvers_output) = resolvers_output {
+                let input_hash = cache.schema_hash().to_owned();
+                if cache.is_fresh(resolvers_output, &input_hash) {
+                    debug!("Resolvers output is up to date");
+                    report_generated_file(
+                        output,
+                        OutputFileKind::ResolverTypeDefinition,
+                        resolvers_output,
+                    )?;
+                } else {
+                    debug!("Processing resolvers");
+                    let file_map = FileMap {
+                        file_store,
+                        file_indices: file_store
+                            .iter()
+                            .map(|(idx, (_, _, kind))| {
+                                if kind == FileKind::Schema {
+                                    idx
+                                } else {
+                                    usize::MAX
+                                }
+                            })
+                            .collect(),
+                    };
+
+                    let mut options = ResolverTypePrinterOptions {
+                        schema_source: schema_source_for(
+                            &config.config.generate.schema_module_specifier,
+                            resolvers_output,
+                            &schema_output,
+                        ),
+                        ..ResolverTypePrinterOptions::default()
+                    };
+                    options.parent_types.extend(
+                        config
+                            .config
+                            .generate
+                            .resolver_parent_types
+                            .iter()
+                            .map(|(key, value)| (key.to_owned(), value.to_owned())),
+                    );
+                    clone_into(
+                        &config.config.generate.resolver_context_type,
+                        &mut options.context_type,
+                    );
+
+                    let mut writer = SourceWriter::new();
+                    writer.set_file_index_mapper(file_map.file_indices.clone());
+                    let mut printer = ResolverTypePrinter::new(options, &mut writer);
+
+                    match schema {
+                        LoadedSchema::GraphQL(ref schema) => {
+                            printer.print_document(schema);
+                        }
+                        LoadedSchema::Introspection(ref schema) => {
+                            let ast = type_system_to_ast(schema);
+                            printer.print_document(&ast);
+                        }
+                    }
+
+                    let buffers = writer.into_buffers();
+                    let output_hash = write_file_and_sourcemap(
+                        &file_map,
+                        output,
+                        OutputFileKind::ResolverTypeDefinition,
+                        resolvers_output,
+                        buffers,
+                    )?;
+                    cache.record(resolvers_output, None, input_hash, output_hash);
+                }
+            }
+
+            let mapped_schema =
+                schema.map_into(|doc| Cow::Owned(ast_to_type_system(doc)), Cow::Borrowed);
+
+            let mut fragments = FragmentRegistry::new();
+            for (_, doc, _) in operations.iter() {
+                fragments.add_document(doc);
+            }
+
+            for (path, doc, file_index) in operations.iter() {
+                if !config.is_affected(*file_index) {
+                    continue;
+                }
+                if is_embedding_source(path) {
+                    // Types for embedded documents are only emitted in the types-only mode.
+                    if config.config.generate.mode != GenerateMode::TypesOnlyTS4_0
+                        || doc.definitions.is_empty()
+                    {
+                        continue;
+                    }
+                }
+                debug!("Processing {}", path.to_string_lossy());
+                // Fragments from other files may be printed as a part of this document.
+                let fragment_files = doc
+                    .definitions
+                    .iter()
+                    .flat_map(|def| {
+                        let selection_set = match def {
+                            ExecutableDefinition::OperationDefinition(op) => &op.selection_set,
+                            ExecutableDefinition::FragmentDefinition(fragment) => {
+                                &fragment.selection_set
+                            }
+                        };
+                        fragments.fragments_used_by(selection_set)
+                    })
+                    .map(|fragment| fragment.position.file)
+                    .collect::<BTreeSet<_>>();
+                let mut source_index = 0;
+                let file_map = FileMap {
+                    file_store,
+                    file_indices: file_store
+                        .iter()
+                        .map(|(idx, (_, _, kind))| {
+                            if kind == FileKind::Schema
+                                || idx == *file_index
+                                || fragment_files.contains(&idx)
+                            {
+                                source_index += 1;
+                                source_index - 1
+                            } else {
+                                usize::MAX
+                            }
+                        })
+                        .collect(),
+                };
+
+                let decl_file_path = {
+                    let mut path = path.clone();
+                    path.set_extension(match config.config.generate.mode {
+                        GenerateMode::WithLoaderTS5_0 => "d.graphql.ts",
+                        GenerateMode::WithLoaderTS4_0 => "graphql.d.ts",
+                        GenerateMode::StandaloneTS4_0 => "graphql.ts",
+                        GenerateMode::TypesOnlyTS4_0 => "graphql-types.ts",
+                    });
+                    path
+                };
+
+                // Output depends on the operation file and fragments used from it.
+                let input_hash = hash_parts(
+                    [decl_file_path.to_string_lossy().as_ref()]
+                        .into_iter()
+                        .chain(
+                            [*file_index]
+                                .iter()
+                                .chain(fragment_files.iter())
+                                .map(|idx| file_store[*idx].1.as_ref()),
+                        ),
+                );
+                if cache.is_fresh(&decl_file_path, &input_hash) {
+                    debug!("{} is up to date", decl_file_path.to_string_lossy());
+                    report_generated_file(
+                        output,
+                        OutputFileKind::OperationTypeDefinition,
+                        &decl_file_path,
+                    )?;
+                    continue;
+                }
+
+                let mut writer = SourceWriter::new();
+                writer.set_file_index_mapper(file_map.file_indices.clone());
+                let mut printer_options = OperationTypePrinterOptions::default();
+                match config.config.generate.mode {
+                    GenerateMode::StandaloneTS4_0 => {
+                        printer_options.print_values = true;
+                        printer_options.emit_document_hash =
+                            config.config.generate.persisted_documents_output.is_some();
+                    }
+                    GenerateMode::TypesOnlyTS4_0 => {
+                        printer_options.types_only = true;
+                    }
+                    _ => {}
+                }
+                printer_options.schema_source = schema_source_for(
+                    &config.config.generate.schema_module_specifier,
+                    &decl_file_path,
+                    &schema_output,
+                );
+                clone_into(
+                    &config.config.generate.name.operation_result_type_suffix,
+                    &mut printer_options.operation_result_type_suffix,
+                );
+                clone_into(
+                    &config.config.generate.name.variables_type_suffix,
+                    &mut printer_options.variables_type_suffix,
+                );
+                clone_into(
+                    &config.config.generate.name.capitalize_operation_names,
+                    &mut printer_options.base_options.capitalize_operation_names,
+                );
+                clone_into(
+                    &config.config.generate.name.query_variable_suffix,
+                    &mut printer_options.base_options.query_variable_suffix,
+                );
+                clone_into(
+                    &config.config.generate.name.mutation_variable_suffix,
+                    &mut printer_options.base_options.mutation_variable_suffix,
+                );
+                clone_into(
+                    &config.config.generate.name.subscription_variable_suffix,
+                    &mut printer_options.base_options.subscription_variable_suffix,
+                );
+
+                print_types_for_operation_document(
+                    printer_options,
+                    &mapped_schema,
+                    doc,
+                    &fragments,
+                    &mut writer,
+                );
+
+                let buffers = writer.into_buffers();
+
+                let output_hash = write_file_and_sourcemap(
+                    &file_map,
+                    output,
+                    OutputFileKind::OperationTypeDefinition,
+                    &decl_file_path,
+                    buffers,
+                )?;
+                cache.record(&decl_file_path, Some(path), input_hash, output_hash);
+            }
+            if let Some(ref persisted_documents_output) =
+                config.config.generate.persisted_documents_output
+            {
+                let persisted_documents_output = config.root_dir.join(persisted_documents_output);
+                write_persisted_documents(
+                    &persisted_documents_output,
+                    operations.iter().map(|(_, doc, _)| doc),
+                    &fragments,
+                )?;
+                output.generated_file(
+                    OutputFileKind::PersistedDocuments,
+                    persisted_documents_output,
+                );
+            }
+            cache.remove_stale_outputs()?;
+            cache.save()?;
+            eprintln!("'generate' finished");
+            Ok(CliContext::SchemaResolved {
+                config,
+                schema,
+                next_schema,
+                operations,
+                file_store,
+                output,
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FileMap<'src> {
+    pub file_store: &'src FileStore<'src>,
+    /// Mapping from file index in file_store to source map index.
+    pub file_indices: Vec<usize>,
+}
+
+/// Writes an output file and its source map.
+/// Files are not touched if they already have the same content.
+/// Returns a hash of written contents.
+fn write_file_and_sourcemap(
+    file_map: &FileMap,
+    cli_output: &mut CliOutput,
+    output_file_kind: OutputFileKind,
+    output_file_path: &Path,
+    buffers: SourceWriterBuffers,
+) -> Result<String> {
+    let source_files = file_map
+        .file_indices
+        .iter()
+        .zip(file_map.file_store.iter())
+        .filter_map(|(idx, (_, (path, _, _)))| {
+            if *idx == usize::MAX {
+                return None;
+            }
+
+            Some(path)
+        })
+        .collect::<Vec<_>>();
+
+    let source_map_file_path = source_map_file_path(output_file_path)?;
+
+    debug!("Writing {}", output_file_path.to_string_lossy());
+    {
+        let parent_dir = output_file_path.parent();
+        if let Some(parent_dir) = parent_dir {
+            fs::create_dir_all(parent_dir)?;
+        }
+    }
+    let output = format!(
+        "{}\n//# sourceMappingURL={}\n",
+        &buffers.buffer,
+        source_map_file_path.file_name().unwrap().to_string_lossy()
+    );
+    write_if_changed(output_file_path, &output)?;
+
+    let mut source_map = String::new();
+    print_source_map_json(
+        output_file_path,
+        &source_files,
+        &buffers.names,
+        &buffers.source_map,
+        &mut source_map,
+    )?;
+
+    debug!("Writing {}", source_map_file_path.to_string_lossy());
+    write_if_changed(&source_map_file_path, &source_map)?;
+    report_generated_file(cli_output, output_file_kind, output_file_path)?;
+
+    Ok(hash_parts([output.as_str(), source_map.as_str()]))
+}
+
+/// Reports an output file and its source map as generated.
+fn report_generated_file(
+    cli_output: &mut CliOutput,
+    output_file_kind: OutputFileKind,
+    output_file_path: &Path,
+) -> Result<()> {
+    let source_map_file_path = source_map_file_path(output_file_path)?;
+    cli_output.generated_file(output_file_kind, output_file_path.to_owned());
+    cli_output.generated_file(output_file_kind.to_source_map_kind(), source_map_file_path);
+    Ok(())
+}
+
+/// Calculates the path of the source map for an output file.
+fn source_map_file_path(output_file_path: &Path) -> Result<PathBuf> {
+    let mut path = output_file_path.to_owned();
+    match path.file_name() {
+        None => Err(CliError::FailedToCalculateSourceMapFileName { path: path.clone() }.into()),
+        Some(file_name) => {
+            let mut file_name = file_name.to_owned();
+            file_name.push(".map");
+            path.set_file_name(file_name);
+            Ok(path)
+        }
+    }
+}
+
+/// Calculates the module specifier to import schema types from given output file.
+fn schema_source_for(
+    schema_module_specifier: &Option<String>,
+    output_file_path: &Path,
+    schema_output: &Option<PathBuf>,
+) -> String {
+    schema_module_specifier.clone().unwrap_or_else(|| {
+        path_to_ts(relative_path(
+            output_file_path,
+            schema_output
+                .as_ref()
+                .expect("This should be prevented by config validation"),
+        ))
+        .to_string_lossy()
+        .to_string()
+    })
+}
+
+/// Removes '.d.ts' suffix
+fn path_to_ts(mut path: PathBuf) -> PathBuf {
+    match path.file_name() {
+        None => path,
+        Some(file_name) => match file_name.to_os_string().into_string() {
+            Err(_) => path,
+            Ok(mut file_name) => {
+                if file_name.ends_with(".d.ts") {
+                    file_name.truncate(file_name.len() - 5);
+                    path.set_file_name(file_name);
+                    return path;
+                }
+                if file_name.ends_with(".ts") {
+                    file_name.truncate(file_name.len() - 3);
+                    path.set_file_name(file_name);
+                    return path;
+                }
+                path
+            }
+        },
+    }
+}
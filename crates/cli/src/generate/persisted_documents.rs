@@ -0,0 +1,36 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use log::debug;
+use nitrogql_ast::operation::{ExecutableDefinition, OperationDocument};
+use nitrogql_error::Result;
+use nitrogql_printer::{persisted_document_hash, print_persisted_document};
+use nitrogql_semantics::FragmentRegistry;
+
+use super::cache::write_if_changed;
+
+/// Writes a manifest that maps hashes of persisted documents to their texts.
+/// All operations in the project are included.
+pub fn write_persisted_documents<'a, 'src>(
+    output_path: &Path,
+    documents: impl IntoIterator<Item = &'a OperationDocument<'src>>,
+    fragments: &FragmentRegistry<'a, 'src>,
+) -> Result<()> {
+    let mut manifest = BTreeMap::new();
+    for document in documents {
+        for def in document.definitions.iter() {
+            let ExecutableDefinition::OperationDefinition(operation) = def else {
+                continue;
+            };
+            let text = print_persisted_document(operation, fragments);
+            manifest.insert(persisted_document_hash(&text), text);
+        }
+    }
+
+    debug!("Writing {}", output_path.to_string_lossy());
+    if let Some(parent_dir) = output_path.parent() {
+        fs::create_dir_all(parent_dir)?;
+    }
+    let content = serde_json::to_string_pretty(&manifest)?;
+    write_if_changed(output_path, &format!("{content}\n"))?;
+    Ok(())
+}
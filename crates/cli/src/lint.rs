@@ -0,0 +1,76 @@
+use nitrogql_checker::{LintRuleSetting, OperationLintRule, Severity, TypeSystemLintRule};
+use nitrogql_config_file::{LintRuleConfig, LintRulesConfig, LintSeverity};
+
+/// Returns operation lint rules enabled in given config.
+pub fn operation_lint_rules(config: &LintRulesConfig) -> Vec<LintRuleSetting<OperationLintRule>> {
+    [
+        rule_setting(&config.named_operations, |_| {
+            OperationLintRule::NamedOperations
+        }),
+        rule_setting(&config.operation_name_suffix, |_| {
+            OperationLintRule::OperationNameSuffix
+        }),
+        rule_setting(&config.no_mutation_literal_arguments, |_| {
+            OperationLintRule::NoMutationLiteralArguments
+        }),
+        rule_setting(&config.max_selection_depth, |options| {
+            OperationLintRule::MaxSelectionDepth {
+                max_depth: options.max_depth,
+            }
+        }),
+        rule_setting(&config.require_id_field, |options| {
+            OperationLintRule::RequireIdField {
+                field_name: options.field_name.clone(),
+            }
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Returns schema lint rules enabled in given config.
+pub fn type_system_lint_rules(
+    config: &LintRulesConfig,
+) -> Vec<LintRuleSetting<TypeSystemLintRule>> {
+    [
+        rule_setting(&config.require_description, |_| {
+            TypeSystemLintRule::RequireDescription
+        }),
+        rule_setting(&config.enum_values_upper_case, |_| {
+            TypeSystemLintRule::EnumValuesUpperCase
+        }),
+        rule_setting(&config.input_type_suffix, |_| {
+            TypeSystemLintRule::InputTypeSuffix
+        }),
+        rule_setting(&config.no_nullable_list_of_nullable_items, |_| {
+            TypeSystemLintRule::NoNullableListOfNullableItems
+        }),
+        rule_setting(&config.deprecation_reason, |_| {
+            TypeSystemLintRule::DeprecationReason
+        }),
+        rule_setting(&config.relay_connection, |_| {
+            TypeSystemLintRule::RelayConnection
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Converts config of one rule. Returns None if the rule is not enabled.
+fn rule_setting<Options, Rule>(
+    config: &Option<LintRuleConfig<Options>>,
+    rule: impl FnOnce(&Options) -> Rule,
+) -> Option<LintRuleSetting<Rule>> {
+    let config = config.as_ref()?;
+    let severity = match config.severity {
+        LintSeverity::Off => return None,
+        LintSeverity::Warning => Severity::Warning,
+        LintSeverity::Error => Severity::Error,
+    };
+    Some(LintRuleSetting {
+        rule: rule(&config.options),
+        severity,
+    })
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fs,
     mem::ManuallyDrop,
     path::{Path, PathBuf},
@@ -8,37 +9,52 @@ use std::{
 use anyhow::Result;
 use clap::Parser;
 use context::OutputFormat;
-use file_store::FileStore;
 use globmatch::wrappers::{build_matchers, match_paths};
 use graphql_type_system::Schema;
 use itertools::Itertools;
 use log::{info, trace};
 use nitrogql_ast::{
-    operation::OperationDocument, set_current_file_of_pos,
+    base::Pos, operation::OperationDocument, set_current_file_of_pos,
     type_system::TypeSystemOrExtensionDocument,
 };
 use nitrogql_introspection::schema_from_introspection_json;
-use nitrogql_utils::{get_cwd, normalize_path};
+use nitrogql_utils::{get_cwd, normalize_path, FileKind, FileStore};
 use output::CliOutput;
 
 use crate::{
     context::{CliContext, LoadedSchema},
     error::CliError,
-    file_store::FileKind,
 };
-use nitrogql_config_file::load_config;
+use nitrogql_config_file::{load_config, EmbeddedConfig};
 
 use nitrogql_error::{print_positioned_error, PositionedError};
-use nitrogql_parser::{parse_operation_document, parse_type_system_document};
+use nitrogql_parser::{
+    extract_embedded_documents, is_embedding_source, parse_embedded_operation_documents,
+    parse_operation_document_recovering, parse_type_system_document_recovering, EmbeddedDocument,
+    EmbeddedDocumentOptions, ParseError,
+};
 
-use self::{check::run_check, context::CliConfig, generate::run_generate};
+use self::{
+    check::run_check,
+    context::CliConfig,
+    diff::run_diff,
+    format::run_format,
+    generate::run_generate,
+    print::{run_print_introspection, run_print_schema},
+    watch::run_watch,
+};
 
 mod check;
 mod context;
+mod custom_scalar;
+mod diff;
 mod error;
-mod file_store;
+mod format;
 mod generate;
+mod lint;
 mod output;
+mod print;
+mod watch;
 
 #[derive(Parser, Debug)]
 struct Args {
@@ -52,11 +68,29 @@ struct Args {
     /// Path to operation document(s).
     operation: Vec<String>,
     #[arg(long)]
+    /// Path to schema document(s) to compare with the current schema.
+    next_schema: Vec<String>,
+    #[arg(long)]
     /// Path to save schema type definition file.
     schema_output: Option<PathBuf>,
+    #[arg(long)]
+    /// Path to save resolved schema for the 'print-schema' command.
+    print_schema_output: Option<PathBuf>,
+    #[arg(long)]
+    /// Path to save introspection JSON for the 'print-introspection' command.
+    print_introspection_output: Option<PathBuf>,
+    #[arg(long)]
+    /// Number of warnings that makes the 'check' command fail when exceeded.
+    max_warnings: Option<usize>,
     /// Output format of CLI.
     #[arg(long, default_value = "human")]
     output_format: OutputFormat,
+    #[arg(long)]
+    /// Watch input files and re-run commands when they change.
+    watch: bool,
+    #[arg(long)]
+    /// Make the 'format' command report unformatted files instead of rewriting them.
+    check: bool,
     commands: Vec<String>,
 }
 
@@ -73,10 +107,27 @@ pub fn run_cli(args: impl IntoIterator<Item = String>) -> usize {
         .init()
         .unwrap();
     let mut output = CliOutput::new();
-    let file_store = Box::leak(Box::new(FileStore::new()));
+    let mut file_store = FileStore::new();
     let args = Args::parse_from(args);
     let output_format = args.output_format;
-    let res = run_cli_impl(args, file_store, &mut output);
+    if args.watch {
+        let commands = args.commands.clone();
+        return match load_cli_config(args) {
+            Ok(config) => run_watch(commands, config, output_format),
+            Err(err) => report(output, Err(err), &file_store, output_format),
+        };
+    }
+    let res = run_cli_impl(args, &mut file_store, &mut output);
+    report(output, res, &file_store, output_format)
+}
+
+/// Prints the result of commands. Returns 0 if successful
+fn report(
+    mut output: CliOutput,
+    res: Result<(), CommandError>,
+    file_store: &FileStore,
+    output_format: OutputFormat,
+) -> usize {
     let code = match res {
         Ok(()) => 0,
         Err(err) => {
@@ -111,11 +162,91 @@ pub fn run_cli(args: impl IntoIterator<Item = String>) -> usize {
     code
 }
 
-fn run_cli_impl(
+fn run_cli_impl<'a>(
     args: Args,
-    file_store: &mut FileStore,
-    output: &mut CliOutput,
+    file_store: &'a mut FileStore<'static>,
+    output: &'a mut CliOutput,
 ) -> Result<(), CommandError> {
+    let commands = args.commands.clone();
+    let config = load_cli_config(args)?;
+
+    // All files are loaded before parsing because parsed documents borrow the file store.
+    let schema_indices = load_glob_files(&config.root_dir, &config.config.schema)?
+        .into_iter()
+        .map(|(path, buf)| file_store.add_file(path, buf, FileKind::Schema))
+        .collect::<Vec<_>>();
+    let mut operation_files = load_glob_files(&config.root_dir, &config.config.operations)?
+        .into_iter()
+        .map(|(path, buf)| {
+            let file_idx =
+                add_operation_file(file_store, path.clone(), buf, &config.config.embedded);
+            (path, file_idx)
+        })
+        .collect::<Vec<_>>();
+    let import_result =
+        load_imported_operation_files(file_store, &mut operation_files, &config.config.embedded);
+    // Next schema files are loaded last because schema files must come before other files in the file store.
+    let next_schema_indices = if config.next_schema.is_empty() {
+        None
+    } else {
+        let next_schema_files = load_glob_files(&config.root_dir, &config.next_schema)?;
+        Some(
+            next_schema_files
+                .into_iter()
+                .map(|(path, buf)| file_store.add_file(path, buf, FileKind::NextSchema))
+                .collect::<Vec<_>>(),
+        )
+    };
+    let file_store = &*file_store;
+
+    let (schema_docs, schema_errors): (Vec<_>, Vec<_>) = schema_indices
+        .into_iter()
+        .map(|file_idx| parse_schema_file(file_store, file_idx))
+        .partition_result();
+    let (operation_docs, operation_errors): (Vec<_>, Vec<_>) = operation_files
+        .into_iter()
+        .map(|(path, file_idx)| {
+            let doc = parse_operation_file(file_store, file_idx)?;
+            Ok::<_, CommandError>((path, doc, file_idx))
+        })
+        .partition_result();
+    // Syntax errors in schema and operations are reported together.
+    if !schema_errors.is_empty() || !operation_errors.is_empty() {
+        return Err(CommandError::merge(
+            schema_errors.into_iter().chain(operation_errors),
+        ));
+    }
+    let merged_schema_doc = resolve_loaded_schema(schema_docs)?;
+    import_result?;
+
+    let next_schema_doc = match next_schema_indices {
+        None => None,
+        Some(next_schema_indices) => {
+            let (next_schema_docs, next_schema_errors): (Vec<_>, Vec<_>) = next_schema_indices
+                .into_iter()
+                .map(|file_idx| parse_schema_file(file_store, file_idx))
+                .partition_result();
+            if !next_schema_errors.is_empty() {
+                return Err(CommandError::merge(next_schema_errors));
+            }
+            Some(resolve_loaded_schema(next_schema_docs)?)
+        }
+    };
+
+    let context = CliContext::SchemaUnresolved {
+        config,
+        schema: merged_schema_doc,
+        next_schema: next_schema_doc,
+        operations: operation_docs,
+        file_store,
+        output,
+    };
+
+    run_commands(&commands, context)
+}
+
+/// Loads config file and merges command line arguments into it.
+fn load_cli_config(args: Args) -> Result<CliConfig, CommandError> {
     if args.commands.is_empty() {
         return Err(CliError::NoCommandSpecified.into());
     }
@@ -140,74 +271,142 @@ fn run_cli_impl(
     if let Some(path) = args.schema_output {
         config.generate.schema_output = Some(path);
     }
+    if let Some(path) = args.print_schema_output {
+        config.print_schema.output = Some(path);
+    }
+    if let Some(path) = args.print_introspection_output {
+        config.print_introspection.output = Some(path);
+    }
     info!("Loaded config {config:?}");
     info!("root_dir {}", root_dir.display());
 
-    let config = CliConfig { root_dir, config };
-
-    if config.config.schema.is_empty() {
+    if config.schema.is_empty() {
         return Err(CliError::NoSchemaSpecified.into());
     }
 
-    let schema_files = load_glob_files(&config.root_dir, &config.config.schema)?;
-    let (schema_docs, schema_errors): (Vec<_>, Vec<_>) = schema_files
-        .into_iter()
-        .map(|(path, buf)| -> Result<_, CommandError> {
-            let file_idx = file_store.add_file(path, buf, FileKind::Schema);
-            let (ref path, buf, _) = file_store.get_file(file_idx).unwrap();
-            // Treat JSON file as introspection result schema.
-            let is_introspection = path.extension().map(|ext| ext == "json").unwrap_or(false);
-            if is_introspection {
-                info!("parsing(introspection) {}", path.to_string_lossy());
-                let doc = schema_from_introspection_json(buf)?;
-                Ok(LoadedSchema::Introspection(doc))
-            } else {
-                info!("parsing(schema) {} {}", path.to_string_lossy(), file_idx);
-                set_current_file_of_pos(file_idx);
-                let doc = parse_type_system_document(buf)?;
-                Ok(LoadedSchema::GraphQL(doc))
-            }
-        })
-        .partition_result();
-    if !schema_errors.is_empty() {
-        return Err(CommandError::merge(schema_errors));
+    Ok(CliConfig {
+        root_dir,
+        config,
+        next_schema: args.next_schema,
+        max_warnings: args.max_warnings,
+        format_check: args.check,
+        affected_operations: None,
+    })
+}
+
+/// Parses a schema file in the file store.
+fn parse_schema_file<'a>(
+    file_store: &'a FileStore,
+    file_idx: usize,
+) -> Result<LoadedSchema<'a, TypeSystemOrExtensionDocument<'a>>, CommandError> {
+    let (ref path, ref buf, _) = file_store[file_idx];
+    parse_schema_source(path, buf, file_idx)
+}
+
+/// Parses the content of a schema file whose index in the file store is `file_idx`.
+fn parse_schema_source<'a>(
+    path: &Path,
+    buf: &'a str,
+    file_idx: usize,
+) -> Result<LoadedSchema<'a, TypeSystemOrExtensionDocument<'a>>, CommandError> {
+    // Treat JSON file as introspection result schema.
+    let is_introspection = path.extension().map(|ext| ext == "json").unwrap_or(false);
+    if is_introspection {
+        info!("parsing(introspection) {}", path.to_string_lossy());
+        let doc = schema_from_introspection_json(buf)?;
+        Ok(LoadedSchema::Introspection(doc))
+    } else {
+        info!("parsing(schema) {} {}", path.to_string_lossy(), file_idx);
+        set_current_file_of_pos(file_idx);
+        let (doc, errors) = parse_type_system_document_recovering(buf);
+        syntax_errors(errors)?;
+        Ok(LoadedSchema::GraphQL(doc))
     }
-    let merged_schema_doc = resolve_loaded_schema(schema_docs)?;
+}
 
-    let operation_files = load_glob_files(&config.root_dir, &config.config.operations)?;
+/// Adds an operation file to the file store.
+fn add_operation_file(
+    file_store: &mut FileStore,
+    path: PathBuf,
+    buf: String,
+    embedded: &EmbeddedConfig,
+) -> usize {
+    let file_idx = file_store.add_file(path, buf, FileKind::Operation);
+    store_embedded_documents(file_store, file_idx, embedded);
+    file_idx
+}
 
-    let (operation_docs, operation_errors): (Vec<_>, Vec<_>) = operation_files
-        .into_iter()
-        .map(
-            |(path, buf)| -> Result<(PathBuf, OperationDocument, usize), CommandError> {
-                info!("parsing(operation) {}", path.to_string_lossy());
-                let file_idx = file_store.add_file(path.clone(), buf, FileKind::Operation);
-                let (_, buf, _) = file_store.get_file(file_idx).unwrap();
-                set_current_file_of_pos(file_idx);
-
-                let doc = parse_operation_document(buf)?;
-                Ok((path, doc, file_idx))
-            },
-        )
-        .partition_result();
-    if !operation_errors.is_empty() {
-        return Err(CommandError::merge(operation_errors));
+/// Extracts documents embedded in a TypeScript or JavaScript file and keeps them in the file store
+/// next to the file content, as documents parsed from them borrow the file store.
+/// Should be called again whenever the file content is replaced.
+fn store_embedded_documents(
+    file_store: &mut FileStore,
+    file_idx: usize,
+    embedded: &EmbeddedConfig,
+) {
+    let (ref path, ref buf, _) = file_store[file_idx];
+    if !is_embedding_source(path) {
+        return;
     }
+    let documents = extract_owned_embedded_documents(buf, embedded);
+    file_store.set_embedded_documents(file_idx, documents);
+}
 
-    let mut context = CliContext::SchemaUnresolved {
-        config,
-        schema: merged_schema_doc,
-        operations: operation_docs,
-        file_store,
-        output,
+/// Extracts documents embedded in a TypeScript or JavaScript file as owned documents.
+fn extract_owned_embedded_documents(
+    buf: &str,
+    embedded: &EmbeddedConfig,
+) -> Vec<EmbeddedDocument<'static>> {
+    let options = EmbeddedDocumentOptions {
+        tag_names: embedded.tag_names.clone(),
+        module_sources: embedded.module_sources.clone(),
     };
+    extract_embedded_documents(buf, &options)
+        .into_iter()
+        .map(EmbeddedDocument::into_owned)
+        .collect()
+}
 
-    for command in args.commands.iter() {
-        context = run_command(command, context)
-            .map_err(|err| CommandError::new(vec![err], command.clone()))?;
+/// Parses an operation file in the file store.
+/// TypeScript and JavaScript files are parsed as a document made of all GraphQL documents in their tagged templates.
+fn parse_operation_file<'a>(
+    file_store: &'a FileStore,
+    file_idx: usize,
+) -> Result<OperationDocument<'a>, CommandError> {
+    let (ref path, ref buf, _) = file_store[file_idx];
+    parse_operation_source(path, buf, file_store.embedded_documents(file_idx), file_idx)
+}
+
+/// Parses the content of an operation file whose index in the file store is `file_idx`.
+/// `embedded` is the documents extracted from the content if the file is a TypeScript or JavaScript file.
+fn parse_operation_source<'a>(
+    path: &Path,
+    buf: &'a str,
+    embedded: &'a [EmbeddedDocument],
+    file_idx: usize,
+) -> Result<OperationDocument<'a>, CommandError> {
+    info!("parsing(operation) {}", path.to_string_lossy());
+    set_current_file_of_pos(file_idx);
+    if is_embedding_source(path) {
+        let (doc, errors) = parse_embedded_operation_documents(embedded);
+        syntax_errors(errors)?;
+        return Ok(doc);
     }
+    let (doc, errors) = parse_operation_document_recovering(buf);
+    syntax_errors(errors)?;
+    Ok(doc)
+}
 
-    Ok(())
+/// Reports all syntax errors found in a file.
+fn syntax_errors(errors: Vec<ParseError>) -> Result<(), CommandError> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CommandError {
+            inner: errors.into_iter().map(PositionedError::from).collect(),
+            command: None,
+        })
+    }
 }
 
 struct CommandError {
@@ -243,6 +442,14 @@ impl<E: Into<PositionedError>> From<E> for CommandError {
     }
 }
 
+fn run_commands(commands: &[String], mut context: CliContext) -> Result<(), CommandError> {
+    for command in commands.iter() {
+        context = run_command(command, context)
+            .map_err(|err| CommandError::new(vec![err], command.clone()))?;
+    }
+    Ok(())
+}
+
 fn run_command<'a>(
     command: &str,
     context: CliContext<'a>,
@@ -250,6 +457,10 @@ fn run_command<'a>(
     match command {
         "check" => run_check(context),
         "generate" => run_generate(context),
+        "print-schema" => run_print_schema(context),
+        "print-introspection" => run_print_introspection(context),
+        "diff" => run_diff(context),
+        "format" => run_format(context),
         command => Err(CliError::UnknownCommand(command.to_owned()).into()),
     }
 }
@@ -258,29 +469,111 @@ fn load_glob_files<'a, S: AsRef<str> + 'a>(
     root: &Path,
     globs: impl IntoIterator<Item = &'a S>,
 ) -> Result<Vec<(PathBuf, String)>> {
+    let paths = glob_paths(root, globs)?;
+    let results = paths
+        .into_iter()
+        .map(|path| {
+            info!("loading {}", path.to_string_lossy());
+            fs::read_to_string(&path).map(|res| (path, res))
+        })
+        .collect::<std::io::Result<_>>();
+
+    results.map_err(|err| err.into())
+}
+
+/// Lists files that match given globs.
+fn glob_paths<'a, S: AsRef<str> + 'a>(
+    root: &Path,
+    globs: impl IntoIterator<Item = &'a S>,
+) -> Result<Vec<PathBuf>> {
     let path_strs: Vec<&str> = globs.into_iter().map(|s| s.as_ref()).collect();
     if path_strs.is_empty() {
         return Ok(vec![]);
     }
 
-    trace!("load_glob_files {} {}", root.display(), path_strs.join(" "));
+    trace!("glob_paths {} {}", root.display(), path_strs.join(" "));
     let schema_matchers = build_matchers(&path_strs, root).map_err(CliError::GlobError)?;
     let (paths, _) = match_paths(schema_matchers, None, None);
     trace!("match_paths {paths:?}");
-    let results = paths
-        .into_iter()
-        .map(|path| {
-            info!("loading {}", path.to_string_lossy());
-            fs::read_to_string(&path).map(|res| (path, res))
+    Ok(paths)
+}
+
+/// Loads operation files imported by `#import` comments that are not loaded yet.
+/// Import paths are resolved relative to the importing file.
+/// Loaded files are added to the file store and appended to `operation_files`.
+fn load_imported_operation_files(
+    file_store: &mut FileStore,
+    operation_files: &mut Vec<(PathBuf, usize)>,
+    embedded: &EmbeddedConfig,
+) -> Result<(), CommandError> {
+    let mut loaded_paths = operation_files
+        .iter()
+        .map(|(path, _)| normalize_path(path))
+        .collect::<HashSet<_>>();
+    let mut errors = vec![];
+    // operation_files grows while iterating so that imports are followed transitively.
+    let mut idx = 0;
+    while idx < operation_files.len() {
+        let (ref path, file_idx) = operation_files[idx];
+        idx += 1;
+        // Syntax errors are reported when the file is parsed for commands.
+        let Ok(doc) = parse_operation_file(file_store, file_idx) else {
+            continue;
+        };
+        for (path, position) in imported_paths(path, &doc) {
+            if !loaded_paths.insert(path.clone()) {
+                continue;
+            }
+            let buf = match read_imported_file(&path, position) {
+                Ok(buf) => buf,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+            let file_idx = add_operation_file(file_store, path.clone(), buf, embedded);
+            operation_files.push((path, file_idx));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CommandError {
+            inner: errors,
+            command: None,
         })
-        .collect::<std::io::Result<_>>();
+    }
+}
 
-    results.map_err(|err| err.into())
+/// Resolves paths imported by `#import` comments in an operation file at `path`,
+/// along with the positions of the comments.
+fn imported_paths(path: &Path, doc: &OperationDocument) -> Vec<(PathBuf, Pos)> {
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    doc.imports
+        .iter()
+        .map(|import| (normalize_path(&base_dir.join(import.path)), import.position))
+        .collect()
+}
+
+/// Reads a file imported by an `#import` comment at `position`.
+fn read_imported_file(path: &Path, position: Pos) -> Result<String, PositionedError> {
+    info!("loading(import) {}", path.to_string_lossy());
+    fs::read_to_string(path).map_err(|err| {
+        PositionedError::new(
+            CliError::FailedToLoadImport {
+                path: path.to_owned(),
+                message: err.to_string(),
+            }
+            .into(),
+            Some(position),
+            vec![],
+        )
+    })
 }
 
 fn resolve_loaded_schema<'src>(
     schema_docs: Vec<LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>>,
-) -> Result<LoadedSchema<TypeSystemOrExtensionDocument>, CliError> {
+) -> Result<LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>, CliError> {
     let mut introsection: Option<Schema<_, _>> = None;
     let mut documents: Vec<TypeSystemOrExtensionDocument> = vec![];
     for doc in schema_docs {
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 /// Kind of input file.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum InputFileKind {
     Schema,
     Operation,
@@ -23,11 +23,16 @@ pub enum OutputFileKind {
     SchemaTypeDefinitionSourceMap,
     OperationTypeDefinition,
     OperationTypeDefinitionSourceMap,
+    ResolverTypeDefinition,
+    ResolverTypeDefinitionSourceMap,
+    SchemaDefinition,
+    Introspection,
+    PersistedDocuments,
 }
 
 impl OutputFileKind {
     /// Convert self to corresponding source map kind.
-    /// If self is already a source map kind, return self.
+    /// If self is already a source map kind or has no source map, return self.
     pub fn to_source_map_kind(self) -> Self {
         match self {
             OutputFileKind::SchemaTypeDefinition => OutputFileKind::SchemaTypeDefinitionSourceMap,
@@ -40,6 +45,15 @@ impl OutputFileKind {
             OutputFileKind::OperationTypeDefinitionSourceMap => {
                 OutputFileKind::OperationTypeDefinitionSourceMap
             }
+            OutputFileKind::ResolverTypeDefinition => {
+                OutputFileKind::ResolverTypeDefinitionSourceMap
+            }
+            OutputFileKind::ResolverTypeDefinitionSourceMap => {
+                OutputFileKind::ResolverTypeDefinitionSourceMap
+            }
+            OutputFileKind::SchemaDefinition => OutputFileKind::SchemaDefinition,
+            OutputFileKind::Introspection => OutputFileKind::Introspection,
+            OutputFileKind::PersistedDocuments => OutputFileKind::PersistedDocuments,
         }
     }
 }
@@ -55,6 +69,13 @@ impl Display for OutputFileKind {
             OutputFileKind::OperationTypeDefinitionSourceMap => {
                 write!(f, "operationTypeDefinitionSourceMap")
             }
+            OutputFileKind::ResolverTypeDefinition => write!(f, "resolverTypeDefinition"),
+            OutputFileKind::ResolverTypeDefinitionSourceMap => {
+                write!(f, "resolverTypeDefinitionSourceMap")
+            }
+            OutputFileKind::SchemaDefinition => write!(f, "schemaDefinition"),
+            OutputFileKind::Introspection => write!(f, "introspection"),
+            OutputFileKind::PersistedDocuments => write!(f, "persistedDocuments"),
         }
     }
 }
@@ -1,21 +1,45 @@
 use std::path::PathBuf;
 
 use json_writer::JSONObjectWriter;
-use nitrogql_checker::CheckError;
+use nitrogql_ast::base::Pos;
+use nitrogql_checker::{CheckError, LintError, Severity};
+use nitrogql_schema_diff::{ChangeSeverity, SchemaChange, SchemaChangeMessage};
 
 mod file_kind;
 
 pub use file_kind::{InputFileKind, OutputFileKind};
-use nitrogql_error::print_positioned_error;
-
-use crate::file_store::FileStore;
+use nitrogql_error::{print_positioned_error, PositionedError};
+use nitrogql_utils::FileStore;
 
 /// Struct that keeps track of all outputs.
 pub struct CliOutput {
     commands_run: Vec<String>,
     command_error: Option<(Option<String>, String)>,
     check_errors: Vec<(file_kind::InputFileKind, CheckError)>,
+    lint_errors: Vec<(file_kind::InputFileKind, LintError)>,
     generated_files: Vec<(file_kind::OutputFileKind, PathBuf)>,
+    schema_changes: Vec<SchemaChange>,
+    /// Set if operations are checked against the next schema.
+    next_schema_errors: Option<Vec<OperationErrors>>,
+    /// Set if the 'format' command is run.
+    format_result: Option<FormatResult>,
+}
+
+/// Errors found in one operation.
+pub struct OperationErrors {
+    /// Label of the operation, such as `query 'GetUser'`.
+    pub operation: String,
+    /// Position of the operation.
+    pub position: Pos,
+    pub errors: Vec<CheckError>,
+}
+
+/// Files reported by the 'format' command.
+pub struct FormatResult {
+    /// Whether files were only checked without being rewritten.
+    pub check: bool,
+    /// Files that were rewritten, or files that are not formatted in check mode.
+    pub files: Vec<(InputFileKind, PathBuf)>,
 }
 
 impl CliOutput {
@@ -25,7 +49,11 @@ impl CliOutput {
             commands_run: Vec::new(),
             command_error: None,
             check_errors: Vec::new(),
+            lint_errors: Vec::new(),
             generated_files: Vec::new(),
+            schema_changes: Vec::new(),
+            next_schema_errors: None,
+            format_result: None,
         }
     }
 
@@ -44,35 +72,134 @@ impl CliOutput {
         self.generated_files.push((kind, path));
     }
 
+    /// Add errors found by checking operations against the next schema.
+    pub fn next_schema_errors(&mut self, errors: Vec<OperationErrors>) {
+        self.next_schema_errors = Some(errors);
+    }
+
+    /// Set the result of the 'format' command.
+    pub fn format_result(&mut self, result: FormatResult) {
+        self.format_result = Some(result);
+    }
+
+    /// Number of warnings found by checks.
+    pub fn warning_count(&self) -> usize {
+        let check_warnings = self
+            .check_errors
+            .iter()
+            .filter(|(_, error)| error.severity() == Severity::Warning)
+            .count();
+        let lint_warnings = self
+            .lint_errors
+            .iter()
+            .filter(|(_, error)| error.severity == Severity::Warning)
+            .count();
+        check_warnings + lint_warnings
+    }
+
     /// Output for human consumption.
     pub fn human_output(self, file_store: &FileStore) {
-        if !self.check_errors.is_empty() {
-            let (schema_errors, operation_errors): (Vec<_>, Vec<_>) = self
-                .check_errors
-                .into_iter()
-                .partition(|(kind, _)| match kind {
-                    InputFileKind::Schema => true,
-                    InputFileKind::Operation => false,
-                });
-            if !schema_errors.is_empty() {
+        let mut check_errors = self
+            .check_errors
+            .into_iter()
+            .map(|(kind, error)| (kind, error.severity(), PositionedError::from(error)))
+            .chain(
+                self.lint_errors
+                    .into_iter()
+                    .map(|(kind, error)| (kind, error.severity, error.into())),
+            )
+            .collect::<Vec<_>>();
+        for severity in [Severity::Error, Severity::Warning] {
+            for (kind, place) in [
+                (InputFileKind::Schema, "schema"),
+                (InputFileKind::Operation, "operations"),
+            ] {
+                let errors: Vec<_>;
+                (errors, check_errors) = check_errors
+                    .into_iter()
+                    .partition(|(k, s, _)| *k == kind && *s == severity);
+                if errors.is_empty() {
+                    continue;
+                }
                 eprintln!(
-                    "Found {} error{} in schema:",
-                    schema_errors.len(),
-                    if schema_errors.len() > 1 { "s" } else { "" }
+                    "Found {} {severity}{} in {place}:",
+                    errors.len(),
+                    if errors.len() > 1 { "s" } else { "" }
                 );
-                for (_, error) in schema_errors {
-                    eprintln!("{}", print_positioned_error(&error.into(), file_store));
+                for (_, _, error) in errors {
+                    eprintln!("{}", print_positioned_error(&error, file_store));
                 }
                 eprintln!();
             }
-            if !operation_errors.is_empty() {
+        }
+        if let Some(next_schema_errors) = self.next_schema_errors {
+            if !next_schema_errors.is_empty() {
                 eprintln!(
-                    "Found {} error{} in operations:",
-                    operation_errors.len(),
-                    if operation_errors.len() > 1 { "s" } else { "" }
+                    "Found {} operation{} that fail{} with the next schema:",
+                    next_schema_errors.len(),
+                    if next_schema_errors.len() > 1 {
+                        "s"
+                    } else {
+                        ""
+                    },
+                    if next_schema_errors.len() > 1 {
+                        ""
+                    } else {
+                        "s"
+                    },
                 );
-                for (_, error) in operation_errors {
-                    eprintln!("{}", print_positioned_error(&error.into(), file_store));
+                for operation_errors in next_schema_errors {
+                    let file = file_store.get_file(operation_errors.position.file);
+                    match file {
+                        Some((path, _, _)) => eprintln!(
+                            "In {} ({}:{}):",
+                            operation_errors.operation,
+                            path.to_string_lossy(),
+                            operation_errors.position.line + 1
+                        ),
+                        None => eprintln!("In {}:", operation_errors.operation),
+                    }
+                    for error in operation_errors.errors {
+                        eprintln!("{}", print_positioned_error(&error.into(), file_store));
+                    }
+                }
+                eprintln!();
+            }
+        }
+        let mut schema_changes = self.schema_changes;
+        for severity in [
+            ChangeSeverity::Breaking,
+            ChangeSeverity::Dangerous,
+            ChangeSeverity::Safe,
+        ] {
+            let changes: Vec<_>;
+            (changes, schema_changes) = schema_changes
+                .into_iter()
+                .partition(|change| change.severity == severity);
+            if changes.is_empty() {
+                continue;
+            }
+            eprintln!(
+                "Found {} {severity} change{} in schema:",
+                changes.len(),
+                if changes.len() > 1 { "s" } else { "" }
+            );
+            for change in changes {
+                eprintln!("{}", print_positioned_error(&change.into(), file_store));
+            }
+            eprintln!();
+        }
+        if let Some(format_result) = self.format_result {
+            if !format_result.files.is_empty() {
+                let count = format_result.files.len();
+                let files = if count > 1 { "files" } else { "file" };
+                if format_result.check {
+                    eprintln!("Found {count} unformatted {files}:");
+                } else {
+                    eprintln!("Formatted {count} {files}:");
+                }
+                for (_, path) in format_result.files {
+                    eprintln!("  {}", path.to_string_lossy());
                 }
                 eprintln!();
             }
@@ -94,31 +221,97 @@ impl CliOutput {
             obj.value("command", command.as_ref());
             obj.value("message", &message);
         }
-        if self.commands_run.iter().any(|c| c == "check") {
+        // Print commands check the schema by themselves.
+        if self.commands_run.iter().any(|c| {
+            c == "check" || c == "print-schema" || c == "print-introspection" || c == "diff"
+        }) {
             let mut obj = writer.object("check");
-            let mut errors = obj.array("errors");
-            for (kind, error) in self.check_errors {
-                let file = (!error.position.builtin)
-                    .then(|| file_store.get_file(error.position.file))
-                    .flatten();
-                let mut obj = errors.object();
-                obj.value("fileType", &kind.to_string());
-                match file {
-                    Some((path, _, _)) => {
-                        let mut obj = obj.object("file");
-                        obj.value("path", &path.to_string_lossy());
-                        obj.value("line", error.position.line as u32);
-                        obj.value("column", error.position.column as u32);
+            {
+                let mut errors = obj.array("errors");
+                for (kind, error) in self.check_errors {
+                    let mut obj = errors.object();
+                    obj.value("fileType", &kind.to_string());
+                    obj.value("severity", error.severity().as_str());
+                    write_file_position(&mut obj, "file", Some(error.position), file_store);
+                    obj.value("message", &error.message.to_string());
+                }
+            }
+            {
+                let mut errors = obj.array("lintErrors");
+                for (kind, error) in self.lint_errors {
+                    let mut obj = errors.object();
+                    obj.value("fileType", &kind.to_string());
+                    obj.value("severity", error.severity.as_str());
+                    obj.value("rule", error.rule_id);
+                    write_file_position(&mut obj, "file", Some(error.position), file_store);
+                    obj.value("message", &error.message.to_string());
+                }
+            }
+            if let Some(next_schema_errors) = self.next_schema_errors {
+                let mut operations = obj.array("nextSchemaErrors");
+                for operation_errors in next_schema_errors {
+                    let mut obj = operations.object();
+                    obj.value("operation", &operation_errors.operation);
+                    write_file_position(
+                        &mut obj,
+                        "file",
+                        Some(operation_errors.position),
+                        file_store,
+                    );
+                    let mut errors = obj.array("errors");
+                    for error in operation_errors.errors {
+                        let mut obj = errors.object();
+                        write_file_position(&mut obj, "file", Some(error.position), file_store);
+                        obj.value("message", &error.message.to_string());
                     }
-                    None => obj.value("file", None::<&bool>),
                 }
-                obj.value("message", &error.message.to_string());
             }
         }
+        let (generated_files, printed_files): (Vec<_>, Vec<_>) =
+            self.generated_files.into_iter().partition(|(kind, _)| {
+                !matches!(
+                    kind,
+                    OutputFileKind::SchemaDefinition | OutputFileKind::Introspection
+                )
+            });
         if self.commands_run.iter().any(|c| c == "generate") {
             let mut obj = writer.object("generate");
             let mut files = obj.array("files");
-            for (kind, path) in self.generated_files {
+            for (kind, path) in generated_files {
+                let mut obj = files.object();
+                obj.value("fileType", &kind.to_string());
+                obj.value("path", &path.to_string_lossy());
+            }
+        }
+        if self
+            .commands_run
+            .iter()
+            .any(|c| c == "print-schema" || c == "print-introspection")
+        {
+            let mut obj = writer.object("print");
+            let mut files = obj.array("files");
+            for (kind, path) in printed_files {
+                let mut obj = files.object();
+                obj.value("fileType", &kind.to_string());
+                obj.value("path", &path.to_string_lossy());
+            }
+        }
+        if self.commands_run.iter().any(|c| c == "diff") {
+            let mut obj = writer.object("diff");
+            let mut changes = obj.array("changes");
+            for change in self.schema_changes {
+                let mut obj = changes.object();
+                obj.value("severity", change.severity.as_str());
+                obj.value("message", &change.message.to_string());
+                write_file_position(&mut obj, "oldFile", change.old_position, file_store);
+                write_file_position(&mut obj, "newFile", change.new_position, file_store);
+            }
+        }
+        if let Some(format_result) = self.format_result {
+            let mut obj = writer.object("format");
+            obj.value("check", format_result.check);
+            let mut files = obj.array("files");
+            for (kind, path) in format_result.files {
                 let mut obj = files.object();
                 obj.value("fileType", &kind.to_string());
                 obj.value("path", &path.to_string_lossy());
@@ -137,24 +330,79 @@ impl CliOutput {
             source.value("name", "nitrogql");
             source.value("url", "https://nitrogql.vercel.app/");
         }
-        writer.value("severity", "ERROR");
         {
             let mut diagnostics = writer.array("diagnostics");
             for (_, error) in self.check_errors {
                 let mut obj = diagnostics.object();
                 obj.value("message", &error.message.to_string());
+                obj.value(
+                    "severity",
+                    match error.severity() {
+                        Severity::Error => "ERROR",
+                        Severity::Warning => "WARNING",
+                    },
+                );
+                write_rdjson_location(&mut obj, error.position, file_store);
+            }
+            for (_, error) in self.lint_errors {
+                let mut obj = diagnostics.object();
+                obj.value("message", &error.message.to_string());
+                obj.value(
+                    "severity",
+                    match error.severity {
+                        Severity::Error => "ERROR",
+                        Severity::Warning => "WARNING",
+                    },
+                );
                 {
-                    let mut location = obj.object("location");
-                    let file = (!error.position.builtin)
-                        .then(|| file_store.get_file(error.position.file))
-                        .flatten();
-                    if let Some((path, _, _)) = file {
-                        location.value("path", &path.to_string_lossy());
-                        let mut range = location.object("range");
-                        let mut start = range.object("start");
-                        start.value("line", error.position.line as u32 + 1);
-                        start.value("column", error.position.column as u32 + 1);
+                    let mut code = obj.object("code");
+                    code.value("value", error.rule_id);
+                }
+                write_rdjson_location(&mut obj, error.position, file_store);
+            }
+            for operation_errors in self.next_schema_errors.into_iter().flatten() {
+                for error in operation_errors.errors {
+                    let mut obj = diagnostics.object();
+                    obj.value(
+                        "message",
+                        &format!(
+                            "{} (in {} with the next schema)",
+                            error.message, operation_errors.operation
+                        ),
+                    );
+                    // Only errors are collected with the next schema.
+                    obj.value("severity", "ERROR");
+                    write_rdjson_location(&mut obj, error.position, file_store);
+                }
+            }
+            for change in self.schema_changes {
+                let mut obj = diagnostics.object();
+                obj.value("message", &change.message.to_string());
+                obj.value(
+                    "severity",
+                    match change.severity {
+                        ChangeSeverity::Breaking => "ERROR",
+                        ChangeSeverity::Dangerous => "WARNING",
+                        ChangeSeverity::Safe => "INFO",
+                    },
+                );
+                match (change.old_position, change.new_position) {
+                    (old_position, Some(new_position)) => {
+                        write_rdjson_location(&mut obj, new_position, file_store);
+                        if let Some(old_position) = old_position {
+                            let mut related_locations = obj.array("related_locations");
+                            let mut related = related_locations.object();
+                            related.value(
+                                "message",
+                                &SchemaChangeMessage::PreviousDefinition.to_string(),
+                            );
+                            write_rdjson_location(&mut related, old_position, file_store);
+                        }
+                    }
+                    (Some(old_position), None) => {
+                        write_rdjson_location(&mut obj, old_position, file_store);
                     }
+                    (None, None) => {}
                 }
             }
         }
@@ -168,3 +416,56 @@ impl Extend<(InputFileKind, CheckError)> for CliOutput {
         self.check_errors.extend(iter);
     }
 }
+
+impl Extend<(InputFileKind, LintError)> for CliOutput {
+    fn extend<T: IntoIterator<Item = (InputFileKind, LintError)>>(&mut self, iter: T) {
+        self.lint_errors.extend(iter);
+    }
+}
+
+impl Extend<SchemaChange> for CliOutput {
+    fn extend<T: IntoIterator<Item = SchemaChange>>(&mut self, iter: T) {
+        self.schema_changes.extend(iter);
+    }
+}
+
+/// Writes a file position as `{ path, line, column }`, or null if position is not in a file.
+fn write_file_position(
+    obj: &mut JSONObjectWriter,
+    key: &str,
+    position: Option<Pos>,
+    file_store: &FileStore,
+) {
+    let file = position
+        .filter(|position| !position.builtin)
+        .and_then(|position| Some((position, file_store.get_file(position.file)?)));
+    match file {
+        Some((position, (path, _, _))) => {
+            let mut obj = obj.object(key);
+            obj.value("path", &path.to_string_lossy());
+            obj.value("line", position.line as u32);
+            obj.value("column", position.column as u32);
+        }
+        None => obj.value(key, None::<&bool>),
+    }
+}
+
+/// Writes a rdjson location object.
+fn write_rdjson_location(obj: &mut JSONObjectWriter, position: Pos, file_store: &FileStore) {
+    let mut location = obj.object("location");
+    let file = (!position.builtin)
+        .then(|| file_store.get_file(position.file))
+        .flatten();
+    if let Some((path, _, _)) = file {
+        location.value("path", &path.to_string_lossy());
+        let mut range = location.object("range");
+        {
+            let mut start = range.object("start");
+            start.value("line", position.line as u32 + 1);
+            start.value("column", position.column as u32 + 1);
+        }
+        let mut end = range.object("end");
+        end.value("line", position.end_line as u32 + 1);
+        end.value("column", position.end_column as u32 + 1);
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use log::{debug, info};
+
+use graphql_builtins::generate_builtins;
+use nitrogql_ast::type_system::{
+    TypeSystemDefinition, TypeSystemDefinitionOrExtension, TypeSystemDocument,
+};
+use nitrogql_error::Result;
+use nitrogql_introspection::schema_to_introspection_json;
+use nitrogql_printer::GraphQLPrinter;
+use nitrogql_semantics::{ast_to_type_system, type_system_to_ast};
+use sourcemap_writer::JustWriter;
+
+use crate::{
+    check::with_resolved_schema,
+    context::{CliConfig, LoadedSchema},
+    output::{CliOutput, OutputFileKind},
+};
+
+use super::CliContext;
+
+/// Prints the resolved schema as a single SDL document.
+pub fn run_print_schema(context: CliContext) -> Result<CliContext> {
+    debug!("Printing schema");
+    let context = with_resolved_schema(context, "print-schema", |schema, config, output| {
+        let sdl = schema.map_into(print_sdl, |schema| print_sdl(&type_system_to_ast(schema)));
+        write_output(
+            &config.config.print_schema.output,
+            config,
+            output,
+            OutputFileKind::SchemaDefinition,
+            &sdl,
+        )
+    })?;
+    info!("Print schema succeeded");
+    eprintln!("'print-schema' finished");
+    Ok(context)
+}
+
+/// Prints the resolved schema as a result of the standard introspection query.
+pub fn run_print_introspection(context: CliContext) -> Result<CliContext> {
+    debug!("Printing introspection");
+    let context =
+        with_resolved_schema(context, "print-introspection", |schema, config, output| {
+            let json = match schema {
+                LoadedSchema::GraphQL(document) => {
+                    schema_to_introspection_json(&ast_to_type_system(document))
+                }
+                LoadedSchema::Introspection(schema) => schema_to_introspection_json(schema),
+            }?;
+            write_output(
+                &config.config.print_introspection.output,
+                config,
+                output,
+                OutputFileKind::Introspection,
+                &json,
+            )
+        })?;
+    info!("Print introspection succeeded");
+    eprintln!("'print-introspection' finished");
+    Ok(context)
+}
+
+/// Writes the output to given file, or to stdout if no file is given.
+fn write_output(
+    output_path: &Option<PathBuf>,
+    config: &CliConfig,
+    output: &mut CliOutput,
+    output_file_kind: OutputFileKind,
+    content: &str,
+) -> Result<()> {
+    match output_path {
+        Some(path) => {
+            let path = config.root_dir.join(path);
+            if let Some(parent_dir) = path.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            fs::write(&path, content)?;
+            output.generated_file(output_file_kind, path);
+        }
+        None => {
+            io::stdout().write_all(content.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints given document, omitting built-in and introspection definitions.
+fn print_sdl(document: &TypeSystemDocument) -> String {
+    let builtins = generate_builtins();
+    let builtin_types = builtins
+        .iter()
+        .filter_map(|def| match def {
+            TypeSystemDefinitionOrExtension::TypeDefinition(def) => Some(def.name().name),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+    let builtin_directives = builtins
+        .iter()
+        .filter_map(|def| match def {
+            TypeSystemDefinitionOrExtension::DirectiveDefinition(def) => Some(def.name.name),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let definitions = document
+        .definitions
+        .iter()
+        .filter(|def| match def {
+            TypeSystemDefinition::SchemaDefinition(_) => true,
+            TypeSystemDefinition::TypeDefinition(def) => {
+                let name = def.name().name;
+                !name.starts_with("__") && !builtin_types.contains(name)
+            }
+            TypeSystemDefinition::DirectiveDefinition(def) => {
+                !builtin_directives.contains(def.name.name)
+            }
+        })
+        .cloned()
+        .collect();
+    let document = TypeSystemDocument { definitions };
+
+    let mut result = String::new();
+    let mut writer = JustWriter::new(&mut result);
+    document.print_graphql(&mut writer);
+    result
+}
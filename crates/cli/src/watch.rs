@@ -0,0 +1,476 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use log::debug;
+use nitrogql_ast::{
+    operation::{ExecutableDefinition, OperationDocument},
+    type_system::TypeSystemOrExtensionDocument,
+};
+use nitrogql_config_file::EmbeddedConfig;
+use nitrogql_parser::{is_embedding_source, EmbeddedDocument};
+use nitrogql_semantics::FragmentRegistry;
+use nitrogql_utils::{normalize_path, FileKind, FileStore};
+use self_cell::self_cell;
+
+use crate::{
+    context::{CliConfig, CliContext, LoadedSchema, OutputFormat},
+    extract_owned_embedded_documents, glob_paths, imported_paths,
+    output::CliOutput,
+    parse_operation_source, parse_schema_source, read_imported_file, report, resolve_loaded_schema,
+    run_commands, CommandError,
+};
+
+/// Interval of polling the file system for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Runs commands, and re-runs them for affected files whenever input files change.
+pub fn run_watch(commands: Vec<String>, config: CliConfig, output_format: OutputFormat) -> ! {
+    let mut watcher = Watcher {
+        commands,
+        config,
+        output_format,
+        files: vec![],
+        imported_paths: HashSet::new(),
+        file_indices: HashMap::new(),
+        mtimes: HashMap::new(),
+    };
+    let mut update = watcher.load_all().map(|_| Some(None));
+    loop {
+        let file_store = watcher.file_store();
+        match update {
+            Ok(None) => {}
+            Ok(Some(changes)) => {
+                let mut output = CliOutput::new();
+                let result = watcher.run(changes, &file_store, &mut output);
+                report(output, result, &file_store, watcher.output_format);
+                eprintln!("Watching for changes...");
+            }
+            Err(err) => {
+                report(
+                    CliOutput::new(),
+                    Err(err),
+                    &file_store,
+                    watcher.output_format,
+                );
+                eprintln!("Watching for changes...");
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+        update = watcher.update();
+    }
+}
+
+/// Changes to operation files. None means that all files are affected.
+type Changes = Option<ChangedOperations>;
+
+struct ChangedOperations {
+    /// Indices of changed or added operation files.
+    file_indices: HashSet<usize>,
+    /// Names of fragments defined in changed files before the change.
+    old_fragments: HashSet<String>,
+    /// Names of fragments spread from changed files before the change.
+    old_spreads: HashSet<String>,
+}
+
+/// Content of a watched file.
+struct FileContent {
+    path: PathBuf,
+    buf: String,
+    kind: FileKind,
+    /// Documents embedded in the file if it is a TypeScript or JavaScript operation file.
+    embedded: Vec<EmbeddedDocument<'static>>,
+}
+
+/// Document parsed from a watched file.
+#[allow(clippy::large_enum_variant)]
+enum ParsedDocument<'src> {
+    Schema(LoadedSchema<'src, TypeSystemOrExtensionDocument<'src>>),
+    Operation(OperationDocument<'src>),
+    /// The file has errors, which are reported by parsing the file again.
+    Invalid,
+}
+
+self_cell!(
+    /// A watched file that keeps the document parsed from its content,
+    /// so that only changed files are parsed again.
+    struct WatchedFile {
+        owner: FileContent,
+
+        #[covariant]
+        dependent: ParsedDocument,
+    }
+);
+
+impl WatchedFile {
+    /// Parses given content of the file at `file_idx` in the file store.
+    fn parse(content: FileContent, file_idx: usize) -> Self {
+        WatchedFile::new(content, |content| match parse_file(content, file_idx) {
+            Ok(doc) => doc,
+            Err(_) => ParsedDocument::Invalid,
+        })
+    }
+
+    fn content(&self) -> &FileContent {
+        self.borrow_owner()
+    }
+
+    fn document(&self) -> &ParsedDocument<'_> {
+        self.borrow_dependent()
+    }
+}
+
+fn parse_file(content: &FileContent, file_idx: usize) -> Result<ParsedDocument<'_>, CommandError> {
+    match content.kind {
+        FileKind::Schema | FileKind::NextSchema => {
+            parse_schema_source(&content.path, &content.buf, file_idx).map(ParsedDocument::Schema)
+        }
+        FileKind::Operation => {
+            parse_operation_source(&content.path, &content.buf, &content.embedded, file_idx)
+                .map(ParsedDocument::Operation)
+        }
+    }
+}
+
+/// Watches input files. Files are kept in the order of the file store,
+/// along with the documents parsed from them.
+struct Watcher {
+    commands: Vec<String>,
+    config: CliConfig,
+    output_format: OutputFormat,
+    /// Watched files indexed by their index in the file store.
+    files: Vec<WatchedFile>,
+    /// Files loaded by `#import` comments. They are watched even if they do not match the globs.
+    imported_paths: HashSet<PathBuf>,
+    /// Mapping from watched file paths to file indices.
+    file_indices: HashMap<PathBuf, usize>,
+    /// Last modified time of watched files.
+    mtimes: HashMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+    /// Loads all files from scratch. Previously loaded files are released.
+    fn load_all(&mut self) -> Result<(), CommandError> {
+        self.files.clear();
+        self.imported_paths.clear();
+        self.file_indices.clear();
+
+        let schema_paths = glob_paths(&self.config.root_dir, &self.config.config.schema)?;
+        let operation_paths = glob_paths(&self.config.root_dir, &self.config.config.operations)?;
+        let next_schema_paths = glob_paths(&self.config.root_dir, &self.config.next_schema)?;
+        for path in schema_paths {
+            self.add_file(path, FileKind::Schema)?;
+        }
+        for path in operation_paths {
+            self.add_file(path, FileKind::Operation)?;
+        }
+        let import_result = self.load_imports();
+        // Next schema files are loaded last because schema files must come before other files in the file store.
+        for path in next_schema_paths {
+            self.add_file(path, FileKind::NextSchema)?;
+        }
+        self.mtimes = modified_times(self.file_indices.keys());
+        import_result
+    }
+
+    /// Checks for changes and parses changed files again.
+    /// Returns None if nothing has changed.
+    fn update(&mut self) -> Result<Option<Changes>, CommandError> {
+        let schema_paths = glob_paths(&self.config.root_dir, &self.config.config.schema)?;
+        let operation_paths = glob_paths(&self.config.root_dir, &self.config.config.operations)?;
+        let next_schema_paths = glob_paths(&self.config.root_dir, &self.config.next_schema)?;
+        let mtimes = modified_times(
+            schema_paths
+                .iter()
+                .chain(operation_paths.iter())
+                .chain(next_schema_paths.iter())
+                .chain(self.imported_paths.iter()),
+        );
+        if mtimes == self.mtimes {
+            return Ok(None);
+        }
+        let changed_paths = mtimes
+            .iter()
+            .filter(|(path, mtime)| self.mtimes.get(*path) != Some(mtime))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        let has_removed_path = self.mtimes.keys().any(|path| !mtimes.contains_key(path));
+        self.mtimes = mtimes;
+
+        if has_removed_path {
+            // Removed files are released by reloading.
+            debug!("Removed files found; reloading all files");
+            self.load_all()?;
+            return Ok(Some(None));
+        }
+        if schema_paths
+            .iter()
+            .chain(next_schema_paths.iter())
+            .any(|path| !self.file_indices.contains_key(path))
+        {
+            // Schema files must come before operation files in the file store.
+            debug!("New schema file found; reloading all files");
+            self.load_all()?;
+            return Ok(Some(None));
+        }
+
+        let mut schema_changed = false;
+        let mut changes = ChangedOperations {
+            file_indices: HashSet::new(),
+            old_fragments: HashSet::new(),
+            old_spreads: HashSet::new(),
+        };
+        for path in changed_paths {
+            let Some(&file_idx) = self.file_indices.get(&path) else {
+                debug!("Added {}", path.to_string_lossy());
+                let file_idx = self.add_file(path, FileKind::Operation)?;
+                changes.file_indices.insert(file_idx);
+                continue;
+            };
+            let buf = fs::read_to_string(&path)?;
+            let file = &self.files[file_idx];
+            if file.content().buf == buf {
+                continue;
+            }
+            debug!("Changed {}", path.to_string_lossy());
+            let kind = file.content().kind;
+            if kind == FileKind::Operation {
+                // Old fragments are recorded because the old document is dropped by the change.
+                if let ParsedDocument::Operation(old_doc) = file.document() {
+                    let registry = FragmentRegistry::from_document(old_doc);
+                    changes.old_fragments.extend(
+                        registry
+                            .iter()
+                            .map(|fragment| fragment.name.name.to_owned()),
+                    );
+                    changes
+                        .old_spreads
+                        .extend(registry.spread_fragment_names().map(str::to_owned));
+                }
+                changes.file_indices.insert(file_idx);
+            } else {
+                schema_changed = true;
+            }
+            let content = file_content(path, buf, kind, &self.config.config.embedded);
+            self.files[file_idx] = WatchedFile::parse(content, file_idx);
+        }
+        self.load_imports()?;
+
+        if schema_changed {
+            return Ok(Some(None));
+        }
+        if changes.file_indices.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Some(changes)))
+    }
+
+    /// Makes a file store that borrows the contents of watched files.
+    fn file_store(&self) -> FileStore<'_> {
+        let mut file_store = FileStore::new();
+        for file in self.files.iter() {
+            let content = file.content();
+            file_store.add_borrowed_file(content.path.clone(), &content.buf, content.kind);
+        }
+        file_store
+    }
+
+    /// Runs commands for affected files with the parsed documents.
+    fn run<'a>(
+        &'a self,
+        changes: Changes,
+        file_store: &'a FileStore<'a>,
+        output: &'a mut CliOutput,
+    ) -> Result<(), CommandError> {
+        let mut schema_docs = vec![];
+        let mut next_schema_docs = vec![];
+        let mut operations = vec![];
+        let mut parse_errors = vec![];
+        for (file_idx, file) in self.files.iter().enumerate() {
+            let content = file.content();
+            match (file.document(), content.kind) {
+                (ParsedDocument::Schema(doc), FileKind::Schema) => schema_docs.push(doc.clone()),
+                (ParsedDocument::Schema(doc), _) => next_schema_docs.push(doc.clone()),
+                (ParsedDocument::Operation(doc), _) => {
+                    operations.push((content.path.clone(), doc.clone(), file_idx))
+                }
+                (ParsedDocument::Invalid, _) => {
+                    if let Err(err) = parse_file(content, file_idx) {
+                        parse_errors.push(err);
+                    }
+                }
+            }
+        }
+        if !parse_errors.is_empty() {
+            return Err(CommandError::merge(parse_errors));
+        }
+        let schema = resolve_loaded_schema(schema_docs)?;
+        let next_schema = if self.config.next_schema.is_empty() {
+            None
+        } else {
+            Some(resolve_loaded_schema(next_schema_docs)?)
+        };
+        let affected_operations = changes.map(|changes| {
+            let affected = affected_operations(&operations, changes);
+            debug!("Affected files: {affected:?}");
+            affected
+        });
+        let context = CliContext::SchemaUnresolved {
+            config: CliConfig {
+                affected_operations,
+                ..self.config.clone()
+            },
+            schema,
+            next_schema,
+            operations,
+            file_store,
+            output,
+        };
+        run_commands(&self.commands, context)
+    }
+
+    /// Reads and parses a file, and starts watching it.
+    fn add_file(&mut self, path: PathBuf, kind: FileKind) -> Result<usize, CommandError> {
+        let buf = fs::read_to_string(&path)?;
+        Ok(self.add_file_content(path, buf, kind))
+    }
+
+    fn add_file_content(&mut self, path: PathBuf, buf: String, kind: FileKind) -> usize {
+        let file_idx = self.files.len();
+        self.file_indices.insert(path.clone(), file_idx);
+        let content = file_content(path, buf, kind, &self.config.config.embedded);
+        self.files.push(WatchedFile::parse(content, file_idx));
+        file_idx
+    }
+
+    /// Loads files imported from operation documents that are not loaded yet,
+    /// and starts watching them. Imports are followed transitively.
+    fn load_imports(&mut self) -> Result<(), CommandError> {
+        let mut loaded_paths = self
+            .files
+            .iter()
+            .filter(|file| file.content().kind == FileKind::Operation)
+            .map(|file| normalize_path(&file.content().path))
+            .collect::<HashSet<_>>();
+        let mut errors = vec![];
+        // files grows while iterating.
+        let mut file_idx = 0;
+        while file_idx < self.files.len() {
+            let file = &self.files[file_idx];
+            file_idx += 1;
+            // Syntax errors are reported when commands are run.
+            let ParsedDocument::Operation(doc) = file.document() else {
+                continue;
+            };
+            for (path, position) in imported_paths(&file.content().path, doc) {
+                if !loaded_paths.insert(path.clone()) {
+                    continue;
+                }
+                match read_imported_file(&path, position) {
+                    Ok(buf) => {
+                        self.mtimes.extend(modified_times([&path].into_iter()));
+                        self.imported_paths.insert(path.clone());
+                        self.add_file_content(path, buf, FileKind::Operation);
+                    }
+                    Err(err) => errors.push(err),
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CommandError {
+                inner: errors,
+                command: None,
+            })
+        }
+    }
+}
+
+/// Makes the content of a watched file, extracting embedded documents from an operation file.
+fn file_content(
+    path: PathBuf,
+    buf: String,
+    kind: FileKind,
+    embedded: &EmbeddedConfig,
+) -> FileContent {
+    let embedded = if kind == FileKind::Operation && is_embedding_source(&path) {
+        extract_owned_embedded_documents(&buf, embedded)
+    } else {
+        vec![]
+    };
+    FileContent {
+        path,
+        buf,
+        kind,
+        embedded,
+    }
+}
+
+/// Calculates the set of operation files affected by changes.
+/// In addition to changed files, files that share fragments with changed files are affected.
+fn affected_operations(
+    operations: &[(PathBuf, OperationDocument, usize)],
+    changes: ChangedOperations,
+) -> HashSet<usize> {
+    let changed_registries = operations
+        .iter()
+        .filter(|(_, _, file_idx)| changes.file_indices.contains(file_idx))
+        .map(|(_, doc, _)| FragmentRegistry::from_document(doc))
+        .collect::<Vec<_>>();
+    let changed_fragments = changed_registries
+        .iter()
+        .flat_map(|registry| registry.iter().map(|fragment| fragment.name.name))
+        .chain(changes.old_fragments.iter().map(String::as_str))
+        .collect::<HashSet<_>>();
+    let changed_spreads = changed_registries
+        .iter()
+        .flat_map(|registry| registry.spread_fragment_names())
+        .chain(changes.old_spreads.iter().map(String::as_str))
+        .collect::<HashSet<_>>();
+
+    let mut all_fragments = FragmentRegistry::new();
+    for (_, doc, _) in operations.iter() {
+        all_fragments.add_document(doc);
+    }
+
+    let mut affected = changes.file_indices;
+    for (_, doc, file_idx) in operations.iter() {
+        let registry = FragmentRegistry::from_document(doc);
+        // Fragments defined here may be spread (or no longer spread) from changed files.
+        let defines_spread_fragment = registry
+            .iter()
+            .any(|fragment| changed_spreads.contains(fragment.name.name));
+        // Fragments defined in changed files may be used from here.
+        let uses_changed_fragment = changed_fragments
+            .iter()
+            .any(|name| registry.is_spread(name))
+            || doc.definitions.iter().any(|def| {
+                let selection_set = match def {
+                    ExecutableDefinition::OperationDefinition(op) => &op.selection_set,
+                    ExecutableDefinition::FragmentDefinition(fragment) => &fragment.selection_set,
+                };
+                all_fragments
+                    .fragments_used_by(selection_set)
+                    .iter()
+                    .any(|fragment| changed_fragments.contains(fragment.name.name))
+            });
+        if defines_spread_fragment || uses_changed_fragment {
+            affected.insert(*file_idx);
+        }
+    }
+    affected
+}
+
+fn modified_times<'a>(paths: impl Iterator<Item = &'a PathBuf>) -> HashMap<PathBuf, SystemTime> {
+    paths
+        .filter_map(|path| {
+            let mtime = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+            Some((path.clone(), mtime))
+        })
+        .collect()
+}
@@ -4,7 +4,7 @@ use serde::Deserialize;
 
 use crate::parsing_utils::{default_true, deserialize_fromstr};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Config {
     /// Path(s) to schema definition files.
     pub schema: Vec<String>,
@@ -12,10 +12,18 @@ pub struct Config {
     pub operations: Vec<String>,
     // extensions
     pub generate: GenerateConfig,
+    pub print_schema: PrintConfig,
+    pub print_introspection: PrintConfig,
+    pub lint: LintConfig,
+    pub format: FormatConfig,
+    /// Config related to GraphQL documents embedded in TypeScript and JavaScript sources.
+    pub embedded: EmbeddedConfig,
+    /// Literals accepted by custom scalars, keyed by scalar name.
+    pub custom_scalars: HashMap<String, CustomScalarConfig>,
 }
 
 /// Config related to the 'generate' command.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct GenerateConfig {
     /// Mode of generation.
     #[serde(deserialize_with = "deserialize_fromstr", default)]
@@ -40,6 +48,19 @@ pub struct GenerateConfig {
     /// Whether to emit runtime for generated schema types.
     #[serde(rename = "emitSchemaRuntime", default)]
     pub emit_schema_runtime: bool,
+    /// Output file path for resolver types.
+    #[serde(rename = "resolversOutput")]
+    pub resolvers_output: Option<PathBuf>,
+    /// Mapping from GraphQL object types to TypeScript types of parent values passed to resolvers.
+    #[serde(rename = "resolverParentTypes", default)]
+    pub resolver_parent_types: HashMap<String, String>,
+    /// TypeScript type of context passed to resolvers.
+    #[serde(rename = "resolverContextType")]
+    pub resolver_context_type: Option<String>,
+    /// Output file path for the persisted documents manifest.
+    /// If specified, generated documents also have their hashes embedded.
+    #[serde(rename = "persistedDocumentsOutput")]
+    pub persisted_documents_output: Option<PathBuf>,
 }
 
 impl Default for GenerateConfig {
@@ -52,10 +73,102 @@ impl Default for GenerateConfig {
             default_export_for_operation: true,
             name: Default::default(),
             emit_schema_runtime: false,
+            resolvers_output: None,
+            resolver_parent_types: Default::default(),
+            resolver_context_type: None,
+            persisted_documents_output: None,
         }
     }
 }
 
+/// Config related to the 'print-schema' and 'print-introspection' commands.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct PrintConfig {
+    /// File path to which the output is written.
+    /// If not specified, the output is printed to stdout.
+    pub output: Option<PathBuf>,
+}
+
+/// Config related to the 'format' command.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FormatConfig {
+    /// Number of spaces for one level of indentation.
+    #[serde(rename = "indentWidth", default = "default_indent_width")]
+    pub indent_width: usize,
+    /// Width of lines that the formatter tries to fit the output in.
+    #[serde(rename = "lineWidth", default = "default_line_width")]
+    pub line_width: usize,
+    /// Whether top-level definitions are sorted by kind and name.
+    #[serde(rename = "sortDefinitions", default)]
+    pub sort_definitions: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        FormatConfig {
+            indent_width: default_indent_width(),
+            line_width: default_line_width(),
+            sort_definitions: false,
+        }
+    }
+}
+
+fn default_indent_width() -> usize {
+    2
+}
+
+fn default_line_width() -> usize {
+    80
+}
+
+/// Config related to GraphQL documents in tagged templates of TypeScript and JavaScript sources.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddedConfig {
+    /// Names of tags whose templates are GraphQL documents.
+    #[serde(rename = "tagNames", default = "default_tag_names")]
+    pub tag_names: Vec<String>,
+    /// Modules from which tags must be imported.
+    /// If empty, tags are recognized by name only.
+    #[serde(rename = "moduleSources", default)]
+    pub module_sources: Vec<String>,
+}
+
+impl Default for EmbeddedConfig {
+    fn default() -> Self {
+        EmbeddedConfig {
+            tag_names: default_tag_names(),
+            module_sources: vec![],
+        }
+    }
+}
+
+fn default_tag_names() -> Vec<String> {
+    vec!["gql".to_owned(), "graphql".to_owned()]
+}
+
+/// Literals accepted by a custom scalar.
+/// Values given to a scalar that is not configured are not checked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomScalarConfig {
+    /// Kinds of literals the scalar accepts.
+    pub literals: Vec<ScalarLiteralKind>,
+    /// Regular expression that string literals must match as a whole.
+    pub pattern: Option<String>,
+}
+
+/// Kind of a literal value in GraphQL documents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalarLiteralKind {
+    Int,
+    Float,
+    String,
+    Boolean,
+    Enum,
+    List,
+    Object,
+}
+
 /// Mode of code generation.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum GenerateMode {
@@ -66,6 +179,8 @@ pub enum GenerateMode {
     WithLoaderTS4_0,
     /// To be used standalone. Emits .graphql.ts that are supported by TS 4.0
     StandaloneTS4_0,
+    /// To be used with documents embedded in TypeScript sources. Emits .graphql-types.ts files that only contain types
+    TypesOnlyTS4_0,
 }
 
 pub struct FromStrError;
@@ -77,13 +192,14 @@ impl FromStr for GenerateMode {
             "with-loader-ts-5.0" => Ok(GenerateMode::WithLoaderTS5_0),
             "with-loader-ts-4.0" => Ok(GenerateMode::WithLoaderTS4_0),
             "standalone-ts-4.0" => Ok(GenerateMode::StandaloneTS4_0),
+            "types-only-ts-4.0" => Ok(GenerateMode::TypesOnlyTS4_0),
             _ => Err(FromStrError),
         }
     }
 }
 
 /// Config related to names of generated variables and types.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Clone, Deserialize)]
 pub struct GenerateNameConfig {
     /// Suffix for type of operation result.
     #[serde(rename = "operationResultTypeSuffix")]
@@ -104,3 +220,130 @@ pub struct GenerateNameConfig {
     #[serde(rename = "subscriptionVariableSuffix")]
     pub subscription_variable_suffix: Option<String>,
 }
+
+/// Config related to lint rules.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct LintConfig {
+    /// Settings of each rule, keyed by rule id.
+    #[serde(default)]
+    pub rules: LintRulesConfig,
+}
+
+/// Settings of lint rules. Rules that are not configured are off.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct LintRulesConfig {
+    /// Operations must be named.
+    #[serde(rename = "named-operations")]
+    pub named_operations: Option<LintRuleConfig>,
+    /// Operation names must end with their operation type, such as `Query`.
+    #[serde(rename = "operation-name-suffix")]
+    pub operation_name_suffix: Option<LintRuleConfig>,
+    /// Arguments in mutations must be passed as variables.
+    #[serde(rename = "no-mutation-literal-arguments")]
+    pub no_mutation_literal_arguments: Option<LintRuleConfig>,
+    /// Selection sets must not be nested deeper than the limit.
+    #[serde(rename = "max-selection-depth")]
+    pub max_selection_depth: Option<LintRuleConfig<MaxSelectionDepthOptions>>,
+    /// An id field must be selected whenever the type has one.
+    #[serde(rename = "require-id-field")]
+    pub require_id_field: Option<LintRuleConfig<RequireIdFieldOptions>>,
+    /// Types, fields and arguments in the schema must have a description.
+    #[serde(rename = "require-description")]
+    pub require_description: Option<LintRuleConfig>,
+    /// Enum values must be UPPER_CASE.
+    #[serde(rename = "enum-values-upper-case")]
+    pub enum_values_upper_case: Option<LintRuleConfig>,
+    /// Names of input object types must end with `Input`.
+    #[serde(rename = "input-type-suffix")]
+    pub input_type_suffix: Option<LintRuleConfig>,
+    /// Nullable list types must not have nullable items.
+    #[serde(rename = "no-nullable-list-of-nullable-items")]
+    pub no_nullable_list_of_nullable_items: Option<LintRuleConfig>,
+    /// `@deprecated` must be given a reason.
+    #[serde(rename = "deprecation-reason")]
+    pub deprecation_reason: Option<LintRuleConfig>,
+    /// Connection types must follow the Relay Cursor Connections Specification.
+    #[serde(rename = "relay-connection")]
+    pub relay_connection: Option<LintRuleConfig>,
+}
+
+/// Setting of one lint rule.
+/// Written either as a severity (`error`) or as an object with `severity` and `options`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    from = "LintRuleConfigParser<Options>",
+    bound(deserialize = "Options: Deserialize<'de> + Default")
+)]
+pub struct LintRuleConfig<Options = NoOptions> {
+    pub severity: LintSeverity,
+    pub options: Options,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LintRuleConfigParser<Options> {
+    Severity(LintSeverity),
+    Full {
+        severity: LintSeverity,
+        #[serde(default)]
+        options: Options,
+    },
+}
+
+impl<Options: Default> From<LintRuleConfigParser<Options>> for LintRuleConfig<Options> {
+    fn from(value: LintRuleConfigParser<Options>) -> Self {
+        match value {
+            LintRuleConfigParser::Severity(severity) => LintRuleConfig {
+                severity,
+                options: Options::default(),
+            },
+            LintRuleConfigParser::Full { severity, options } => {
+                LintRuleConfig { severity, options }
+            }
+        }
+    }
+}
+
+/// Severity of a lint rule.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Off,
+    #[serde(alias = "warn")]
+    Warning,
+    Error,
+}
+
+/// Options for rules that take no options.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct NoOptions {}
+
+/// Options for the 'max-selection-depth' rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaxSelectionDepthOptions {
+    /// Maximum depth of selection sets. Root fields are at depth 1.
+    #[serde(rename = "maxDepth")]
+    pub max_depth: usize,
+}
+
+impl Default for MaxSelectionDepthOptions {
+    fn default() -> Self {
+        MaxSelectionDepthOptions { max_depth: 10 }
+    }
+}
+
+/// Options for the 'require-id-field' rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RequireIdFieldOptions {
+    /// Name of the id field.
+    #[serde(rename = "fieldName")]
+    pub field_name: String,
+}
+
+impl Default for RequireIdFieldOptions {
+    fn default() -> Self {
+        RequireIdFieldOptions {
+            field_name: String::from("id"),
+        }
+    }
+}
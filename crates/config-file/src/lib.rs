@@ -11,7 +11,11 @@ mod parsing_utils;
 #[cfg(test)]
 mod tests;
 
-pub use config::{Config, GenerateConfig, GenerateMode};
+pub use config::{
+    Config, CustomScalarConfig, EmbeddedConfig, FormatConfig, GenerateConfig, GenerateMode,
+    LintConfig, LintRuleConfig, LintRulesConfig, LintSeverity, MaxSelectionDepthOptions, NoOptions,
+    PrintConfig, RequireIdFieldOptions, ScalarLiteralKind,
+};
 #[cfg(feature = "execute_config")]
 pub use execute::execute_config;
 #[cfg(feature = "fs")]
@@ -29,8 +29,7 @@ stdout.write(JSON.stringify(config));
 
         let result = command.wait_with_output()?;
         if !result.status.success() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
+            return Err(io::Error::other(
                 "Node.js process exited with non-zero status",
             ));
         }
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
-use crate::{parsing_utils::StringOrVecString, Config, GenerateConfig};
+use crate::{
+    parsing_utils::StringOrVecString, Config, CustomScalarConfig, EmbeddedConfig, FormatConfig,
+    GenerateConfig, LintConfig, PrintConfig,
+};
 
 #[derive(Deserialize)]
 struct ConfigParser {
@@ -11,25 +16,50 @@ struct ConfigParser {
 
 #[derive(Deserialize)]
 struct Extensions {
-    nitrogql: Option<GenerateConfigParser>,
+    nitrogql: Option<NitrogqlConfigParser>,
 }
 
 #[derive(Deserialize)]
-struct GenerateConfigParser {
+struct NitrogqlConfigParser {
     generate: Option<GenerateConfig>,
+    #[serde(rename = "printSchema")]
+    print_schema: Option<PrintConfig>,
+    #[serde(rename = "printIntrospection")]
+    print_introspection: Option<PrintConfig>,
+    lint: Option<LintConfig>,
+    format: Option<FormatConfig>,
+    embedded: Option<EmbeddedConfig>,
+    #[serde(rename = "customScalars")]
+    custom_scalars: Option<HashMap<String, CustomScalarConfig>>,
 }
 
 /// Parse config file from given string.
 /// Returns None if there is a validation error.
 pub fn parse_config(source: &str) -> Option<Config> {
     let parsed: ConfigParser = serde_yaml::from_str(source).unwrap();
+    let nitrogql = parsed.extensions.and_then(|e| e.nitrogql);
+    let (generate, print_schema, print_introspection, lint, format, embedded, custom_scalars) =
+        match nitrogql {
+            Some(nitrogql) => (
+                nitrogql.generate,
+                nitrogql.print_schema,
+                nitrogql.print_introspection,
+                nitrogql.lint,
+                nitrogql.format,
+                nitrogql.embedded,
+                nitrogql.custom_scalars,
+            ),
+            None => (None, None, None, None, None, None, None),
+        };
     Some(Config {
         schema: parsed.schema.map(|s| s.into_vec()).unwrap_or_default(),
         operations: parsed.documents.map(|s| s.into_vec()).unwrap_or_default(),
-        generate: parsed
-            .extensions
-            .and_then(|e| e.nitrogql)
-            .and_then(|g| g.generate)
-            .unwrap_or_default(),
+        generate: generate.unwrap_or_default(),
+        print_schema: print_schema.unwrap_or_default(),
+        print_introspection: print_introspection.unwrap_or_default(),
+        lint: lint.unwrap_or_default(),
+        format: format.unwrap_or_default(),
+        embedded: embedded.unwrap_or_default(),
+        custom_scalars: custom_scalars.unwrap_or_default(),
     })
 }
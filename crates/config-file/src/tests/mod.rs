@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::{parse_config, GenerateMode};
+use crate::{parse_config, GenerateMode, LintSeverity, ScalarLiteralKind};
 
 #[test]
 fn parse_schema_and_documents() {
@@ -41,6 +41,7 @@ extensions:
             mode: with-loader-ts-5.0
             schemaOutput: src/generated/schema.d.ts
             schemaModuleSpecifier: "@generated/schema"
+            persistedDocumentsOutput: persisted-documents.json
 "#;
 
     let config = parse_config(config).unwrap();
@@ -53,6 +54,10 @@ extensions:
         config.generate.schema_module_specifier,
         Some("@generated/schema".to_owned())
     );
+    assert_eq!(
+        config.generate.persisted_documents_output,
+        Some(PathBuf::from("persisted-documents.json"))
+    );
 }
 
 #[test]
@@ -144,3 +149,172 @@ extensions:
         Some("@generated/schema".to_owned())
     );
 }
+
+#[test]
+fn parse_print_config() {
+    let config = r#"
+schema: schema.graphql
+extensions:
+    nitrogql:
+        printSchema:
+            output: generated/schema.graphql
+        printIntrospection:
+            output: generated/introspection.json
+"#;
+
+    let config = parse_config(config).unwrap();
+    assert_eq!(
+        config.print_schema.output,
+        Some(PathBuf::from("generated/schema.graphql"))
+    );
+    assert_eq!(
+        config.print_introspection.output,
+        Some(PathBuf::from("generated/introspection.json"))
+    );
+    assert_eq!(config.generate.schema_output, None);
+}
+
+#[test]
+fn parse_format_config() {
+    let config = r#"
+schema: schema.graphql
+extensions:
+    nitrogql:
+        format:
+            indentWidth: 4
+            sortDefinitions: true
+"#;
+
+    let config = parse_config(config).unwrap();
+    assert_eq!(config.format.indent_width, 4);
+    assert_eq!(config.format.line_width, 80);
+    assert!(config.format.sort_definitions);
+
+    let config = parse_config("schema: schema.graphql").unwrap();
+    assert_eq!(config.format.indent_width, 2);
+    assert!(!config.format.sort_definitions);
+}
+
+#[test]
+fn parse_custom_scalars_config() {
+    let config = r#"
+schema: schema.graphql
+extensions:
+    nitrogql:
+        customScalars:
+            DateTime:
+                literals: [string]
+                pattern: "\\d{4}-\\d{2}-\\d{2}T.*"
+            JSON:
+                literals: [string, int, float, boolean, list, object]
+"#;
+
+    let config = parse_config(config).unwrap();
+    let date_time = &config.custom_scalars["DateTime"];
+    assert_eq!(date_time.literals, vec![ScalarLiteralKind::String]);
+    assert_eq!(date_time.pattern.as_deref(), Some(r"\d{4}-\d{2}-\d{2}T.*"));
+    let json = &config.custom_scalars["JSON"];
+    assert_eq!(json.literals.len(), 6);
+    assert_eq!(json.pattern, None);
+
+    let config = parse_config("schema: schema.graphql").unwrap();
+    assert!(config.custom_scalars.is_empty());
+}
+
+#[test]
+fn parse_embedded_config() {
+    let config = r#"
+schema: schema.graphql
+documents: src/**/*.tsx
+extensions:
+    nitrogql:
+        generate:
+            mode: types-only-ts-4.0
+        embedded:
+            moduleSources: ["@apollo/client"]
+"#;
+
+    let config = parse_config(config).unwrap();
+    assert_eq!(config.generate.mode, GenerateMode::TypesOnlyTS4_0);
+    assert_eq!(config.embedded.tag_names, vec!["gql", "graphql"]);
+    assert_eq!(config.embedded.module_sources, vec!["@apollo/client"]);
+
+    let config = parse_config("schema: schema.graphql").unwrap();
+    assert!(config.embedded.module_sources.is_empty());
+}
+
+#[test]
+fn parse_lint_config() {
+    let config = r#"
+schema: schema.graphql
+extensions:
+    nitrogql:
+        lint:
+            rules:
+                named-operations: error
+                operation-name-suffix: warn
+                max-selection-depth:
+                    severity: warning
+                    options:
+                        maxDepth: 3
+                require-id-field:
+                    severity: error
+"#;
+
+    let config = parse_config(config).unwrap();
+    let rules = config.lint.rules;
+    assert_eq!(
+        rules.named_operations.map(|rule| rule.severity),
+        Some(LintSeverity::Error)
+    );
+    assert_eq!(
+        rules.operation_name_suffix.map(|rule| rule.severity),
+        Some(LintSeverity::Warning)
+    );
+    assert!(rules.no_mutation_literal_arguments.is_none());
+    let max_selection_depth = rules.max_selection_depth.unwrap();
+    assert_eq!(max_selection_depth.severity, LintSeverity::Warning);
+    assert_eq!(max_selection_depth.options.max_depth, 3);
+    let require_id_field = rules.require_id_field.unwrap();
+    assert_eq!(require_id_field.severity, LintSeverity::Error);
+    assert_eq!(require_id_field.options.field_name, "id");
+}
+
+#[test]
+fn parse_schema_lint_config() {
+    let config = r#"
+schema: schema.graphql
+extensions:
+    nitrogql:
+        lint:
+            rules:
+                require-description: warn
+                enum-values-upper-case: error
+                no-nullable-list-of-nullable-items: off
+                relay-connection:
+                    severity: error
+"#;
+
+    let config = parse_config(config).unwrap();
+    let rules = config.lint.rules;
+    assert_eq!(
+        rules.require_description.map(|rule| rule.severity),
+        Some(LintSeverity::Warning)
+    );
+    assert_eq!(
+        rules.enum_values_upper_case.map(|rule| rule.severity),
+        Some(LintSeverity::Error)
+    );
+    assert!(rules.input_type_suffix.is_none());
+    assert_eq!(
+        rules
+            .no_nullable_list_of_nullable_items
+            .map(|rule| rule.severity),
+        Some(LintSeverity::Off)
+    );
+    assert!(rules.deprecation_reason.is_none());
+    assert_eq!(
+        rules.relay_connection.map(|rule| rule.severity),
+        Some(LintSeverity::Error)
+    );
+}
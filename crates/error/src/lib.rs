@@ -36,6 +36,21 @@ impl PositionedError {
     pub fn into_inner(self) -> anyhow::Error {
         self.inner
     }
+
+    /// Returns a reference to the inner error.
+    pub fn inner(&self) -> &anyhow::Error {
+        &self.inner
+    }
+
+    /// Returns the position of the error, if any.
+    pub fn position(&self) -> Option<&Pos> {
+        self.position.as_ref()
+    }
+
+    /// Returns additional information attached to the error.
+    pub fn additional_info(&self) -> &[(Pos, String)] {
+        &self.additional_info
+    }
 }
 
 impl<E> From<E> for PositionedError
@@ -126,6 +141,19 @@ fn message_for_line(
     .bold();
 
     let trimmed_column = pos.column.saturating_sub(minimum_indent);
+    // Underline the whole node when it has a span. Multi-line nodes are underlined until the end of the first line.
+    let underline_len = relevant_lines
+        .iter()
+        .find(|(line_no, _)| *line_no == pos.line)
+        .map_or(1, |(_, line)| {
+            let end_column = if pos.end_line == pos.line {
+                pos.end_column
+            } else {
+                line.trim_end().chars().count()
+            };
+            end_column.saturating_sub(pos.column).max(1)
+        });
+    let underline = "^".repeat(underline_len);
 
     let mut result = if is_additional {
         format!("{INDENT}{src_string}")
@@ -146,11 +174,13 @@ fn message_for_line(
         } else if is_additional {
             let error_str = format!("{error}").bright_green().underline();
             result.push_str(&format!(
-                "{INDENT}{trimmed_line}\n{INDENT}{spaces}^\n{INDENT}{spaces}{error_str}\n"
+                "{INDENT}{trimmed_line}\n{INDENT}{spaces}{underline}\n{INDENT}{spaces}{error_str}\n"
             ));
         } else {
             let error_str = format!("{error}").bright_yellow().underline();
-            result.push_str(&format!("{trimmed_line}\n{spaces}^\n{spaces}{error_str}\n"));
+            result.push_str(&format!(
+                "{trimmed_line}\n{spaces}{underline}\n{spaces}{error_str}\n"
+            ));
         }
     }
     result
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, HashMap};
+
+use log::debug;
+use nitrogql_ast::{
+    operation::ExecutableDefinition,
+    selection_set::{Selection, SelectionSet},
+    OperationDocument,
+};
+use nitrogql_parser::parse_operation_document;
+use nitrogql_printer::GraphQLPrinter;
+use sourcemap_writer::JustWriter;
+
+/// Fragment defined in a registered operation document.
+/// Only the printed definition is kept so that registered documents are parsed once
+/// and do not have to be kept alive between conversions.
+struct RegisteredFragment {
+    name: String,
+    /// Printed fragment definition.
+    source: String,
+    /// Names of fragments spread directly from this fragment.
+    spreads: Vec<String>,
+}
+
+/// Fragments defined in operation documents of the project, keyed by paths of the documents.
+pub struct FragmentFiles {
+    files: BTreeMap<String, Vec<RegisteredFragment>>,
+}
+
+impl FragmentFiles {
+    pub const fn new() -> Self {
+        Self {
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Registers fragments defined in given document.
+    /// Registering the same path again replaces the previous fragments.
+    /// Files that fail to parse register no fragments; they are reported when converted themselves.
+    pub fn register(&mut self, path: &str, source: &str) {
+        let document = match parse_operation_document(source) {
+            Ok(document) => document,
+            Err(err) => {
+                debug!("Failed to parse {path}: {}", err.into_message());
+                self.files.remove(path);
+                return;
+            }
+        };
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                ExecutableDefinition::OperationDefinition(_) => None,
+                ExecutableDefinition::FragmentDefinition(fragment) => Some(fragment),
+            })
+            .map(|fragment| {
+                let mut source = String::new();
+                fragment.print_graphql(&mut JustWriter::new(&mut source));
+                let mut spreads = vec![];
+                collect_spreads(&fragment.selection_set, &mut spreads);
+                RegisteredFragment {
+                    name: fragment.name.name.to_owned(),
+                    source,
+                    spreads: spreads.into_iter().map(str::to_owned).collect(),
+                }
+            })
+            .collect();
+        self.files.insert(path.to_owned(), fragments);
+    }
+
+    /// Removes fragments registered from given path.
+    pub fn unregister(&mut self, path: &str) {
+        self.files.remove(path);
+    }
+
+    /// Returns the source of registered fragments that are (directly or indirectly)
+    /// spread from given document and are not defined in it.
+    /// When multiple files define the same fragment, the one registered with the first path is used.
+    pub fn sources_used_by(&self, document: &OperationDocument) -> String {
+        let mut index = HashMap::new();
+        for fragment in self.files.values().flatten() {
+            index.entry(fragment.name.as_str()).or_insert(fragment);
+        }
+
+        let mut visited = vec![];
+        let mut pending = vec![];
+        for def in document.definitions.iter() {
+            match def {
+                ExecutableDefinition::OperationDefinition(op) => {
+                    collect_spreads(&op.selection_set, &mut pending);
+                }
+                ExecutableDefinition::FragmentDefinition(fragment) => {
+                    visited.push(fragment.name.name);
+                    collect_spreads(&fragment.selection_set, &mut pending);
+                }
+            }
+        }
+        let mut sources = vec![];
+        while let Some(name) = pending.pop() {
+            if visited.contains(&name) {
+                continue;
+            }
+            visited.push(name);
+            if let Some(fragment) = index.get(name) {
+                sources.push(fragment.source.as_str());
+                pending.extend(fragment.spreads.iter().map(String::as_str));
+            }
+        }
+        sources.join("\n\n")
+    }
+}
+
+/// Collects names of fragments spread directly from given selection set.
+fn collect_spreads<'a>(selection_set: &SelectionSet<'a>, result: &mut Vec<&'a str>) {
+    for selection in selection_set.selections.iter() {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(ref selection_set) = field.selection_set {
+                    collect_spreads(selection_set, result);
+                }
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                result.push(fragment_spread.fragment_name.name);
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                collect_spreads(&inline_fragment.selection_set, result);
+            }
+        }
+    }
+}
@@ -1,11 +1,19 @@
 use nitrogql_ast::OperationDocument;
 use nitrogql_printer::{print_js_for_operation_document, OperationJSPrinterOptions};
+use nitrogql_semantics::FragmentRegistry;
 use sourcemap_writer::SourceWriter;
 
-pub fn print_js(document: &OperationDocument) -> String {
+pub fn print_js<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    emit_document_hash: bool,
+) -> String {
     let mut writer = SourceWriter::new();
-    let options = OperationJSPrinterOptions::default();
-    print_js_for_operation_document(options, document, &mut writer);
+    let options = OperationJSPrinterOptions {
+        emit_document_hash,
+        ..Default::default()
+    };
+    print_js_for_operation_document(options, document, fragments, &mut writer);
     let buffers = writer.into_buffers();
     buffers.buffer
 }
@@ -1,18 +1,27 @@
 #![cfg_attr(target_family = "wasm", no_main)]
+mod fragment_files;
 mod js_printer;
 
 use std::{cell::RefCell, mem::ManuallyDrop, slice};
 
+use fragment_files::FragmentFiles;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use js_printer::print_js;
 use log::{debug, error};
 use nitrogql_config_file::Config;
 use nitrogql_parser::{parse_operation_document, ParseError};
+use nitrogql_semantics::FragmentRegistry;
 
 thread_local! {
     /// Loaded config.
-    static CONFIG: RefCell<Option<Config>> = RefCell::new(None);
+    static CONFIG: RefCell<Option<Config>> = const { RefCell::new(None) };
     /// Result of last operation.
-    static RESULT: RefCell<Option<String>> = RefCell::new(None);
+    static RESULT: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Globs of operation documents in loaded config.
+    static OPERATION_GLOBS: RefCell<Option<GlobSet>> = const { RefCell::new(None) };
+    /// Fragments defined in operation documents of the project.
+    /// They can be used from converted documents.
+    static FRAGMENT_FILES: RefCell<FragmentFiles> = const { RefCell::new(FragmentFiles::new()) };
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -55,6 +64,65 @@ pub extern "C" fn load_config(config_file_ptr: *const u8, config_file_len: usize
     load_config_impl(config_file)
 }
 
+/// Returns whether given path matches globs of operation documents in loaded config.
+/// Path should be relative to the config file and use `/` as the separator.
+#[no_mangle]
+pub extern "C" fn is_operation_file(path_ptr: *const u8, path_len: usize) -> bool {
+    let path = read_str_ptr(path_ptr, path_len);
+    OPERATION_GLOBS.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(|globs| globs.is_match(path))
+    })
+}
+
+/// Registers an operation document of the project so that its fragments can be used from other documents.
+/// Registering the same path again replaces the previous source.
+#[no_mangle]
+pub extern "C" fn register_fragment_file(
+    path_ptr: *const u8,
+    path_len: usize,
+    source_ptr: *const u8,
+    source_len: usize,
+) {
+    let path = read_str_ptr(path_ptr, path_len);
+    let source = read_str_ptr(source_ptr, source_len);
+    debug!("register_fragment_file {path}");
+    FRAGMENT_FILES.with(|cell| cell.borrow_mut().register(path, source));
+}
+
+/// Removes an operation document registered by `register_fragment_file`.
+#[no_mangle]
+pub extern "C" fn unregister_fragment_file(path_ptr: *const u8, path_len: usize) {
+    let path = read_str_ptr(path_ptr, path_len);
+    debug!("unregister_fragment_file {path}");
+    FRAGMENT_FILES.with(|cell| cell.borrow_mut().unregister(path));
+}
+
+/// Lists paths imported by `#import` comments in given GraphQL string.
+/// Paths are written to the result separated by newlines.
+/// Returns true if successful.
+#[no_mangle]
+pub extern "C" fn list_imports(source_ptr: *const u8, source_len: usize) -> bool {
+    let source = read_str_ptr(source_ptr, source_len);
+    match parse_operation_document(source) {
+        Ok(document) => {
+            let imports = document
+                .imports
+                .iter()
+                .map(|import| import.path)
+                .collect::<Vec<_>>();
+            RESULT.with(|cell| cell.replace(Some(imports.join("\n"))));
+            true
+        }
+        Err(err) => {
+            debug!("list_imports: {}", err.into_message());
+            RESULT.with(|cell| cell.replace(None));
+            false
+        }
+    }
+}
+
 /// Converts given GraphQL string to JS.
 /// Returns true if successful.
 #[no_mangle]
@@ -96,8 +164,29 @@ pub extern "C" fn get_result_size() -> usize {
 
 fn convert_to_js_impl(source: &str) -> Result<String, ParseError> {
     let document = parse_operation_document(source)?;
-    let js = print_js(&document);
-    Ok(js)
+    // Only fragments used by this document are parsed here.
+    let fragment_source = FRAGMENT_FILES.with(|cell| cell.borrow().sources_used_by(&document));
+    let fragment_document = match parse_operation_document(&fragment_source) {
+        Ok(document) => Some(document),
+        Err(err) => {
+            debug!(
+                "Failed to parse registered fragments: {}",
+                err.into_message()
+            );
+            None
+        }
+    };
+    let fragments = fragment_document
+        .as_ref()
+        .map(FragmentRegistry::from_document)
+        .unwrap_or_default();
+    // Hashes are embedded when the project generates a persisted documents manifest.
+    let emit_document_hash = CONFIG.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(|config| config.generate.persisted_documents_output.is_some())
+    });
+    Ok(print_js(&document, &fragments, emit_document_hash))
 }
 
 fn load_config_impl(config_file: &str) -> bool {
@@ -105,6 +194,8 @@ fn load_config_impl(config_file: &str) -> bool {
     match config {
         None => false,
         Some(config) => {
+            let globs = build_glob_set(&config.operations);
+            OPERATION_GLOBS.with(|cell| cell.replace(globs));
             CONFIG.with(|cell| cell.replace(Some(config)));
             debug!("Loaded config from given source");
             true
@@ -112,6 +203,24 @@ fn load_config_impl(config_file: &str) -> bool {
     }
 }
 
+/// Builds a glob set from globs in config. Invalid globs are ignored.
+fn build_glob_set(globs: &[String]) -> Option<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        // Paths given to the glob set are relative to the config file.
+        let glob = glob.strip_prefix("./").unwrap_or(glob);
+        match Glob::new(glob) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(err) => {
+                error!("Invalid glob {glob}: {err}");
+            }
+        }
+    }
+    builder.build().ok()
+}
+
 fn read_str_ptr(ptr: *const u8, len: usize) -> &'static str {
     let slice = unsafe { slice::from_raw_parts(ptr, len) };
     std::str::from_utf8(slice).unwrap()
@@ -49,6 +49,8 @@ struct IntrospectionType<'src> {
     input_fields: Option<Vec<IntrospectionInputValue<'src>>>,
     #[serde(rename = "ofType")]
     of_type: Option<Box<IntrospectionType<'src>>>,
+    #[serde(rename = "specifiedByURL")]
+    specified_by_url: Option<Cow<'src, str>>,
 }
 
 #[derive(Deserialize)]
@@ -195,6 +197,7 @@ fn as_type_definition<'src, D: Default>(
         Ok(TypeDefinition::Scalar(ScalarDefinition {
             name,
             description,
+            specified_by_url: value.specified_by_url.clone(),
         }))
     } else if kind == "OBJECT" {
         let fields = value
@@ -240,7 +243,9 @@ fn as_type_definition<'src, D: Default>(
         }))
     } else if kind == "UNION" {
         let Some(ref possible_types) = value.possible_types else {
-            return Err(IntrospectionError::Introspection("__Type of kind UNION must have a list 'possibleTypes' field".into()));
+            return Err(IntrospectionError::Introspection(
+                "__Type of kind UNION must have a list 'possibleTypes' field".into(),
+            ));
         };
         let possible_types = possible_types
             .iter()
@@ -255,7 +260,9 @@ fn as_type_definition<'src, D: Default>(
         }))
     } else if kind == "ENUM" {
         let Some(ref enum_values) = value.enum_values else {
-            return Err(IntrospectionError::Introspection("__Type of kind ENUM must have a list 'enumValues' field".into()));
+            return Err(IntrospectionError::Introspection(
+                "__Type of kind ENUM must have a list 'enumValues' field".into(),
+            ));
         };
         let members = enum_values
             .iter()
@@ -282,7 +289,9 @@ fn as_type_definition<'src, D: Default>(
         }))
     } else if kind == "INPUT_OBJECT" {
         let Some(ref fields) = value.input_fields else {
-            return Err(IntrospectionError::Introspection("__Type of kind INPUT_OBJECT must have a list 'inputFields' field".into()));
+            return Err(IntrospectionError::Introspection(
+                "__Type of kind INPUT_OBJECT must have a list 'inputFields' field".into(),
+            ));
         };
         let fields = fields
             .iter()
@@ -1,20 +1,30 @@
-//! Module for reading introspection json (result of the standard introspection query) into the schema object.
+//! Module for converting between introspection json (result of the standard introspection query) and the schema object.
 
 use std::borrow::Cow;
 
 use error::IntrospectionError;
-use graphql_type_system::Schema;
+use graphql_type_system::{Schema, Text};
 
 mod error;
 mod introspection;
 #[cfg(test)]
 mod tests;
+mod to_introspection;
 
 use introspection::IntrospectionResult;
+use to_introspection::to_introspection;
 
 pub fn schema_from_introspection_json<D: Default>(
     source: &str,
-) -> Result<Schema<Cow<str>, D>, IntrospectionError> {
+) -> Result<Schema<Cow<'_, str>, D>, IntrospectionError> {
     let json: IntrospectionResult = serde_json::from_str(source)?;
     introspection::introspection(&json)
 }
+
+/// Generates introspection json from schema.
+pub fn schema_to_introspection_json<'src, S: Text<'src>, D>(
+    schema: &Schema<S, D>,
+) -> Result<String, IntrospectionError> {
+    let output = to_introspection(schema)?;
+    Ok(serde_json::to_string_pretty(&output)?)
+}
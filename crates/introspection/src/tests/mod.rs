@@ -1,8 +1,11 @@
+use graphql_builtins::generate_builtins;
 use insta::assert_display_snapshot;
+use nitrogql_parser::parse_type_system_document;
 use nitrogql_printer::GraphQLPrinter;
+use nitrogql_semantics::{ast_to_type_system, resolve_extensions};
 use sourcemap_writer::JustWriter;
 
-use crate::schema_from_introspection_json;
+use crate::{schema_from_introspection_json, schema_to_introspection_json};
 
 #[test]
 fn read_introspection() {
@@ -1337,3 +1340,45 @@ fn read_introspection() {
     schema.print_graphql(&mut writer);
     assert_display_snapshot!(buffer);
 }
+
+const SDL: &str = r#"
+"Root query."
+type Query {
+  "Look up a node."
+  node(id: ID!): Node
+  search(term: String = "", first: Int = 10, filter: Filter = {kinds: [USER]}): [SearchResult!]!
+  legacy: String @deprecated(reason: "Use search.")
+}
+interface Node { id: ID! }
+type User implements Node { id: ID! name: String }
+type Post implements Node { id: ID! author: User }
+union SearchResult = User | Post
+enum Kind {
+  USER
+  POST @deprecated
+}
+input Filter { kinds: [Kind!] }
+scalar Date @specifiedBy(url: "https://example.com/date")
+"Marks cached fields."
+directive @cache(ttl: Int = 60) repeatable on FIELD_DEFINITION | OBJECT
+"#;
+
+fn introspection_json_of(sdl: &str) -> String {
+    let mut document = parse_type_system_document(sdl).unwrap();
+    document.extend(generate_builtins());
+    let document = resolve_extensions(document).unwrap();
+    let schema = ast_to_type_system(&document);
+    schema_to_introspection_json(&schema).unwrap()
+}
+
+#[test]
+fn write_introspection() {
+    assert_display_snapshot!(introspection_json_of(SDL));
+}
+
+#[test]
+fn write_introspection_round_trip() {
+    let json = introspection_json_of(SDL);
+    let schema = schema_from_introspection_json::<()>(&json).unwrap();
+    assert_eq!(schema_to_introspection_json(&schema).unwrap(), json);
+}
@@ -0,0 +1,329 @@
+use std::ops::Deref;
+
+use graphql_type_system::{
+    DirectiveDefinition, EnumMember, Field, InputValue, Node, Schema, Text, Type, TypeDefinition,
+};
+use serde::Serialize;
+
+use crate::error::IntrospectionError;
+
+/// Serializable form of the standard introspection query result.
+#[derive(Serialize)]
+pub struct IntrospectionOutput<'a> {
+    #[serde(rename = "__schema")]
+    schema: SchemaOutput<'a>,
+}
+
+#[derive(Serialize)]
+struct SchemaOutput<'a> {
+    description: Option<&'a str>,
+    #[serde(rename = "queryType")]
+    query_type: Option<NameOutput<'a>>,
+    #[serde(rename = "mutationType")]
+    mutation_type: Option<NameOutput<'a>>,
+    #[serde(rename = "subscriptionType")]
+    subscription_type: Option<NameOutput<'a>>,
+    types: Vec<TypeOutput<'a>>,
+    directives: Vec<DirectiveOutput<'a>>,
+}
+
+#[derive(Serialize)]
+struct NameOutput<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct TypeOutput<'a> {
+    kind: &'static str,
+    name: &'a str,
+    description: Option<&'a str>,
+    #[serde(rename = "specifiedByURL")]
+    specified_by_url: Option<&'a str>,
+    fields: Option<Vec<FieldOutput<'a>>>,
+    #[serde(rename = "inputFields")]
+    input_fields: Option<Vec<InputValueOutput<'a>>>,
+    interfaces: Option<Vec<TypeRefOutput<'a>>>,
+    #[serde(rename = "enumValues")]
+    enum_values: Option<Vec<EnumValueOutput<'a>>>,
+    #[serde(rename = "possibleTypes")]
+    possible_types: Option<Vec<TypeRefOutput<'a>>>,
+}
+
+/// Reference to a type, as in `__Type` with only `kind`, `name` and `ofType` queried.
+#[derive(Serialize)]
+struct TypeRefOutput<'a> {
+    kind: &'static str,
+    name: Option<&'a str>,
+    #[serde(rename = "ofType")]
+    of_type: Option<Box<TypeRefOutput<'a>>>,
+}
+
+#[derive(Serialize)]
+struct FieldOutput<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    args: Vec<InputValueOutput<'a>>,
+    #[serde(rename = "type")]
+    ty: TypeRefOutput<'a>,
+    #[serde(rename = "isDeprecated")]
+    is_deprecated: bool,
+    #[serde(rename = "deprecationReason")]
+    deprecation_reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct InputValueOutput<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    #[serde(rename = "type")]
+    ty: TypeRefOutput<'a>,
+    #[serde(rename = "defaultValue")]
+    default_value: Option<&'a str>,
+    #[serde(rename = "isDeprecated")]
+    is_deprecated: bool,
+    #[serde(rename = "deprecationReason")]
+    deprecation_reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct EnumValueOutput<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    #[serde(rename = "isDeprecated")]
+    is_deprecated: bool,
+    #[serde(rename = "deprecationReason")]
+    deprecation_reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct DirectiveOutput<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    #[serde(rename = "isRepeatable")]
+    is_repeatable: bool,
+    locations: Vec<&'a str>,
+    args: Vec<InputValueOutput<'a>>,
+}
+
+/// Generates introspection result from schema.
+/// Types, directives and possible types are sorted by name so that output is stable.
+pub fn to_introspection<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+) -> Result<IntrospectionOutput<'a>, IntrospectionError> {
+    let root_types = schema.root_types();
+    // Without a schema definition, root types are found by their default names.
+    let has_schema_definition = root_types.query_type.is_some()
+        || root_types.mutation_type.is_some()
+        || root_types.subscription_type.is_some();
+    let root_type = |root_type: &'a Option<Node<S, D>>, default_name: &'static str| {
+        if has_schema_definition {
+            root_type.as_ref().map(|name| NameOutput { name })
+        } else {
+            schema
+                .get_type(default_name)
+                .map(|_| NameOutput { name: default_name })
+        }
+    };
+
+    let mut types = schema
+        .iter_types()
+        .map(|(_, def)| type_output(schema, def))
+        .collect::<Result<Vec<_>, _>>()?;
+    types.sort_by_key(|ty| ty.name);
+
+    let mut directives = schema
+        .iter_directives()
+        .map(|(_, def)| directive_output(schema, def))
+        .collect::<Result<Vec<_>, _>>()?;
+    directives.sort_by_key(|directive| directive.name);
+
+    Ok(IntrospectionOutput {
+        schema: SchemaOutput {
+            description: schema.description().as_ref().map(|desc| -> &str { desc }),
+            query_type: root_type(&root_types.query_type, "Query"),
+            mutation_type: root_type(&root_types.mutation_type, "Mutation"),
+            subscription_type: root_type(&root_types.subscription_type, "Subscription"),
+            types,
+            directives,
+        },
+    })
+}
+
+fn type_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    def: &'a TypeDefinition<S, D>,
+) -> Result<TypeOutput<'a>, IntrospectionError> {
+    let mut result = TypeOutput {
+        kind: type_kind(def),
+        name: def.name(),
+        description: def.description(),
+        specified_by_url: None,
+        fields: None,
+        input_fields: None,
+        interfaces: None,
+        enum_values: None,
+        possible_types: None,
+    };
+    match def {
+        TypeDefinition::Scalar(def) => {
+            result.specified_by_url = def.specified_by_url.as_deref();
+        }
+        TypeDefinition::Object(def) => {
+            result.fields = Some(fields_output(schema, &def.fields)?);
+            result.interfaces = Some(named_types_output(
+                schema,
+                def.interfaces.iter().map(|name| -> &str { name }),
+            )?);
+        }
+        TypeDefinition::Interface(def) => {
+            result.fields = Some(fields_output(schema, &def.fields)?);
+            result.interfaces = Some(named_types_output(
+                schema,
+                def.interfaces.iter().map(|name| -> &str { name }),
+            )?);
+            let mut implementers = schema
+                .iter_types()
+                .filter_map(|(_, ty)| ty.as_object())
+                .filter(|obj| obj.interfaces.iter().any(|i| **i == *def.name))
+                .map(|obj| -> &str { &obj.name })
+                .collect::<Vec<_>>();
+            implementers.sort();
+            result.possible_types = Some(named_types_output(schema, implementers.into_iter())?);
+        }
+        TypeDefinition::Union(def) => {
+            let mut members = def
+                .possible_types
+                .iter()
+                .map(|name| -> &str { name })
+                .collect::<Vec<_>>();
+            members.sort();
+            result.possible_types = Some(named_types_output(schema, members.into_iter())?);
+        }
+        TypeDefinition::Enum(def) => {
+            result.enum_values = Some(def.members.iter().map(enum_value_output).collect());
+        }
+        TypeDefinition::InputObject(def) => {
+            result.input_fields = Some(input_values_output(schema, &def.fields)?);
+        }
+    }
+    Ok(result)
+}
+
+fn type_kind<S, D>(def: &TypeDefinition<S, D>) -> &'static str {
+    match def {
+        TypeDefinition::Scalar(_) => "SCALAR",
+        TypeDefinition::Object(_) => "OBJECT",
+        TypeDefinition::Interface(_) => "INTERFACE",
+        TypeDefinition::Union(_) => "UNION",
+        TypeDefinition::Enum(_) => "ENUM",
+        TypeDefinition::InputObject(_) => "INPUT_OBJECT",
+    }
+}
+
+fn fields_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    fields: &'a [Field<S, D>],
+) -> Result<Vec<FieldOutput<'a>>, IntrospectionError> {
+    fields
+        .iter()
+        .map(|field| {
+            Ok(FieldOutput {
+                name: &field.name,
+                description: field.description.as_ref().map(|desc| -> &str { desc }),
+                args: input_values_output(schema, &field.arguments)?,
+                ty: type_ref_output(schema, &field.r#type)?,
+                is_deprecated: field.deprecation.is_some(),
+                deprecation_reason: field.deprecation.as_deref(),
+            })
+        })
+        .collect()
+}
+
+fn input_values_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    input_values: &'a [InputValue<S, D>],
+) -> Result<Vec<InputValueOutput<'a>>, IntrospectionError> {
+    input_values
+        .iter()
+        .map(|input_value| {
+            Ok(InputValueOutput {
+                name: &input_value.name,
+                description: input_value
+                    .description
+                    .as_ref()
+                    .map(|desc| -> &str { desc }),
+                ty: type_ref_output(schema, &input_value.r#type)?,
+                default_value: input_value
+                    .default_value
+                    .as_ref()
+                    .map(|value| -> &str { value }),
+                is_deprecated: input_value.deprecation.is_some(),
+                deprecation_reason: input_value.deprecation.as_deref(),
+            })
+        })
+        .collect()
+}
+
+fn enum_value_output<S: Deref<Target = str>, D>(member: &EnumMember<S, D>) -> EnumValueOutput<'_> {
+    EnumValueOutput {
+        name: &member.name,
+        description: member.description.as_ref().map(|desc| -> &str { desc }),
+        is_deprecated: member.deprecation.is_some(),
+        deprecation_reason: member.deprecation.as_deref(),
+    }
+}
+
+fn directive_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    def: &'a DirectiveDefinition<S, D>,
+) -> Result<DirectiveOutput<'a>, IntrospectionError> {
+    Ok(DirectiveOutput {
+        name: &def.name,
+        description: def.description.as_ref().map(|desc| -> &str { desc }),
+        is_repeatable: def.repeatable.is_some(),
+        locations: def.locations.iter().map(|loc| -> &str { loc }).collect(),
+        args: input_values_output(schema, &def.arguments)?,
+    })
+}
+
+fn type_ref_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    ty: &'a Type<S, D>,
+) -> Result<TypeRefOutput<'a>, IntrospectionError> {
+    match ty {
+        Type::Named(named) => named_type_output(schema, named),
+        Type::List(inner) => Ok(TypeRefOutput {
+            kind: "LIST",
+            name: None,
+            of_type: Some(Box::new(type_ref_output(schema, inner.as_inner())?)),
+        }),
+        Type::NonNull(inner) => Ok(TypeRefOutput {
+            kind: "NON_NULL",
+            name: None,
+            of_type: Some(Box::new(type_ref_output(schema, inner.as_inner())?)),
+        }),
+    }
+}
+
+fn named_types_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    names: impl Iterator<Item = &'a str>,
+) -> Result<Vec<TypeRefOutput<'a>>, IntrospectionError> {
+    names.map(|name| named_type_output(schema, name)).collect()
+}
+
+fn named_type_output<'a, 'src, S: Text<'src>, D>(
+    schema: &'a Schema<S, D>,
+    name: &'a str,
+) -> Result<TypeRefOutput<'a>, IntrospectionError> {
+    let Some(def) = schema.get_type(name) else {
+        return Err(IntrospectionError::Introspection(format!(
+            "Type '{name}' is not defined"
+        )));
+    };
+    Ok(TypeRefOutput {
+        kind: type_kind(def),
+        name: Some(name),
+        of_type: None,
+    })
+}
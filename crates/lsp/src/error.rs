@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LspError {
+    #[error("Introspection JSON can only be specified once")]
+    IntrospectionOnce,
+    #[error("Cannot mix GraphQL and Introspection JSON for schema")]
+    MixGraphQLAndIntrospection,
+    #[error("{0}")]
+    GlobError(String),
+    #[error("Invalid pattern for custom scalar '{name}': {message}")]
+    InvalidCustomScalarPattern { name: String, message: String },
+}
@@ -0,0 +1,220 @@
+use std::{borrow::Cow, collections::BTreeMap, path::PathBuf};
+
+use graphql_type_system::{Field, OriginalNodeRef, TypeDefinition};
+use nitrogql_ast::base::Pos;
+use nitrogql_semantics::direct_fields_of_output_type;
+use serde_json::{json, Value};
+
+use crate::{
+    position::lsp_range,
+    project::Project,
+    target::{find_target, Target},
+    uri::path_to_uri,
+};
+
+/// LSP CompletionItemKind for fields.
+const COMPLETION_ITEM_KIND_FIELD: u32 = 5;
+/// LSP DiagnosticSeverity for errors.
+const DIAGNOSTIC_SEVERITY_ERROR: u32 = 1;
+/// LSP DiagnosticSeverity for warnings.
+const DIAGNOSTIC_SEVERITY_WARNING: u32 = 2;
+
+/// Generates diagnostics for all files in project.
+/// Files without errors are included with an empty list.
+pub fn diagnostics(project: &Project) -> BTreeMap<PathBuf, Vec<Value>> {
+    let mut result = project
+        .file_store
+        .iter()
+        .map(|(_, (path, _, _))| (path.to_owned(), vec![]))
+        .collect::<BTreeMap<_, _>>();
+    let errors = project
+        .errors
+        .iter()
+        .map(|error| (error, DIAGNOSTIC_SEVERITY_ERROR))
+        .chain(
+            project
+                .warnings
+                .iter()
+                .map(|warning| (warning, DIAGNOSTIC_SEVERITY_WARNING)),
+        );
+    for (error, severity) in errors {
+        let Some(position) = error.position() else {
+            continue;
+        };
+        let Some((ref path, source, _)) = project.file_store.get_file(position.file) else {
+            continue;
+        };
+        if position.builtin {
+            continue;
+        }
+        let related_information = error
+            .additional_info()
+            .iter()
+            .filter_map(|(pos, message)| {
+                location(project, pos).map(|location| {
+                    json!({
+                        "location": location,
+                        "message": message,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        let diagnostic = json!({
+            "range": lsp_range(source, position),
+            "severity": severity,
+            "source": "nitrogql",
+            "message": error.inner().to_string(),
+            "relatedInformation": related_information,
+        });
+        result.entry(path.clone()).or_default().push(diagnostic);
+    }
+    result
+}
+
+/// Generates hover contents for given position of an operation document.
+pub fn hover(project: &Project, file_idx: usize, offset: usize) -> Option<Value> {
+    let schema = project.schema.as_ref()?;
+    let document = project.operation(file_idx)?;
+    let (_, source, _) = project.file_store.get_file(file_idx)?;
+    let fragments = project.fragments();
+    let (contents, pos) = match find_target(schema, &fragments, document, offset)? {
+        Target::Field {
+            parent_type,
+            name,
+            definition,
+        } => {
+            let definition = definition?;
+            (
+                field_documentation(parent_type.name(), &definition),
+                name.position,
+            )
+        }
+        Target::Type(ty) => {
+            let mut contents = format!("```graphql\n{} {}\n```", type_keyword(ty), ty.name());
+            if let Some(description) = ty.description() {
+                contents.push_str("\n\n");
+                contents.push_str(description);
+            }
+            return Some(json!({
+                "contents": { "kind": "markdown", "value": contents },
+            }));
+        }
+        Target::Fragment(fragment) => (
+            format!(
+                "```graphql\nfragment {} on {}\n```",
+                fragment.name.name, fragment.type_condition.name
+            ),
+            fragment.name.position,
+        ),
+        Target::SelectionSet { .. } => return None,
+    };
+    // Range is not attached for fragment hovers as the position points to another place.
+    let range = (pos.file == file_idx).then(|| lsp_range(source, &pos));
+    Some(json!({
+        "contents": { "kind": "markdown", "value": contents },
+        "range": range,
+    }))
+}
+
+/// Finds the definition of the element at given position of an operation document.
+pub fn definition(project: &Project, file_idx: usize, offset: usize) -> Option<Value> {
+    let schema = project.schema.as_ref()?;
+    let document = project.operation(file_idx)?;
+    let fragments = project.fragments();
+    let pos = match find_target(schema, &fragments, document, offset)? {
+        Target::Field { definition, .. } => *definition?.original_node_ref(),
+        Target::Type(ty) => *ty.original_node_ref(),
+        Target::Fragment(fragment) => fragment.name.position,
+        Target::SelectionSet { .. } => return None,
+    };
+    location(project, &pos)
+}
+
+/// Lists completion candidates for given position of an operation document.
+pub fn completion(project: &Project, file_idx: usize, offset: usize) -> Vec<Value> {
+    let Some(schema) = project.schema.as_ref() else {
+        return vec![];
+    };
+    let Some(document) = project.operation(file_idx) else {
+        return vec![];
+    };
+    let fragments = project.fragments();
+    let parent_type = match find_target(schema, &fragments, document, offset) {
+        Some(Target::Field { parent_type, .. }) | Some(Target::SelectionSet { parent_type }) => {
+            parent_type
+        }
+        _ => return vec![],
+    };
+    let Some(fields) = direct_fields_of_output_type(parent_type) else {
+        return vec![];
+    };
+    fields
+        .iter()
+        .map(|field| {
+            let mut item = json!({
+                "label": field.name.to_string(),
+                "kind": COMPLETION_ITEM_KIND_FIELD,
+                "detail": field.r#type.to_string(),
+            });
+            if let Some(ref description) = field.description {
+                item["documentation"] = json!({
+                    "kind": "markdown",
+                    "value": description.to_string(),
+                });
+            }
+            if field.deprecation.is_some() {
+                item["deprecated"] = json!(true);
+                item["tags"] = json!([1]);
+            }
+            item
+        })
+        .collect()
+}
+
+/// Converts a Pos into an LSP location.
+fn location(project: &Project, pos: &Pos) -> Option<Value> {
+    if pos.builtin {
+        return None;
+    }
+    let (ref path, source, _) = project.file_store.get_file(pos.file)?;
+    Some(json!({
+        "uri": path_to_uri(path),
+        "range": lsp_range(source, pos),
+    }))
+}
+
+fn field_documentation(parent_type_name: &str, field: &Field<Cow<str>, Pos>) -> String {
+    let arguments = if field.arguments.is_empty() {
+        String::new()
+    } else {
+        let arguments = field
+            .arguments
+            .iter()
+            .map(|argument| format!("{}: {}", argument.name, argument.r#type))
+            .collect::<Vec<_>>();
+        format!("({})", arguments.join(", "))
+    };
+    let mut result = format!(
+        "```graphql\n{}.{}{}: {}\n```",
+        parent_type_name, field.name, arguments, field.r#type
+    );
+    if let Some(ref description) = field.description {
+        result.push_str("\n\n");
+        result.push_str(description);
+    }
+    if let Some(ref reason) = field.deprecation {
+        result.push_str(&format!("\n\n**Deprecated**: {reason}"));
+    }
+    result
+}
+
+fn type_keyword<S, D>(ty: &TypeDefinition<S, D>) -> &'static str {
+    match ty {
+        TypeDefinition::Scalar(_) => "scalar",
+        TypeDefinition::Object(_) => "type",
+        TypeDefinition::Interface(_) => "interface",
+        TypeDefinition::Union(_) => "union",
+        TypeDefinition::Enum(_) => "enum",
+        TypeDefinition::InputObject(_) => "input",
+    }
+}
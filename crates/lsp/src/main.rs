@@ -0,0 +1,28 @@
+use std::{io, process};
+
+use server::Server;
+
+mod error;
+mod features;
+mod position;
+mod project;
+mod rpc;
+mod server;
+mod target;
+#[cfg(test)]
+mod tests;
+mod uri;
+mod workspace;
+
+fn main() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut server = Server::new();
+    let exit_code = server
+        .run(&mut stdin.lock(), &mut stdout.lock())
+        .unwrap_or_else(|err| {
+            eprintln!("{err}");
+            1
+        });
+    process::exit(exit_code);
+}
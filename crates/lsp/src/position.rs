@@ -0,0 +1,51 @@
+use nitrogql_ast::base::Pos;
+use serde_json::{json, Value};
+
+/// Converts a 0-based (line, column) pair of a Pos into an LSP position.
+/// Pos counts columns in chars while LSP counts them in UTF-16 code units,
+/// so characters outside the BMP count twice in the result.
+pub fn lsp_position(source: &str, line: usize, column: usize) -> Value {
+    let character = source.lines().nth(line).map_or(column, |line| {
+        line.chars().take(column).map(char::len_utf16).sum()
+    });
+    json!({ "line": line, "character": character })
+}
+
+/// Converts a Pos into an LSP range.
+pub fn lsp_range(source: &str, pos: &Pos) -> Value {
+    let (end_line, end_column) = if (pos.end_line, pos.end_column) < (pos.line, pos.column) {
+        (pos.line, pos.column)
+    } else {
+        (pos.end_line, pos.end_column)
+    };
+    json!({
+        "start": lsp_position(source, pos.line, pos.column),
+        "end": lsp_position(source, end_line, end_column),
+    })
+}
+
+/// Converts an LSP position into a byte offset in source.
+/// `character` is in UTF-16 code units.
+pub fn offset_of_position(source: &str, line: usize, character: usize) -> usize {
+    let mut line_start = 0;
+    for _ in 0..line {
+        match source[line_start..].find('\n') {
+            Some(idx) => line_start += idx + 1,
+            None => return source.len(),
+        }
+    }
+    let mut units = 0;
+    for (idx, ch) in source[line_start..].char_indices() {
+        if units >= character || ch == '\n' {
+            return line_start + idx;
+        }
+        units += ch.len_utf16();
+    }
+    source.len()
+}
+
+/// Returns whether given byte offset is within the node at pos.
+/// The end of the node is inclusive so that the cursor right after a name still hits it.
+pub fn contains_offset(pos: &Pos, offset: usize) -> bool {
+    !pos.builtin && pos.offset <= offset && offset <= pos.end_offset
+}
@@ -0,0 +1,204 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+use graphql_builtins::generate_builtins;
+use graphql_type_system::Schema;
+use log::info;
+use nitrogql_ast::{
+    base::Pos, operation::OperationDocument, set_current_file_of_pos,
+    type_system::TypeSystemOrExtensionDocument,
+};
+use nitrogql_checker::{
+    check_operation_document_with_fragments, check_type_system_document, CheckError,
+    CustomScalarRules, Severity,
+};
+use nitrogql_error::PositionedError;
+use nitrogql_introspection::schema_from_introspection_json;
+use nitrogql_parser::{
+    is_embedding_source, parse_operation_document_recovering, parse_type_system_document_recovering,
+};
+use nitrogql_semantics::{ast_to_type_system, resolve_extensions, FragmentRegistry};
+use nitrogql_utils::{normalize_path, FileKind, FileStore};
+
+use crate::error::LspError;
+
+/// Result of loading, parsing and checking all files of a workspace.
+pub struct Project<'src> {
+    pub file_store: FileStore<'src>,
+    /// Resolved schema. None if schema could not be loaded.
+    pub schema: Option<Schema<Cow<'src, str>, Pos>>,
+    /// Parsed operation documents with their file index.
+    /// Definitions with syntax errors are not included.
+    pub operations: Vec<(usize, OperationDocument<'src>)>,
+    /// Errors found in the project.
+    pub errors: Vec<PositionedError>,
+    /// Warnings found in the project.
+    pub warnings: Vec<PositionedError>,
+}
+
+impl<'src> Project<'src> {
+    /// Parses and checks given files. Schema files must come first.
+    pub fn new(
+        files: impl IntoIterator<Item = (PathBuf, &'src str, FileKind)>,
+        custom_scalars: &CustomScalarRules,
+    ) -> Self {
+        let mut project = Project {
+            file_store: FileStore::new(),
+            schema: None,
+            operations: vec![],
+            errors: vec![],
+            warnings: vec![],
+        };
+        let mut schema_docs = vec![];
+        let mut introspection = None;
+        let mut operation_files = vec![];
+        for (path, content, kind) in files {
+            let file_idx = project.file_store.add_borrowed_file(path, content, kind);
+            set_current_file_of_pos(file_idx);
+            let (ref path, _, _) = project.file_store[file_idx];
+            match kind {
+                FileKind::Schema => {
+                    let is_introspection =
+                        path.extension().map(|ext| ext == "json").unwrap_or(false);
+                    if is_introspection {
+                        info!("parsing(introspection) {}", path.to_string_lossy());
+                        if introspection.is_some() {
+                            project
+                                .errors
+                                .push(error_at_start(LspError::IntrospectionOnce));
+                            continue;
+                        }
+                        match schema_from_introspection_json(content) {
+                            Ok(schema) => introspection = Some((file_idx, schema)),
+                            Err(err) => project.errors.push(error_at_start(err)),
+                        }
+                    } else {
+                        info!("parsing(schema) {}", path.to_string_lossy());
+                        let (doc, errors) = parse_type_system_document_recovering(content);
+                        schema_docs.push(doc);
+                        project.errors.extend(errors.into_iter().map(Into::into));
+                    }
+                }
+                // Documents embedded in TypeScript and JavaScript sources are not supported yet.
+                FileKind::Operation if is_embedding_source(path) => {}
+                FileKind::Operation => operation_files.push((file_idx, content)),
+                // Next schema is only used by the CLI.
+                FileKind::NextSchema => {}
+            }
+        }
+
+        let has_schema_errors = match introspection {
+            Some((file_idx, schema)) => {
+                if !schema_docs.is_empty() {
+                    set_current_file_of_pos(file_idx);
+                    project
+                        .errors
+                        .push(error_at_start(LspError::MixGraphQLAndIntrospection));
+                }
+                project.schema = Some(schema);
+                !project.errors.is_empty()
+            }
+            None => project.load_graphql_schema(schema_docs),
+        };
+
+        for (file_idx, content) in operation_files {
+            set_current_file_of_pos(file_idx);
+            // Definitions without syntax errors are still checked.
+            let (doc, errors) = parse_operation_document_recovering(content);
+            project.operations.push((file_idx, doc));
+            project.errors.extend(errors.into_iter().map(Into::into));
+        }
+
+        if !has_schema_errors {
+            if let Some(ref schema) = project.schema {
+                let mut fragments = FragmentRegistry::new();
+                for (_, doc) in project.operations.iter() {
+                    fragments.add_document(doc);
+                }
+                let errors = project
+                    .operations
+                    .iter()
+                    .flat_map(|(_, doc)| {
+                        check_operation_document_with_fragments(
+                            schema,
+                            doc,
+                            &fragments,
+                            custom_scalars,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                project.add_check_errors(errors);
+            }
+        }
+        project
+    }
+
+    /// Resolves schema from parsed GraphQL documents.
+    /// Returns true if there is an error in schema.
+    fn load_graphql_schema(
+        &mut self,
+        schema_docs: Vec<TypeSystemOrExtensionDocument<'src>>,
+    ) -> bool {
+        if !self.errors.is_empty() {
+            return true;
+        }
+        if schema_docs.is_empty() {
+            return false;
+        }
+        let mut document = TypeSystemOrExtensionDocument::merge(schema_docs);
+        document.extend(generate_builtins());
+        let resolved = match resolve_extensions(document) {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                self.errors.push(err.into());
+                return true;
+            }
+        };
+        let errors = check_type_system_document(&resolved);
+        let has_errors = errors.iter().any(|err| err.severity() == Severity::Error);
+        self.add_check_errors(errors);
+        self.schema = Some(ast_to_type_system(&resolved));
+        has_errors
+    }
+
+    /// Adds errors from the checker to errors or warnings by their severity.
+    fn add_check_errors(&mut self, errors: impl IntoIterator<Item = CheckError>) {
+        for error in errors {
+            match error.severity() {
+                Severity::Error => self.errors.push(error.into()),
+                Severity::Warning => self.warnings.push(error.into()),
+            }
+        }
+    }
+
+    /// Returns the index of given file.
+    pub fn file_index(&self, path: &Path) -> Option<usize> {
+        let path = normalize_path(path);
+        self.file_store
+            .iter()
+            .find_map(|(idx, (file_path, _, _))| (file_path == path).then_some(idx))
+    }
+
+    /// Returns the parsed operation document of given file.
+    pub fn operation(&self, file_idx: usize) -> Option<&OperationDocument<'src>> {
+        self.operations
+            .iter()
+            .find_map(|(idx, doc)| (*idx == file_idx).then_some(doc))
+    }
+
+    /// Returns a registry of fragments defined in all operation documents.
+    pub fn fragments(&self) -> FragmentRegistry<'_, 'src> {
+        let mut fragments = FragmentRegistry::new();
+        for (_, doc) in self.operations.iter() {
+            fragments.add_document(doc);
+        }
+        fragments
+    }
+}
+
+/// Creates an error that points to the start of current file.
+fn error_at_start(err: impl Into<anyhow::Error>) -> PositionedError {
+    PositionedError::new(err.into(), Some(Pos::new(0, 0)), vec![])
+}
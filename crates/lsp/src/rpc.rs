@@ -0,0 +1,83 @@
+use std::io::{self, BufRead, Write};
+
+use serde_json::{json, Value};
+
+/// JSON-RPC error code for messages that are not valid JSON.
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for unknown methods.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for invalid parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+/// LSP error code for requests received before `initialize`.
+pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+/// Reads one message framed with a `Content-Length` header.
+/// Returns None when the input is closed.
+/// A message whose content is not valid JSON is returned as an inner error
+/// so that the caller can report it and keep reading.
+pub fn read_message(
+    reader: &mut impl BufRead,
+) -> io::Result<Option<Result<Value, serde_json::Error>>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(content_length) = content_length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing Content-Length header",
+        ));
+    };
+    let mut buf = vec![0; content_length];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(serde_json::from_slice(&buf)))
+}
+
+/// Writes one message with a `Content-Length` header.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+/// Creates a successful response.
+pub fn response(id: Value, result: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": result,
+    })
+}
+
+/// Creates an error response.
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": code,
+            "message": message,
+        },
+    })
+}
+
+/// Creates a notification.
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+    })
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::HashSet,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+use log::{error, info};
+use nitrogql_utils::FileKind;
+use self_cell::self_cell;
+use serde_json::{json, Value};
+
+use crate::{
+    features::{completion, definition, diagnostics, hover},
+    position::offset_of_position,
+    project::Project,
+    rpc::{
+        error_response, notification, read_message, response, write_message, INVALID_PARAMS,
+        METHOD_NOT_FOUND, PARSE_ERROR, SERVER_NOT_INITIALIZED,
+    },
+    uri::{path_to_uri, uri_to_path},
+    workspace::Workspace,
+};
+
+self_cell!(
+    /// Project built from a snapshot of workspace files.
+    struct CachedProject {
+        owner: Vec<(PathBuf, String, FileKind)>,
+        #[covariant]
+        dependent: Project,
+    }
+);
+
+/// Language server that serves one workspace.
+pub struct Server {
+    workspace: Option<Workspace>,
+    /// Project built from current workspace. None if not built since the last change.
+    project: Option<CachedProject>,
+    /// Files for which non-empty diagnostics are published.
+    files_with_diagnostics: HashSet<PathBuf>,
+    shutdown_requested: bool,
+    exit_code: Option<i32>,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self {
+            workspace: None,
+            project: None,
+            files_with_diagnostics: HashSet::new(),
+            shutdown_requested: false,
+            exit_code: None,
+        }
+    }
+
+    /// Runs the server until `exit` notification is received or input is closed.
+    /// Returns the exit code.
+    pub fn run(&mut self, reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<i32> {
+        while let Some(message) = read_message(reader)? {
+            let outgoing = match message {
+                Ok(message) => self.handle_message(message),
+                Err(err) => {
+                    error!("Failed to parse message: {err}");
+                    vec![error_response(Value::Null, PARSE_ERROR, &err.to_string())]
+                }
+            };
+            for outgoing in outgoing {
+                write_message(writer, &outgoing)?;
+            }
+            if let Some(exit_code) = self.exit_code() {
+                return Ok(exit_code);
+            }
+        }
+        Ok(1)
+    }
+
+    /// Returns the exit code if `exit` notification has been received.
+    pub fn exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+
+    /// Handles one incoming message. Returns messages to send to the client.
+    pub fn handle_message(&mut self, message: Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            // Responses to requests from server are ignored.
+            return vec![];
+        };
+        let params = &message["params"];
+        match message.get("id") {
+            Some(id) => {
+                let result = if self.workspace.is_none() && method != "initialize" {
+                    Err((
+                        SERVER_NOT_INITIALIZED,
+                        "Server is not initialized".to_owned(),
+                    ))
+                } else {
+                    self.handle_request(method, params)
+                };
+                let mut outgoing = match result {
+                    Ok(result) => vec![response(id.clone(), result)],
+                    Err((code, message)) => vec![error_response(id.clone(), code, &message)],
+                };
+                if method == "initialize" {
+                    outgoing.extend(self.publish_diagnostics());
+                }
+                outgoing
+            }
+            None => self.handle_notification(method, params),
+        }
+    }
+
+    fn handle_request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        info!("request {method}");
+        match method {
+            "initialize" => {
+                let root = params["rootUri"]
+                    .as_str()
+                    .and_then(uri_to_path)
+                    .or_else(|| params["rootPath"].as_str().map(PathBuf::from))
+                    .ok_or((INVALID_PARAMS, "No root directory specified".to_owned()))?;
+                let workspace = Workspace::load(&root).map_err(|err| {
+                    error!("Failed to load workspace: {err}");
+                    (INVALID_PARAMS, err.to_string())
+                })?;
+                self.workspace = Some(workspace);
+                self.project = None;
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": {
+                            "openClose": true,
+                            // Full sync
+                            "change": 1,
+                            "save": true,
+                        },
+                        "hoverProvider": true,
+                        "definitionProvider": true,
+                        "completionProvider": {},
+                    },
+                    "serverInfo": {
+                        "name": "nitrogql",
+                    },
+                }))
+            }
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => {
+                let (path, line, character) = text_document_position(params)?;
+                Ok(self
+                    .project()
+                    .and_then(|project| {
+                        let file_idx = project.file_index(&path)?;
+                        let (_, ref source, _) = project.file_store[file_idx];
+                        hover(
+                            project,
+                            file_idx,
+                            offset_of_position(source, line, character),
+                        )
+                    })
+                    .unwrap_or(Value::Null))
+            }
+            "textDocument/definition" => {
+                let (path, line, character) = text_document_position(params)?;
+                Ok(self
+                    .project()
+                    .and_then(|project| {
+                        let file_idx = project.file_index(&path)?;
+                        let (_, ref source, _) = project.file_store[file_idx];
+                        definition(
+                            project,
+                            file_idx,
+                            offset_of_position(source, line, character),
+                        )
+                    })
+                    .unwrap_or(Value::Null))
+            }
+            "textDocument/completion" => {
+                let (path, line, character) = text_document_position(params)?;
+                let items = self.completion(path, line, character);
+                Ok(json!(items))
+            }
+            method => Err((METHOD_NOT_FOUND, format!("Unknown method '{method}'"))),
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        info!("notification {method}");
+        if method == "exit" {
+            self.exit_code = Some(if self.shutdown_requested { 0 } else { 1 });
+            return vec![];
+        }
+        let Some(workspace) = self.workspace.as_mut() else {
+            return vec![];
+        };
+        let path = params["textDocument"]["uri"].as_str().and_then(uri_to_path);
+        match (method, path) {
+            ("textDocument/didOpen", Some(path)) => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                workspace.set_open_file(path, text.to_owned());
+                // The opened file may be a new file.
+                if let Err(err) = workspace.reload() {
+                    error!("Failed to reload workspace: {err}");
+                }
+            }
+            ("textDocument/didChange", Some(path)) => {
+                // With full sync, the last change has the whole content.
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                let Some(text) = text else {
+                    return vec![];
+                };
+                workspace.set_open_file(path, text.to_owned());
+            }
+            ("textDocument/didClose", Some(path)) => {
+                workspace.close_file(&path);
+                if let Err(err) = workspace.reload() {
+                    error!("Failed to reload workspace: {err}");
+                }
+            }
+            ("textDocument/didSave", Some(_)) => {
+                if let Err(err) = workspace.reload() {
+                    error!("Failed to reload workspace: {err}");
+                }
+            }
+            _ => return vec![],
+        }
+        self.project = None;
+        self.publish_diagnostics()
+    }
+
+    /// Checks the whole project and generates `textDocument/publishDiagnostics` notifications.
+    fn publish_diagnostics(&mut self) -> Vec<Value> {
+        let Some(diagnostics) = self.project().map(diagnostics) else {
+            return vec![];
+        };
+        let mut outgoing = vec![];
+        let mut files_with_diagnostics = HashSet::new();
+        for (path, diagnostics) in diagnostics {
+            if diagnostics.is_empty() {
+                if !self.files_with_diagnostics.contains(&path) {
+                    continue;
+                }
+            } else {
+                files_with_diagnostics.insert(path.clone());
+            }
+            outgoing.push(notification(
+                "textDocument/publishDiagnostics",
+                json!({
+                    "uri": path_to_uri(&path),
+                    "diagnostics": diagnostics,
+                }),
+            ));
+        }
+        // Clear diagnostics for files that no longer belong to the project.
+        for path in self
+            .files_with_diagnostics
+            .difference(&files_with_diagnostics)
+        {
+            if !outgoing
+                .iter()
+                .any(|message| message["params"]["uri"] == path_to_uri(path))
+            {
+                outgoing.push(notification(
+                    "textDocument/publishDiagnostics",
+                    json!({
+                        "uri": path_to_uri(path),
+                        "diagnostics": [],
+                    }),
+                ));
+            }
+        }
+        self.files_with_diagnostics = files_with_diagnostics;
+        outgoing
+    }
+
+    /// Completes at given position of an operation file.
+    /// Incomplete selection sets such as `{ }` are kept in the parsed document, so they can be completed.
+    fn completion(&mut self, path: PathBuf, line: usize, character: usize) -> Vec<Value> {
+        let items = self.project().and_then(|project| {
+            let file_idx = project.file_index(&path)?;
+            let (_, ref source, kind) = project.file_store[file_idx];
+            if kind != FileKind::Operation {
+                return None;
+            }
+            let offset = offset_of_position(source, line, character);
+            Some(completion(project, file_idx, offset))
+        });
+        items.unwrap_or_default()
+    }
+
+    /// Returns the project built from current workspace.
+    /// The project is built again only after the workspace has changed.
+    fn project(&mut self) -> Option<&Project<'_>> {
+        let workspace = self.workspace.as_ref()?;
+        let project = self.project.get_or_insert_with(|| {
+            let files = workspace
+                .files()
+                .into_iter()
+                .map(|(path, content, kind)| (path, content.to_owned(), kind))
+                .collect();
+            CachedProject::new(files, |files| {
+                Project::new(
+                    files
+                        .iter()
+                        .map(|(path, content, kind)| (path.clone(), content.as_str(), *kind)),
+                    workspace.custom_scalars(),
+                )
+            })
+        });
+        Some(project.borrow_dependent())
+    }
+}
+
+/// Extracts a file path and a position from TextDocumentPositionParams.
+fn text_document_position(params: &Value) -> Result<(PathBuf, usize, usize), (i64, String)> {
+    let path = params["textDocument"]["uri"]
+        .as_str()
+        .and_then(uri_to_path)
+        .ok_or((INVALID_PARAMS, "Invalid text document".to_owned()))?;
+    let line = params["position"]["line"].as_u64();
+    let character = params["position"]["character"].as_u64();
+    let (Some(line), Some(character)) = (line, character) else {
+        return Err((INVALID_PARAMS, "Invalid position".to_owned()));
+    };
+    Ok((path, line as usize, character as usize))
+}
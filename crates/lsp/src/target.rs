@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+
+use graphql_type_system::{Field, Node, Schema, TypeDefinition};
+use nitrogql_ast::{
+    base::{Ident, Pos},
+    operation::{ExecutableDefinition, FragmentDefinition, OperationDocument, OperationType},
+    selection_set::{Selection, SelectionSet},
+};
+use nitrogql_semantics::{direct_fields_of_output_type, FragmentRegistry};
+
+use crate::position::contains_offset;
+
+type TypeNode<'a, 'src> = &'a Node<TypeDefinition<Cow<'src, str>, Pos>, Pos>;
+
+/// Syntax element of an operation document that the cursor points to.
+#[allow(clippy::large_enum_variant)]
+pub enum Target<'a, 'src> {
+    /// Name of a field selection.
+    Field {
+        parent_type: TypeNode<'a, 'src>,
+        name: &'a Ident<'src>,
+        /// Definition of the field. None if parent type does not have the field.
+        definition: Option<Cow<'a, Field<Cow<'src, str>, Pos>>>,
+    },
+    /// Name of a type.
+    Type(TypeNode<'a, 'src>),
+    /// Name of a spread fragment.
+    Fragment(&'a FragmentDefinition<'src>),
+    /// Inside a selection set but not on any selection.
+    SelectionSet { parent_type: TypeNode<'a, 'src> },
+}
+
+/// Finds the innermost element that contains given byte offset.
+pub fn find_target<'a, 'src>(
+    schema: &'a Schema<Cow<'src, str>, Pos>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    document: &'a OperationDocument<'src>,
+    offset: usize,
+) -> Option<Target<'a, 'src>> {
+    document.definitions.iter().find_map(|def| match def {
+        ExecutableDefinition::OperationDefinition(op) => {
+            if !contains_offset(&op.selection_set.position, offset) {
+                return None;
+            }
+            let root_types = schema.root_types().unwrap_or_default();
+            let root_type_name = match op.operation_type {
+                OperationType::Query => root_types.query_type,
+                OperationType::Mutation => root_types.mutation_type,
+                OperationType::Subscription => root_types.subscription_type,
+            };
+            let root_type = schema.get_type(&root_type_name)?;
+            find_in_selection_set(schema, fragments, root_type, &op.selection_set, offset)
+        }
+        ExecutableDefinition::FragmentDefinition(fragment) => {
+            let type_condition = schema.get_type(fragment.type_condition.name)?;
+            if contains_offset(&fragment.type_condition.position, offset) {
+                return Some(Target::Type(type_condition));
+            }
+            if !contains_offset(&fragment.selection_set.position, offset) {
+                return None;
+            }
+            find_in_selection_set(
+                schema,
+                fragments,
+                type_condition,
+                &fragment.selection_set,
+                offset,
+            )
+        }
+    })
+}
+
+fn find_in_selection_set<'a, 'src>(
+    schema: &'a Schema<Cow<'src, str>, Pos>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    parent_type: TypeNode<'a, 'src>,
+    selection_set: &'a SelectionSet<'src>,
+    offset: usize,
+) -> Option<Target<'a, 'src>> {
+    for selection in selection_set.selections.iter() {
+        match selection {
+            Selection::Field(field) => {
+                let on_name = contains_offset(&field.name.position, offset)
+                    || field
+                        .alias
+                        .as_ref()
+                        .is_some_and(|alias| contains_offset(&alias.position, offset));
+                let in_selection_set = field
+                    .selection_set
+                    .as_ref()
+                    .filter(|selection_set| contains_offset(&selection_set.position, offset));
+                if !on_name && in_selection_set.is_none() {
+                    continue;
+                }
+                let definition = find_field(parent_type, field.name.name);
+                if on_name {
+                    return Some(Target::Field {
+                        parent_type,
+                        name: &field.name,
+                        definition,
+                    });
+                }
+                let field_type = schema.get_type(definition?.r#type.unwrapped())?;
+                return find_in_selection_set(
+                    schema,
+                    fragments,
+                    field_type,
+                    in_selection_set?,
+                    offset,
+                );
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if contains_offset(&fragment_spread.fragment_name.position, offset) {
+                    return fragments
+                        .get(fragment_spread.fragment_name.name)
+                        .map(Target::Fragment);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                let type_condition = match inline_fragment.type_condition {
+                    Some(ref type_condition) => {
+                        let ty = schema.get_type(type_condition.name);
+                        if contains_offset(&type_condition.position, offset) {
+                            return ty.map(Target::Type);
+                        }
+                        ty
+                    }
+                    None => Some(parent_type),
+                };
+                if contains_offset(&inline_fragment.selection_set.position, offset) {
+                    return find_in_selection_set(
+                        schema,
+                        fragments,
+                        type_condition?,
+                        &inline_fragment.selection_set,
+                        offset,
+                    );
+                }
+            }
+        }
+    }
+    Some(Target::SelectionSet { parent_type })
+}
+
+/// Finds a field of given name from an output type.
+fn find_field<'a, 'src>(
+    parent_type: TypeNode<'a, 'src>,
+    name: &str,
+) -> Option<Cow<'a, Field<Cow<'src, str>, Pos>>> {
+    direct_fields_of_output_type(parent_type)?
+        .into_iter()
+        .find(|field| field.name == name)
+}
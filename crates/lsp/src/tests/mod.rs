@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+use nitrogql_checker::CustomScalarRules;
+use nitrogql_utils::FileKind;
+use serde_json::Value;
+
+use crate::project::Project;
+
+const SCHEMA: &str = r#"
+type Query {
+  "The current user."
+  me: User!
+  user(id: ID!): User
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  "Name of the user."
+  name: String!
+  nickname: String @deprecated(reason: "Use name instead.")
+  friends: [User!]!
+}
+"#;
+
+fn build_project(operation: &str) -> Project<'_> {
+    Project::new(
+        vec![
+            (
+                PathBuf::from("/project/schema.graphql"),
+                SCHEMA,
+                FileKind::Schema,
+            ),
+            (
+                PathBuf::from("/project/operation.graphql"),
+                operation,
+                FileKind::Operation,
+            ),
+        ],
+        &CustomScalarRules::new(),
+    )
+}
+
+/// Removes the cursor marker `$|` from source and returns its byte offset.
+fn with_cursor(source: &str) -> (String, usize) {
+    let offset = source.find("$|").expect("cursor marker not found");
+    (source.replacen("$|", "", 1), offset)
+}
+
+fn to_string(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+mod diagnostics {
+    use insta::assert_snapshot;
+    use serde_json::json;
+
+    use crate::features::diagnostics;
+
+    use super::{build_project, to_string};
+
+    #[test]
+    fn no_errors() {
+        let project = build_project("query { me { id name } }");
+        assert_snapshot!(to_string(&json!(diagnostics(&project))));
+    }
+
+    #[test]
+    fn check_error() {
+        let project = build_project("query {\n  me {\n    id\n    age\n  }\n}\n");
+        assert_snapshot!(to_string(&json!(diagnostics(&project))));
+    }
+
+    #[test]
+    fn parse_error() {
+        let project = build_project("query {\n  me {\n    id\n");
+        assert_snapshot!(to_string(&json!(diagnostics(&project))));
+    }
+
+    #[test]
+    fn parse_error_and_check_error() {
+        let project = build_project("query A {\n  me { id( }\n}\nquery B {\n  me { age }\n}\n");
+        assert_snapshot!(to_string(&json!(diagnostics(&project))));
+    }
+
+    #[test]
+    fn deprecation_warning() {
+        let project = build_project("query {\n  me {\n    nickname\n  }\n}\n");
+        assert_snapshot!(to_string(&json!(diagnostics(&project))));
+    }
+
+    #[test]
+    fn non_bmp_characters() {
+        // 🎉 is one char but two UTF-16 code units.
+        let project = build_project("query { user(id: \"🎉\") { age } }");
+        assert_snapshot!(to_string(&json!(diagnostics(&project))));
+    }
+}
+
+mod hover {
+    use insta::assert_snapshot;
+
+    use crate::features::hover;
+
+    use super::{build_project, to_string, with_cursor};
+
+    #[test]
+    fn field_with_description() {
+        let (source, offset) = with_cursor("query { me { na$|me } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&hover(&project, 1, offset).unwrap()));
+    }
+
+    #[test]
+    fn field_with_arguments() {
+        let (source, offset) = with_cursor("query { u$|ser(id: \"1\") { id } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&hover(&project, 1, offset).unwrap()));
+    }
+
+    #[test]
+    fn deprecated_field() {
+        let (source, offset) = with_cursor("query { me { nickname$| } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&hover(&project, 1, offset).unwrap()));
+    }
+
+    #[test]
+    fn outside_of_field() {
+        let (source, offset) = with_cursor("query { me { id $| } }");
+        let project = build_project(&source);
+        assert!(hover(&project, 1, offset).is_none());
+    }
+}
+
+mod definition {
+    use insta::assert_snapshot;
+
+    use crate::features::definition;
+
+    use super::{build_project, to_string, with_cursor};
+
+    #[test]
+    fn nested_field() {
+        let (source, offset) = with_cursor("query { me { friends { $|name } } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&definition(&project, 1, offset).unwrap()));
+    }
+
+    #[test]
+    fn type_condition() {
+        let (source, offset) = with_cursor("query { me { ... on No$|de { id } } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&definition(&project, 1, offset).unwrap()));
+    }
+
+    #[test]
+    fn fragment_spread() {
+        let (source, offset) =
+            with_cursor("query { me { ...User$|Fields } }\n\nfragment UserFields on User { id }\n");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&definition(&project, 1, offset).unwrap()));
+    }
+
+    #[test]
+    fn builtin_field() {
+        let (source, offset) = with_cursor("query { me { __type$|name } }");
+        let project = build_project(&source);
+        assert!(definition(&project, 1, offset).is_none());
+    }
+}
+
+mod completion {
+    use insta::assert_snapshot;
+    use serde_json::json;
+
+    use crate::features::completion;
+
+    use super::{build_project, to_string, with_cursor};
+
+    #[test]
+    fn in_selection_set() {
+        let (source, offset) = with_cursor("query { me { id $| } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&json!(completion(&project, 1, offset))));
+    }
+
+    #[test]
+    fn empty_selection_set() {
+        let (source, offset) = with_cursor("query { me { $| } }");
+        let project = build_project(&source);
+        let labels: Vec<_> = completion(&project, 1, offset)
+            .into_iter()
+            .map(|item| item["label"].as_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(labels, ["id", "name", "nickname", "friends", "__typename"]);
+    }
+
+    #[test]
+    fn partial_field_name() {
+        let (source, offset) = with_cursor("query { me { fr$| } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&json!(completion(&project, 1, offset))));
+    }
+
+    #[test]
+    fn root_fields() {
+        let (source, offset) = with_cursor("query { $|me { id } }");
+        let project = build_project(&source);
+        assert_snapshot!(to_string(&json!(completion(&project, 1, offset))));
+    }
+}
+
+mod rpc {
+    use std::io::BufReader;
+
+    use serde_json::json;
+
+    use crate::rpc::{read_message, write_message};
+
+    #[test]
+    fn roundtrip() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" });
+        let mut buf = vec![];
+        write_message(&mut buf, &message).unwrap();
+        write_message(&mut buf, &message).unwrap();
+        let mut reader = BufReader::new(buf.as_slice());
+        let mut read = || read_message(&mut reader).unwrap().map(Result::unwrap);
+        assert_eq!(read(), Some(message.clone()));
+        assert_eq!(read(), Some(message));
+        assert_eq!(read(), None);
+    }
+}
+
+mod server {
+    use std::{fs, io::BufReader};
+
+    use insta::assert_snapshot;
+    use serde_json::json;
+
+    use crate::{
+        rpc::{read_message, write_message},
+        server::Server,
+        uri::path_to_uri,
+    };
+
+    use super::SCHEMA;
+
+    #[test]
+    fn session() {
+        let root = std::env::temp_dir().join(format!("nitrogql-lsp-test-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("graphql.config.yaml"),
+            "schema: ./schema.graphql\ndocuments: ./operation.graphql\n",
+        )
+        .unwrap();
+        fs::write(root.join("schema.graphql"), SCHEMA).unwrap();
+        fs::write(root.join("operation.graphql"), "query { me { id } }\n").unwrap();
+        let operation_uri = path_to_uri(&root.join("operation.graphql"));
+
+        let mut server = Server::new();
+        let mut send = |message| server.handle_message(message);
+
+        let initialized = send(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "rootUri": path_to_uri(&root) },
+        }));
+        let changed = send(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": operation_uri,
+                    "languageId": "graphql",
+                    "version": 1,
+                    "text": "query { me { age } }\n",
+                },
+            },
+        }));
+        let incomplete = send(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": operation_uri, "version": 2 },
+                "contentChanges": [{ "text": "query { me { } }\n" }],
+            },
+        }));
+        let completion = send(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/completion",
+            "params": {
+                "textDocument": { "uri": operation_uri },
+                "position": { "line": 0, "character": 13 },
+            },
+        }));
+        let shutdown = send(json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }));
+        send(json!({ "jsonrpc": "2.0", "method": "exit" }));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(server.exit_code(), Some(0));
+        assert_snapshot!(serde_json::to_string_pretty(&json!({
+            "initialized": initialized,
+            "changed": changed,
+            "incomplete": incomplete,
+            "completion": completion,
+            "shutdown": shutdown,
+        }))
+        .unwrap()
+        // Replace temporary paths for stable snapshots
+        .replace(&path_to_uri(&root), "file:///project"));
+    }
+
+    #[test]
+    fn malformed_message() {
+        let mut input = b"Content-Length: 8\r\n\r\n{\"id\": 1".to_vec();
+        write_message(
+            &mut input,
+            &json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+        )
+        .unwrap();
+        write_message(&mut input, &json!({ "jsonrpc": "2.0", "method": "exit" })).unwrap();
+        let mut output = vec![];
+        let exit_code = Server::new()
+            .run(&mut BufReader::new(input.as_slice()), &mut output)
+            .unwrap();
+        let mut reader = BufReader::new(output.as_slice());
+        let parse_error = read_message(&mut reader).unwrap().unwrap().unwrap();
+        let not_initialized = read_message(&mut reader).unwrap().unwrap().unwrap();
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(parse_error["id"], json!(null));
+        assert_eq!(parse_error["error"]["code"], json!(-32700));
+        assert_eq!(not_initialized["id"], json!(2));
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use globmatch::wrappers::{build_matchers, match_paths};
+use log::{info, trace};
+use nitrogql_checker::{CustomScalarRule, CustomScalarRules, LiteralKind};
+use nitrogql_config_file::{load_config, Config, ScalarLiteralKind};
+use nitrogql_utils::{normalize_path, FileKind};
+
+use crate::error::LspError;
+
+/// Set of files that belong to a project.
+/// Contents of files opened in the editor take precedence over those on disk.
+#[derive(Debug)]
+pub struct Workspace {
+    root_dir: PathBuf,
+    config: Config,
+    custom_scalars: CustomScalarRules,
+    schema_files: Vec<(PathBuf, String)>,
+    operation_files: Vec<(PathBuf, String)>,
+    open_files: HashMap<PathBuf, String>,
+}
+
+impl Workspace {
+    /// Creates a workspace with no files loaded.
+    pub fn new(root_dir: PathBuf, config: Config) -> Result<Self, LspError> {
+        let custom_scalars = custom_scalar_rules(&config)?;
+        Ok(Self {
+            root_dir,
+            config,
+            custom_scalars,
+            schema_files: vec![],
+            operation_files: vec![],
+            open_files: HashMap::new(),
+        })
+    }
+
+    /// Loads config file found from given directory and files specified by it.
+    pub fn load(root: &Path) -> anyhow::Result<Self> {
+        let (root_dir, config) = match load_config(root, None)? {
+            Some((config_path, config)) => {
+                info!("Loaded config file from {}", config_path.display());
+                (
+                    normalize_path(config_path.parent().unwrap_or(Path::new(""))),
+                    config,
+                )
+            }
+            None => (normalize_path(root), Config::default()),
+        };
+        let mut workspace = Self::new(root_dir, config)?;
+        workspace.reload()?;
+        Ok(workspace)
+    }
+
+    /// Re-reads files specified by config from file system.
+    pub fn reload(&mut self) -> anyhow::Result<()> {
+        self.schema_files = load_glob_files(&self.root_dir, &self.config.schema)?;
+        self.operation_files = load_glob_files(&self.root_dir, &self.config.operations)?;
+        Ok(())
+    }
+
+    /// Rules of custom scalars declared in config.
+    pub fn custom_scalars(&self) -> &CustomScalarRules {
+        &self.custom_scalars
+    }
+
+    /// Sets the content of a file opened in the editor.
+    pub fn set_open_file(&mut self, path: PathBuf, text: String) {
+        self.open_files.insert(normalize_path(&path), text);
+    }
+
+    /// Marks a file as closed so that its content on disk is used.
+    pub fn close_file(&mut self, path: &Path) {
+        self.open_files.remove(&normalize_path(path));
+    }
+
+    /// Lists files in the project, schema files first.
+    pub fn files(&self) -> Vec<(PathBuf, &str, FileKind)> {
+        let schema_files = self
+            .schema_files
+            .iter()
+            .map(|(path, content)| (path, content, FileKind::Schema));
+        let operation_files = self
+            .operation_files
+            .iter()
+            .map(|(path, content)| (path, content, FileKind::Operation));
+        schema_files
+            .chain(operation_files)
+            .map(|(path, content, kind)| {
+                let content = self.open_files.get(path).unwrap_or(content);
+                (path.clone(), content.as_str(), kind)
+            })
+            .collect()
+    }
+}
+
+/// Compiles rules of custom scalars declared in config.
+fn custom_scalar_rules(config: &Config) -> Result<CustomScalarRules, LspError> {
+    config
+        .custom_scalars
+        .iter()
+        .map(|(name, scalar)| {
+            let literals = scalar
+                .literals
+                .iter()
+                .map(|kind| match kind {
+                    ScalarLiteralKind::Int => LiteralKind::Int,
+                    ScalarLiteralKind::Float => LiteralKind::Float,
+                    ScalarLiteralKind::String => LiteralKind::String,
+                    ScalarLiteralKind::Boolean => LiteralKind::Boolean,
+                    ScalarLiteralKind::Enum => LiteralKind::Enum,
+                    ScalarLiteralKind::List => LiteralKind::List,
+                    ScalarLiteralKind::Object => LiteralKind::Object,
+                })
+                .collect();
+            let rule =
+                CustomScalarRule::new(literals, scalar.pattern.as_deref()).map_err(|err| {
+                    LspError::InvalidCustomScalarPattern {
+                        name: name.clone(),
+                        message: err.to_string(),
+                    }
+                })?;
+            Ok((name.clone(), rule))
+        })
+        .collect()
+}
+
+fn load_glob_files(root: &Path, globs: &[String]) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let path_strs: Vec<&str> = globs.iter().map(|s| s.as_str()).collect();
+    if path_strs.is_empty() {
+        return Ok(vec![]);
+    }
+
+    trace!("load_glob_files {} {}", root.display(), path_strs.join(" "));
+    let matchers = build_matchers(&path_strs, root).map_err(LspError::GlobError)?;
+    let (paths, _) = match_paths(matchers, None, None);
+    let results = paths
+        .into_iter()
+        .map(|path| {
+            info!("loading {}", path.to_string_lossy());
+            fs::read_to_string(&path).map(|res| (normalize_path(&path), res))
+        })
+        .collect::<std::io::Result<_>>()?;
+    Ok(results)
+}
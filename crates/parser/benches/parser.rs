@@ -0,0 +1,170 @@
+//! Compares the hand-written parser with the one generated from the pest grammar.
+//!
+//! Run with `cargo bench -p nitrogql-parser --features pest`.
+
+use std::{
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use nitrogql_parser::{parse_operation_document, parse_type_system_document, pest_parser};
+
+/// Minimum time spent on measuring each parser for each input.
+const MEASUREMENT_TIME: Duration = Duration::from_secs(2);
+
+fn main() {
+    let inputs = [
+        // Comparable to the schema of a large public API fetched by introspection.
+        ("introspection-sized SDL", generate_schema(100), true),
+        // The pest parser takes tens of seconds to parse this.
+        ("large SDL", generate_schema(400), true),
+        ("operations", generate_operations(100), false),
+    ];
+    for (name, source, is_schema) in inputs {
+        println!("{name} ({} KiB)", source.len() / 1024);
+        let (hand_written, pest) = if is_schema {
+            assert_same_result(
+                parse_type_system_document(&source),
+                pest_parser::parse_type_system_document(&source),
+            );
+            (
+                measure(|| parse_type_system_document(black_box(&source)).is_ok()),
+                measure(|| pest_parser::parse_type_system_document(black_box(&source)).is_ok()),
+            )
+        } else {
+            assert_same_result(
+                parse_operation_document(&source),
+                pest_parser::parse_operation_document(&source),
+            );
+            (
+                measure(|| parse_operation_document(black_box(&source)).is_ok()),
+                measure(|| pest_parser::parse_operation_document(black_box(&source)).is_ok()),
+            )
+        };
+        println!("  hand-written: {}", describe(hand_written, source.len()));
+        println!("  pest:         {}", describe(pest, source.len()));
+        println!(
+            "  speedup:      {:.1}x",
+            pest.as_secs_f64() / hand_written.as_secs_f64()
+        );
+    }
+}
+
+/// Checks that both parsers produce the same AST including positions.
+fn assert_same_result<T: std::fmt::Debug, E: std::fmt::Debug>(
+    hand_written: Result<T, E>,
+    pest: Result<T, E>,
+) {
+    let hand_written = hand_written.expect("hand-written parser failed");
+    let pest = pest.expect("pest parser failed");
+    assert!(
+        format!("{hand_written:?}") == format!("{pest:?}"),
+        "parsers produced different results"
+    );
+}
+
+/// Runs given function repeatedly and returns the shortest duration of one run.
+/// Slow runs are repeated only until `MEASUREMENT_TIME` elapses.
+fn measure(mut run: impl FnMut() -> bool) -> Duration {
+    let started = Instant::now();
+    let mut shortest = Duration::MAX;
+    while shortest == Duration::MAX || started.elapsed() < MEASUREMENT_TIME {
+        let start = Instant::now();
+        assert!(run());
+        shortest = shortest.min(start.elapsed());
+    }
+    shortest
+}
+
+fn describe(duration: Duration, bytes: usize) -> String {
+    let megabytes_per_second = bytes as f64 / duration.as_secs_f64() / 1024.0 / 1024.0;
+    format!("{duration:>12.3?} ({megabytes_per_second:.1} MiB/s)")
+}
+
+/// Generates a schema with `types` object types and accompanying interfaces, enums and input types.
+fn generate_schema(types: usize) -> String {
+    let mut schema = String::from(
+        "schema {\n  query: Query\n  mutation: Mutation\n}\n\n\
+         directive @cost(weight: Int = 1, multipliers: [String!]) repeatable on FIELD_DEFINITION | OBJECT\n\n\
+         \"\"\"\nAn object with an ID.\n\"\"\"\ninterface Node {\n  \"ID of the object.\"\n  id: ID!\n}\n\n",
+    );
+    for i in 0..types {
+        write!(
+            schema,
+            r#""""
+Type number {i}.
+It is generated for benchmarking.
+"""
+type Type{i} implements Node @cost(weight: {i}) {{
+  "ID of the object."
+  id: ID!
+  "Name of the object, which may be translated."
+  name(locale: String = "en", fallback: Boolean = true): String!
+  "Related objects."
+  related(first: Int = 10, after: String, orderBy: Order{i} = {{ field: CREATED_AT, direction: ASC }}): [Type{i}!]! @deprecated(reason: "Use \"relatedConnection\" instead.")
+  status: Status{i}
+  createdAt: DateTime!
+  tags: [String!]
+}}
+
+"Statuses of Type{i}."
+enum Status{i} {{
+  "Newly created."
+  ACTIVE
+  ARCHIVED @deprecated
+  DELETED
+}}
+
+input Order{i} {{
+  field: OrderField = CREATED_AT
+  direction: Direction! = ASC
+}}
+
+union Result{i} = Type{i} | Error
+
+"#
+        )
+        .unwrap();
+    }
+    schema.push_str("type Query {\n");
+    for i in 0..types {
+        writeln!(schema, "  type{i}(id: ID!): Type{i}").unwrap();
+    }
+    schema.push_str("}\n\nextend type Query {\n  node(id: ID!): Node\n}\n");
+    schema
+}
+
+/// Generates a document with `operations` operations and fragments.
+fn generate_operations(operations: usize) -> String {
+    let mut document = String::from("#import \"./fragments.graphql\"\n\n");
+    for i in 0..operations {
+        write!(
+            document,
+            r#"# Operation number {i}.
+query Query{i}($id: ID!, $first: Int = 10, $locale: String = "en") @cached(ttl: 60) {{
+  type{i}(id: $id) {{
+    ...Type{i}Fields
+    related(first: $first, orderBy: {{ field: CREATED_AT, direction: DESC }}) {{
+      id
+      displayName: name(locale: $locale)
+      ... on Node {{
+        id
+      }}
+    }}
+  }}
+}}
+
+fragment Type{i}Fields on Type{i} {{
+  id
+  name
+  status @include(if: true)
+  tags
+}}
+
+"#
+        )
+        .unwrap();
+    }
+    document
+}
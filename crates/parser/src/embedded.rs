@@ -0,0 +1,452 @@
+//! Extraction of GraphQL documents embedded in tagged templates of TypeScript and JavaScript sources.
+
+use std::{borrow::Cow, collections::HashSet, ops::Range, path::Path};
+
+use nitrogql_ast::OperationDocument;
+pub use nitrogql_utils::EmbeddedDocument;
+
+use crate::{
+    parser::{
+        document_start::{document_start_at, with_document_start},
+        parse_operation_document_recovering,
+    },
+    ParseError,
+};
+
+/// Extensions of source files that contain documents in tagged templates.
+const EMBEDDING_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+/// Keywords after which `/` starts a regular expression rather than a division.
+const KEYWORDS_BEFORE_EXPRESSION: &[&str] = &[
+    "return",
+    "typeof",
+    "instanceof",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "case",
+    "do",
+    "else",
+    "yield",
+    "await",
+];
+
+/// Returns true if given file is a TypeScript or JavaScript source.
+/// Documents in such files are embedded in tagged templates.
+pub fn is_embedding_source(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| EMBEDDING_EXTENSIONS.iter().any(|e| ext == *e))
+}
+
+/// Options for finding embedded documents.
+#[derive(Debug, Clone)]
+pub struct EmbeddedDocumentOptions {
+    /// Names of tags whose templates are GraphQL documents.
+    pub tag_names: Vec<String>,
+    /// Modules from which tags must be imported.
+    /// If empty, tags are recognized by name regardless of where they come from.
+    pub module_sources: Vec<String>,
+}
+
+impl Default for EmbeddedDocumentOptions {
+    fn default() -> Self {
+        EmbeddedDocumentOptions {
+            tag_names: vec!["gql".to_owned(), "graphql".to_owned()],
+            module_sources: vec![],
+        }
+    }
+}
+
+/// Finds GraphQL documents in tagged templates of a TypeScript or JavaScript source.
+pub fn extract_embedded_documents<'src>(
+    source: &'src str,
+    options: &EmbeddedDocumentOptions,
+) -> Vec<EmbeddedDocument<'src>> {
+    let mut scanner = Scanner {
+        source,
+        bytes: source.as_bytes(),
+        pos: 0,
+        regex_allowed: true,
+        templates: vec![],
+        imports: vec![],
+    };
+    scanner.scan_code(false);
+
+    let tags = if options.module_sources.is_empty() {
+        options.tag_names.iter().map(|name| name.as_str()).collect()
+    } else {
+        scanner
+            .imports
+            .iter()
+            .filter(|import| options.module_sources.iter().any(|m| m == import.module))
+            .flat_map(|import| import.bindings.iter())
+            .filter(|(imported, local)| {
+                let name = if *imported == "default" {
+                    local
+                } else {
+                    imported
+                };
+                options.tag_names.iter().any(|tag| tag == name)
+            })
+            .map(|(_, local)| *local)
+            .collect::<HashSet<_>>()
+    };
+
+    let mut templates = scanner.templates;
+    templates.retain(|template| tags.contains(template.tag));
+    // Templates nested in interpolations are found before the enclosing ones.
+    templates.sort_by_key(|template| template.content.start);
+    templates
+        .into_iter()
+        .map(|template| EmbeddedDocument {
+            source: mask_interpolations(source, &template),
+            start: document_start_at(source, template.content.start),
+        })
+        .collect()
+}
+
+/// Parses embedded documents of one file and merges them into one operation document.
+/// Like `parse_operation_document_recovering`, parts with syntax errors are skipped.
+pub fn parse_embedded_operation_documents<'src>(
+    documents: &'src [EmbeddedDocument<'_>],
+) -> (OperationDocument<'src>, Vec<ParseError>) {
+    let mut result = OperationDocument {
+        imports: vec![],
+        definitions: vec![],
+    };
+    let mut errors = vec![];
+    for document in documents {
+        let (parsed, parse_errors) = with_document_start(document.start, || {
+            parse_operation_document_recovering(&document.source)
+        });
+        result.imports.extend(parsed.imports);
+        result.definitions.extend(parsed.definitions);
+        errors.extend(parse_errors);
+    }
+    (result, errors)
+}
+
+struct TaggedTemplate<'src> {
+    tag: &'src str,
+    /// Byte range of the content between backquotes.
+    content: Range<usize>,
+    /// Byte ranges of `${...}` in the content.
+    interpolations: Vec<Range<usize>>,
+}
+
+struct Import<'src> {
+    module: &'src str,
+    /// Pairs of imported name and local name. Default imports have the imported name `default`.
+    bindings: Vec<(&'src str, &'src str)>,
+}
+
+/// Scanner that skips over comments, strings and regular expressions to find templates and imports.
+struct Scanner<'src> {
+    source: &'src str,
+    bytes: &'src [u8],
+    pos: usize,
+    /// Whether a `/` at the current position starts a regular expression.
+    regex_allowed: bool,
+    templates: Vec<TaggedTemplate<'src>>,
+    imports: Vec<Import<'src>>,
+}
+
+impl<'src> Scanner<'src> {
+    /// Scans code until the end of the source.
+    /// If `in_interpolation`, stops after the `}` that closes the current interpolation.
+    fn scan_code(&mut self, in_interpolation: bool) {
+        let mut depth = 0usize;
+        // Identifier just before the current position, which may be a tag.
+        let mut last_ident = None;
+        let mut after_dot = false;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            if byte.is_ascii_whitespace() || self.skip_comment() {
+                if byte.is_ascii_whitespace() {
+                    self.pos += 1;
+                }
+                continue;
+            }
+            let mut ident = None;
+            match byte {
+                b'/' if self.regex_allowed => {
+                    self.skip_regex();
+                    self.regex_allowed = false;
+                }
+                b'\'' | b'"' => {
+                    self.skip_string();
+                    self.regex_allowed = false;
+                }
+                b'`' => {
+                    self.scan_template(last_ident);
+                    self.regex_allowed = false;
+                }
+                b'{' => {
+                    depth += 1;
+                    self.pos += 1;
+                    self.regex_allowed = true;
+                }
+                b'}' => {
+                    self.pos += 1;
+                    if depth == 0 && in_interpolation {
+                        return;
+                    }
+                    depth = depth.saturating_sub(1);
+                    self.regex_allowed = true;
+                }
+                b')' | b']' => {
+                    self.pos += 1;
+                    self.regex_allowed = false;
+                }
+                b'0'..=b'9' => {
+                    self.read_while(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'_');
+                    self.regex_allowed = false;
+                }
+                _ if is_ident_byte(byte) => {
+                    let name = self.read_while(is_ident_byte);
+                    if name == "import" && !after_dot {
+                        if let Some(import) = self.parse_import() {
+                            self.imports.push(import);
+                        }
+                    }
+                    self.regex_allowed = KEYWORDS_BEFORE_EXPRESSION.contains(&name);
+                    ident = (!after_dot).then_some(name);
+                }
+                _ => {
+                    self.pos += 1;
+                    self.regex_allowed = true;
+                }
+            }
+            after_dot = byte == b'.';
+            last_ident = ident;
+        }
+    }
+
+    /// Scans a template starting at the current backquote.
+    fn scan_template(&mut self, tag: Option<&'src str>) {
+        self.pos += 1;
+        let start = self.pos;
+        let mut interpolations = vec![];
+        let end = loop {
+            match self.bytes.get(self.pos) {
+                None => break self.pos,
+                // An escape at the end of an incomplete source must not move past the end.
+                Some(b'\\') => self.pos = (self.pos + 2).min(self.bytes.len()),
+                Some(b'`') => {
+                    self.pos += 1;
+                    break self.pos - 1;
+                }
+                Some(b'$') if self.bytes.get(self.pos + 1) == Some(&b'{') => {
+                    let interpolation_start = self.pos;
+                    self.pos += 2;
+                    self.regex_allowed = true;
+                    self.scan_code(true);
+                    self.pos = self.pos.min(self.bytes.len());
+                    interpolations.push(interpolation_start..self.pos);
+                }
+                Some(_) => self.pos += 1,
+            }
+        };
+        if let Some(tag) = tag {
+            self.templates.push(TaggedTemplate {
+                tag,
+                content: start..end.min(self.bytes.len()),
+                interpolations,
+            });
+        }
+    }
+
+    /// Skips a comment at the current position. Returns false if there is none.
+    fn skip_comment(&mut self) -> bool {
+        let rest = &self.bytes[self.pos..];
+        if rest.starts_with(b"//") {
+            self.read_while(|b| b != b'\n');
+            true
+        } else if rest.starts_with(b"/*") {
+            self.pos = self.source[self.pos + 2..]
+                .find("*/")
+                .map_or(self.bytes.len(), |idx| self.pos + 2 + idx + 2);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_trivia(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace())
+            || self.skip_comment()
+        {
+            if self
+                .bytes
+                .get(self.pos)
+                .is_some_and(|b| b.is_ascii_whitespace())
+            {
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// Skips a string literal and returns its content.
+    fn skip_string(&mut self) -> &'src str {
+        let quote = self.bytes[self.pos];
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'\n' => break,
+                _ if byte == quote => {
+                    self.pos += 1;
+                    return &self.source[start..self.pos - 1];
+                }
+                _ => self.pos += 1,
+            }
+        }
+        self.pos = self.pos.min(self.bytes.len());
+        &self.source[start..self.pos]
+    }
+
+    fn skip_regex(&mut self) {
+        self.pos += 1;
+        let mut in_class = false;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 1,
+                b'[' => in_class = true,
+                b']' => in_class = false,
+                b'/' if !in_class => {
+                    self.pos += 1;
+                    break;
+                }
+                b'\n' => break,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        self.pos = self.pos.min(self.bytes.len());
+        // flags
+        self.read_while(is_ident_byte);
+    }
+
+    fn read_while(&mut self, pred: impl Fn(u8) -> bool) -> &'src str {
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| pred(*b)) {
+            self.pos += 1;
+        }
+        &self.source[start..self.pos]
+    }
+
+    fn read_ident(&mut self) -> Option<&'src str> {
+        self.skip_trivia();
+        let ident = self.read_while(is_ident_byte);
+        (!ident.is_empty()).then_some(ident)
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_trivia();
+        let matches = self.bytes.get(self.pos) == Some(&byte);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    /// Parses an import declaration after the `import` keyword.
+    /// Returns None and restores the position if it is not an import declaration with bindings.
+    fn parse_import(&mut self) -> Option<Import<'src>> {
+        let start = self.pos;
+        let import = self.parse_import_clause();
+        if import.is_none() {
+            self.pos = start;
+        }
+        import
+    }
+
+    fn parse_import_clause(&mut self) -> Option<Import<'src>> {
+        let mut bindings = vec![];
+        let mut ident = self.read_ident();
+        if ident == Some("type") {
+            // Type-only imports do not import tags.
+            return None;
+        }
+        if let Some(local) = ident.filter(|ident| *ident != "from") {
+            bindings.push(("default", local));
+            if !self.eat(b',') {
+                ident = self.read_ident();
+            } else {
+                ident = None;
+            }
+        }
+        if ident.is_none() {
+            if self.eat(b'{') {
+                while !self.eat(b'}') {
+                    self.skip_trivia();
+                    let imported = match self.bytes.get(self.pos) {
+                        Some(b'\'' | b'"') => self.skip_string(),
+                        _ => self.read_ident()?,
+                    };
+                    let local = match self.read_ident() {
+                        Some("as") => self.read_ident()?,
+                        Some(_) => return None,
+                        None => imported,
+                    };
+                    bindings.push((imported, local));
+                    if !self.eat(b',') {
+                        if !self.eat(b'}') {
+                            return None;
+                        }
+                        break;
+                    }
+                }
+            } else if self.eat(b'*') {
+                // Namespace imports cannot be used as tags directly.
+                self.read_ident().filter(|ident| *ident == "as")?;
+                self.read_ident()?;
+            }
+            ident = self.read_ident();
+        }
+        if ident != Some("from") {
+            return None;
+        }
+        self.skip_trivia();
+        if !matches!(self.bytes.get(self.pos), Some(b'\'' | b'"')) {
+            return None;
+        }
+        let module = self.skip_string();
+        Some(Import { module, bindings })
+    }
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || !byte.is_ascii()
+}
+
+/// Returns the content of a template with interpolations replaced by spaces.
+/// Line breaks in interpolations are kept so that lines in the file are not shifted.
+fn mask_interpolations<'src>(source: &'src str, template: &TaggedTemplate) -> Cow<'src, str> {
+    let content = &source[template.content.clone()];
+    if template.interpolations.is_empty() {
+        return Cow::Borrowed(content);
+    }
+    let mut masked = content.as_bytes().to_vec();
+    for range in template.interpolations.iter() {
+        // Interpolations of an unterminated template may not fit in its content.
+        let start = range
+            .start
+            .clamp(template.content.start, template.content.end);
+        let end = range.end.clamp(start, template.content.end);
+        for byte in masked[start - template.content.start..end - template.content.start].iter_mut()
+        {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    // Interpolations consist of whole characters, so replacing their bytes keeps the string valid.
+    Cow::Owned(String::from_utf8(masked).unwrap())
+}
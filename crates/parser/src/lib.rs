@@ -1,4 +1,15 @@
+mod embedded;
 mod parser;
 mod tests;
 
-pub use parser::{parse_operation_document, parse_type_system_document, ParseError};
+pub use embedded::{
+    extract_embedded_documents, is_embedding_source, parse_embedded_operation_documents,
+    EmbeddedDocument, EmbeddedDocumentOptions,
+};
+#[cfg(feature = "pest")]
+pub use parser::pest_parser;
+pub use parser::{
+    parse_document, parse_operation_document, parse_operation_document_at,
+    parse_operation_document_recovering, parse_type_system_document,
+    parse_type_system_document_recovering, parse_value, ParseError,
+};
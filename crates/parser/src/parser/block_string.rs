@@ -0,0 +1,41 @@
+/// Computes the value of a block string from its raw content between the triple quotes,
+/// following `BlockStringValue()` of the GraphQL specification.
+/// Common indentation and leading and trailing blank lines are removed.
+pub fn block_string_value(raw: &str) -> String {
+    let raw = raw.replace("\\\"\"\"", "\"\"\"");
+    let lines = raw
+        .split("\r\n")
+        .flat_map(|line| line.split(['\n', '\r']))
+        .collect::<Vec<_>>();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter_map(|line| {
+            let indent = leading_whitespace_len(line);
+            (indent < line.len()).then_some(indent)
+        })
+        .min()
+        .unwrap_or(0);
+    let lines = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            if idx == 0 {
+                line
+            } else {
+                &line[common_indent.min(line.len())..]
+            }
+        })
+        .collect::<Vec<_>>();
+    let is_blank = |line: &&str| leading_whitespace_len(line) == line.len();
+    let start = lines.iter().position(|line| !is_blank(line));
+    let end = lines.iter().rposition(|line| !is_blank(line));
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn leading_whitespace_len(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
@@ -0,0 +1,272 @@
+//! Hand-written recursive descent parser that builds AST directly from tokens.
+//!
+//! Positions of nodes are the same as those produced by the pest grammar:
+//! a node spans from its first token to its last token,
+//! except that a node also spans the ignored tokens after it when it ends with an omitted optional part
+//! or a repetition that matched only once, such as `A ~ ("|" ~ A)*` or directives.
+
+use nitrogql_ast::{
+    base::{Ident, Keyword, Pos},
+    document::{Definition, Document},
+    operation::OperationDocument,
+    type_system::TypeSystemOrExtensionDocument,
+    value::Value,
+};
+
+use self::recover::Recovery;
+use super::{
+    document_start::position_in_file,
+    lexer::{Lexer, Location, Token, TokenKind},
+    ParseError,
+};
+
+mod operation;
+mod recover;
+mod type_system;
+mod value;
+
+type ParseResult<T> = Result<T, ParseError>;
+
+pub struct Parser<'src> {
+    lexer: Lexer<'src>,
+    /// Token read ahead of the last consumed token.
+    peeked: Option<Token<'src>>,
+    /// End of the last consumed token.
+    last_end: Location,
+    /// Set when the parser recovers from syntax errors instead of stopping at the first one.
+    recovery: Option<Recovery>,
+}
+
+impl<'src> Parser<'src> {
+    pub fn new(source: &'src str) -> Self {
+        Parser {
+            lexer: Lexer::new(source),
+            peeked: None,
+            last_end: (0, 0, 0),
+            recovery: None,
+        }
+    }
+
+    /// Parses an executable document, which consists of operations, fragments and import comments.
+    pub fn parse_operation_document(mut self) -> ParseResult<OperationDocument<'src>> {
+        self.operation_document()
+    }
+
+    fn operation_document(&mut self) -> ParseResult<OperationDocument<'src>> {
+        let mut definitions = vec![];
+        loop {
+            // Import comments are recognized only between definitions.
+            self.lexer.collect_imports = true;
+            let token = self.peek();
+            self.lexer.collect_imports = false;
+            let token = match token {
+                Ok(token) => token,
+                Err(error) => {
+                    self.skip_definition(error, self.last_end.2)?;
+                    continue;
+                }
+            };
+            if token.kind == TokenKind::EndOfInput && (!definitions.is_empty() || self.has_errors())
+            {
+                break;
+            }
+            match self.parse_executable_definition() {
+                Ok(definition) => definitions.push(definition),
+                Err(error) => self.skip_definition(error, token.start.2)?,
+            }
+        }
+        Ok(OperationDocument {
+            imports: self.lexer.take_imports(),
+            definitions,
+        })
+    }
+
+    /// Parses a document of type system definitions and extensions.
+    pub fn parse_type_system_document(
+        mut self,
+    ) -> ParseResult<TypeSystemOrExtensionDocument<'src>> {
+        self.type_system_document()
+    }
+
+    fn type_system_document(&mut self) -> ParseResult<TypeSystemOrExtensionDocument<'src>> {
+        let mut definitions = vec![];
+        loop {
+            let token = match self.peek() {
+                Ok(token) => token,
+                Err(error) => {
+                    self.skip_definition(error, self.last_end.2)?;
+                    continue;
+                }
+            };
+            if token.kind == TokenKind::EndOfInput && (!definitions.is_empty() || self.has_errors())
+            {
+                break;
+            }
+            match self.parse_type_system_definition_or_extension() {
+                Ok(definition) => definitions.push(definition),
+                Err(error) => self.skip_definition(error, token.start.2)?,
+            }
+        }
+        Ok(TypeSystemOrExtensionDocument { definitions })
+    }
+
+    /// Parses a document that may contain both executable definitions and type system definitions.
+    pub fn parse_document(mut self) -> ParseResult<Document<'src>> {
+        let mut definitions = vec![];
+        loop {
+            self.lexer.collect_imports = true;
+            let token = self.peek()?;
+            self.lexer.collect_imports = false;
+            let definition = match token.kind {
+                TokenKind::EndOfInput if !definitions.is_empty() => break,
+                TokenKind::BraceL => {
+                    Definition::ExecutableDefinition(self.parse_executable_definition()?)
+                }
+                TokenKind::Name
+                    if ["query", "mutation", "subscription", "fragment"].contains(&token.text) =>
+                {
+                    Definition::ExecutableDefinition(self.parse_executable_definition()?)
+                }
+                TokenKind::Name | TokenKind::StringValue | TokenKind::BlockStringValue => {
+                    Definition::TypeSystemDefinitionOrExtension(
+                        self.parse_type_system_definition_or_extension()?,
+                    )
+                }
+                _ => return Err(self.unexpected("Definition")),
+            };
+            definitions.push(definition);
+        }
+        Ok(Document {
+            imports: self.lexer.take_imports(),
+            definitions,
+        })
+    }
+
+    /// Parses a document that consists of one value.
+    pub fn parse_value_document(mut self) -> ParseResult<Value<'src>> {
+        let value = self.parse_value()?;
+        self.expect(TokenKind::EndOfInput, "end of input")?;
+        Ok(value)
+    }
+
+    /// Returns the next token without consuming it.
+    fn peek(&mut self) -> ParseResult<Token<'src>> {
+        match self.peeked {
+            Some(token) => Ok(token),
+            None => {
+                let token = self.lexer.next_token()?;
+                self.peeked = Some(token);
+                Ok(token)
+            }
+        }
+    }
+
+    /// Returns whether the next token is of given kind.
+    fn peek_is(&mut self, kind: TokenKind) -> ParseResult<bool> {
+        Ok(self.peek()?.kind == kind)
+    }
+
+    /// Returns whether the next token is given keyword.
+    fn peek_keyword(&mut self, keyword: &str) -> ParseResult<bool> {
+        Ok(self.peek()?.is_keyword(keyword))
+    }
+
+    /// Consumes the next token.
+    fn next(&mut self) -> ParseResult<Token<'src>> {
+        let token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.lexer.next_token()?,
+        };
+        self.last_end = token.end;
+        Ok(token)
+    }
+
+    /// Consumes the next token if it is of given kind.
+    fn eat(&mut self, kind: TokenKind) -> ParseResult<Option<Token<'src>>> {
+        if self.peek_is(kind)? {
+            self.next().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Consumes the next token if it is given keyword.
+    fn eat_keyword(&mut self, keyword: &str) -> ParseResult<Option<Keyword<'src>>> {
+        if self.peek_keyword(keyword)? {
+            let token = self.next()?;
+            Ok(Some(self.keyword(token)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Consumes the next token, which must be of given kind.
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> ParseResult<Token<'src>> {
+        let token = self.peek()?;
+        if token.kind != kind {
+            return Err(self.unexpected(expected));
+        }
+        self.next()
+    }
+
+    /// Consumes the next token, which must be given keyword.
+    fn expect_keyword(&mut self, keyword: &str) -> ParseResult<Keyword<'src>> {
+        match self.eat_keyword(keyword)? {
+            Some(keyword) => Ok(keyword),
+            None => Err(self.unexpected(&format!("`{keyword}`"))),
+        }
+    }
+
+    /// Consumes a name.
+    fn expect_name(&mut self) -> ParseResult<Ident<'src>> {
+        let token = self.expect(TokenKind::Name, "Name")?;
+        Ok(self.ident(token))
+    }
+
+    /// Makes an error for the next token which is not what is expected.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let token = self.peeked.expect("unexpected() is called without peeking");
+        ParseError {
+            position: self.pos(token.start, token.end),
+            message: format!("expected {expected}, found {}", token.describe()),
+        }
+    }
+
+    /// Location at which the next token begins.
+    /// Used as the end of a node that ends with an omitted optional part.
+    fn next_start(&mut self) -> ParseResult<Location> {
+        Ok(self.peek()?.start)
+    }
+
+    /// End of a node that ends with a repetition of `count` items.
+    fn end_with_repetition(&mut self, count: usize) -> ParseResult<Location> {
+        if count > 1 {
+            Ok(self.last_end)
+        } else {
+            self.next_start()
+        }
+    }
+
+    fn pos(&self, start: Location, end: Location) -> Pos {
+        Pos::new_span(position_in_file(start), position_in_file(end))
+    }
+
+    /// Pos that spans from `start` to the end of the last consumed token.
+    fn pos_from(&self, start: Location) -> Pos {
+        self.pos(start, self.last_end)
+    }
+
+    fn ident(&self, token: Token<'src>) -> Ident<'src> {
+        Ident {
+            position: self.pos(token.start, token.end),
+            name: token.text,
+        }
+    }
+
+    fn keyword(&self, token: Token<'src>) -> Keyword<'src> {
+        Keyword {
+            position: self.pos(token.start, token.end),
+            name: token.text,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use nitrogql_ast::{
+    base::Ident,
+    operation::{ExecutableDefinition, FragmentDefinition, OperationDefinition, OperationType},
+    selection_set::{Field, FragmentSpread, InlineFragment, Selection, SelectionSet},
+    variable::{VariableDefinition, VariablesDefinition},
+};
+
+use super::{ParseResult, Parser};
+use crate::parser::lexer::TokenKind;
+
+impl<'src> Parser<'src> {
+    /// Parses an OperationDefinition or a FragmentDefinition.
+    pub(super) fn parse_executable_definition(
+        &mut self,
+    ) -> ParseResult<ExecutableDefinition<'src>> {
+        let token = self.peek()?;
+        if token.is_keyword("fragment") {
+            return self
+                .parse_fragment_definition()
+                .map(ExecutableDefinition::FragmentDefinition);
+        }
+        if token.kind == TokenKind::BraceL {
+            // shorthand for an anonymous query
+            let selection_set = self.parse_selection_set()?;
+            return Ok(ExecutableDefinition::OperationDefinition(
+                OperationDefinition {
+                    position: selection_set.position,
+                    operation_type: OperationType::Query,
+                    shorthand: true,
+                    name: None,
+                    variables_definition: None,
+                    directives: vec![],
+                    selection_set,
+                },
+            ));
+        }
+        let Some(operation_type) = self.parse_operation_type()? else {
+            return Err(self.unexpected("OperationDefinition or FragmentDefinition"));
+        };
+        let name = match self.peek()?.kind {
+            TokenKind::Name => Some(self.expect_name()?),
+            _ => None,
+        };
+        let variables_definition = self.parse_variables_definition()?;
+        let directives = self.parse_directives()?;
+        let selection_set = self.parse_selection_set()?;
+        Ok(ExecutableDefinition::OperationDefinition(
+            OperationDefinition {
+                position: self.pos_from(token.start),
+                operation_type,
+                shorthand: false,
+                name,
+                variables_definition,
+                directives,
+                selection_set,
+            },
+        ))
+    }
+
+    /// Parses an OperationType if the next token is one.
+    pub(super) fn parse_operation_type(&mut self) -> ParseResult<Option<OperationType>> {
+        let operation_type = match self.peek()? {
+            token if token.is_keyword("query") => OperationType::Query,
+            token if token.is_keyword("mutation") => OperationType::Mutation,
+            token if token.is_keyword("subscription") => OperationType::Subscription,
+            _ => return Ok(None),
+        };
+        self.next()?;
+        Ok(Some(operation_type))
+    }
+
+    fn parse_fragment_definition(&mut self) -> ParseResult<FragmentDefinition<'src>> {
+        let start = self.peek()?.start;
+        self.expect_keyword("fragment")?;
+        let name = self.parse_fragment_name()?;
+        let type_condition = self.parse_type_condition()?;
+        let directives = self.parse_directives()?;
+        let selection_set = self.parse_selection_set()?;
+        Ok(FragmentDefinition {
+            position: self.pos_from(start),
+            name,
+            type_condition,
+            directives,
+            selection_set,
+        })
+    }
+
+    /// Parses a Name other than `on`.
+    fn parse_fragment_name(&mut self) -> ParseResult<Ident<'src>> {
+        if self.peek_keyword("on")? {
+            return Err(self.unexpected("FragmentName"));
+        }
+        self.expect_name()
+    }
+
+    /// Parses `on` followed by a type name.
+    fn parse_type_condition(&mut self) -> ParseResult<Ident<'src>> {
+        self.expect_keyword("on")?;
+        self.expect_name()
+    }
+
+    fn parse_variables_definition(&mut self) -> ParseResult<Option<VariablesDefinition<'src>>> {
+        let Some(open) = self.eat(TokenKind::ParenL)? else {
+            return Ok(None);
+        };
+        let mut definitions = vec![];
+        loop {
+            definitions.push(self.parse_variable_definition()?);
+            if self.eat(TokenKind::ParenR)?.is_some() {
+                break;
+            }
+        }
+        Ok(Some(VariablesDefinition {
+            position: self.pos_from(open.start),
+            definitions,
+        }))
+    }
+
+    fn parse_variable_definition(&mut self) -> ParseResult<VariableDefinition<'src>> {
+        let start = self.peek()?.start;
+        let name = self.parse_variable()?;
+        self.expect(TokenKind::Colon, "`:`")?;
+        let r#type = self.parse_type()?;
+        let default_value = match self.eat(TokenKind::Equals)? {
+            Some(_) => Some(self.parse_value()?),
+            None => None,
+        };
+        let directives = self.parse_directives()?;
+        let end = self.end_with_directives(&directives)?;
+        Ok(VariableDefinition {
+            pos: self.pos(start, end),
+            name,
+            r#type,
+            default_value,
+            directives,
+        })
+    }
+
+    /// Parses a SelectionSet enclosed in braces.
+    pub(super) fn parse_selection_set(&mut self) -> ParseResult<SelectionSet<'src>> {
+        let open = self.expect(TokenKind::BraceL, "SelectionSet")?;
+        let selections = self.parse_block_items(|parser, first| {
+            parser.parse_selection(if first {
+                "Selection"
+            } else {
+                "Selection or `}`"
+            })
+        })?;
+        Ok(SelectionSet {
+            position: self.pos_from(open.start),
+            selections,
+        })
+    }
+
+    fn parse_selection(&mut self, expected: &str) -> ParseResult<Selection<'src>> {
+        let token = self.peek()?;
+        match token.kind {
+            TokenKind::Spread => {}
+            TokenKind::Name => return self.parse_field().map(Selection::Field),
+            _ => return Err(self.unexpected(expected)),
+        }
+        self.next()?;
+        let next = self.peek()?;
+        if next.kind == TokenKind::Name && !next.is_keyword("on") {
+            let fragment_name = self.expect_name()?;
+            let directives = self.parse_directives()?;
+            let end = self.end_with_directives(&directives)?;
+            return Ok(Selection::FragmentSpread(FragmentSpread {
+                position: self.pos(token.start, end),
+                fragment_name,
+                directives,
+            }));
+        }
+        let type_condition = if next.is_keyword("on") {
+            Some(self.parse_type_condition()?)
+        } else {
+            None
+        };
+        let directives = self.parse_directives()?;
+        let selection_set = self.parse_selection_set()?;
+        Ok(Selection::InlineFragment(InlineFragment {
+            position: self.pos_from(token.start),
+            type_condition,
+            directives,
+            selection_set,
+        }))
+    }
+
+    fn parse_field(&mut self) -> ParseResult<Field<'src>> {
+        let mut name = self.expect_name()?;
+        let mut alias = None;
+        if self.eat(TokenKind::Colon)?.is_some() {
+            alias = Some(name);
+            name = self.expect_name()?;
+        }
+        let arguments = self.parse_arguments()?;
+        let directives = self.parse_directives()?;
+        let selection_set = if self.peek_is(TokenKind::BraceL)? {
+            Some(self.parse_selection_set()?)
+        } else {
+            None
+        };
+        Ok(Field {
+            alias,
+            name,
+            arguments,
+            directives,
+            selection_set,
+        })
+    }
+}
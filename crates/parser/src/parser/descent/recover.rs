@@ -0,0 +1,240 @@
+//! Recovery from syntax errors.
+//!
+//! A recovering parser records an error and skips tokens instead of stopping:
+//! an error in a block such as a SelectionSet skips to the `}` that closes the block,
+//! keeping the items parsed so far,
+//! and an error elsewhere in a definition skips to the next top-level definition.
+//! A keyword at the beginning of a line is taken as the beginning of a top-level definition
+//! so that a missing `}` does not hide the definitions after it.
+
+use nitrogql_ast::{operation::OperationDocument, type_system::TypeSystemOrExtensionDocument};
+
+use super::{ParseResult, Parser};
+use crate::parser::{
+    lexer::{Token, TokenKind},
+    ParseError,
+};
+
+/// Keywords that begin a top-level definition of an operation document.
+const OPERATION_KEYWORDS: &[&str] = &["query", "mutation", "subscription", "fragment"];
+
+/// Keywords that begin a top-level definition of a type system document.
+const TYPE_SYSTEM_KEYWORDS: &[&str] = &[
+    "schema",
+    "scalar",
+    "type",
+    "interface",
+    "union",
+    "enum",
+    "input",
+    "directive",
+    "extend",
+];
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum DocumentKind {
+    Operation,
+    TypeSystem,
+}
+
+impl DocumentKind {
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            DocumentKind::Operation => OPERATION_KEYWORDS,
+            DocumentKind::TypeSystem => TYPE_SYSTEM_KEYWORDS,
+        }
+    }
+}
+
+/// State of a parser that recovers from syntax errors.
+pub(super) struct Recovery {
+    kind: DocumentKind,
+    errors: Vec<ParseError>,
+}
+
+impl<'src> Parser<'src> {
+    /// Parses an operation document, collecting all syntax errors.
+    pub fn parse_operation_document_recovering(
+        mut self,
+    ) -> (OperationDocument<'src>, Vec<ParseError>) {
+        self.recovery = Some(Recovery {
+            kind: DocumentKind::Operation,
+            errors: vec![],
+        });
+        let document = self.operation_document();
+        self.finish_recovery(document, || OperationDocument {
+            imports: vec![],
+            definitions: vec![],
+        })
+    }
+
+    /// Parses a type system document, collecting all syntax errors.
+    pub fn parse_type_system_document_recovering(
+        mut self,
+    ) -> (TypeSystemOrExtensionDocument<'src>, Vec<ParseError>) {
+        self.recovery = Some(Recovery {
+            kind: DocumentKind::TypeSystem,
+            errors: vec![],
+        });
+        let document = self.type_system_document();
+        self.finish_recovery(document, || TypeSystemOrExtensionDocument {
+            definitions: vec![],
+        })
+    }
+
+    fn finish_recovery<D>(
+        self,
+        document: ParseResult<D>,
+        empty: impl FnOnce() -> D,
+    ) -> (D, Vec<ParseError>) {
+        let mut errors = self.recovery.map(|r| r.errors).unwrap_or_default();
+        match document {
+            Ok(document) => (document, errors),
+            Err(error) => {
+                errors.push(error);
+                (empty(), errors)
+            }
+        }
+    }
+
+    /// Returns whether any error has been recorded.
+    pub(super) fn has_errors(&self) -> bool {
+        self.recovery
+            .as_ref()
+            .is_some_and(|recovery| !recovery.errors.is_empty())
+    }
+
+    /// Records given error if recovering, or returns it otherwise.
+    fn record(&mut self, error: ParseError) -> ParseResult<()> {
+        let Some(recovery) = &mut self.recovery else {
+            return Err(error);
+        };
+        // Enclosing blocks that lack `}` report the same error.
+        if recovery.errors.last().map(|last| last.position) != Some(error.position) {
+            recovery.errors.push(error);
+        }
+        Ok(())
+    }
+
+    /// Records an error in a top-level definition that begins at byte offset `start`,
+    /// and skips tokens up to the next definition.
+    pub(super) fn skip_definition(&mut self, error: ParseError, start: usize) -> ParseResult<()> {
+        self.record(error)?;
+        let keywords = self
+            .recovery
+            .as_ref()
+            .map_or(&[][..], |r| r.kind.keywords());
+        let mut depth = 0usize;
+        loop {
+            // The lexer has skipped an invalid token on error.
+            let Ok(token) = self.peek() else {
+                continue;
+            };
+            if token.kind == TokenKind::EndOfInput {
+                return Ok(());
+            }
+            let at_keyword = token.kind == TokenKind::Name && keywords.contains(&token.text);
+            if token.start.2 > start && (depth == 0 && at_keyword || self.begins_definition(token))
+            {
+                return Ok(());
+            }
+            match token.kind {
+                TokenKind::BraceL | TokenKind::ParenL | TokenKind::BracketL => depth += 1,
+                TokenKind::BraceR | TokenKind::ParenR | TokenKind::BracketR => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+            self.next()?;
+        }
+    }
+
+    /// Records an error in a block and skips tokens to the `}` that closes the block,
+    /// or to the next top-level definition if the block is not closed.
+    fn skip_block(&mut self, error: ParseError) -> ParseResult<()> {
+        self.record(error)?;
+        let mut depth = 0usize;
+        loop {
+            let Ok(token) = self.peek() else {
+                continue;
+            };
+            if token.kind == TokenKind::EndOfInput || self.begins_definition(token) {
+                return Ok(());
+            }
+            match token.kind {
+                TokenKind::BraceR if depth == 0 => {
+                    self.next()?;
+                    return Ok(());
+                }
+                TokenKind::BraceL | TokenKind::ParenL | TokenKind::BracketL => depth += 1,
+                TokenKind::BraceR | TokenKind::ParenR | TokenKind::BracketR => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+            self.next()?;
+        }
+    }
+
+    /// Returns whether given token, which has just been peeked,
+    /// begins a top-level definition at the beginning of a line.
+    /// Always false unless recovering.
+    fn begins_definition(&self, token: Token<'src>) -> bool {
+        let Some(recovery) = &self.recovery else {
+            return false;
+        };
+        if token.start.1 != 0 {
+            return false;
+        }
+        match token.kind {
+            // A keyword followed by `:` or `(` is a field or an argument.
+            TokenKind::Name => {
+                recovery.kind.keywords().contains(&token.text)
+                    && !matches!(self.lexer.peek_byte(), Some(b':' | b'('))
+            }
+            // Anonymous operation
+            TokenKind::BraceL => recovery.kind == DocumentKind::Operation,
+            // Description
+            TokenKind::StringValue | TokenKind::BlockStringValue => {
+                recovery.kind == DocumentKind::TypeSystem
+            }
+            _ => false,
+        }
+    }
+
+    /// Parses items of a block until `}`, after `{` has been consumed.
+    /// `parse_item` is given whether the item is the first one, which is required.
+    /// When recovering, an error in an item skips the rest of the block and keeps the items before it.
+    pub(super) fn parse_block_items<T>(
+        &mut self,
+        mut parse_item: impl FnMut(&mut Self, bool) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        let mut items = vec![];
+        loop {
+            match self.parse_block_item(items.is_empty(), &mut parse_item) {
+                Ok(Some(item)) => items.push(item),
+                Ok(None) => return Ok(items),
+                Err(error) => {
+                    self.skip_block(error)?;
+                    return Ok(items);
+                }
+            }
+        }
+    }
+
+    /// Parses an item of a block, or returns None at the end of the block.
+    fn parse_block_item<T>(
+        &mut self,
+        first: bool,
+        parse_item: &mut impl FnMut(&mut Self, bool) -> ParseResult<T>,
+    ) -> ParseResult<Option<T>> {
+        if !first && self.eat(TokenKind::BraceR)?.is_some() {
+            return Ok(None);
+        }
+        let token = self.peek()?;
+        if self.begins_definition(token) {
+            return Err(self.unexpected("`}`"));
+        }
+        parse_item(self, first).map(Some)
+    }
+}
@@ -0,0 +1,484 @@
+use nitrogql_ast::{
+    base::{Ident, Keyword, Pos},
+    operation::OperationType,
+    type_system::{
+        ArgumentsDefinition, DirectiveDefinition, EnumTypeDefinition, EnumTypeExtension,
+        EnumValueDefinition, FieldDefinition, InputObjectTypeDefinition, InputObjectTypeExtension,
+        InputValueDefinition, InterfaceTypeDefinition, InterfaceTypeExtension,
+        ObjectTypeDefinition, ObjectTypeExtension, ScalarTypeDefinition, ScalarTypeExtension,
+        SchemaDefinition, SchemaExtension, TypeDefinition, TypeExtension,
+        TypeSystemDefinitionOrExtension, UnionTypeDefinition, UnionTypeExtension,
+    },
+    value::StringValue,
+};
+
+use super::{ParseResult, Parser};
+use crate::parser::lexer::{Location, TokenKind};
+
+const DIRECTIVE_LOCATIONS: &[&str] = &[
+    // ExecutableDirectiveLocation
+    "QUERY",
+    "MUTATION",
+    "SUBSCRIPTION",
+    "FIELD",
+    "FRAGMENT_DEFINITION",
+    "FRAGMENT_SPREAD",
+    "INLINE_FRAGMENT",
+    "VARIABLE_DEFINITION",
+    // TypeSystemDirectiveLocation
+    "SCHEMA",
+    "SCALAR",
+    "OBJECT",
+    "FIELD_DEFINITION",
+    "ARGUMENT_DEFINITION",
+    "INTERFACE",
+    "UNION",
+    "ENUM",
+    "ENUM_VALUE",
+    "INPUT_OBJECT",
+    "INPUT_FIELD_DEFINITION",
+];
+
+impl<'src> Parser<'src> {
+    /// Parses a TypeSystemDefinition or a TypeSystemExtension.
+    pub(super) fn parse_type_system_definition_or_extension(
+        &mut self,
+    ) -> ParseResult<TypeSystemDefinitionOrExtension<'src>> {
+        let start = self.peek()?.start;
+        let description = self.parse_description()?;
+        let token = self.peek()?;
+        let definition = match token.text {
+            _ if token.kind != TokenKind::Name => None,
+            "schema" => Some(TypeSystemDefinitionOrExtension::SchemaDefinition(
+                self.parse_schema_definition(start, description)?,
+            )),
+            "directive" => Some(TypeSystemDefinitionOrExtension::DirectiveDefinition(
+                self.parse_directive_definition(description)?,
+            )),
+            "extend" if description.is_none() => Some(self.parse_type_system_extension()?),
+            _ => self
+                .parse_type_definition(description)?
+                .map(TypeSystemDefinitionOrExtension::TypeDefinition),
+        };
+        definition.ok_or_else(|| self.unexpected("TypeSystemDefinition or TypeSystemExtension"))
+    }
+
+    /// Parses a Description if the next token is a string.
+    fn parse_description(&mut self) -> ParseResult<Option<StringValue>> {
+        match self.peek()?.kind {
+            TokenKind::StringValue | TokenKind::BlockStringValue => {
+                self.parse_string_value().map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_schema_definition(
+        &mut self,
+        start: Location,
+        description: Option<StringValue>,
+    ) -> ParseResult<SchemaDefinition<'src>> {
+        self.expect_keyword("schema")?;
+        let directives = self.parse_directives()?;
+        let definitions = self.parse_root_operation_type_definitions()?;
+        Ok(SchemaDefinition {
+            description,
+            position: self.pos_from(start),
+            directives,
+            definitions,
+        })
+    }
+
+    fn parse_root_operation_type_definitions(
+        &mut self,
+    ) -> ParseResult<Vec<(OperationType, Ident<'src>)>> {
+        self.expect(TokenKind::BraceL, "RootOperationTypeDefinitions")?;
+        self.parse_block_items(|parser, _| {
+            let Some(operation_type) = parser.parse_operation_type()? else {
+                return Err(parser.unexpected("OperationType"));
+            };
+            parser.expect(TokenKind::Colon, "`:`")?;
+            Ok((operation_type, parser.expect_name()?))
+        })
+    }
+
+    fn parse_directive_definition(
+        &mut self,
+        description: Option<StringValue>,
+    ) -> ParseResult<DirectiveDefinition<'src>> {
+        let start = self.peek()?.start;
+        let directive_keyword = self.expect_keyword("directive")?;
+        self.expect(TokenKind::At, "`@`")?;
+        let name = self.expect_name()?;
+        let arguments = self.parse_arguments_definition()?;
+        let repeatable = self.eat_keyword("repeatable")?.map(|keyword| Ident {
+            position: keyword.position,
+            name: keyword.name,
+        });
+        self.expect_keyword("on")?;
+        self.eat(TokenKind::Pipe)?;
+        let mut locations = vec![];
+        loop {
+            if !DIRECTIVE_LOCATIONS.contains(&self.peek()?.text) {
+                return Err(self.unexpected("DirectiveLocation"));
+            }
+            locations.push(self.expect_name()?);
+            if self.eat(TokenKind::Pipe)?.is_none() {
+                break;
+            }
+        }
+        let end = self.end_with_repetition(locations.len())?;
+        Ok(DirectiveDefinition {
+            description,
+            position: self.pos(start, end),
+            name,
+            arguments,
+            repeatable,
+            locations,
+            directive_keyword,
+        })
+    }
+
+    /// Parses a TypeDefinition if the next token is a keyword that begins one.
+    fn parse_type_definition(
+        &mut self,
+        description: Option<StringValue>,
+    ) -> ParseResult<Option<TypeDefinition<'src>>> {
+        let token = self.peek()?;
+        let definition = match token.text {
+            "scalar" => {
+                let (keyword, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let end = self.end_with_directives(&directives)?;
+                TypeDefinition::Scalar(ScalarTypeDefinition {
+                    description,
+                    position: self.pos(token.start, end),
+                    name,
+                    directives,
+                    scalar_keyword: keyword,
+                })
+            }
+            "type" => {
+                let (keyword, name) = self.parse_keyword_and_name()?;
+                let implements = self.parse_implements_interfaces()?;
+                let directives = self.parse_directives()?;
+                let fields = self.parse_fields_definition()?;
+                TypeDefinition::Object(ObjectTypeDefinition {
+                    description,
+                    position: self.pos_ending_with(token.start, &fields)?,
+                    name,
+                    implements,
+                    directives,
+                    fields: fields.unwrap_or_default(),
+                    type_keyword: keyword,
+                })
+            }
+            "interface" => {
+                let (keyword, name) = self.parse_keyword_and_name()?;
+                let implements = self.parse_implements_interfaces()?;
+                let directives = self.parse_directives()?;
+                let fields = self.parse_fields_definition()?;
+                TypeDefinition::Interface(InterfaceTypeDefinition {
+                    description,
+                    position: self.pos_ending_with(token.start, &fields)?,
+                    name,
+                    implements,
+                    directives,
+                    fields: fields.unwrap_or_default(),
+                    interface_keyword: keyword,
+                })
+            }
+            "union" => {
+                let (keyword, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let members = match self.eat(TokenKind::Equals)? {
+                    Some(_)
+                        if self.peek_is(TokenKind::Pipe)? || self.peek_is(TokenKind::Name)? =>
+                    {
+                        self.parse_union_member_types()?
+                    }
+                    _ => vec![],
+                };
+                let end = self.end_with_repetition(members.len())?;
+                TypeDefinition::Union(UnionTypeDefinition {
+                    description,
+                    position: self.pos(token.start, end),
+                    name,
+                    directives,
+                    members,
+                    union_keyword: keyword,
+                })
+            }
+            "enum" => {
+                let (keyword, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let values = self.parse_enum_values_definition()?;
+                TypeDefinition::Enum(EnumTypeDefinition {
+                    description,
+                    position: self.pos_ending_with(token.start, &values)?,
+                    name,
+                    directives,
+                    values: values.unwrap_or_default(),
+                    enum_keyword: keyword,
+                })
+            }
+            "input" => {
+                let (keyword, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let fields = self.parse_input_fields_definition()?;
+                TypeDefinition::InputObject(InputObjectTypeDefinition {
+                    description,
+                    position: self.pos_ending_with(token.start, &fields)?,
+                    name,
+                    directives,
+                    fields: fields.unwrap_or_default(),
+                    input_keyword: keyword,
+                })
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(definition))
+    }
+
+    fn parse_type_system_extension(
+        &mut self,
+    ) -> ParseResult<TypeSystemDefinitionOrExtension<'src>> {
+        let start = self.peek()?.start;
+        self.expect_keyword("extend")?;
+        let token = self.peek()?;
+        if token.is_keyword("schema") {
+            self.next()?;
+            let directives = self.parse_directives()?;
+            let definitions = if self.peek_is(TokenKind::BraceL)? {
+                Some(self.parse_root_operation_type_definitions()?)
+            } else if directives.is_empty() {
+                return Err(self.unexpected("Directives or RootOperationTypeDefinitions"));
+            } else {
+                None
+            };
+            return Ok(TypeSystemDefinitionOrExtension::SchemaExtension(
+                SchemaExtension {
+                    position: self.pos_ending_with(start, &definitions)?,
+                    directives,
+                    definitions: definitions.unwrap_or_default(),
+                },
+            ));
+        }
+        let extension = match token.text {
+            "scalar" => {
+                let (_, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let end = self.end_with_directives(&directives)?;
+                TypeExtension::Scalar(ScalarTypeExtension {
+                    position: self.pos(start, end),
+                    name,
+                    directives,
+                })
+            }
+            "type" => {
+                let (_, name) = self.parse_keyword_and_name()?;
+                let implements = self.parse_implements_interfaces()?;
+                let directives = self.parse_directives()?;
+                let fields = self.parse_fields_definition()?;
+                if implements.is_empty() && directives.is_empty() && fields.is_none() {
+                    return Err(
+                        self.unexpected("ImplementsInterfaces, Directives or FieldsDefinition")
+                    );
+                }
+                TypeExtension::Object(ObjectTypeExtension {
+                    position: self.pos_ending_with(start, &fields)?,
+                    name,
+                    implements,
+                    directives,
+                    fields: fields.unwrap_or_default(),
+                })
+            }
+            "interface" => {
+                let (_, name) = self.parse_keyword_and_name()?;
+                let implements = self.parse_implements_interfaces()?;
+                let directives = self.parse_directives()?;
+                let fields = self.parse_fields_definition()?;
+                TypeExtension::Interface(InterfaceTypeExtension {
+                    position: self.pos_ending_with(start, &fields)?,
+                    name,
+                    implements,
+                    directives,
+                    fields: fields.unwrap_or_default(),
+                })
+            }
+            "union" => {
+                let (_, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let members = if self.eat(TokenKind::Equals)?.is_some() {
+                    self.parse_union_member_types()?
+                } else if directives.is_empty() {
+                    return Err(self.unexpected("Directives or `=`"));
+                } else {
+                    vec![]
+                };
+                let end = if members.is_empty() {
+                    self.end_with_directives(&directives)?
+                } else {
+                    self.end_with_repetition(members.len())?
+                };
+                TypeExtension::Union(UnionTypeExtension {
+                    position: self.pos(start, end),
+                    name,
+                    directives,
+                    members,
+                })
+            }
+            "enum" => {
+                let (_, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let values = self.parse_enum_values_definition()?;
+                TypeExtension::Enum(EnumTypeExtension {
+                    position: self.pos_ending_with(start, &values)?,
+                    name,
+                    directives,
+                    values: values.unwrap_or_default(),
+                })
+            }
+            "input" => {
+                let (_, name) = self.parse_keyword_and_name()?;
+                let directives = self.parse_directives()?;
+                let fields = self.parse_input_fields_definition()?;
+                TypeExtension::InputObject(InputObjectTypeExtension {
+                    position: self.pos_ending_with(start, &fields)?,
+                    name,
+                    directives,
+                    fields: fields.unwrap_or_default(),
+                })
+            }
+            _ => return Err(self.unexpected("SchemaExtension or TypeExtension")),
+        };
+        Ok(TypeSystemDefinitionOrExtension::TypeExtension(extension))
+    }
+
+    /// Parses the keyword that begins a type definition and the name of the type.
+    fn parse_keyword_and_name(&mut self) -> ParseResult<(Keyword<'src>, Ident<'src>)> {
+        let token = self.next()?;
+        let keyword = self.keyword(token);
+        Ok((keyword, self.expect_name()?))
+    }
+
+    /// Pos of a type definition whose last part is given optional block.
+    /// When the block is omitted, the definition ends with a repetition or an optional part
+    /// and thus spans ignored tokens after it.
+    fn pos_ending_with<T>(&mut self, start: Location, block: &Option<T>) -> ParseResult<Pos> {
+        let end = match block {
+            Some(_) => self.last_end,
+            None => self.next_start()?,
+        };
+        Ok(self.pos(start, end))
+    }
+
+    /// Parses ImplementsInterfaces if the next token is `implements`.
+    fn parse_implements_interfaces(&mut self) -> ParseResult<Vec<Ident<'src>>> {
+        if self.eat_keyword("implements")?.is_none() {
+            return Ok(vec![]);
+        }
+        self.eat(TokenKind::Amp)?;
+        let mut interfaces = vec![self.expect_name()?];
+        while self.eat(TokenKind::Amp)?.is_some() {
+            interfaces.push(self.expect_name()?);
+        }
+        Ok(interfaces)
+    }
+
+    /// Parses UnionMemberTypes, which follows `=`.
+    fn parse_union_member_types(&mut self) -> ParseResult<Vec<Ident<'src>>> {
+        self.eat(TokenKind::Pipe)?;
+        let mut members = vec![self.expect_name()?];
+        while self.eat(TokenKind::Pipe)?.is_some() {
+            members.push(self.expect_name()?);
+        }
+        Ok(members)
+    }
+
+    /// Parses FieldsDefinition if the next token is `{`.
+    fn parse_fields_definition(&mut self) -> ParseResult<Option<Vec<FieldDefinition<'src>>>> {
+        if self.eat(TokenKind::BraceL)?.is_none() {
+            return Ok(None);
+        }
+        self.parse_block_items(|parser, _| {
+            let description = parser.parse_description()?;
+            let name = parser.expect_name()?;
+            let arguments = parser.parse_arguments_definition()?;
+            parser.expect(TokenKind::Colon, "`:`")?;
+            Ok(FieldDefinition {
+                description,
+                name,
+                arguments,
+                r#type: parser.parse_type()?,
+                directives: parser.parse_directives()?,
+            })
+        })
+        .map(Some)
+    }
+
+    /// Parses ArgumentsDefinition if the next token is `(`.
+    fn parse_arguments_definition(&mut self) -> ParseResult<Option<ArgumentsDefinition<'src>>> {
+        if self.eat(TokenKind::ParenL)?.is_none() {
+            return Ok(None);
+        }
+        let mut input_values = vec![];
+        loop {
+            input_values.push(self.parse_input_value_definition()?);
+            if self.eat(TokenKind::ParenR)?.is_some() {
+                return Ok(Some(ArgumentsDefinition { input_values }));
+            }
+        }
+    }
+
+    /// Parses InputFieldsDefinition if the next token is `{`.
+    fn parse_input_fields_definition(
+        &mut self,
+    ) -> ParseResult<Option<Vec<InputValueDefinition<'src>>>> {
+        if self.eat(TokenKind::BraceL)?.is_none() {
+            return Ok(None);
+        }
+        self.parse_block_items(|parser, _| parser.parse_input_value_definition())
+            .map(Some)
+    }
+
+    fn parse_input_value_definition(&mut self) -> ParseResult<InputValueDefinition<'src>> {
+        let description = self.parse_description()?;
+        let name = self.expect_name()?;
+        self.expect(TokenKind::Colon, "`:`")?;
+        let r#type = self.parse_type()?;
+        let default_value = match self.eat(TokenKind::Equals)? {
+            Some(_) => Some(self.parse_value()?),
+            None => None,
+        };
+        Ok(InputValueDefinition {
+            description,
+            position: name.position,
+            name,
+            r#type,
+            default_value,
+            directives: self.parse_directives()?,
+        })
+    }
+
+    /// Parses EnumValuesDefinition if the next token is `{`.
+    fn parse_enum_values_definition(
+        &mut self,
+    ) -> ParseResult<Option<Vec<EnumValueDefinition<'src>>>> {
+        if self.eat(TokenKind::BraceL)?.is_none() {
+            return Ok(None);
+        }
+        self.parse_block_items(|parser, _| {
+            let description = parser.parse_description()?;
+            if matches!(parser.peek()?.text, "true" | "false" | "null") {
+                return Err(parser.unexpected("EnumValue"));
+            }
+            Ok(EnumValueDefinition {
+                description,
+                name: parser.expect_name()?,
+                directives: parser.parse_directives()?,
+            })
+        })
+        .map(Some)
+    }
+}
@@ -0,0 +1,259 @@
+use nitrogql_ast::{
+    directive::Directive,
+    r#type::{ListType, NamedType, NonNullType, Type},
+    value::{
+        Arguments, BooleanValue, EnumValue, FloatValue, IntValue, ListValue, NullValue,
+        ObjectValue, StringValue, Value,
+    },
+    variable::Variable,
+};
+
+use super::{ParseResult, Parser};
+use crate::parser::{
+    block_string::block_string_value,
+    lexer::{Location, Token, TokenKind},
+    ParseError,
+};
+
+impl<'src> Parser<'src> {
+    /// Parses a Value.
+    pub(super) fn parse_value(&mut self) -> ParseResult<Value<'src>> {
+        let token = self.peek()?;
+        let position = self.pos(token.start, token.end);
+        let value = match token.kind {
+            TokenKind::Dollar => return self.parse_variable().map(Value::Variable),
+            TokenKind::IntValue => Value::IntValue(IntValue {
+                position,
+                value: token.text,
+            }),
+            TokenKind::FloatValue => Value::FloatValue(FloatValue {
+                position,
+                value: token.text,
+            }),
+            TokenKind::StringValue | TokenKind::BlockStringValue => {
+                return self.parse_string_value().map(Value::StringValue)
+            }
+            TokenKind::Name => match token.text {
+                "true" | "false" => Value::BooleanValue(BooleanValue {
+                    position,
+                    keyword: token.text,
+                    value: token.text == "true",
+                }),
+                "null" => Value::NullValue(NullValue {
+                    position,
+                    keyword: token.text,
+                }),
+                _ => Value::EnumValue(EnumValue {
+                    position,
+                    value: token.text,
+                }),
+            },
+            TokenKind::BracketL => return self.parse_list_value(),
+            TokenKind::BraceL => return self.parse_object_value(),
+            _ => return Err(self.unexpected("Value")),
+        };
+        self.next()?;
+        Ok(value)
+    }
+
+    fn parse_list_value(&mut self) -> ParseResult<Value<'src>> {
+        let start = self.next()?.start;
+        let mut values = vec![];
+        while self.eat(TokenKind::BracketR)?.is_none() {
+            values.push(self.parse_value()?);
+        }
+        Ok(Value::ListValue(ListValue {
+            position: self.pos_from(start),
+            values,
+        }))
+    }
+
+    fn parse_object_value(&mut self) -> ParseResult<Value<'src>> {
+        let start = self.next()?.start;
+        let mut fields = vec![];
+        while self.eat(TokenKind::BraceR)?.is_none() {
+            let name = self.expect_name()?;
+            self.expect(TokenKind::Colon, "`:`")?;
+            fields.push((name, self.parse_value()?));
+        }
+        Ok(Value::ObjectValue(ObjectValue {
+            position: self.pos_from(start),
+            fields,
+        }))
+    }
+
+    /// Parses a Variable such as `$foo`.
+    pub(super) fn parse_variable(&mut self) -> ParseResult<Variable<'src>> {
+        let start = self.expect(TokenKind::Dollar, "Variable")?.start;
+        let name = self.expect(TokenKind::Name, "Name")?;
+        Ok(Variable {
+            name: name.text,
+            position: self.pos_from(start),
+        })
+    }
+
+    /// Parses a StringValue or a BlockStringValue.
+    pub(super) fn parse_string_value(&mut self) -> ParseResult<StringValue> {
+        let token = self.peek()?;
+        let position = self.pos(token.start, token.end);
+        let value = match token.kind {
+            TokenKind::StringValue => self.unescape(token)?,
+            TokenKind::BlockStringValue => block_string_value(&token.text[3..token.text.len() - 3]),
+            _ => return Err(self.unexpected("StringValue")),
+        };
+        self.next()?;
+        Ok(StringValue { position, value })
+    }
+
+    /// Parses Arguments if the next token is `(`.
+    pub(super) fn parse_arguments(&mut self) -> ParseResult<Option<Arguments<'src>>> {
+        let Some(open) = self.eat(TokenKind::ParenL)? else {
+            return Ok(None);
+        };
+        let mut arguments = vec![];
+        loop {
+            let name = self.expect_name()?;
+            self.expect(TokenKind::Colon, "`:`")?;
+            arguments.push((name, self.parse_value()?));
+            if self.eat(TokenKind::ParenR)?.is_some() {
+                break;
+            }
+        }
+        Ok(Some(Arguments {
+            position: self.pos_from(open.start),
+            arguments,
+        }))
+    }
+
+    /// Parses zero or more Directives.
+    pub(super) fn parse_directives(&mut self) -> ParseResult<Vec<Directive<'src>>> {
+        let mut directives = vec![];
+        while let Some(at) = self.eat(TokenKind::At)? {
+            let name = self.expect_name()?;
+            let arguments = self.parse_arguments()?;
+            let end = if arguments.is_some() {
+                self.last_end
+            } else {
+                self.next_start()?
+            };
+            directives.push(Directive {
+                position: self.pos(at.start, end),
+                name,
+                arguments,
+            });
+        }
+        Ok(directives)
+    }
+
+    /// End of a node whose last part is optional directives.
+    pub(super) fn end_with_directives(
+        &mut self,
+        directives: &[Directive<'src>],
+    ) -> ParseResult<Location> {
+        match directives {
+            [_, .., Directive {
+                arguments: Some(_), ..
+            }] => Ok(self.last_end),
+            // the last directive spans ignored tokens after it
+            _ => self.next_start(),
+        }
+    }
+
+    /// Parses a Type.
+    pub(super) fn parse_type(&mut self) -> ParseResult<Type<'src>> {
+        let ty = if let Some(open) = self.eat(TokenKind::BracketL)? {
+            let inner = self.parse_type()?;
+            self.expect(TokenKind::BracketR, "`]`")?;
+            Type::List(Box::new(ListType {
+                position: self.pos_from(open.start),
+                r#type: inner,
+            }))
+        } else {
+            let name = self.expect(TokenKind::Name, "Type")?;
+            Type::Named(NamedType {
+                name: self.ident(name),
+            })
+        };
+        if self.eat(TokenKind::Bang)?.is_some() {
+            return Ok(Type::NonNull(Box::new(NonNullType { r#type: ty })));
+        }
+        Ok(ty)
+    }
+
+    /// Returns the value of a string token with escape sequences replaced.
+    fn unescape(&self, token: Token<'src>) -> ParseResult<String> {
+        let content = &token.text[1..token.text.len() - 1];
+        let invalid = |sequence: &str| ParseError {
+            position: self.pos(token.start, token.end),
+            message: format!("invalid escape sequence `{sequence}`"),
+        };
+        let mut value = String::with_capacity(content.len());
+        let mut rest = content;
+        while let Some(backslash) = rest.find('\\') {
+            value.push_str(&rest[..backslash]);
+            rest = &rest[backslash..];
+            let escape = rest[1..].chars().next().unwrap_or_default();
+            let (character, len) = match escape {
+                '"' => ('"', 2),
+                '\\' => ('\\', 2),
+                '/' => ('/', 2),
+                'b' => ('\u{0008}', 2),
+                'f' => ('\u{000c}', 2),
+                'n' => ('\n', 2),
+                'r' => ('\r', 2),
+                't' => ('\t', 2),
+                'u' => unescape_unicode(rest).ok_or_else(|| {
+                    let sequence_len = rest[2..]
+                        .find(|c: char| !c.is_ascii_hexdigit() && c != '{' && c != '}')
+                        .map_or(rest.len(), |len| len + 2);
+                    invalid(&rest[..sequence_len])
+                })?,
+                _ => return Err(invalid(&rest[..1 + escape.len_utf8()])),
+            };
+            value.push(character);
+            rest = &rest[len..];
+        }
+        value.push_str(rest);
+        Ok(value)
+    }
+}
+
+/// Reads an escaped unicode character at the beginning of `source`, which begins with `\u`.
+/// A surrogate pair written as two escape sequences makes one character.
+/// Returns the character and the length of escape sequences.
+fn unescape_unicode(source: &str) -> Option<(char, usize)> {
+    let (code, len) = escaped_code_point(source)?;
+    if let Some(character) = char::from_u32(code) {
+        return Some((character, len));
+    }
+    // leading surrogate must be followed by a trailing surrogate
+    if !(0xD800..=0xDBFF).contains(&code) {
+        return None;
+    }
+    let (trailing, trailing_len) = escaped_code_point(&source[len..])?;
+    if !(0xDC00..=0xDFFF).contains(&trailing) {
+        return None;
+    }
+    let code = 0x10000 + ((code - 0xD800) << 10) + (trailing - 0xDC00);
+    Some((char::from_u32(code)?, len + trailing_len))
+}
+
+/// Reads `\u{...}` or `\uXXXX` at the beginning of `source`.
+/// Returns the code point and the length of the escape sequence.
+fn escaped_code_point(source: &str) -> Option<(u32, usize)> {
+    let digits = source.strip_prefix("\\u")?;
+    if let Some(braced) = digits.strip_prefix('{') {
+        let len = braced.find('}')?;
+        let hex = &braced[..len];
+        if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let code = u32::from_str_radix(hex, 16).ok()?;
+        return Some((code, len + 4));
+    }
+    let hex = digits.get(..4)?;
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((u32::from_str_radix(hex, 16).ok()?, 6))
+}
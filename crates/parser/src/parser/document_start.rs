@@ -0,0 +1,35 @@
+use std::cell::Cell;
+
+thread_local! {
+    /// Line, column and byte offset in the file at which the document being parsed begins.
+    static DOCUMENT_START: Cell<(usize, usize, usize)> = const { Cell::new((0, 0, 0)) };
+}
+
+/// Runs given function with positions shifted so that the document begins at `start` in its file.
+pub fn with_document_start<R>(start: (usize, usize, usize), f: impl FnOnce() -> R) -> R {
+    let previous = DOCUMENT_START.with(|cell| cell.replace(start));
+    let result = f();
+    DOCUMENT_START.with(|cell| cell.set(previous));
+    result
+}
+
+/// Converts a 0-based (line, column, offset) in the document being parsed to one in its file.
+pub fn position_in_file((line, column, offset): (usize, usize, usize)) -> (usize, usize, usize) {
+    let (start_line, start_column, start_offset) = DOCUMENT_START.with(|cell| cell.get());
+    let column = if line == 0 {
+        start_column + column
+    } else {
+        column
+    };
+    (start_line + line, column, start_offset + offset)
+}
+
+/// Calculates the 0-based line, column and byte offset of given offset in a document.
+/// Columns are counted in characters.
+pub fn document_start_at(document: &str, offset: usize) -> (usize, usize, usize) {
+    let before = &document[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let column = before[line_start..].chars().count();
+    (line, column, offset)
+}
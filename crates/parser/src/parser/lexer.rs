@@ -0,0 +1,384 @@
+//! Lexer that splits a GraphQL document into tokens.
+//! Tokens borrow their text from the source so that no string is copied.
+
+use nitrogql_ast::{base::Pos, operation::ImportComment};
+
+use super::{document_start::position_in_file, ParseError};
+
+/// 0-based line, column and byte offset in the document being parsed.
+/// Columns are counted in characters.
+pub type Location = (usize, usize, usize);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `!`
+    Bang,
+    /// `$`
+    Dollar,
+    /// `&`
+    Amp,
+    /// `(`
+    ParenL,
+    /// `)`
+    ParenR,
+    /// `...`
+    Spread,
+    /// `:`
+    Colon,
+    /// `=`
+    Equals,
+    /// `@`
+    At,
+    /// `[`
+    BracketL,
+    /// `]`
+    BracketR,
+    /// `{`
+    BraceL,
+    /// `|`
+    Pipe,
+    /// `}`
+    BraceR,
+    Name,
+    IntValue,
+    FloatValue,
+    /// String enclosed in `"`, including quotes.
+    StringValue,
+    /// String enclosed in `"""`, including quotes.
+    BlockStringValue,
+    EndOfInput,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Token<'src> {
+    pub kind: TokenKind,
+    pub text: &'src str,
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Token<'_> {
+    /// Returns whether this token is a name that equals given keyword.
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Name && self.text == keyword
+    }
+
+    /// Describes this token for error messages.
+    pub fn describe(&self) -> String {
+        match self.kind {
+            TokenKind::StringValue => "string".into(),
+            TokenKind::BlockStringValue => "block string".into(),
+            TokenKind::EndOfInput => "end of input".into(),
+            _ => format!("`{}`", self.text),
+        }
+    }
+}
+
+pub struct Lexer<'src> {
+    source: &'src str,
+    offset: usize,
+    line: usize,
+    column: usize,
+    /// Whether comments in the form of `#import "path"` are collected as import comments.
+    pub collect_imports: bool,
+    imports: Vec<ImportComment<'src>>,
+}
+
+impl<'src> Lexer<'src> {
+    pub fn new(source: &'src str) -> Self {
+        Lexer {
+            source,
+            offset: 0,
+            line: 0,
+            column: 0,
+            collect_imports: false,
+            imports: vec![],
+        }
+    }
+
+    /// Takes import comments collected so far.
+    pub fn take_imports(&mut self) -> Vec<ImportComment<'src>> {
+        std::mem::take(&mut self.imports)
+    }
+
+    /// Skips ignored tokens and reads the next token.
+    pub fn next_token(&mut self) -> Result<Token<'src>, ParseError> {
+        self.skip_ignored();
+        let start = self.location();
+        let bytes = self.source.as_bytes();
+        let Some(&byte) = bytes.get(self.offset) else {
+            return Ok(self.token(TokenKind::EndOfInput, start));
+        };
+        let kind = match byte {
+            b'!' => TokenKind::Bang,
+            b'$' => TokenKind::Dollar,
+            b'&' => TokenKind::Amp,
+            b'(' => TokenKind::ParenL,
+            b')' => TokenKind::ParenR,
+            b':' => TokenKind::Colon,
+            b'=' => TokenKind::Equals,
+            b'@' => TokenKind::At,
+            b'[' => TokenKind::BracketL,
+            b']' => TokenKind::BracketR,
+            b'{' => TokenKind::BraceL,
+            b'|' => TokenKind::Pipe,
+            b'}' => TokenKind::BraceR,
+            b'.' if bytes[self.offset..].starts_with(b"...") => {
+                self.advance_ascii(3);
+                return Ok(self.token(TokenKind::Spread, start));
+            }
+            b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                let len = bytes[self.offset..]
+                    .iter()
+                    .position(|b| !is_name_continue(*b))
+                    .unwrap_or(bytes.len() - self.offset);
+                self.advance_ascii(len);
+                return Ok(self.token(TokenKind::Name, start));
+            }
+            b'-' | b'0'..=b'9' => return self.number(start),
+            b'"' => return self.string(start),
+            _ => {
+                let character = self.source[self.offset..]
+                    .chars()
+                    .next()
+                    .unwrap_or_default();
+                let error = self.error_at(start, format!("unexpected character `{character}`"));
+                // Skip the character so that a parser recovering from errors can go on.
+                self.advance(character.len_utf8());
+                return Err(error);
+            }
+        };
+        self.advance_ascii(1);
+        Ok(self.token(kind, start))
+    }
+
+    /// Returns the first byte of the next token without advancing.
+    pub fn peek_byte(&self) -> Option<u8> {
+        let bytes = self.source.as_bytes();
+        let mut pos = self.offset;
+        while let Some(&byte) = bytes.get(pos) {
+            match byte {
+                b' ' | b'\t' | b',' | b'\r' | b'\n' => pos += 1,
+                b'#' => {
+                    pos += bytes[pos..]
+                        .iter()
+                        .position(|b| matches!(b, b'\n' | b'\r'))
+                        .unwrap_or(bytes.len() - pos);
+                }
+                _ if self.source[pos..].starts_with('\u{feff}') => pos += '\u{feff}'.len_utf8(),
+                _ => return Some(byte),
+            }
+        }
+        None
+    }
+
+    /// Current location of the lexer.
+    fn location(&self) -> Location {
+        (self.line, self.column, self.offset)
+    }
+
+    /// Makes a token that spans from `start` to current location.
+    fn token(&self, kind: TokenKind, start: Location) -> Token<'src> {
+        Token {
+            kind,
+            text: &self.source[start.2..self.offset],
+            start,
+            end: self.location(),
+        }
+    }
+
+    fn error_at(&self, start: Location, message: String) -> ParseError {
+        ParseError {
+            position: Pos::new_span(position_in_file(start), position_in_file(self.location())),
+            message,
+        }
+    }
+
+    /// Advances over ASCII characters that are not line terminators.
+    fn advance_ascii(&mut self, len: usize) {
+        self.offset += len;
+        self.column += len;
+    }
+
+    /// Advances over `len` bytes of any characters.
+    fn advance(&mut self, len: usize) {
+        let end = self.offset + len;
+        for &byte in &self.source.as_bytes()[self.offset..end] {
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 0;
+            } else if !is_utf8_continuation(byte) {
+                self.column += 1;
+            }
+        }
+        self.offset = end;
+    }
+
+    /// Skips whitespaces, line terminators, commas and comments.
+    fn skip_ignored(&mut self) {
+        let bytes = self.source.as_bytes();
+        while let Some(&byte) = bytes.get(self.offset) {
+            match byte {
+                b' ' | b'\t' | b',' | b'\r' => self.advance_ascii(1),
+                b'\n' => {
+                    self.offset += 1;
+                    self.line += 1;
+                    self.column = 0;
+                }
+                b'#' => {
+                    if self.collect_imports && self.import_comment() {
+                        continue;
+                    }
+                    let len = bytes[self.offset..]
+                        .iter()
+                        .position(|b| matches!(b, b'\n' | b'\r'))
+                        .unwrap_or(bytes.len() - self.offset);
+                    self.advance(len);
+                }
+                _ if self.source[self.offset..].starts_with('\u{feff}') => {
+                    self.offset += '\u{feff}'.len_utf8();
+                    self.column += 1;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Reads an import comment such as `#import "./fragments.graphql"` which spans to the end of the line.
+    /// Returns false without advancing if the comment at current location is not an import comment.
+    fn import_comment(&mut self) -> bool {
+        let rest = &self.source[self.offset..];
+        let Some(after_keyword) = rest.strip_prefix("#import") else {
+            return false;
+        };
+        let quoted = after_keyword.trim_start_matches([' ', '\t']);
+        if quoted.len() == after_keyword.len() {
+            return false;
+        }
+        let mut chars = quoted.chars();
+        let Some(quote @ ('"' | '\'')) = chars.next() else {
+            return false;
+        };
+        let content = &quoted[1..];
+        let content_len = content
+            .find(['"', '\'', '\n', '\r'])
+            .unwrap_or(content.len());
+        if content_len == 0 || !content[content_len..].starts_with(quote) {
+            return false;
+        }
+        let path = &content[..content_len];
+        let after_path = content[content_len + 1..].trim_start_matches([' ', '\t']);
+        let line_end = if after_path.starts_with("\r\n") {
+            2
+        } else if after_path.starts_with(['\n', '\r']) {
+            1
+        } else if after_path.is_empty() {
+            0
+        } else {
+            return false;
+        };
+        let start = self.location();
+        self.advance(rest.len() - after_path.len() + line_end);
+        self.imports.push(ImportComment {
+            position: Pos::new_span(position_in_file(start), position_in_file(self.location())),
+            path,
+        });
+        true
+    }
+
+    fn number(&mut self, start: Location) -> Result<Token<'src>, ParseError> {
+        let bytes = self.source.as_bytes();
+        let digits_from = |pos: usize| {
+            bytes[pos..]
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .map_or(bytes.len(), |len| pos + len)
+        };
+        let mut pos = self.offset;
+        if bytes[pos] == b'-' {
+            pos += 1;
+        }
+        let mut kind = TokenKind::IntValue;
+        match bytes.get(pos) {
+            Some(b'0') => pos += 1,
+            Some(b'1'..=b'9') => pos = digits_from(pos),
+            _ => return Err(self.invalid_number(start, pos)),
+        }
+        if bytes.get(pos) == Some(&b'.') {
+            kind = TokenKind::FloatValue;
+            pos = digits_from(pos + 1);
+        }
+        if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+            kind = TokenKind::FloatValue;
+            pos += 1;
+            if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+                pos += 1;
+            }
+            let exponent_end = digits_from(pos);
+            if exponent_end == pos {
+                return Err(self.invalid_number(start, pos));
+            }
+            pos = exponent_end;
+        }
+        if bytes
+            .get(pos)
+            .is_some_and(|b| *b == b'.' || is_name_continue(*b))
+        {
+            return Err(self.invalid_number(start, pos + 1));
+        }
+        self.advance_ascii(pos - self.offset);
+        Ok(self.token(kind, start))
+    }
+
+    fn invalid_number(&mut self, start: Location, end: usize) -> ParseError {
+        self.advance_ascii(end.min(self.source.len()) - self.offset);
+        self.error_at(start, "invalid number".into())
+    }
+
+    fn string(&mut self, start: Location) -> Result<Token<'src>, ParseError> {
+        let bytes = self.source.as_bytes();
+        let rest = &bytes[self.offset..];
+        if rest.starts_with(b"\"\"\"") {
+            let mut pos = 3;
+            loop {
+                if pos >= rest.len() {
+                    self.advance(rest.len());
+                    return Err(self.error_at(start, "unterminated block string".into()));
+                }
+                if rest[pos..].starts_with(b"\\\"\"\"") {
+                    pos += 4;
+                } else if rest[pos..].starts_with(b"\"\"\"") {
+                    self.advance(pos + 3);
+                    return Ok(self.token(TokenKind::BlockStringValue, start));
+                } else {
+                    pos += 1;
+                }
+            }
+        }
+        let mut pos = 1;
+        loop {
+            match rest.get(pos) {
+                Some(b'"') => {
+                    self.advance(pos + 1);
+                    return Ok(self.token(TokenKind::StringValue, start));
+                }
+                Some(b'\\') if !matches!(rest.get(pos + 1), None | Some(b'\n' | b'\r')) => {
+                    pos += 2;
+                }
+                None | Some(b'\n' | b'\r') => {
+                    self.advance(pos);
+                    return Err(self.error_at(start, "unterminated string".into()));
+                }
+                Some(_) => pos += 1,
+            }
+        }
+    }
+}
+
+fn is_name_continue(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn is_utf8_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
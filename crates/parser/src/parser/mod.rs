@@ -1,16 +1,22 @@
-use nitrogql_ast::{base::Pos, OperationDocument, TypeSystemOrExtensionDocument};
+use nitrogql_ast::{
+    base::Pos, value::Value, Document, OperationDocument, TypeSystemOrExtensionDocument,
+};
 use nitrogql_error::PositionedError;
-use pest::Parser;
-use pest_derive::Parser;
 use thiserror::Error;
 
-use self::builder::{build_operation_document, build_type_system_or_extension_document};
+use self::{descent::Parser, document_start::with_document_start};
 
-mod builder;
+pub use self::recover::{
+    parse_operation_document_recovering, parse_type_system_document_recovering,
+};
 
-#[derive(Parser)]
-#[grammar = "parser/grammar.pest"]
-pub struct RawParser;
+mod block_string;
+mod descent;
+pub(crate) mod document_start;
+mod lexer;
+#[cfg(feature = "pest")]
+pub mod pest_parser;
+mod recover;
 
 #[derive(Error, Debug)]
 #[error("Parse error: {0}")]
@@ -30,20 +36,6 @@ impl ParseError {
     }
 }
 
-impl From<pest::error::Error<Rule>> for ParseError {
-    fn from(error: pest::error::Error<Rule>) -> Self {
-        // convert 1-based line and column to 0-based
-        let (line, col) = match error.line_col {
-            pest::error::LineColLocation::Pos((line, column)) => (line - 1, column - 1),
-            pest::error::LineColLocation::Span((line, column), _) => (line - 1, column - 1),
-        };
-        let position = Pos::new(line, col);
-        let message = error.variant.message().into_owned();
-
-        ParseError { position, message }
-    }
-}
-
 impl From<ParseError> for PositionedError {
     fn from(value: ParseError) -> Self {
         let position = value.position;
@@ -54,16 +46,34 @@ impl From<ParseError> for PositionedError {
     }
 }
 
-pub fn parse_operation_document(document: &str) -> Result<OperationDocument, ParseError> {
-    let res = RawParser::parse(Rule::ExecutableDocument, document)?;
+pub fn parse_operation_document(document: &str) -> Result<OperationDocument<'_>, ParseError> {
+    Parser::new(document).parse_operation_document()
+}
 
-    Ok(build_operation_document(res))
+/// Parses an operation document that is a part of a larger file, such as a template literal in a TypeScript file.
+/// `start` is the 0-based line, column and byte offset in the file at which `document` begins.
+/// Positions in the result are relative to the file.
+pub fn parse_operation_document_at(
+    document: &str,
+    start: (usize, usize, usize),
+) -> Result<OperationDocument<'_>, ParseError> {
+    with_document_start(start, || parse_operation_document(document))
 }
 
 pub fn parse_type_system_document(
     document: &str,
-) -> Result<TypeSystemOrExtensionDocument, ParseError> {
-    let res = RawParser::parse(Rule::TypeSystemExtensionDocument, document)?;
+) -> Result<TypeSystemOrExtensionDocument<'_>, ParseError> {
+    Parser::new(document).parse_type_system_document()
+}
+
+/// Parses a document that may mix executable definitions and type system definitions,
+/// as allowed by the `Document` rule of the GraphQL specification.
+/// Use `Document::split` to separate them.
+pub fn parse_document(document: &str) -> Result<Document<'_>, ParseError> {
+    Parser::new(document).parse_document()
+}
 
-    Ok(build_type_system_or_extension_document(res))
+/// Parses a standalone GraphQL value such as `{ foo: [1, 2] }`.
+pub fn parse_value(source: &str) -> Result<Value<'_>, ParseError> {
+    Parser::new(source).parse_value_document()
 }
@@ -0,0 +1,92 @@
+//! This module builds ast from raw parser result.
+
+use self::{
+    operation::build_executable_definition, type_system::build_type_system_definition_or_extension,
+    utils::PairExt, value::build_value,
+};
+
+use super::Rule;
+use nitrogql_ast::{
+    operation::{ImportComment, OperationDocument},
+    type_system::TypeSystemOrExtensionDocument,
+    value::Value,
+};
+use pest::iterators::{Pair, Pairs};
+
+mod base;
+mod directives;
+mod operation;
+mod selection_set;
+mod r#type;
+mod type_system;
+mod utils;
+mod value;
+
+pub fn build_operation_document(pairs: Pairs<Rule>) -> OperationDocument {
+    let Some(pair) = pairs.into_iter().next() else {
+        panic!("Empty document")
+    };
+    match pair.as_rule() {
+        Rule::ExecutableDocument => {
+            let mut imports = vec![];
+            let mut definitions = vec![];
+            for pair in pair.into_inner() {
+                match pair.as_rule() {
+                    Rule::ImportComment => imports.push(build_import_comment(pair)),
+                    Rule::ExecutableDefinition => {
+                        definitions.push(build_executable_definition(pair))
+                    }
+                    _ => {}
+                }
+            }
+            OperationDocument {
+                imports,
+                definitions,
+            }
+        }
+        rule => panic!("Unexpected Rule {:?}", rule),
+    }
+}
+
+fn build_import_comment(pair: Pair<Rule>) -> ImportComment {
+    let position = pair.to_pos();
+    let path = pair.only_child().only_child();
+    ImportComment {
+        position,
+        path: path.as_str(),
+    }
+}
+
+pub fn build_type_system_or_extension_document(
+    pairs: Pairs<Rule>,
+) -> TypeSystemOrExtensionDocument {
+    let Some(pair) = pairs.into_iter().next() else {
+        panic!("Empty document")
+    };
+    match pair.as_rule() {
+        Rule::TypeSystemExtensionDocument => {
+            let definitions: Vec<_> = pair
+                .into_inner()
+                .filter(|pair| pair.is_rule(Rule::TypeSystemDefinitionOrExtension))
+                .map(build_type_system_definition_or_extension)
+                .collect();
+            TypeSystemOrExtensionDocument { definitions }
+        }
+        rule => panic!("Unexpected Rule {:?}", rule),
+    }
+}
+
+pub fn build_value_document(pairs: Pairs<Rule>) -> Value {
+    let Some(pair) = pairs.into_iter().next() else {
+        panic!("Empty document")
+    };
+    match pair.as_rule() {
+        Rule::ValueDocument => {
+            let Some(value) = pair.into_inner().find(|pair| pair.is_rule(Rule::Value)) else {
+                panic!("Value not found")
+            };
+            build_value(value)
+        }
+        rule => panic!("Unexpected Rule {:?}", rule),
+    }
+}
@@ -47,8 +47,25 @@ pub fn build_executable_definition(pair: Pair<Rule>) -> ExecutableDefinition {
     let pair = pair.only_child();
     let position = pair.to_pos();
     match pair.as_rule() {
+        Rule::OperationDefinition
+            if pair
+                .clone()
+                .into_inner()
+                .next()
+                .is_some_and(|child| child.is_rule(Rule::SelectionSet)) =>
+        {
+            // shorthand for an anonymous query
+            ExecutableDefinition::OperationDefinition(OperationDefinition {
+                position,
+                operation_type: OperationType::Query,
+                shorthand: true,
+                name: None,
+                variables_definition: None,
+                directives: vec![],
+                selection_set: build_selection_set(pair.only_child()),
+            })
+        }
         Rule::OperationDefinition => {
-            // TODO: handling of OperationSet (abbreviated syntax)
             let (operation_type, name, variables_definition, directives, selection_set) = parts!(
                 pair,
                 OperationType,
@@ -60,6 +77,7 @@ pub fn build_executable_definition(pair: Pair<Rule>) -> ExecutableDefinition {
             ExecutableDefinition::OperationDefinition(OperationDefinition {
                 position,
                 operation_type: str_to_operation_type(operation_type.as_str()),
+                shorthand: false,
                 name: name.map(|pair| pair.to_ident()),
                 variables_definition: variables_definition.map(build_variables_definition),
                 directives: directives.map_or(vec![], build_directives),
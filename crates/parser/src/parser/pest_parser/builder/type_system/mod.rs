@@ -81,6 +81,7 @@ fn build_schema_definition(pair: Pair<Rule>) -> SchemaDefinition {
 }
 
 fn build_directive_definition(pair: Pair<Rule>) -> DirectiveDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, arguments, repeatable, _, locations) = parts!(
         pair,
         Description opt,
@@ -93,7 +94,7 @@ fn build_directive_definition(pair: Pair<Rule>) -> DirectiveDefinition {
     );
     DirectiveDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         arguments: arguments.map(build_arguments_definition),
         repeatable: repeatable.map(|pair| pair.to_ident()),
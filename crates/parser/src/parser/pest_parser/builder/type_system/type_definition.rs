@@ -2,7 +2,7 @@ use pest::iterators::Pair;
 
 use super::{super::Rule, build_description};
 use crate::{
-    parser::builder::{
+    parser::pest_parser::builder::{
         directives::build_directives, r#type::build_type, utils::PairExt, value::build_value,
     },
     parts,
@@ -34,6 +34,7 @@ pub fn build_type_definition(pair: Pair<Rule>) -> TypeDefinition {
 }
 
 fn build_scalar_type_definition(pair: Pair<Rule>) -> ScalarTypeDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, directives) = parts!(
         pair,
         Description opt,
@@ -43,7 +44,7 @@ fn build_scalar_type_definition(pair: Pair<Rule>) -> ScalarTypeDefinition {
     );
     ScalarTypeDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         scalar_keyword: keyword.to_keyword(),
@@ -51,6 +52,7 @@ fn build_scalar_type_definition(pair: Pair<Rule>) -> ScalarTypeDefinition {
 }
 
 fn build_object_type_definition(pair: Pair<Rule>) -> ObjectTypeDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, implements, directives, fields) = parts!(
         pair,
         Description opt,
@@ -63,7 +65,7 @@ fn build_object_type_definition(pair: Pair<Rule>) -> ObjectTypeDefinition {
 
     ObjectTypeDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         implements: implements.map_or(vec![], build_implements_interfaces),
         directives: directives.map_or(vec![], build_directives),
@@ -73,6 +75,7 @@ fn build_object_type_definition(pair: Pair<Rule>) -> ObjectTypeDefinition {
 }
 
 fn build_interface_type_definition(pair: Pair<Rule>) -> InterfaceTypeDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, implements, directives, fields) = parts!(
         pair,
         Description opt,
@@ -84,7 +87,7 @@ fn build_interface_type_definition(pair: Pair<Rule>) -> InterfaceTypeDefinition
     );
     InterfaceTypeDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         implements: implements.map_or(vec![], build_implements_interfaces),
         directives: directives.map_or(vec![], build_directives),
@@ -94,6 +97,7 @@ fn build_interface_type_definition(pair: Pair<Rule>) -> InterfaceTypeDefinition
 }
 
 fn build_union_type_definition(pair: Pair<Rule>) -> UnionTypeDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, directives, members) = parts!(
         pair,
         Description opt,
@@ -104,7 +108,7 @@ fn build_union_type_definition(pair: Pair<Rule>) -> UnionTypeDefinition {
     );
     UnionTypeDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         members: members.map_or(vec![], |members| {
@@ -116,6 +120,7 @@ fn build_union_type_definition(pair: Pair<Rule>) -> UnionTypeDefinition {
 }
 
 fn build_enum_type_definition(pair: Pair<Rule>) -> EnumTypeDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, directives, values) = parts!(
         pair,
         Description opt,
@@ -126,7 +131,7 @@ fn build_enum_type_definition(pair: Pair<Rule>) -> EnumTypeDefinition {
     );
     EnumTypeDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         values: values.map_or(vec![], |pair| {
@@ -138,6 +143,7 @@ fn build_enum_type_definition(pair: Pair<Rule>) -> EnumTypeDefinition {
 }
 
 fn build_input_object_type_definition(pair: Pair<Rule>) -> InputObjectTypeDefinition {
+    let definition_pos = pair.to_pos();
     let (description, keyword, name, directives, fields) = parts!(
         pair,
         Description opt,
@@ -148,7 +154,7 @@ fn build_input_object_type_definition(pair: Pair<Rule>) -> InputObjectTypeDefini
     );
     InputObjectTypeDefinition {
         description: description.map(build_description),
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         fields: fields.map_or(vec![], build_input_fields_definition),
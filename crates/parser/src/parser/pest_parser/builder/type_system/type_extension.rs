@@ -8,7 +8,7 @@ use super::{
     },
 };
 use crate::{
-    parser::builder::{directives::build_directives, utils::PairExt},
+    parser::pest_parser::builder::{directives::build_directives, utils::PairExt},
     parts,
 };
 use nitrogql_ast::type_system::{
@@ -34,6 +34,7 @@ pub fn build_type_extension(pair: Pair<Rule>) -> TypeExtension {
 }
 
 fn build_scalar_type_extension(pair: Pair<Rule>) -> ScalarTypeExtension {
+    let definition_pos = pair.to_pos();
     let (keyword, _, name, directives) = parts!(
         pair,
         KEYWORD_extend,
@@ -42,13 +43,14 @@ fn build_scalar_type_extension(pair: Pair<Rule>) -> ScalarTypeExtension {
         Directives opt
     );
     ScalarTypeExtension {
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
     }
 }
 
 fn build_object_type_extension(pair: Pair<Rule>) -> ObjectTypeExtension {
+    let definition_pos = pair.to_pos();
     let (keyword, _, name, implements, directives, fields) = parts!(
         pair,
         KEYWORD_extend,
@@ -60,7 +62,7 @@ fn build_object_type_extension(pair: Pair<Rule>) -> ObjectTypeExtension {
     );
 
     ObjectTypeExtension {
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         implements: implements.map_or(vec![], build_implements_interfaces),
         directives: directives.map_or(vec![], build_directives),
@@ -69,6 +71,7 @@ fn build_object_type_extension(pair: Pair<Rule>) -> ObjectTypeExtension {
 }
 
 fn build_interface_type_extension(pair: Pair<Rule>) -> InterfaceTypeExtension {
+    let definition_pos = pair.to_pos();
     let (keyword, _, name, implements, directives, fields) = parts!(
         pair,
         KEYWORD_extend,
@@ -79,7 +82,7 @@ fn build_interface_type_extension(pair: Pair<Rule>) -> InterfaceTypeExtension {
         FieldsDefinition opt
     );
     InterfaceTypeExtension {
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         implements: implements.map_or(vec![], build_implements_interfaces),
         directives: directives.map_or(vec![], build_directives),
@@ -88,6 +91,7 @@ fn build_interface_type_extension(pair: Pair<Rule>) -> InterfaceTypeExtension {
 }
 
 fn build_union_type_extension(pair: Pair<Rule>) -> UnionTypeExtension {
+    let definition_pos = pair.to_pos();
     let (keyword, _, name, directives, members) = parts!(
         pair,
         KEYWORD_extend,
@@ -97,7 +101,7 @@ fn build_union_type_extension(pair: Pair<Rule>) -> UnionTypeExtension {
         UnionMemberTypes opt
     );
     UnionTypeExtension {
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         members: members.map_or(vec![], |members| {
@@ -108,6 +112,7 @@ fn build_union_type_extension(pair: Pair<Rule>) -> UnionTypeExtension {
 }
 
 fn build_enum_type_extension(pair: Pair<Rule>) -> EnumTypeExtension {
+    let definition_pos = pair.to_pos();
     let (keyword, _, name, directives, values) = parts!(
         pair,
         KEYWORD_extend,
@@ -117,7 +122,7 @@ fn build_enum_type_extension(pair: Pair<Rule>) -> EnumTypeExtension {
         EnumValuesDefinition opt
     );
     EnumTypeExtension {
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         values: values.map_or(vec![], |pair| {
@@ -128,6 +133,7 @@ fn build_enum_type_extension(pair: Pair<Rule>) -> EnumTypeExtension {
 }
 
 fn build_input_object_type_extension(pair: Pair<Rule>) -> InputObjectTypeExtension {
+    let definition_pos = pair.to_pos();
     let (keyword, _, name, directives, fields) = parts!(
         pair,
         KEYWORD_extend,
@@ -137,7 +143,7 @@ fn build_input_object_type_extension(pair: Pair<Rule>) -> InputObjectTypeExtensi
         InputFieldsDefinition opt
     );
     InputObjectTypeExtension {
-        position: keyword.to_pos(),
+        position: keyword.to_pos().with_end(definition_pos),
         name: name.to_ident(),
         directives: directives.map_or(vec![], build_directives),
         fields: fields.map_or(vec![], build_input_fields_definition),
@@ -1,6 +1,7 @@
 //! Utils for dealing with Pair<Rule>
 
 use super::super::Rule;
+use crate::parser::document_start::position_in_file;
 use nitrogql_ast::base::{Ident, Keyword, Pos, Punc};
 use pest::iterators::Pair;
 
@@ -14,6 +15,7 @@ pub trait PairExt<'a> {
     /// Generate a Pos for this pair.
     fn to_pos(&self) -> Pos;
     /// Generate a Punc from this pair.
+    #[allow(dead_code)]
     fn to_punc(&self) -> Punc<'a>;
     /// Generate a Keyword from this pair.
     fn to_keyword(&self) -> Keyword<'a>;
@@ -53,9 +55,14 @@ impl<'a> PairExt<'a> for Pair<'a, Rule> {
     }
 
     fn to_pos(&self) -> Pos {
-        let (line, column) = self.line_col();
+        let span = self.as_span();
+        let (line, column) = span.start_pos().line_col();
+        let (end_line, end_column) = span.end_pos().line_col();
         // convert 1-based to 0-based
-        Pos::new(line - 1, column - 1)
+        Pos::new_span(
+            position_in_file((line - 1, column - 1, span.start())),
+            position_in_file((end_line - 1, end_column - 1, span.end())),
+        )
     }
     fn to_punc(&self) -> Punc<'a> {
         Punc {
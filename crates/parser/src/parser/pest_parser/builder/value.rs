@@ -1,4 +1,4 @@
-use crate::parts;
+use crate::{parser::block_string::block_string_value, parts};
 
 use super::{base::build_variable, utils::PairExt, Rule};
 use pest::iterators::Pair;
@@ -97,7 +97,7 @@ pub fn build_string_value(pair: Pair<Rule>) -> StringValue {
             let (mid, _) = end.split_at(end.len() - 3);
             StringValue {
                 position,
-                value: mid.into(),
+                value: block_string_value(mid),
             }
         }
         Rule::NormalStringValue => {
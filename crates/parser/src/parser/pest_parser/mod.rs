@@ -0,0 +1,63 @@
+//! Parser generated from the pest grammar.
+//! Kept for comparing results and performance with the hand-written parser.
+
+use nitrogql_ast::{base::Pos, value::Value, OperationDocument, TypeSystemOrExtensionDocument};
+use pest::Parser;
+use pest_derive::Parser;
+
+use self::builder::{
+    build_operation_document, build_type_system_or_extension_document, build_value_document,
+};
+use super::{document_start::position_in_file, ParseError};
+
+mod builder;
+
+#[derive(Parser)]
+#[grammar = "parser/pest_parser/grammar.pest"]
+pub struct RawParser;
+
+impl From<pest::error::Error<Rule>> for ParseError {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        // convert 1-based line and column to 0-based
+        let ((line, col), (end_line, end_col)) = match error.line_col {
+            pest::error::LineColLocation::Pos((line, column)) => {
+                ((line - 1, column - 1), (line - 1, column - 1))
+            }
+            pest::error::LineColLocation::Span((line, column), (end_line, end_column)) => {
+                ((line - 1, column - 1), (end_line - 1, end_column - 1))
+            }
+        };
+        let (offset, end_offset) = match error.location {
+            pest::error::InputLocation::Pos(offset) => (offset, offset),
+            pest::error::InputLocation::Span((start, end)) => (start, end),
+        };
+        let position = Pos::new_span(
+            position_in_file((line, col, offset)),
+            position_in_file((end_line, end_col, end_offset)),
+        );
+        let message = error.variant.message().into_owned();
+
+        ParseError { position, message }
+    }
+}
+
+pub fn parse_operation_document(document: &str) -> Result<OperationDocument<'_>, ParseError> {
+    let res = RawParser::parse(Rule::ExecutableDocument, document)?;
+
+    Ok(build_operation_document(res))
+}
+
+pub fn parse_type_system_document(
+    document: &str,
+) -> Result<TypeSystemOrExtensionDocument<'_>, ParseError> {
+    let res = RawParser::parse(Rule::TypeSystemExtensionDocument, document)?;
+
+    Ok(build_type_system_or_extension_document(res))
+}
+
+/// Parses a standalone GraphQL value such as `{ foo: [1, 2] }`.
+pub fn parse_value(source: &str) -> Result<Value<'_>, ParseError> {
+    let res = RawParser::parse(Rule::ValueDocument, source)?;
+
+    Ok(build_value_document(res))
+}
@@ -0,0 +1,23 @@
+//! Parsing with error recovery.
+//! When a document has syntax errors, the parser skips the broken parts and goes on,
+//! so that the result is a partial document along with all the errors.
+
+use nitrogql_ast::{OperationDocument, TypeSystemOrExtensionDocument};
+
+use super::{descent::Parser, ParseError};
+
+/// Parses an operation document, collecting all syntax errors.
+/// Returns the document made of what could be parsed, along with the errors.
+pub fn parse_operation_document_recovering(
+    document: &str,
+) -> (OperationDocument<'_>, Vec<ParseError>) {
+    Parser::new(document).parse_operation_document_recovering()
+}
+
+/// Parses a type system document, collecting all syntax errors.
+/// Returns the document made of what could be parsed, along with the errors.
+pub fn parse_type_system_document_recovering(
+    document: &str,
+) -> (TypeSystemOrExtensionDocument<'_>, Vec<ParseError>) {
+    Parser::new(document).parse_type_system_document_recovering()
+}
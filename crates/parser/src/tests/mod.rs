@@ -89,6 +89,54 @@ mod operation {
             .unwrap()
         ));
     }
+    #[test]
+    fn query_shorthand() {
+        assert_snapshot!(print_graphql(
+            parse_operation_document(
+                "
+                {
+                    foo
+                    ...Fragment
+                }
+                fragment Fragment on Query { bar }
+                "
+            )
+            .unwrap()
+        ));
+    }
+    #[test]
+    fn fragment_definition() {
+        assert_snapshot!(print_graphql(
+            parse_operation_document(
+                "
+                fragment F on User @wow(a: 1) {
+                    id
+                    ...G
+                }
+                query { ...F }
+                "
+            )
+            .unwrap()
+        ));
+    }
+    #[test]
+    fn import_comments() {
+        assert_snapshot!(print_graphql(
+            parse_operation_document(
+                "
+                #import \"./fragments.graphql\"
+                # normal comment
+                #import './other.graphql'
+                query {
+                    #import \"./not-top-level.graphql\"
+                    foo
+                    ...Fragment
+                }
+                #import \"./last.graphql\""
+            )
+            .unwrap()
+        ));
+    }
 
     fn print_graphql<T: GraphQLPrinter>(value: T) -> String {
         let mut result = String::new();
@@ -267,3 +315,415 @@ mod definition {
         result
     }
 }
+
+#[cfg(test)]
+mod document {
+    use insta::assert_snapshot;
+    use nitrogql_printer::GraphQLPrinter;
+    use sourcemap_writer::JustWriter;
+
+    use crate::parse_document;
+
+    #[test]
+    fn mixed_document() {
+        let (operations, type_system) = parse_document(
+            "
+            #import \"./fragments.graphql\"
+            type Query { me: User }
+            { me { ...F } }
+            \"User of the service\"
+            type User { id: ID! }
+            fragment F on User { id }
+            extend type User { name: String }
+            query Q { me { id } }
+            ",
+        )
+        .unwrap()
+        .split();
+        let mut result = String::new();
+        let mut writer = JustWriter::new(&mut result);
+        operations.print_graphql(&mut writer);
+        type_system.print_graphql(&mut writer);
+        assert_snapshot!(result);
+    }
+
+    #[test]
+    fn unexpected_token() {
+        let error = parse_document("type Query { me: User }\n[").unwrap_err();
+        assert_snapshot!(format!("{:?}", error));
+    }
+}
+
+#[cfg(test)]
+mod string {
+    use insta::assert_snapshot;
+    use nitrogql_ast::{
+        base::Pos,
+        value::{StringValue, Value},
+    };
+    use nitrogql_printer::GraphQLPrinter;
+    use sourcemap_writer::{JustWriter, SourceMapWriter};
+
+    use crate::parse_value;
+
+    #[test]
+    fn block_string_value() {
+        let values = [
+            "\"\"\"\n    Common indentation\n      is removed.\n\n    Blank lines are kept.\n  \"\"\"",
+            "\"\"\"First line is not dedented\n  second\n    third\"\"\"",
+            "\"\"\"\r\n\tCRLF\r\n\tand CR\r\tline breaks\r\n\"\"\"",
+            "\"\"\" Escaped \\\"\"\" and raw \\n \"\"\"",
+            "\"\"\"\n  \n   \n\"\"\"",
+        ];
+        let result = values
+            .map(|source| format!("{:?}", string_value(source)))
+            .join("\n");
+        assert_snapshot!(result);
+    }
+
+    #[test]
+    fn printed_string_has_same_value() {
+        let values = [
+            "plain",
+            "quote \" and backslash \\ and \\n",
+            "control \u{0} \u{8} \u{c} \u{1b} \r\t",
+            "multiple\nlines\n  with indentation",
+            "  all lines\n  indented",
+            "\nleading and trailing blank lines\n",
+            "triple \"\"\" quotes\nand \"\"\"\" more",
+            "ends with quote\n\"",
+            "",
+        ];
+        for value in values {
+            let mut printed = String::new();
+            let mut writer = JustWriter::new(&mut printed);
+            writer.indent();
+            StringValue {
+                position: Pos::default(),
+                value: value.to_owned(),
+            }
+            .print_graphql(&mut writer);
+            assert_eq!(string_value(&printed), value, "printed as {printed}");
+        }
+    }
+
+    fn string_value(source: &str) -> String {
+        match parse_value(source).unwrap() {
+            Value::StringValue(value) => value.value,
+            value => panic!("{value:?} is not a string"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod position {
+    use crate::parser::parse_operation_document;
+    use insta::assert_debug_snapshot;
+    use nitrogql_ast::{
+        operation::ExecutableDefinition,
+        selection_set::Selection,
+        value::{Arguments, Value},
+    };
+
+    #[test]
+    fn spans() {
+        let doc = parse_operation_document(
+            "query sample {
+  foo(arg: [1, 2])
+}
+",
+        )
+        .unwrap();
+        let ExecutableDefinition::OperationDefinition(ref op) = doc.definitions[0] else {
+            panic!("Expected an operation definition")
+        };
+        let Selection::Field(ref field) = op.selection_set.selections[0] else {
+            panic!("Expected a field")
+        };
+        let Some(Arguments { ref arguments, .. }) = field.arguments else {
+            panic!("Expected arguments")
+        };
+        let Value::ListValue(ref list) = arguments[0].1 else {
+            panic!("Expected a list value")
+        };
+        assert_debug_snapshot!([
+            op.position,
+            op.name.unwrap().position,
+            op.selection_set.position,
+            field.name.position,
+            list.position,
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod embedded {
+    use crate::{
+        extract_embedded_documents, parse_embedded_operation_documents, EmbeddedDocumentOptions,
+    };
+    use insta::assert_debug_snapshot;
+    use nitrogql_ast::operation::ExecutableDefinition;
+
+    #[test]
+    fn tagged_templates() {
+        let source = r#"import { gql } from "@apollo/client";
+// gql`not a document`
+const re = /`/g;
+const s = "gql`also not a document`";
+const untagged = `query Untagged { a }`;
+const member = api.gql`query Member { a }`;
+export const Query = gql`
+  query Q { ...F }
+  ${Fragment}
+`;
+const Fragment = graphql`fragment F on User { id ${`nested`} }`;
+"#;
+        let documents = extract_embedded_documents(source, &EmbeddedDocumentOptions::default());
+        assert_debug_snapshot!(documents
+            .iter()
+            .map(|document| (document.source.as_ref(), document.start))
+            .collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn module_sources() {
+        let source = r#"import { gql as apolloGql } from "@apollo/client";
+import graphql from "graphql-tag";
+import { gql } from "other";
+apolloGql`query A { a }`;
+graphql`query B { b }`;
+gql`query C { c }`;
+"#;
+        let documents = extract_embedded_documents(
+            source,
+            &EmbeddedDocumentOptions {
+                module_sources: vec!["@apollo/client".to_owned(), "graphql-tag".to_owned()],
+                ..Default::default()
+            },
+        );
+        assert_debug_snapshot!(documents
+            .iter()
+            .map(|document| document.source.as_ref())
+            .collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn positions_in_file() {
+        let source = "const a = 1;
+const q = gql`query Q {
+  foo
+}`;
+const r = gql`query R { bar }`;
+";
+        let documents = extract_embedded_documents(source, &EmbeddedDocumentOptions::default());
+        let (document, errors) = parse_embedded_operation_documents(&documents);
+        assert!(errors.is_empty());
+        assert_debug_snapshot!(document
+            .definitions
+            .iter()
+            .map(|def| {
+                let ExecutableDefinition::OperationDefinition(ref op) = def else {
+                    panic!("Expected an operation definition")
+                };
+                op.name.unwrap().position
+            })
+            .collect::<Vec<_>>());
+
+        let documents = extract_embedded_documents(
+            "const q = 1;\nconst r = gql`query R { bar(: 1) }`;\n",
+            &EmbeddedDocumentOptions::default(),
+        );
+        let (_, errors) = parse_embedded_operation_documents(&documents);
+        assert_debug_snapshot!(errors);
+    }
+
+    #[test]
+    fn incomplete_sources() {
+        // Sources being edited end in the middle of templates, strings and escapes.
+        let sources = [
+            "gql`${`\\",
+            "gql`${\"\\",
+            "gql`${'a\\",
+            "gql`${/a\\",
+            "gql`query { a } ${`${`\\",
+            "gql`query { a \\",
+            "gql`${`\\é",
+        ];
+        for source in sources {
+            let documents = extract_embedded_documents(source, &EmbeddedDocumentOptions::default());
+            assert_eq!(documents.len(), 1, "{source}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod recover {
+    use crate::{parse_operation_document_recovering, parse_type_system_document_recovering};
+    use insta::assert_snapshot;
+    use nitrogql_printer::GraphQLPrinter;
+    use sourcemap_writer::JustWriter;
+
+    #[test]
+    fn operation_document() {
+        let (document, errors) = parse_operation_document_recovering(
+            "#import \"./fragments.graphql\"
+query A { a }
+query B { b( }
+fragment F on User { id
+query C($v: Int) { c(v: $v) }
+mutation D { d: }
+",
+        );
+        assert_snapshot!(print_result(document, errors));
+    }
+
+    #[test]
+    fn type_system_document() {
+        let (document, errors) = parse_type_system_document_recovering(
+            "\"Root\"
+type Query {
+  a: Int
+  type: Strin g
+}
+extend type Query @dir(arg: \"type Foo\")
+\"\"\"
+Broken enum
+\"\"\"
+enum E { A B
+scalar Date
+union U = A | B
+directive @dir(arg: String) on OBJECT",
+        );
+        assert_snapshot!(print_result(document, errors));
+    }
+
+    #[test]
+    fn partial_selection_sets() {
+        let (document, errors) = parse_operation_document_recovering(
+            "query A {
+  me {
+  }
+  user(id: ) { name }
+}
+query B { me { name",
+        );
+        assert_snapshot!(print_result(document, errors));
+    }
+
+    #[test]
+    fn no_definition() {
+        let (document, errors) = parse_operation_document_recovering("# only comments\n");
+        assert_snapshot!(print_result(document, errors));
+    }
+
+    fn print_result<T: GraphQLPrinter>(document: T, errors: Vec<crate::ParseError>) -> String {
+        let mut result = String::new();
+        let mut writer = JustWriter::new(&mut result);
+        document.print_graphql(&mut writer);
+        result.push_str("\n---\n");
+        for error in errors {
+            result.push_str(&format!("{error:?}\n"));
+        }
+        result
+    }
+}
+
+#[cfg(all(test, feature = "pest"))]
+mod pest_parser {
+    use crate::{parse_operation_document, parse_type_system_document, parse_value, pest_parser};
+
+    /// Asserts that both parsers produce the same result including positions.
+    macro_rules! assert_same {
+        ($parse:ident, $source:expr) => {
+            assert_eq!(
+                format!("{:#?}", $parse($source).unwrap()),
+                format!("{:#?}", pest_parser::$parse($source).unwrap()),
+            )
+        };
+    }
+
+    #[test]
+    fn operation_document() {
+        assert_same!(
+            parse_operation_document,
+            "#import \"./a.graphql\"
+#import './b.graphql'  
+# comment
+query A($a: Int = 1 @x, $b: [String!]! @y(z: 2)  , $c: ID) @dir {
+  foo: bar(arg: { a: [1, 2.5e3, \"s\\u00e9\\n\", \"\"\"block\"\"\", true, null, ENUM, $a] }) @skip(if: false) {
+    ...Frag  # spread
+    ...Frag @a @b
+    ...Frag @a(b: 1)
+    ... on User { id }
+    ... @include(if: $b) { id }
+  }
+}
+#import \"./c.graphql\"
+fragment Frag on User @a { id, name }
+subscription { s }
+"
+        );
+        assert_same!(
+            parse_operation_document,
+            "\u{feff}query Q($a: Int @a(b: 1) @c(d: 2)) {\r\n  a(s: \"\u{e9}\\u{1F600}\") # \u{e9}t\u{e9}\r\n  ...F @a(b: 1) @c(d: 2)\r\n  ...G @a(b: 1) @c\r\n}\r\n#import \"./x.graphql\""
+        );
+        assert_same!(
+            parse_operation_document,
+            "{ a }\n# comment\n{\n  b { ...F }\n}\nfragment F on B { c }"
+        );
+        assert_same!(
+            parse_operation_document,
+            "query\n#import \"./not-an-import.graphql\"\nQ { a }\n"
+        );
+    }
+
+    #[test]
+    fn type_system_document() {
+        assert_same!(
+            parse_type_system_document,
+            "\"\"\"
+Schema
+\"\"\"
+schema @a { query: Query mutation: Mutation }
+scalar Date  # trailing comment
+scalar Time @a
+scalar Url @a(b: 1)
+\"Object\"
+type Query implements & Node & Entity @a {
+  \"Field\" field(\"Arg\" arg: Int = 1 @a, other: [[Int]!]): String @deprecated
+}
+type Empty @a
+interface Node { id: ID! }
+interface Bare
+union U = | A | B
+union V @a = A
+enum E @a { A \"Desc\" B @deprecated(reason: \"no\") }
+enum F
+input I { a: Int! = 1 @a, b: I }
+input J
+directive @a(b: Int) repeatable on | FIELD | OBJECT
+directive @b on ENUM_VALUE
+extend schema @a
+extend schema { subscription: Subscription }
+extend scalar Date @b
+extend type Query { more: Int }
+extend type Query implements Other
+extend type Query @b(c: 1)
+extend interface Node @a
+extend union U = C
+extend union U @a
+extend enum E { C }
+extend input I @a
+"
+        );
+        assert_same!(
+            parse_type_system_document,
+            "scalar A @a(b: 1) @c(d: 2)\r\nextend scalar A @a @b(c: 1)\r\nunion U = A | B\r\nextend union U = C\r\nextend union U @a(b: 1) @c(d: 2)\r\nextend type T implements I\r\ndirective @d on FIELD\r\n\"\u{e9}\" type T { f: Int }"
+        );
+    }
+
+    #[test]
+    fn value() {
+        assert_same!(parse_value, "{ a: [1, -0.5, \"x\"], b: { c: null } }");
+    }
+}
@@ -0,0 +1,79 @@
+/// A comment in the source text.
+#[derive(Debug, Clone, Copy)]
+pub struct Comment<'src> {
+    /// Byte offset of '#'.
+    pub offset: usize,
+    /// Text of the comment including '#', without trailing whitespace.
+    pub text: &'src str,
+    /// Whether the comment follows other tokens on the same line.
+    pub trailing: bool,
+}
+
+/// Collects comments in given source text, skipping string literals.
+pub fn collect_comments(source: &str) -> Vec<Comment<'_>> {
+    let bytes = source.as_bytes();
+    let mut result = vec![];
+    let mut line_has_token = false;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'#' => {
+                let end = source[index..]
+                    .find(['\n', '\r'])
+                    .map_or(source.len(), |len| index + len);
+                result.push(Comment {
+                    offset: index,
+                    text: source[index..end].trim_end(),
+                    trailing: line_has_token,
+                });
+                index = end;
+            }
+            b'\n' | b'\r' => {
+                line_has_token = false;
+                index += 1;
+            }
+            b' ' | b'\t' | b',' => {
+                index += 1;
+            }
+            b'"' if bytes[index..].starts_with(b"\"\"\"") => {
+                line_has_token = true;
+                index += 3;
+                while index < bytes.len() && !bytes[index..].starts_with(b"\"\"\"") {
+                    if bytes[index..].starts_with(b"\\\"\"\"") {
+                        index += 4;
+                    } else {
+                        index += 1;
+                    }
+                }
+                index += 3;
+            }
+            b'"' => {
+                line_has_token = true;
+                index += 1;
+                while index < bytes.len() && !matches!(bytes[index], b'"' | b'\n' | b'\r') {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+                index += 1;
+            }
+            _ => {
+                line_has_token = true;
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
+/// Checks whether there is an empty line right before given offset.
+pub fn has_blank_line_before(source: &str, offset: usize) -> bool {
+    let newlines = source[..offset.min(source.len())]
+        .bytes()
+        .rev()
+        .take_while(|b| matches!(b, b' ' | b'\t' | b',' | b'\r' | b'\n'))
+        .filter(|b| *b == b'\n')
+        .count();
+    newlines >= 2
+}
@@ -0,0 +1,206 @@
+//! Document model for laying out formatted output within a line width.
+
+/// Intermediate representation of formatted output.
+#[derive(Debug, Clone)]
+pub enum Doc {
+    /// Text that does not contain line breaks.
+    Text(String),
+    Concat(Vec<Doc>),
+    /// Contents are printed in one line if they fit in the line width.
+    /// Otherwise, line breaks directly in the group are broken.
+    Group(Box<Doc>),
+    /// Increases indentation of lines in contents.
+    Indent(Box<Doc>),
+    /// A space, or a line break if the enclosing group is broken.
+    Line,
+    /// Nothing, or a line break if the enclosing group is broken.
+    SoftLine,
+    /// A line break that is always printed. Enclosing groups are broken.
+    HardLine,
+    /// `broken` if the enclosing group is broken, `flat` otherwise.
+    IfBreak {
+        broken: String,
+        flat: String,
+    },
+    /// Text deferred to the end of the current line, such as a trailing comment.
+    /// Enclosing groups are broken so that the text stays on the line where it is put.
+    LineSuffix(String),
+}
+
+pub fn text(s: impl Into<String>) -> Doc {
+    Doc::Text(s.into())
+}
+
+pub fn concat(docs: Vec<Doc>) -> Doc {
+    Doc::Concat(docs)
+}
+
+pub fn group(doc: Doc) -> Doc {
+    Doc::Group(Box::new(doc))
+}
+
+pub fn indent(doc: Doc) -> Doc {
+    Doc::Indent(Box::new(doc))
+}
+
+/// Joins docs with given separator.
+pub fn join(docs: impl IntoIterator<Item = Doc>, separator: impl Fn() -> Doc) -> Doc {
+    let mut result = vec![];
+    for (idx, doc) in docs.into_iter().enumerate() {
+        if idx > 0 {
+            result.push(separator());
+        }
+        result.push(doc);
+    }
+    concat(result)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+type Command<'a> = (usize, Mode, &'a Doc);
+
+/// Prints given doc as a string.
+pub fn render(doc: &Doc, indent_width: usize, line_width: usize) -> String {
+    let mut printer = Renderer {
+        indent_width,
+        output: String::new(),
+        column: 0,
+        pending_indent: None,
+        line_suffix: vec![],
+    };
+    let mut commands: Vec<Command> = vec![(0, Mode::Break, doc)];
+    while let Some((level, mode, doc)) = commands.pop() {
+        match doc {
+            Doc::Text(s) => printer.write(s),
+            Doc::Concat(docs) => {
+                commands.extend(docs.iter().rev().map(|doc| (level, mode, doc)));
+            }
+            Doc::Group(inner) => {
+                let mode = if mode == Mode::Flat
+                    || (!must_break(inner)
+                        && fits(
+                            (level, Mode::Flat, inner),
+                            &commands,
+                            line_width as isize - printer.column as isize,
+                        )) {
+                    Mode::Flat
+                } else {
+                    Mode::Break
+                };
+                commands.push((level, mode, inner));
+            }
+            Doc::Indent(inner) => commands.push((level + 1, mode, inner)),
+            Doc::Line => match mode {
+                Mode::Flat => printer.write(" "),
+                Mode::Break => printer.newline(level),
+            },
+            Doc::SoftLine => {
+                if mode == Mode::Break {
+                    printer.newline(level);
+                }
+            }
+            Doc::HardLine => printer.newline(level),
+            Doc::IfBreak { broken, flat } => match mode {
+                Mode::Flat => printer.write(flat),
+                Mode::Break => printer.write(broken),
+            },
+            Doc::LineSuffix(s) => printer.line_suffix.push(s),
+        }
+    }
+    printer.flush_line_suffix();
+    printer.output
+}
+
+struct Renderer<'a> {
+    indent_width: usize,
+    output: String,
+    column: usize,
+    /// Indentation level to be written before the next text.
+    pending_indent: Option<usize>,
+    line_suffix: Vec<&'a str>,
+}
+
+impl<'a> Renderer<'a> {
+    fn write(&mut self, s: &str) {
+        if s.is_empty() {
+            return;
+        }
+        if let Some(level) = self.pending_indent.take() {
+            self.output.push_str(&" ".repeat(level * self.indent_width));
+        }
+        self.output.push_str(s);
+        self.column += s.chars().count();
+    }
+    fn newline(&mut self, level: usize) {
+        self.flush_line_suffix();
+        let trimmed_len = self.output.trim_end_matches(' ').len();
+        self.output.truncate(trimmed_len);
+        self.output.push('\n');
+        self.column = level * self.indent_width;
+        self.pending_indent = Some(level);
+    }
+    fn flush_line_suffix(&mut self) {
+        for s in std::mem::take(&mut self.line_suffix) {
+            self.write(s);
+        }
+    }
+}
+
+/// Checks whether `next` printed in flat mode fits in `width`,
+/// followed by the rest of `commands` up to the next line break.
+fn fits(next: Command, commands: &[Command], mut width: isize) -> bool {
+    let mut rest_index = commands.len();
+    let mut stack = vec![next];
+    while width >= 0 {
+        let Some((level, mode, doc)) = stack.pop() else {
+            if rest_index == 0 {
+                return true;
+            }
+            rest_index -= 1;
+            stack.push(commands[rest_index]);
+            continue;
+        };
+        match doc {
+            Doc::Text(s) => width -= s.chars().count() as isize,
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (level, mode, doc))),
+            Doc::Group(inner) => {
+                let mode = if must_break(inner) { Mode::Break } else { mode };
+                stack.push((level, mode, inner));
+            }
+            Doc::Indent(inner) => stack.push((level + 1, mode, inner)),
+            Doc::Line => match mode {
+                Mode::Flat => width -= 1,
+                Mode::Break => return true,
+            },
+            Doc::SoftLine => {
+                if mode == Mode::Break {
+                    return true;
+                }
+            }
+            Doc::HardLine => return true,
+            Doc::IfBreak { broken, flat } => {
+                let s = match mode {
+                    Mode::Flat => flat,
+                    Mode::Break => broken,
+                };
+                width -= s.chars().count() as isize;
+            }
+            Doc::LineSuffix(_) => {}
+        }
+    }
+    false
+}
+
+/// Checks whether a group containing given doc must be broken.
+fn must_break(doc: &Doc) -> bool {
+    match doc {
+        Doc::HardLine | Doc::LineSuffix(_) => true,
+        Doc::Concat(docs) => docs.iter().any(must_break),
+        Doc::Group(inner) | Doc::Indent(inner) => must_break(inner),
+        _ => false,
+    }
+}
@@ -0,0 +1,382 @@
+//! Formatter that pretty-prints GraphQL documents while preserving comments.
+
+use nitrogql_ast::{
+    base::HasPos,
+    directive::Directive,
+    r#type::Type,
+    value::{Arguments, StringValue, Value},
+    OperationDocument, TypeSystemOrExtensionDocument,
+};
+
+use self::{
+    comments::{collect_comments, has_blank_line_before, Comment},
+    doc::{concat, group, indent, render, text, Doc},
+    string::print_string_value,
+};
+
+mod comments;
+mod doc;
+mod operation;
+mod string;
+#[cfg(test)]
+mod tests;
+mod type_system;
+
+/// Options for formatting GraphQL documents.
+#[derive(Debug, Clone)]
+pub struct GraphQLFormatterOptions {
+    /// Number of spaces for one level of indentation.
+    pub indent_width: usize,
+    /// Width of lines that the formatter tries to fit the output in.
+    pub line_width: usize,
+    /// Whether top-level definitions are sorted by kind and name.
+    /// If false, definitions are kept in the original order.
+    pub sort_definitions: bool,
+}
+
+impl Default for GraphQLFormatterOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 2,
+            line_width: 80,
+            sort_definitions: false,
+        }
+    }
+}
+
+/// Formats an operation document parsed from `source`.
+pub fn format_operation_document(
+    source: &str,
+    document: &OperationDocument,
+    options: &GraphQLFormatterOptions,
+) -> String {
+    // Import comments are printed separately from other comments.
+    let comments = collect_comments(source)
+        .into_iter()
+        .filter(|comment| {
+            document
+                .imports
+                .iter()
+                .all(|import| import.position.offset != comment.offset)
+        })
+        .collect();
+    let mut formatter = Formatter::new(source, comments);
+    let doc = formatter.operation_document(document, options.sort_definitions);
+    render(&doc, options.indent_width, options.line_width)
+}
+
+/// Formats a type system document parsed from `source`.
+pub fn format_type_system_document(
+    source: &str,
+    document: &TypeSystemOrExtensionDocument,
+    options: &GraphQLFormatterOptions,
+) -> String {
+    let mut formatter = Formatter::new(source, collect_comments(source));
+    let doc = formatter.type_system_document(document, options.sort_definitions);
+    render(&doc, options.indent_width, options.line_width)
+}
+
+struct Formatter<'src> {
+    source: &'src str,
+    comments: Vec<Comment<'src>>,
+    /// Index of the next comment to be printed.
+    next_comment: usize,
+    /// Comments at or after this index are not printed until the limit is changed.
+    comment_limit: usize,
+}
+
+/// Comments that precede a node.
+struct Leading {
+    /// Comments that follow other code on the same line, printed at the end of the current line.
+    trailing: Vec<Doc>,
+    /// Comments on their own lines, each followed by a line break.
+    own_line: Vec<Doc>,
+    /// Offset of the first own-line comment, or the node if there is none.
+    start: usize,
+}
+
+/// How items in a bracketed list are laid out.
+#[derive(Copy, Clone)]
+enum ListStyle {
+    /// Each item is on its own line, like fields in a selection set.
+    Block,
+    /// Items are on one line if they fit, like arguments.
+    /// If `padded`, spaces are put inside the brackets when printed in one line.
+    Inline { padded: bool },
+}
+
+/// A top-level definition with the range it occupies in the source.
+struct TopLevel<'a, Def> {
+    definition: &'a Def,
+    start: usize,
+    end: usize,
+    sort_key: (u8, &'a str),
+}
+
+impl<'src> Formatter<'src> {
+    fn new(source: &'src str, comments: Vec<Comment<'src>>) -> Self {
+        let comment_limit = comments.len();
+        Formatter {
+            source,
+            comments,
+            next_comment: 0,
+            comment_limit,
+        }
+    }
+
+    /// Takes comments that come before given offset.
+    fn take_comments(&mut self, offset: usize) -> Vec<Comment<'src>> {
+        let start = self.next_comment;
+        while self.next_comment < self.comment_limit
+            && self.comments[self.next_comment].offset < offset
+        {
+            self.next_comment += 1;
+        }
+        self.comments[start..self.next_comment].to_vec()
+    }
+
+    /// Takes comments that come before a node at given offset.
+    /// If `allow_trailing` is false, all comments are printed on their own lines.
+    fn leading(&mut self, offset: usize, allow_trailing: bool) -> Leading {
+        let mut leading = Leading {
+            trailing: vec![],
+            own_line: vec![],
+            start: offset,
+        };
+        for comment in self.take_comments(offset) {
+            if allow_trailing && comment.trailing && leading.own_line.is_empty() {
+                leading
+                    .trailing
+                    .push(Doc::LineSuffix(format!(" {}", comment.text)));
+                continue;
+            }
+            if leading.own_line.is_empty() {
+                leading.start = comment.offset;
+            } else if has_blank_line_before(self.source, comment.offset) {
+                leading.own_line.push(Doc::HardLine);
+            }
+            leading.own_line.push(text(comment.text));
+            leading.own_line.push(Doc::HardLine);
+        }
+        if !leading.own_line.is_empty() && has_blank_line_before(self.source, offset) {
+            leading.own_line.push(Doc::HardLine);
+        }
+        leading
+    }
+
+    /// Prints comments that are left before given offset after a node.
+    fn remaining_comments(&mut self, offset: usize) -> Doc {
+        let leading = self.leading(offset, true);
+        let mut docs = leading.trailing;
+        if !leading.own_line.is_empty() {
+            docs.push(Doc::HardLine);
+            if has_blank_line_before(self.source, leading.start) {
+                docs.push(Doc::HardLine);
+            }
+            docs.extend(without_last_line_breaks(leading.own_line));
+        }
+        concat(docs)
+    }
+
+    /// Prints a bracketed list of items.
+    /// `anchor` returns the offset of each item, which comments are placed before.
+    /// `end` is the offset of the closing bracket, if known.
+    #[allow(clippy::too_many_arguments)]
+    fn list<T>(
+        &mut self,
+        open: &str,
+        close: &str,
+        items: &[T],
+        anchor: impl Fn(&T) -> usize,
+        end: Option<usize>,
+        style: ListStyle,
+        mut print: impl FnMut(&mut Self, &T) -> Doc,
+    ) -> Doc {
+        let mut docs = vec![];
+        for (idx, item) in items.iter().enumerate() {
+            let leading = self.leading(anchor(item), true);
+            docs.extend(leading.trailing);
+            match (style, idx) {
+                (ListStyle::Block, 0) => docs.push(Doc::HardLine),
+                (ListStyle::Block, _) => {
+                    docs.push(Doc::HardLine);
+                    if has_blank_line_before(self.source, leading.start) {
+                        docs.push(Doc::HardLine);
+                    }
+                }
+                (ListStyle::Inline { padded: true }, 0) => docs.push(Doc::Line),
+                (ListStyle::Inline { padded: false }, 0) => docs.push(Doc::SoftLine),
+                (ListStyle::Inline { .. }, _) => {
+                    docs.push(Doc::IfBreak {
+                        broken: String::new(),
+                        flat: ",".to_owned(),
+                    });
+                    docs.push(Doc::Line);
+                }
+            }
+            docs.extend(leading.own_line);
+            docs.push(print(self, item));
+        }
+        if let Some(end) = end {
+            docs.push(self.remaining_comments(end));
+        }
+        match style {
+            ListStyle::Block => concat(vec![
+                text(open),
+                indent(concat(docs)),
+                Doc::HardLine,
+                text(close),
+            ]),
+            ListStyle::Inline { padded } => group(concat(vec![
+                text(open),
+                indent(concat(docs)),
+                if padded { Doc::Line } else { Doc::SoftLine },
+                text(close),
+            ])),
+        }
+    }
+
+    /// Prints top-level definitions separated by empty lines.
+    /// Comments before a definition and at the end of its last line move together with the definition.
+    fn definitions<Def>(
+        &mut self,
+        definitions: Vec<TopLevel<Def>>,
+        sort: bool,
+        mut print: impl FnMut(&mut Self, &Def) -> Doc,
+    ) -> Doc {
+        // Assign comments to definitions in the source order.
+        let mut comment_ranges = vec![];
+        let mut next_comment = 0;
+        for def in definitions.iter() {
+            let start = next_comment;
+            while next_comment < self.comments.len() {
+                let comment = &self.comments[next_comment];
+                let belongs_to_def = comment.offset < def.end
+                    || (comment.trailing && !self.source[def.end..comment.offset].contains('\n'));
+                if !belongs_to_def {
+                    break;
+                }
+                next_comment += 1;
+            }
+            comment_ranges.push((start, next_comment));
+        }
+
+        let mut order = (0..definitions.len()).collect::<Vec<_>>();
+        if sort {
+            order.sort_by_key(|idx| definitions[*idx].sort_key);
+        }
+        let mut docs = vec![];
+        for (idx, def_idx) in order.into_iter().enumerate() {
+            let def = &definitions[def_idx];
+            (self.next_comment, self.comment_limit) = comment_ranges[def_idx];
+            if idx > 0 {
+                docs.push(Doc::HardLine);
+                docs.push(Doc::HardLine);
+            }
+            docs.extend(self.leading(def.start, false).own_line);
+            docs.push(print(self, def.definition));
+            docs.push(self.remaining_comments(usize::MAX));
+        }
+
+        // Comments at the end of the document.
+        (self.next_comment, self.comment_limit) = (next_comment, self.comments.len());
+        if docs.is_empty() {
+            docs.extend(without_last_line_breaks(
+                self.leading(usize::MAX, false).own_line,
+            ));
+        } else {
+            docs.push(self.remaining_comments(usize::MAX));
+        }
+        docs.push(Doc::HardLine);
+        concat(docs)
+    }
+
+    fn directives(&mut self, directives: &[Directive]) -> Doc {
+        concat(
+            directives
+                .iter()
+                .map(|directive| {
+                    concat(vec![
+                        text(format!(" @{}", directive.name.name)),
+                        match directive.arguments {
+                            Some(ref arguments) => self.arguments(arguments),
+                            None => concat(vec![]),
+                        },
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    fn arguments(&mut self, arguments: &Arguments) -> Doc {
+        self.list(
+            "(",
+            ")",
+            &arguments.arguments,
+            |(name, _)| name.position.offset,
+            Some(arguments.position.end_offset.saturating_sub(1)),
+            ListStyle::Inline { padded: false },
+            |formatter, (name, value)| {
+                concat(vec![text(name.name), text(": "), formatter.value(value)])
+            },
+        )
+    }
+
+    fn value(&mut self, value: &Value) -> Doc {
+        match value {
+            Value::Variable(variable) => text(format!("${}", variable.name)),
+            Value::IntValue(value) => text(value.value),
+            Value::FloatValue(value) => text(value.value),
+            Value::StringValue(value) => self.string(value),
+            Value::BooleanValue(value) => text(value.keyword),
+            Value::NullValue(value) => text(value.keyword),
+            Value::EnumValue(value) => text(value.value),
+            Value::ListValue(value) => self.list(
+                "[",
+                "]",
+                &value.values,
+                |value| value.position().offset,
+                Some(value.position.end_offset.saturating_sub(1)),
+                ListStyle::Inline { padded: false },
+                |formatter, value| formatter.value(value),
+            ),
+            Value::ObjectValue(value) if value.fields.is_empty() => text("{}"),
+            Value::ObjectValue(value) => self.list(
+                "{",
+                "}",
+                &value.fields,
+                |(name, _)| name.position.offset,
+                Some(value.position.end_offset.saturating_sub(1)),
+                ListStyle::Inline { padded: true },
+                |formatter, (name, value)| {
+                    concat(vec![text(name.name), text(": "), formatter.value(value)])
+                },
+            ),
+        }
+    }
+
+    /// Prints a string value. Block strings are kept as block strings.
+    fn string(&self, value: &StringValue) -> Doc {
+        let is_block = self.source[value.position.offset..].starts_with("\"\"\"");
+        print_string_value(&value.value, is_block)
+    }
+
+    /// Prints a description followed by a line break.
+    fn description(&self, description: &Option<StringValue>) -> Doc {
+        match description {
+            Some(description) => concat(vec![self.string(description), Doc::HardLine]),
+            None => concat(vec![]),
+        }
+    }
+}
+
+fn print_type(ty: &Type) -> Doc {
+    text(ty.to_string())
+}
+
+fn without_last_line_breaks(mut docs: Vec<Doc>) -> Vec<Doc> {
+    while matches!(docs.last(), Some(Doc::HardLine)) {
+        docs.pop();
+    }
+    docs
+}
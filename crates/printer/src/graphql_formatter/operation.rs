@@ -0,0 +1,148 @@
+use nitrogql_ast::{
+    base::HasPos,
+    operation::{ExecutableDefinition, FragmentDefinition, OperationDefinition},
+    selection_set::{Selection, SelectionSet},
+    variable::VariableDefinition,
+    OperationDocument,
+};
+
+use super::{
+    doc::{concat, text, Doc},
+    print_type, Formatter, ListStyle, TopLevel,
+};
+
+impl Formatter<'_> {
+    pub(super) fn operation_document(&mut self, document: &OperationDocument, sort: bool) -> Doc {
+        let mut docs = vec![];
+        for import in document.imports.iter() {
+            docs.push(text(format!("#import \"{}\"", import.path)));
+            docs.push(Doc::HardLine);
+        }
+        if !document.imports.is_empty() {
+            docs.push(Doc::HardLine);
+        }
+        let definitions = document
+            .definitions
+            .iter()
+            .map(|def| TopLevel {
+                definition: def,
+                start: def.position().offset,
+                end: def.position().end_offset,
+                sort_key: match def {
+                    ExecutableDefinition::OperationDefinition(op) => {
+                        (0, op.name.map_or("", |name| name.name))
+                    }
+                    ExecutableDefinition::FragmentDefinition(fragment) => (1, fragment.name.name),
+                },
+            })
+            .collect();
+        docs.push(
+            self.definitions(definitions, sort, |formatter, def| match def {
+                ExecutableDefinition::OperationDefinition(op) => formatter.operation(op),
+                ExecutableDefinition::FragmentDefinition(fragment) => formatter.fragment(fragment),
+            }),
+        );
+        concat(docs)
+    }
+
+    fn operation(&mut self, op: &OperationDefinition) -> Doc {
+        if op.shorthand {
+            return self.selection_set(&op.selection_set);
+        }
+        let mut docs = vec![text(op.operation_type.as_str())];
+        if let Some(name) = op.name {
+            docs.push(text(format!(" {}", name.name)));
+        }
+        if let Some(ref variables) = op.variables_definition {
+            docs.push(self.list(
+                "(",
+                ")",
+                &variables.definitions,
+                |def| def.pos.offset,
+                Some(variables.position.end_offset.saturating_sub(1)),
+                ListStyle::Inline { padded: false },
+                |formatter, def| formatter.variable_definition(def),
+            ));
+        }
+        docs.push(self.directives(&op.directives));
+        docs.push(text(" "));
+        docs.push(self.selection_set(&op.selection_set));
+        concat(docs)
+    }
+
+    fn variable_definition(&mut self, def: &VariableDefinition) -> Doc {
+        let mut docs = vec![
+            text(format!("${}: ", def.name.name)),
+            print_type(&def.r#type),
+        ];
+        if let Some(ref default_value) = def.default_value {
+            docs.push(text(" = "));
+            docs.push(self.value(default_value));
+        }
+        docs.push(self.directives(&def.directives));
+        concat(docs)
+    }
+
+    fn fragment(&mut self, fragment: &FragmentDefinition) -> Doc {
+        concat(vec![
+            text(format!(
+                "fragment {} on {}",
+                fragment.name.name, fragment.type_condition.name
+            )),
+            self.directives(&fragment.directives),
+            text(" "),
+            self.selection_set(&fragment.selection_set),
+        ])
+    }
+
+    fn selection_set(&mut self, selection_set: &SelectionSet) -> Doc {
+        self.list(
+            "{",
+            "}",
+            &selection_set.selections,
+            |selection| match selection {
+                Selection::Field(field) => field.alias.unwrap_or(field.name).position.offset,
+                Selection::FragmentSpread(spread) => spread.position.offset,
+                Selection::InlineFragment(fragment) => fragment.position.offset,
+            },
+            Some(selection_set.position.end_offset.saturating_sub(1)),
+            ListStyle::Block,
+            |formatter, selection| formatter.selection(selection),
+        )
+    }
+
+    fn selection(&mut self, selection: &Selection) -> Doc {
+        match selection {
+            Selection::Field(field) => {
+                let mut docs = vec![];
+                if let Some(alias) = field.alias {
+                    docs.push(text(format!("{}: ", alias.name)));
+                }
+                docs.push(text(field.name.name));
+                if let Some(ref arguments) = field.arguments {
+                    docs.push(self.arguments(arguments));
+                }
+                docs.push(self.directives(&field.directives));
+                if let Some(ref selection_set) = field.selection_set {
+                    docs.push(text(" "));
+                    docs.push(self.selection_set(selection_set));
+                }
+                concat(docs)
+            }
+            Selection::FragmentSpread(spread) => concat(vec![
+                text(format!("...{}", spread.fragment_name.name)),
+                self.directives(&spread.directives),
+            ]),
+            Selection::InlineFragment(fragment) => {
+                let mut docs = vec![text("...")];
+                if let Some(type_condition) = fragment.type_condition {
+                    docs.push(text(format!(" on {}", type_condition.name)));
+                }
+                docs.push(self.directives(&fragment.directives));
+                docs.push(text(" "));
+                docs.push(self.selection_set(&fragment.selection_set));
+                concat(docs)
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use super::doc::{concat, text, Doc};
+use crate::graphql_printer::utils::{is_block_representable, quote_string};
+
+/// Prints a string value.
+/// If `block` is true, the value is printed as a block string whenever it is representable as one.
+/// Trailing whitespace in lines of block strings is not preserved.
+pub fn print_string_value(value: &str, block: bool) -> Doc {
+    if block && is_block_representable(value) {
+        let mut docs = vec![text("\"\"\"")];
+        for line in value.split('\n') {
+            docs.push(Doc::HardLine);
+            docs.push(text(line.trim_end().replace("\"\"\"", "\\\"\"\"")));
+        }
+        docs.push(Doc::HardLine);
+        docs.push(text("\"\"\""));
+        concat(docs)
+    } else {
+        text(quote_string(value))
+    }
+}
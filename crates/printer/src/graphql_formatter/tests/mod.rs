@@ -0,0 +1,237 @@
+use insta::assert_snapshot;
+use nitrogql_parser::{parse_operation_document, parse_type_system_document};
+
+use crate::{format_operation_document, format_type_system_document, GraphQLFormatterOptions};
+
+mod operation {
+    use super::*;
+
+    #[test]
+    fn selections_and_variables() {
+        assert_snapshot!(format_operation(
+            "query GetUser($id: ID!,$includePosts:Boolean=false) @live {
+  user(id:$id) { id, name
+    ... on Admin { permissions }
+    posts(first: 10) @include(if: $includePosts) { ...PostFields } }
+}
+fragment PostFields on Post { id title   }",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn comments() {
+        assert_snapshot!(format_operation(
+            "#import \"./fragments.graphql\"
+# Comment before operation
+query Me { # after brace
+  # before field
+
+  me {
+    id # after id
+    # at end of selection set
+  }
+}
+
+# Comment at end of document
+",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn comments_in_lists() {
+        assert_snapshot!(format_operation(
+            "query Q($a: Int # after a
+  $b: Int) {
+  user(id: 1 # after id
+  ) { id }
+  posts(
+    # before first
+    first: 10
+  ) { id }
+}
+",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn query_shorthand() {
+        assert_snapshot!(format_operation(
+            "# Comment before shorthand
+{ me { id,name } }",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn values() {
+        assert_snapshot!(format_operation(
+            r#"mutation {
+  createPost(input: {title: "Hello \"world\"", tags: ["a", "b"], draft: true, score: 1.5, meta: {}, kind: ARTICLE, parent: null})
+}"#,
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn sort_definitions() {
+        assert_snapshot!(format_operation(
+            "fragment B on User { id }
+# About A
+fragment A on User { id }
+query Q { me { ...A ...B } }
+mutation M { noop }",
+            &GraphQLFormatterOptions {
+                sort_definitions: true,
+                ..Default::default()
+            },
+        ));
+    }
+
+    #[test]
+    fn indent_width() {
+        assert_snapshot!(format_operation(
+            "query { me { friends { id } } }",
+            &GraphQLFormatterOptions {
+                indent_width: 4,
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+mod type_system {
+    use super::*;
+
+    #[test]
+    fn definitions() {
+        assert_snapshot!(format_type_system(
+            "schema { query: Query mutation: Mutation }
+directive @cache(maxAge: Int) repeatable on FIELD_DEFINITION|OBJECT
+\"A user\"
+type User implements Node&Entity @key(fields: \"id\") {
+  id: ID!
+  \"Name of the user\"
+  name(format: NameFormat = FULL): String @deprecated(reason: \"Use fullName\")
+  friends: [User!]!
+}
+interface Node { id: ID! }
+union SearchResult = User|Post
+enum NameFormat { FULL SHORT }
+input UserFilter { name: String = \"\", ids: [ID!] }
+scalar Date @specifiedBy(url: \"https://example.com\")
+extend type Query { me: User }
+extend schema @link(url: \"https://example.com\")",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn block_string_descriptions() {
+        assert_snapshot!(format_type_system(
+            "\"\"\"
+    Type with a long description.
+
+      Indented line.
+    \"\"\"
+type Query {
+  \"\"\"Single line block string\"\"\"
+  a: Int
+  \"\"\"  starts with whitespace\"\"\"
+  b: Int
+}",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn comments() {
+        assert_snapshot!(format_type_system(
+            "# The root type
+type Query {
+  # Comment on field
+  a: Int # trailing
+  # Comment after fields
+}
+# Comment before enum
+
+enum Color {
+  RED
+  # Comment after blank line
+  GREEN
+}",
+            &GraphQLFormatterOptions::default(),
+        ));
+    }
+
+    #[test]
+    fn line_width() {
+        assert_snapshot!(format_type_system(
+            "union LongUnion = FirstMember | SecondMember | ThirdMember | FourthMember | FifthMember
+type Query {
+  search(query: String!, first: Int = 10, after: String, filter: SearchFilter): [LongUnion!]!
+}",
+            &GraphQLFormatterOptions {
+                line_width: 60,
+                ..Default::default()
+            },
+        ));
+    }
+
+    #[test]
+    fn sort_definitions() {
+        assert_snapshot!(format_type_system(
+            "type User { id: ID! }
+# Comment about Query
+type Query { me: User }
+directive @auth on FIELD_DEFINITION
+schema { query: Query }
+enum Animal { CAT DOG }",
+            &GraphQLFormatterOptions {
+                sort_definitions: true,
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+#[test]
+fn idempotent() {
+    let options = GraphQLFormatterOptions {
+        line_width: 40,
+        ..Default::default()
+    };
+    let operation = format_operation(
+        "# c1
+query Q($a: Int = 1, $b: [String!] = [\"x\", \"y\"], $c: Boolean) { # c2
+  field(a: $a, b: $b, c: $c, d: {nested: {deep: [1, 2, 3]}}) { id } # c3
+}",
+        &options,
+    );
+    assert_eq!(operation, format_operation(&operation, &options));
+
+    let schema = format_type_system(
+        "\"\"\"
+  Doc
+\"\"\"
+type Query { # c1
+  field(argumentOne: Int, argumentTwo: String): Int # c2
+}
+union U = AAAAAAAAAA | BBBBBBBBBB | CCCCCCCCCC
+",
+        &options,
+    );
+    assert_eq!(schema, format_type_system(&schema, &options));
+}
+
+fn format_operation(source: &str, options: &GraphQLFormatterOptions) -> String {
+    let document = parse_operation_document(source).unwrap();
+    format_operation_document(source, &document, options)
+}
+
+fn format_type_system(source: &str, options: &GraphQLFormatterOptions) -> String {
+    let document = parse_type_system_document(source).unwrap();
+    format_type_system_document(source, &document, options)
+}
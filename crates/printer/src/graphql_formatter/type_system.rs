@@ -0,0 +1,357 @@
+use nitrogql_ast::{
+    base::{HasPos, Ident, Pos},
+    operation::OperationType,
+    type_system::{
+        ArgumentsDefinition, DirectiveDefinition, EnumValueDefinition, FieldDefinition,
+        InputValueDefinition, TypeDefinition, TypeExtension, TypeSystemDefinitionOrExtension,
+    },
+    value::StringValue,
+    TypeSystemOrExtensionDocument,
+};
+
+use super::{
+    doc::{concat, group, indent, join, text, Doc},
+    print_type, Formatter, ListStyle, TopLevel,
+};
+
+impl Formatter<'_> {
+    pub(super) fn type_system_document(
+        &mut self,
+        document: &TypeSystemOrExtensionDocument,
+        sort: bool,
+    ) -> Doc {
+        let definitions = document
+            .definitions
+            .iter()
+            .map(|def| {
+                let (position, description, sort_key) = match def {
+                    TypeSystemDefinitionOrExtension::SchemaDefinition(def) => {
+                        (def.position, &def.description, (0, ""))
+                    }
+                    TypeSystemDefinitionOrExtension::SchemaExtension(def) => {
+                        (def.position, &None, (0, ""))
+                    }
+                    TypeSystemDefinitionOrExtension::DirectiveDefinition(def) => {
+                        (def.position, &def.description, (1, def.name.name))
+                    }
+                    TypeSystemDefinitionOrExtension::TypeDefinition(def) => {
+                        (*def.position(), description_of(def), (2, def.name().name))
+                    }
+                    TypeSystemDefinitionOrExtension::TypeExtension(def) => {
+                        let (name, position) = extension_name_and_position(def);
+                        (*position, &None, (2, name.name))
+                    }
+                };
+                let start = match description {
+                    Some(description) => description.position.offset.min(position.offset),
+                    None => position.offset,
+                };
+                TopLevel {
+                    definition: def,
+                    start,
+                    end: position.end_offset,
+                    sort_key,
+                }
+            })
+            .collect();
+        self.definitions(definitions, sort, |formatter, def| {
+            formatter.type_system_definition(def)
+        })
+    }
+
+    fn type_system_definition(&mut self, def: &TypeSystemDefinitionOrExtension) -> Doc {
+        match def {
+            TypeSystemDefinitionOrExtension::SchemaDefinition(def) => concat(vec![
+                self.description(&def.description),
+                text("schema"),
+                self.directives(&def.directives),
+                text(" "),
+                self.root_operation_types(&def.definitions, def.position.end_offset),
+            ]),
+            TypeSystemDefinitionOrExtension::SchemaExtension(def) => {
+                let mut docs = vec![text("extend schema"), self.directives(&def.directives)];
+                if !def.definitions.is_empty() {
+                    docs.push(text(" "));
+                    docs.push(self.root_operation_types(&def.definitions, def.position.end_offset));
+                }
+                concat(docs)
+            }
+            TypeSystemDefinitionOrExtension::DirectiveDefinition(def) => {
+                self.directive_definition(def)
+            }
+            TypeSystemDefinitionOrExtension::TypeDefinition(def) => self.type_definition(def),
+            TypeSystemDefinitionOrExtension::TypeExtension(def) => self.type_extension(def),
+        }
+    }
+
+    fn root_operation_types(&mut self, definitions: &[(OperationType, Ident)], end: usize) -> Doc {
+        self.list(
+            "{",
+            "}",
+            definitions,
+            |(_, name)| name.position.offset,
+            Some(end.saturating_sub(1)),
+            ListStyle::Block,
+            |_, (operation_type, name)| text(format!("{}: {}", operation_type.as_str(), name.name)),
+        )
+    }
+
+    fn directive_definition(&mut self, def: &DirectiveDefinition) -> Doc {
+        let mut docs = vec![
+            self.description(&def.description),
+            text(format!("directive @{}", def.name.name)),
+        ];
+        if let Some(ref arguments) = def.arguments {
+            docs.push(self.arguments_definition(arguments));
+        }
+        if def.repeatable.is_some() {
+            docs.push(text(" repeatable"));
+        }
+        docs.push(text(" on"));
+        docs.push(alternatives(&def.locations));
+        concat(docs)
+    }
+
+    fn type_definition(&mut self, def: &TypeDefinition) -> Doc {
+        let end = def.position().end_offset;
+        match def {
+            TypeDefinition::Scalar(def) => concat(vec![
+                self.description(&def.description),
+                text(format!("scalar {}", def.name.name)),
+                self.directives(&def.directives),
+            ]),
+            TypeDefinition::Object(def) => concat(vec![
+                self.description(&def.description),
+                text(format!("type {}", def.name.name)),
+                implements(&def.implements),
+                self.directives(&def.directives),
+                self.fields_definition(&def.fields, end),
+            ]),
+            TypeDefinition::Interface(def) => concat(vec![
+                self.description(&def.description),
+                text(format!("interface {}", def.name.name)),
+                implements(&def.implements),
+                self.directives(&def.directives),
+                self.fields_definition(&def.fields, end),
+            ]),
+            TypeDefinition::Union(def) => concat(vec![
+                self.description(&def.description),
+                text(format!("union {}", def.name.name)),
+                self.directives(&def.directives),
+                union_members(&def.members),
+            ]),
+            TypeDefinition::Enum(def) => concat(vec![
+                self.description(&def.description),
+                text(format!("enum {}", def.name.name)),
+                self.directives(&def.directives),
+                self.enum_values_definition(&def.values, end),
+            ]),
+            TypeDefinition::InputObject(def) => concat(vec![
+                self.description(&def.description),
+                text(format!("input {}", def.name.name)),
+                self.directives(&def.directives),
+                self.input_fields_definition(&def.fields, end),
+            ]),
+        }
+    }
+
+    fn type_extension(&mut self, def: &TypeExtension) -> Doc {
+        let end = extension_name_and_position(def).1.end_offset;
+        match def {
+            TypeExtension::Scalar(def) => concat(vec![
+                text(format!("extend scalar {}", def.name.name)),
+                self.directives(&def.directives),
+            ]),
+            TypeExtension::Object(def) => concat(vec![
+                text(format!("extend type {}", def.name.name)),
+                implements(&def.implements),
+                self.directives(&def.directives),
+                self.fields_definition(&def.fields, end),
+            ]),
+            TypeExtension::Interface(def) => concat(vec![
+                text(format!("extend interface {}", def.name.name)),
+                implements(&def.implements),
+                self.directives(&def.directives),
+                self.fields_definition(&def.fields, end),
+            ]),
+            TypeExtension::Union(def) => concat(vec![
+                text(format!("extend union {}", def.name.name)),
+                self.directives(&def.directives),
+                union_members(&def.members),
+            ]),
+            TypeExtension::Enum(def) => concat(vec![
+                text(format!("extend enum {}", def.name.name)),
+                self.directives(&def.directives),
+                self.enum_values_definition(&def.values, end),
+            ]),
+            TypeExtension::InputObject(def) => concat(vec![
+                text(format!("extend input {}", def.name.name)),
+                self.directives(&def.directives),
+                self.input_fields_definition(&def.fields, end),
+            ]),
+        }
+    }
+
+    fn fields_definition(&mut self, fields: &[FieldDefinition], end: usize) -> Doc {
+        if fields.is_empty() {
+            return concat(vec![]);
+        }
+        concat(vec![
+            text(" "),
+            self.list(
+                "{",
+                "}",
+                fields,
+                |field| description_or(&field.description, &field.name),
+                Some(end.saturating_sub(1)),
+                ListStyle::Block,
+                |formatter, field| formatter.field_definition(field),
+            ),
+        ])
+    }
+
+    fn field_definition(&mut self, field: &FieldDefinition) -> Doc {
+        let mut docs = vec![self.description(&field.description), text(field.name.name)];
+        if let Some(ref arguments) = field.arguments {
+            docs.push(self.arguments_definition(arguments));
+        }
+        docs.push(text(": "));
+        docs.push(print_type(&field.r#type));
+        docs.push(self.directives(&field.directives));
+        concat(docs)
+    }
+
+    fn arguments_definition(&mut self, arguments: &ArgumentsDefinition) -> Doc {
+        self.list(
+            "(",
+            ")",
+            &arguments.input_values,
+            |input| description_or(&input.description, &input.name),
+            None,
+            ListStyle::Inline { padded: false },
+            |formatter, input| formatter.input_value_definition(input),
+        )
+    }
+
+    fn input_fields_definition(&mut self, fields: &[InputValueDefinition], end: usize) -> Doc {
+        if fields.is_empty() {
+            return concat(vec![]);
+        }
+        concat(vec![
+            text(" "),
+            self.list(
+                "{",
+                "}",
+                fields,
+                |input| description_or(&input.description, &input.name),
+                Some(end.saturating_sub(1)),
+                ListStyle::Block,
+                |formatter, input| formatter.input_value_definition(input),
+            ),
+        ])
+    }
+
+    fn input_value_definition(&mut self, input: &InputValueDefinition) -> Doc {
+        let mut docs = vec![
+            self.description(&input.description),
+            text(format!("{}: ", input.name.name)),
+            print_type(&input.r#type),
+        ];
+        if let Some(ref default_value) = input.default_value {
+            docs.push(text(" = "));
+            docs.push(self.value(default_value));
+        }
+        docs.push(self.directives(&input.directives));
+        concat(docs)
+    }
+
+    fn enum_values_definition(&mut self, values: &[EnumValueDefinition], end: usize) -> Doc {
+        if values.is_empty() {
+            return concat(vec![]);
+        }
+        concat(vec![
+            text(" "),
+            self.list(
+                "{",
+                "}",
+                values,
+                |value| description_or(&value.description, &value.name),
+                Some(end.saturating_sub(1)),
+                ListStyle::Block,
+                |formatter, value| {
+                    concat(vec![
+                        formatter.description(&value.description),
+                        text(value.name.name),
+                        formatter.directives(&value.directives),
+                    ])
+                },
+            ),
+        ])
+    }
+}
+
+fn description_of<'a>(def: &'a TypeDefinition) -> &'a Option<StringValue> {
+    match def {
+        TypeDefinition::Scalar(def) => &def.description,
+        TypeDefinition::Object(def) => &def.description,
+        TypeDefinition::Interface(def) => &def.description,
+        TypeDefinition::Union(def) => &def.description,
+        TypeDefinition::Enum(def) => &def.description,
+        TypeDefinition::InputObject(def) => &def.description,
+    }
+}
+
+fn extension_name_and_position<'a, 'src>(
+    def: &'a TypeExtension<'src>,
+) -> (&'a Ident<'src>, &'a Pos) {
+    match def {
+        TypeExtension::Scalar(def) => (&def.name, &def.position),
+        TypeExtension::Object(def) => (&def.name, &def.position),
+        TypeExtension::Interface(def) => (&def.name, &def.position),
+        TypeExtension::Union(def) => (&def.name, &def.position),
+        TypeExtension::Enum(def) => (&def.name, &def.position),
+        TypeExtension::InputObject(def) => (&def.name, &def.position),
+    }
+}
+
+/// Returns the offset of a node that starts with an optional description.
+fn description_or(description: &Option<StringValue>, name: &Ident) -> usize {
+    match description {
+        Some(description) => description.position.offset,
+        None => name.position.offset,
+    }
+}
+
+fn implements(interfaces: &[Ident]) -> Doc {
+    if interfaces.is_empty() {
+        return concat(vec![]);
+    }
+    concat(vec![
+        text(" implements "),
+        join(interfaces.iter().map(|name| text(name.name)), || {
+            text(" & ")
+        }),
+    ])
+}
+
+fn union_members(members: &[Ident]) -> Doc {
+    if members.is_empty() {
+        return concat(vec![]);
+    }
+    concat(vec![text(" ="), alternatives(members)])
+}
+
+/// Prints names separated by `|`, like union members and directive locations.
+/// If they do not fit in one line, each name is put on its own line.
+fn alternatives(names: &[Ident]) -> Doc {
+    group(indent(concat(vec![
+        Doc::Line,
+        Doc::IfBreak {
+            broken: "| ".to_owned(),
+            flat: String::new(),
+        },
+        join(names.iter().map(|name| text(name.name)), || {
+            concat(vec![Doc::Line, text("| ")])
+        }),
+    ])))
+}
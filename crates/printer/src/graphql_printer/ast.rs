@@ -23,6 +23,13 @@ use {
 
 impl GraphQLPrinter for OperationDocument<'_> {
     fn print_graphql(&self, writer: &mut impl SourceMapWriter) {
+        for import in self.imports.iter() {
+            writer.write_for(&format!("#import \"{}\"", import.path), import);
+            writer.write("\n");
+        }
+        if !self.imports.is_empty() {
+            writer.write("\n");
+        }
         for def in self.definitions.iter() {
             def.print_graphql(writer);
             writer.write("\n");
@@ -45,6 +52,10 @@ impl GraphQLPrinter for ExecutableDefinition<'_> {
 
 impl GraphQLPrinter for OperationDefinition<'_> {
     fn print_graphql(&self, writer: &mut impl SourceMapWriter) {
+        if self.shorthand {
+            self.selection_set.print_graphql(writer);
+            return;
+        }
         writer.write(self.operation_type.as_str());
         if let Some(ref name) = self.name {
             writer.write(" ");
@@ -200,8 +211,12 @@ impl GraphQLPrinter for FragmentDefinition<'_> {
         self.name.print_graphql(writer);
         writer.write(" on ");
         self.type_condition.print_graphql(writer);
+        for d in self.directives.iter() {
+            writer.write(" ");
+            d.print_graphql(writer);
+        }
         writer.write(" ");
-        todo!()
+        self.selection_set.print_graphql(writer);
     }
 }
 
@@ -509,9 +524,11 @@ impl GraphQLPrinter for DirectiveDefinition<'_> {
             writer.write(" ");
             token.print_graphql(writer);
         }
-        writer.write(" on");
-        for loc in self.locations.iter() {
-            writer.write(" | ");
+        writer.write(" on ");
+        for (idx, loc) in self.locations.iter().enumerate() {
+            if idx > 0 {
+                writer.write(" | ");
+            }
             loc.print_graphql(writer);
         }
         writer.write("\n");
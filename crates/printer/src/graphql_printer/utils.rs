@@ -1,48 +1,55 @@
 use sourcemap_writer::SourceMapWriter;
 
 /// Print string in the GraphQL string literal format.
+/// Multiline strings are printed as block strings when the block string has the same value.
 pub fn print_string(s: &str, writer: &mut impl SourceMapWriter) {
-    let mut result = String::with_capacity(s.len());
-    let is_multiline = s.find('\n').is_some();
-    if is_multiline {
-        // print as multiline string
-        result.push_str("\"\"\"");
-        let mut dq_count: usize = 0;
-        for c in s.chars() {
-            if c != '"' {
-                if dq_count > 0 {
-                    result.push_str(&"\"".repeat(dq_count));
-                    dq_count = 0;
-                }
-                result.push(c);
-                continue;
-            }
-            dq_count += 1;
-            if dq_count == 3 {
-                // """ in string
-                result.push_str("\\\"\"\"");
-                dq_count = 0;
-            }
-        }
-        if dq_count > 0 {
-            result.push_str(&"\"".repeat(dq_count));
-        }
-        result.push_str("\"\"\"");
-        writer.write(&result);
+    if s.contains('\n') && is_block_representable(s) {
+        // lines are indented by the writer, which is removed as common indentation when parsed
+        writer.write("\"\"\"\n");
+        writer.write(&s.replace("\"\"\"", "\\\"\"\""));
+        writer.write("\n\"\"\"");
     } else {
-        // single line string
-        result.push('"');
-        for c in s.chars() {
-            match c {
-                '\r' => result.push_str("\\r"),
-                '\n' => result.push_str("\\n"),
-                c if c.is_control() => {
-                    result.push_str(&format!("\\u{{{:x}}}", c as u32));
-                }
-                c => result.push(c),
-            }
+        writer.write(&quote_string(s));
+    }
+}
+
+/// Checks whether given value stays the same when printed as an indented block string.
+pub fn is_block_representable(value: &str) -> bool {
+    let lines = value.split('\n').collect::<Vec<_>>();
+    let has_content = |line: &&str| !line.trim_matches([' ', '\t']).is_empty();
+    let first_and_last_have_content =
+        lines.first().is_some_and(has_content) && lines.last().is_some_and(has_content);
+    let some_line_is_not_indented = lines
+        .iter()
+        .any(|line| has_content(line) && leading_whitespace_len(line) == 0);
+    first_and_last_have_content
+        && some_line_is_not_indented
+        && !value
+            .chars()
+            .any(|c| c.is_control() && c != '\n' && c != '\t')
+}
+
+fn leading_whitespace_len(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Prints a string value as a quoted string literal.
+pub fn quote_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\u{8}' => result.push_str("\\b"),
+            '\u{c}' => result.push_str("\\f"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04X}", c as u32)),
+            c => result.push(c),
         }
-        result.push('"');
-        writer.write(&result);
     }
+    result.push('"');
+    result
 }
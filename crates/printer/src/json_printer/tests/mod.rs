@@ -9,6 +9,10 @@ use super::to_json::JsonPrintable;
 static POS: Pos = Pos {
     line: 0,
     column: 0,
+    end_line: 0,
+    end_column: 0,
+    offset: 0,
+    end_offset: 0,
     file: 0,
     builtin: false,
 };
@@ -38,6 +38,19 @@ impl JsonPrintable for [&ExecutableDefinition<'_>] {
     }
 }
 
+/// Document node that has the hash of its persisted document in `__meta__`.
+pub struct DocumentWithHash<'a, 'src> {
+    pub hash: &'a str,
+    pub definitions: &'a [&'a ExecutableDefinition<'src>],
+}
+
+impl JsonPrintable for DocumentWithHash<'_, '_> {
+    fn print_json(&self, writer: &mut JSONObjectWriter) {
+        writer.object("__meta__").value("hash", self.hash);
+        self.definitions.print_json(writer);
+    }
+}
+
 impl JsonPrintable for ExecutableDefinition<'_> {
     fn print_json(&self, writer: &mut JSONObjectWriter) {
         writer.value("kind", "OperationDefinition");
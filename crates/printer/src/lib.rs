@@ -1,14 +1,22 @@
+mod graphql_formatter;
 mod graphql_printer;
 mod jsdoc;
 mod json_printer;
 mod operation_base_printer;
 mod operation_js_printer;
 mod operation_type_printer;
+mod persisted_document;
+mod resolver_type_printer;
 mod schema_type_printer;
 mod ts_types;
 mod utils;
 
+pub use graphql_formatter::{
+    format_operation_document, format_type_system_document, GraphQLFormatterOptions,
+};
 pub use graphql_printer::GraphQLPrinter;
+pub use persisted_document::{persisted_document_hash, print_persisted_document};
+pub use resolver_type_printer::printer::{ResolverTypePrinter, ResolverTypePrinterOptions};
 pub use schema_type_printer::printer::{
     SchemaTypePrinter, SchemaTypePrinterContext, SchemaTypePrinterOptions,
 };
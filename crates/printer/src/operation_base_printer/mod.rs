@@ -1,9 +1,14 @@
+use std::borrow::Cow;
+
 use nitrogql_ast::operation::{
     ExecutableDefinition, OperationDefinition, OperationDocument, OperationType,
 };
+use nitrogql_semantics::FragmentRegistry;
 use nitrogql_utils::capitalize;
 use sourcemap_writer::SourceMapWriter;
 
+use crate::persisted_document::persisted_document_fragments;
+
 use self::options::OperationBasePrinterOptions;
 
 pub mod options;
@@ -84,12 +89,12 @@ pub fn operation_variable_name(
         operation
             .name
             .map(|name| capitalize(name.name))
-            .unwrap_or(String::new())
+            .unwrap_or_default()
     } else {
         operation
             .name
             .map(|name| name.name.to_owned())
-            .unwrap_or(String::new())
+            .unwrap_or_default()
     };
     format!(
         "{}{}",
@@ -101,3 +106,34 @@ pub fn operation_variable_name(
         }
     )
 }
+
+/// Collects definitions that constitute the document for given operation.
+/// Result includes the operation itself and fragments that are (directly or indirectly) used by it,
+/// in the same order as the persisted document so that its hash matches the emitted document.
+/// Fragments that are not reachable from the operation are not included,
+/// as servers reject documents with unused fragments.
+pub fn definitions_for_operation<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+    operation: &'a OperationDefinition<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+) -> Vec<Cow<'a, ExecutableDefinition<'src>>> {
+    // To follow the community conventions, generated JSON has only one operation in it
+    let mut definitions = document
+        .definitions
+        .iter()
+        .filter(|def| match def {
+            ExecutableDefinition::FragmentDefinition(_) => false,
+            ExecutableDefinition::OperationDefinition(op) => {
+                op.name.map(|ident| ident.name) == operation.name.map(|ident| ident.name)
+            }
+        })
+        .map(Cow::Borrowed)
+        .collect::<Vec<_>>();
+    definitions.extend(
+        persisted_document_fragments(operation, fragments)
+            .into_iter()
+            .map(|fragment| ExecutableDefinition::FragmentDefinition(fragment.clone()))
+            .map(Cow::Owned),
+    );
+    definitions
+}
@@ -39,6 +39,7 @@ pub struct PrintOperationContext<'a> {
 #[derive(Copy, Clone, Debug)]
 pub struct PrintFragmentContext<'a> {
     /// Name of the variable for this fragment.
+    #[allow(dead_code)]
     pub var_name: &'a str,
     /// Whether this fragment is exported.
     pub exported: bool,
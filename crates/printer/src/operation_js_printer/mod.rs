@@ -1,20 +1,25 @@
 use nitrogql_ast::OperationDocument;
+use nitrogql_semantics::FragmentRegistry;
 use sourcemap_writer::SourceMapWriter;
 
 use crate::operation_base_printer::OperationPrinter;
 
 use self::visitor::{OperationJSPrinterOptions, OperationJSPrinterVisitor};
 
+#[cfg(test)]
+mod tests;
 pub mod visitor;
 
 /// Print a JavaScript module for given operation document.
-pub fn print_js_for_operation_document(
+/// `fragments` are fragments defined in other documents that can be spread from this document.
+pub fn print_js_for_operation_document<'a, 'src>(
     options: OperationJSPrinterOptions,
-    operation: &OperationDocument,
+    operation: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
     writer: &mut impl SourceMapWriter,
 ) {
-    let base_options = options.base_options;
-    let visitor = OperationJSPrinterVisitor::new(operation);
+    let base_options = options.base_options.clone();
+    let visitor = OperationJSPrinterVisitor::new(options, operation, fragments);
     let mut printer = OperationPrinter::new(base_options, visitor, writer);
     printer.print_document(operation);
 }
@@ -0,0 +1,189 @@
+use insta::assert_snapshot;
+
+use nitrogql_ast::OperationDocument;
+use nitrogql_parser::parse_operation_document;
+use nitrogql_semantics::FragmentRegistry;
+use sourcemap_writer::JustWriter;
+
+use crate::{print_js_for_operation_document, OperationJSPrinterOptions};
+
+#[test]
+fn operation_and_fragment() {
+    let doc = parse_operation_document(
+        "
+        query {
+            me {
+                ...F
+            }
+        }
+        fragment F on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document(&doc));
+}
+
+#[test]
+fn query_shorthand() {
+    let doc = parse_operation_document(
+        "
+        {
+            me {
+                ...F
+            }
+        }
+        fragment F on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document(&doc));
+}
+
+#[test]
+fn import_comments() {
+    let doc = parse_operation_document(
+        "
+        #import \"./fragments.graphql\"
+        #import \"../other.graphql\"
+        query {
+            me {
+                ...F
+                ...G
+            }
+        }
+        ",
+    )
+    .unwrap();
+    let imported = parse_operation_document(
+        "
+        fragment F on User {
+            id
+        }
+        fragment G on User {
+            name
+            ...H
+        }
+        fragment H on User {
+            age
+        }
+        fragment Unused on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document_with_fragments(
+        &doc,
+        &FragmentRegistry::from_document(&imported),
+        OperationJSPrinterOptions::default(),
+    ));
+}
+
+#[test]
+fn unused_local_fragment() {
+    let doc = parse_operation_document(
+        "
+        query {
+            me {
+                ...F
+            }
+        }
+        fragment F on User {
+            id
+        }
+        fragment Unused on User {
+            name
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document(&doc));
+}
+
+#[test]
+fn document_hash() {
+    let doc = parse_operation_document(
+        "
+        query {
+            me {
+                ...F
+            }
+        }
+        fragment F on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document_with_options(
+        &doc,
+        OperationJSPrinterOptions {
+            emit_document_hash: true,
+            ..Default::default()
+        }
+    ));
+}
+
+#[test]
+fn document_hash_with_imported_fragments() {
+    let doc = parse_operation_document(
+        "
+        #import \"./fragments.graphql\"
+        query {
+            me {
+                ...Local
+                ...Imported
+            }
+        }
+        fragment Unused on User {
+            name
+        }
+        fragment Local on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    let imported = parse_operation_document(
+        "
+        fragment Imported on User {
+            avatarUrl
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document_with_fragments(
+        &doc,
+        &FragmentRegistry::from_document(&imported),
+        OperationJSPrinterOptions {
+            emit_document_hash: true,
+            ..Default::default()
+        },
+    ));
+}
+
+fn print_document(document: &OperationDocument) -> String {
+    print_document_with_options(document, OperationJSPrinterOptions::default())
+}
+
+fn print_document_with_options(
+    document: &OperationDocument,
+    options: OperationJSPrinterOptions,
+) -> String {
+    print_document_with_fragments(document, &FragmentRegistry::new(), options)
+}
+
+fn print_document_with_fragments<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+    options: OperationJSPrinterOptions,
+) -> String {
+    let mut result = String::new();
+    let mut writer = JustWriter::new(&mut result);
+    print_js_for_operation_document(options, document, fragments, &mut writer);
+    result
+}
@@ -1,37 +1,72 @@
 use nitrogql_ast::{operation::ExecutableDefinition, OperationDocument};
+use nitrogql_semantics::FragmentRegistry;
 use sourcemap_writer::SourceMapWriter;
 
 use crate::{
-    json_printer::print_to_json_string,
+    json_printer::{print_to_json_string, DocumentWithHash},
     operation_base_printer::{
-        options::OperationBasePrinterOptions, OperationPrinterVisitor, PrintFragmentContext,
+        definitions_for_operation, options::OperationBasePrinterOptions, OperationPrinterVisitor, PrintFragmentContext,
         PrintOperationContext,
     },
+    persisted_document::{persisted_document_hash, print_persisted_document},
 };
 
+/// Name of the variable that exports fragments defined in a module.
+const FRAGMENTS_VAR_NAME: &str = "__nitrogql_fragments";
+
 #[derive(Clone, Debug, Default)]
 pub struct OperationJSPrinterOptions {
     pub base_options: OperationBasePrinterOptions,
+    /// Whether the hash of the persisted document is embedded in each document node.
+    pub emit_document_hash: bool,
 }
 
 pub struct OperationJSPrinterVisitor<'a, 'src> {
+    options: OperationJSPrinterOptions,
     context: OperationJSPrinterContext<'a, 'src>,
 }
 
 impl<'a, 'src> OperationJSPrinterVisitor<'a, 'src> {
-    pub fn new(operation: &'a OperationDocument<'src>) -> Self {
-        let context = OperationJSPrinterContext { operation };
-        Self { context }
+    pub fn new(
+        options: OperationJSPrinterOptions,
+        operation: &'a OperationDocument<'src>,
+        fragments: &FragmentRegistry<'a, 'src>,
+    ) -> Self {
+        let mut fragment_registry = FragmentRegistry::from_document(operation);
+        fragment_registry.extend(fragments);
+        let context = OperationJSPrinterContext {
+            operation,
+            fragment_registry,
+        };
+        Self { options, context }
     }
 }
 
 pub struct OperationJSPrinterContext<'a, 'src> {
     operation: &'a OperationDocument<'src>,
+    fragment_registry: FragmentRegistry<'a, 'src>,
 }
 
 impl<'a, 'src> OperationPrinterVisitor for OperationJSPrinterVisitor<'a, 'src> {
     fn print_header(&self, _writer: &mut impl SourceMapWriter) {}
-    fn print_trailer(&self, _writer: &mut impl SourceMapWriter) {}
+    fn print_trailer(&self, writer: &mut impl SourceMapWriter) {
+        // Fragments defined in this module are exported so that other modules can use them.
+        let fragments = self
+            .context
+            .operation
+            .definitions
+            .iter()
+            .filter(|def| matches!(def, ExecutableDefinition::FragmentDefinition(_)))
+            .map(print_to_json_string)
+            .collect::<Vec<_>>();
+        if fragments.is_empty() {
+            return;
+        }
+        writer.write(&format!(
+            "export const {FRAGMENTS_VAR_NAME} = [{}];\n",
+            fragments.join(", ")
+        ));
+    }
     fn print_operation_definition(
         &self,
         context: PrintOperationContext,
@@ -44,20 +79,27 @@ impl<'a, 'src> OperationPrinterVisitor for OperationJSPrinterVisitor<'a, 'src> {
         writer.write("const ");
         writer.write_for(context.var_name, &operation.name_pos());
         writer.write(" = ");
-        // To follow the community conventions, generated JSON has only one operation in it
-        let this_document = self
-            .context
-            .operation
-            .definitions
+        let this_document = definitions_for_operation(
+            self.context.operation,
+            operation,
+            &self.context.fragment_registry,
+        );
+        let this_document = this_document
             .iter()
-            .filter(|def| match def {
-                ExecutableDefinition::FragmentDefinition(_) => true,
-                ExecutableDefinition::OperationDefinition(op) => {
-                    op.name.map(|ident| ident.name) == operation.name.map(|ident| ident.name)
-                }
-            })
+            .map(|def| def.as_ref())
             .collect::<Vec<_>>();
-        writer.write(&print_to_json_string(&this_document[..]));
+        if self.options.emit_document_hash {
+            let hash = persisted_document_hash(&print_persisted_document(
+                operation,
+                &self.context.fragment_registry,
+            ));
+            writer.write(&print_to_json_string(&DocumentWithHash {
+                hash: &hash,
+                definitions: &this_document,
+            }));
+        } else {
+            writer.write(&print_to_json_string(&this_document[..]));
+        }
         writer.write(";\n\n");
     }
 
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 use graphql_type_system::Schema;
 use nitrogql_ast::{base::Pos, OperationDocument};
+use nitrogql_semantics::FragmentRegistry;
 use sourcemap_writer::SourceMapWriter;
 
 use crate::operation_base_printer::OperationPrinter;
@@ -16,14 +17,16 @@ pub mod type_printer;
 pub mod visitor;
 
 /// Print a TypeScript module for given operation document.
-pub fn print_types_for_operation_document(
+/// `fragments` are fragments defined in other documents that can be spread from this document.
+pub fn print_types_for_operation_document<'a, 'src>(
     options: OperationTypePrinterOptions,
-    schema: &Schema<Cow<str>, Pos>,
-    operation: &OperationDocument,
+    schema: &'a Schema<Cow<'src, str>, Pos>,
+    operation: &'a OperationDocument<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
     writer: &mut impl SourceMapWriter,
 ) {
     let base_options = options.base_options.clone();
-    let visitor = OperationTypePrinterVisitor::new(options, schema, operation);
+    let visitor = OperationTypePrinterVisitor::new(options, schema, operation, fragments);
     let mut printer = OperationPrinter::new(base_options, visitor, writer);
     printer.print_document(operation);
 }
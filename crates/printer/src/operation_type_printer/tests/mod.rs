@@ -5,6 +5,7 @@ use nitrogql_ast::{OperationDocument, TypeSystemDocument};
 use nitrogql_parser::{parse_operation_document, parse_type_system_document};
 use nitrogql_semantics::ast_to_type_system;
 use nitrogql_semantics::resolve_extensions;
+use nitrogql_semantics::FragmentRegistry;
 use sourcemap_writer::JustWriter;
 
 use crate::print_types_for_operation_document;
@@ -76,6 +77,22 @@ fn basic_type_printing() {
     assert_snapshot!(printed);
 }
 
+#[test]
+fn query_shorthand() {
+    let doc = parse_operation_document(
+        "
+        {
+            me {
+                id name
+            }
+        }
+        ",
+    )
+    .unwrap();
+    let printed = print_document(&doc);
+    assert_snapshot!(printed);
+}
+
 #[test]
 fn fragment_spread() {
     let doc = parse_operation_document(
@@ -184,7 +201,89 @@ fn print_values() {
         print_values: true,
         ..Default::default()
     };
-    print_types_for_operation_document(options, &schema, &doc, &mut writer);
+    print_types_for_operation_document(
+        options,
+        &schema,
+        &doc,
+        &FragmentRegistry::new(),
+        &mut writer,
+    );
+    assert_snapshot!(result);
+}
+
+#[test]
+fn types_only() {
+    let doc = parse_operation_document(
+        "
+        query Me($id: ID!) {
+            me {
+                id name
+            }
+        }
+        ",
+    )
+    .unwrap();
+    let mut result = String::new();
+    let mut writer = JustWriter::new(&mut result);
+    let schema = type_system();
+    let schema = ast_to_type_system(&schema);
+    let options = OperationTypePrinterOptions {
+        types_only: true,
+        ..Default::default()
+    };
+    print_types_for_operation_document(
+        options,
+        &schema,
+        &doc,
+        &FragmentRegistry::new(),
+        &mut writer,
+    );
+    assert_snapshot!(result);
+}
+
+#[test]
+fn fragment_from_other_document() {
+    let fragment_doc = parse_operation_document(
+        "
+        fragment F on User {
+            name
+            ...G
+        }
+        fragment G on User {
+            age
+        }
+        fragment Unused on User {
+            type
+        }
+        ",
+    )
+    .unwrap();
+    let doc = parse_operation_document(
+        "
+        query {
+            me {
+                id
+                ...F
+            }
+        }
+        ",
+    )
+    .unwrap();
+    let mut result = String::new();
+    let mut writer = JustWriter::new(&mut result);
+    let schema = type_system();
+    let schema = ast_to_type_system(&schema);
+    let options = OperationTypePrinterOptions {
+        print_values: true,
+        ..Default::default()
+    };
+    print_types_for_operation_document(
+        options,
+        &schema,
+        &doc,
+        &FragmentRegistry::from_document(&fragment_doc),
+        &mut writer,
+    );
     assert_snapshot!(result);
 }
 
@@ -557,6 +656,7 @@ fn print_document(document: &OperationDocument) -> String {
         OperationTypePrinterOptions::default(),
         &schema,
         document,
+        &FragmentRegistry::new(),
         &mut writer,
     );
     result
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, convert::identity, iter::once};
+use std::{convert::identity, iter::once};
 
 use crate::{
     ts_types::{ts_types_util::ts_union, type_to_ts_type::get_ts_type_of_type, ObjectField},
@@ -9,12 +9,12 @@ use itertools::{Either, Itertools};
 use nitrogql_ast::{
     base::Pos,
     directive::Directive,
-    operation::{FragmentDefinition, OperationDocument},
+    operation::OperationDocument,
     selection_set::{Selection, SelectionSet},
     value::Value,
     variable::VariablesDefinition,
 };
-use nitrogql_semantics::direct_fields_of_output_type;
+use nitrogql_semantics::{direct_fields_of_output_type, FragmentRegistry};
 use sourcemap_writer::SourceMapWriter;
 
 use super::{
@@ -27,10 +27,12 @@ use super::{
 pub struct QueryTypePrinterContext<'a, 'src, S: Text<'src>> {
     pub options: &'a OperationTypePrinterOptions,
     pub schema: &'a Schema<S, Pos>,
+    #[allow(dead_code)]
     pub operation: &'a OperationDocument<'src>,
-    pub fragment_definitions: &'a HashMap<&'src str, &'a FragmentDefinition<'src>>,
+    pub fragment_definitions: &'a FragmentRegistry<'a, 'src>,
 }
 
+#[allow(dead_code)]
 pub trait TypePrinter<'src, S: Text<'src>> {
     fn print_type(
         &self,
@@ -337,10 +339,8 @@ fn check_skip_directive<'src, S: Text<'src>>(
                             return true;
                         }
                     }
-                    Value::BooleanValue(b) => {
-                        if b.value {
-                            return true;
-                        }
+                    Value::BooleanValue(b) if b.value => {
+                        return true;
                     }
                     _ => {}
                 }
@@ -363,10 +363,8 @@ fn check_skip_directive<'src, S: Text<'src>>(
                             return true;
                         }
                     }
-                    Value::BooleanValue(b) => {
-                        if !b.value {
-                            return true;
-                        }
+                    Value::BooleanValue(b) if !b.value => {
+                        return true;
                     }
                     _ => {}
                 }
@@ -1,19 +1,21 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::borrow::Cow;
 
 use graphql_type_system::{NamedType, Node, RootTypes, Schema, Text};
 use nitrogql_ast::{
     base::Pos,
-    operation::{ExecutableDefinition, FragmentDefinition, OperationType},
+    operation::OperationType,
     OperationDocument,
 };
+use nitrogql_semantics::FragmentRegistry;
 use sourcemap_writer::SourceMapWriter;
 
 use crate::{
-    json_printer::print_to_json_string,
+    json_printer::{print_to_json_string, DocumentWithHash},
     operation_base_printer::{
-        options::OperationBasePrinterOptions, OperationPrinterVisitor, PrintFragmentContext,
+        definitions_for_operation, options::OperationBasePrinterOptions, OperationPrinterVisitor, PrintFragmentContext,
         PrintOperationContext,
     },
+    persisted_document::{persisted_document_hash, print_persisted_document},
     ts_types::TSType,
 };
 
@@ -26,6 +28,12 @@ pub struct OperationTypePrinterOptions {
     pub base_options: OperationBasePrinterOptions,
     /// Whether value of variables should be printed.
     pub print_values: bool,
+    /// Whether the hash of the persisted document is embedded in each printed document node.
+    /// Effective only when `print_values` is true.
+    pub emit_document_hash: bool,
+    /// Whether only the result and variables types of operations are printed.
+    /// These types are exported and no document node is declared.
+    pub types_only: bool,
     /// Name of the root TypeScript namespace that contains schema types.
     pub schema_root_namespace: String,
     /// Source of schema type to import from.
@@ -43,6 +51,8 @@ impl Default for OperationTypePrinterOptions {
         Self {
             base_options: OperationBasePrinterOptions::default(),
             print_values: false,
+            emit_document_hash: false,
+            types_only: false,
             schema_root_namespace: "Schema".to_owned(),
             schema_source: "".to_owned(),
             typed_document_node_source: "@graphql-typed-document-node/core".to_owned(),
@@ -65,17 +75,10 @@ where
         options: OperationTypePrinterOptions,
         schema: &'a Schema<Cow<'src, str>, Pos>,
         operation: &'a OperationDocument<'src>,
+        fragments: &FragmentRegistry<'a, 'src>,
     ) -> Self {
-        let fragment_definitions = operation
-            .definitions
-            .iter()
-            .filter_map(|def| match def {
-                ExecutableDefinition::OperationDefinition(_) => None,
-                ExecutableDefinition::FragmentDefinition(fragment_def) => {
-                    Some((fragment_def.name.name, fragment_def))
-                }
-            })
-            .collect();
+        let mut fragment_definitions = FragmentRegistry::from_document(operation);
+        fragment_definitions.extend(fragments);
         let context = OperationTypePrinterContext {
             schema,
             operation,
@@ -88,15 +91,17 @@ where
 pub struct OperationTypePrinterContext<'a, 'src, S: Text<'src>> {
     pub schema: &'a Schema<S, Pos>,
     pub operation: &'a OperationDocument<'src>,
-    pub fragment_definitions: HashMap<&'src str, &'a FragmentDefinition<'src>>,
+    pub fragment_definitions: FragmentRegistry<'a, 'src>,
 }
 
 impl<'a, 'src> OperationPrinterVisitor for OperationTypePrinterVisitor<'a, 'src> {
     fn print_header(&self, writer: &mut impl SourceMapWriter) {
-        writer.write(&format!(
-            "import type {{ TypedDocumentNode }} from \"{}\";\n",
-            self.options.typed_document_node_source
-        ));
+        if !self.options.types_only {
+            writer.write(&format!(
+                "import type {{ TypedDocumentNode }} from \"{}\";\n",
+                self.options.typed_document_node_source
+            ));
+        }
         writer.write(&format!(
             "import type * as {} from \"{}\";\n\n",
             self.options.schema_root_namespace, self.options.schema_source,
@@ -113,6 +118,9 @@ impl<'a, 'src> OperationPrinterVisitor for OperationTypePrinterVisitor<'a, 'src>
             "{}{}",
             context.var_name, self.options.operation_result_type_suffix
         );
+        if self.options.types_only {
+            writer.write("export ");
+        }
         writer.write("type ");
         writer.write_for(&result_type_name, &operation.name_pos());
         writer.write_for(" = ", &operation.selection_set);
@@ -144,11 +152,17 @@ impl<'a, 'src> OperationPrinterVisitor for OperationTypePrinterVisitor<'a, 'src>
         let input_variable_name =
             format!("{}{}", context.var_name, self.options.variables_type_suffix);
 
+        if self.options.types_only {
+            writer.write("export ");
+        }
         writer.write("type ");
         writer.write_for(&input_variable_name, &operation.name_pos());
         writer.write(" = ");
         input_variable_type.print_type(writer);
         writer.write(";\n\n");
+        if self.options.types_only {
+            return;
+        }
 
         if context.exported {
             writer.write("export ");
@@ -167,20 +181,27 @@ impl<'a, 'src> OperationPrinterVisitor for OperationTypePrinterVisitor<'a, 'src>
             return;
         }
         writer.write("> = ");
-        // To follow the community conventions, generated JSON has only one operation in it
-        let this_document = self
-            .context
-            .operation
-            .definitions
+        let this_document = definitions_for_operation(
+            self.context.operation,
+            operation,
+            &self.context.fragment_definitions,
+        );
+        let this_document = this_document
             .iter()
-            .filter(|def| match def {
-                ExecutableDefinition::FragmentDefinition(_) => true,
-                ExecutableDefinition::OperationDefinition(op) => {
-                    op.name.map(|ident| ident.name) == operation.name.map(|ident| ident.name)
-                }
-            })
+            .map(|def| def.as_ref())
             .collect::<Vec<_>>();
-        writer.write(&print_to_json_string(&this_document[..]));
+        if self.options.emit_document_hash {
+            let hash = persisted_document_hash(&print_persisted_document(
+                operation,
+                &self.context.fragment_definitions,
+            ));
+            writer.write(&print_to_json_string(&DocumentWithHash {
+                hash: &hash,
+                definitions: &this_document,
+            }));
+        } else {
+            writer.write(&print_to_json_string(&this_document[..]));
+        }
         // Use the `as unknown as` technique to avoid the type system complaining about
         // the type of the JSON object not matching the type of the TypedDocumentNode
         // (because of the use of enums in the TypedDocumentNode type)
@@ -229,6 +250,9 @@ impl<'a, 'src> OperationPrinterVisitor for OperationTypePrinterVisitor<'a, 'src>
         context: PrintOperationContext,
         writer: &mut impl SourceMapWriter,
     ) {
+        if self.options.types_only {
+            return;
+        }
         writer.write("export { ");
         writer.write(context.var_name);
         writer.write(" as default };\n\n");
@@ -0,0 +1,47 @@
+//! Persisted documents let clients send a hash of an operation instead of its document.
+//! The hash is calculated from a canonical form of the document,
+//! so that it does not change with whitespace, comments or the order of fragments in the source.
+
+use nitrogql_ast::operation::{FragmentDefinition, OperationDefinition};
+use nitrogql_semantics::FragmentRegistry;
+use sha2::{Digest, Sha256};
+use sourcemap_writer::{JustWriter, SourceMapWriter};
+
+use crate::GraphQLPrinter;
+
+#[cfg(test)]
+mod tests;
+
+/// Prints the canonical document for given operation.
+/// The document consists of the operation followed by fragments reachable from it, sorted by name.
+pub fn print_persisted_document<'a, 'src>(
+    operation: &'a OperationDefinition<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+) -> String {
+    let used_fragments = persisted_document_fragments(operation, fragments);
+
+    let mut result = String::new();
+    let mut writer = JustWriter::new(&mut result);
+    operation.print_graphql(&mut writer);
+    for fragment in used_fragments {
+        writer.write("\n\n");
+        fragment.print_graphql(&mut writer);
+    }
+    result
+}
+
+/// Returns fragments that belong to the persisted document of given operation,
+/// which are fragments reachable from the operation sorted by name.
+pub fn persisted_document_fragments<'a, 'src>(
+    operation: &'a OperationDefinition<'src>,
+    fragments: &FragmentRegistry<'a, 'src>,
+) -> Vec<&'a FragmentDefinition<'src>> {
+    let mut used_fragments = fragments.fragments_used_by(&operation.selection_set);
+    used_fragments.sort_by_key(|fragment| fragment.name.name);
+    used_fragments
+}
+
+/// Calculates the hash of a persisted document, which is a hex-encoded SHA-256 of the document text.
+pub fn persisted_document_hash(document: &str) -> String {
+    format!("{:x}", Sha256::digest(document.as_bytes()))
+}
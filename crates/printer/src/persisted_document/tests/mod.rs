@@ -0,0 +1,155 @@
+use insta::assert_snapshot;
+use nitrogql_ast::operation::{ExecutableDefinition, OperationDefinition, OperationDocument};
+use nitrogql_parser::parse_operation_document;
+use nitrogql_semantics::FragmentRegistry;
+
+use sourcemap_writer::{JustWriter, SourceMapWriter};
+
+use crate::{operation_base_printer::definitions_for_operation, GraphQLPrinter};
+
+use super::{persisted_document_hash, print_persisted_document};
+
+#[test]
+fn operation_with_reachable_fragments() {
+    let doc = parse_operation_document(
+        "
+        query GetUser($id: ID!) {
+            user(id: $id) {
+                ...UserFields
+            }
+        }
+        fragment Unused on User {
+            name
+        }
+        fragment UserFields on User {
+            id
+            ...Avatar
+        }
+        fragment Avatar on User {
+            avatarUrl(size: 64)
+        }
+        ",
+    )
+    .unwrap();
+    assert_snapshot!(print_document(&doc));
+}
+
+#[test]
+fn hash_is_stable_across_formatting() {
+    let doc1 = parse_operation_document(
+        "
+        query GetUser { user { ...A ...B } }
+        fragment A on User { id }
+        fragment B on User { name }
+        ",
+    )
+    .unwrap();
+    let doc2 = parse_operation_document(
+        "
+        # Comments do not affect the hash.
+        fragment B on User {
+            name
+        }
+
+        query GetUser {
+            user {
+                ...A
+                ...B
+            }
+        }
+
+        fragment A on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    let doc3 = parse_operation_document(
+        "
+        query GetUser { user { ...A ...B id } }
+        fragment A on User { id }
+        fragment B on User { name }
+        ",
+    )
+    .unwrap();
+    let hash1 = persisted_document_hash(&print_document(&doc1));
+    let hash2 = persisted_document_hash(&print_document(&doc2));
+    let hash3 = persisted_document_hash(&print_document(&doc3));
+    assert_eq!(hash1, hash2);
+    assert_ne!(hash1, hash3);
+}
+
+#[test]
+fn emitted_document_matches_persisted_document() {
+    let doc = parse_operation_document(
+        "
+        #import \"./fragments.graphql\"
+        query GetUser {
+            user {
+                ...Local
+                ...Imported
+            }
+        }
+        fragment Unused on User {
+            name
+        }
+        fragment Local on User {
+            id
+        }
+        ",
+    )
+    .unwrap();
+    let imported = parse_operation_document(
+        "
+        fragment Imported on User {
+            avatarUrl
+        }
+        fragment ImportedUnused on User {
+            email
+        }
+        ",
+    )
+    .unwrap();
+    let mut fragments = FragmentRegistry::from_document(&doc);
+    fragments.add_document(&imported);
+    let operation = first_operation(&doc);
+
+    let mut emitted = String::new();
+    let mut writer = JustWriter::new(&mut emitted);
+    for (idx, def) in definitions_for_operation(&doc, operation, &fragments)
+        .iter()
+        .enumerate()
+    {
+        if idx > 0 {
+            writer.write("\n\n");
+        }
+        def.print_graphql(&mut writer);
+    }
+    assert_eq!(emitted, print_persisted_document(operation, &fragments));
+}
+
+#[test]
+fn hash_is_sha256() {
+    assert_eq!(
+        persisted_document_hash("query { me }"),
+        "8aea24594ba227016a1ff9978a84a4257e7d9edbe6d5b0471481ad7e3f4562b5"
+    );
+}
+
+fn print_document(document: &OperationDocument) -> String {
+    let fragments = FragmentRegistry::from_document(document);
+    print_persisted_document(first_operation(document), &fragments)
+}
+
+fn first_operation<'a, 'src>(
+    document: &'a OperationDocument<'src>,
+) -> &'a OperationDefinition<'src> {
+    document
+        .definitions
+        .iter()
+        .find_map(|def| match def {
+            ExecutableDefinition::OperationDefinition(op) => Some(op),
+            ExecutableDefinition::FragmentDefinition(_) => None,
+        })
+        .unwrap()
+}
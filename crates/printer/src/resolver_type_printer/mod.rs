@@ -0,0 +1,2 @@
+pub mod printer;
+mod tests;
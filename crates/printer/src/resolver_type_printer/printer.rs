@@ -0,0 +1,293 @@
+use std::{
+    borrow::{Borrow, Cow},
+    collections::HashMap,
+};
+
+use graphql_type_system::{Schema, TypeDefinition as SchemaTypeDefinition};
+use nitrogql_ast::{
+    base::Pos,
+    r#type::{NamedType, Type},
+    type_system::{
+        ArgumentsDefinition, FieldDefinition, TypeDefinition, TypeSystemDefinition,
+        TypeSystemDocument,
+    },
+};
+use nitrogql_semantics::ast_to_type_system;
+use sourcemap_writer::SourceMapWriter;
+
+use crate::{
+    ts_types::{
+        ts_types_util::ts_union, type_to_ts_type::get_ts_type_of_type, ObjectField, TSType,
+    },
+    utils::interface_implementers,
+};
+
+pub struct ResolverTypePrinterOptions {
+    /// Name of the root TypeScript namespace that contains schema types.
+    pub schema_root_namespace: String,
+    /// Source of schema type to import from.
+    pub schema_source: String,
+    /// Source of GraphQLResolveInfo to import from.
+    pub graphql_source: String,
+    /// Type of parent value for each object type. Provided as raw TypeScript code.
+    /// Object types not in this map use the schema type.
+    pub parent_types: HashMap<String, String>,
+    /// Type of context passed to resolvers. Provided as raw TypeScript code.
+    pub context_type: String,
+}
+
+impl Default for ResolverTypePrinterOptions {
+    fn default() -> Self {
+        ResolverTypePrinterOptions {
+            schema_root_namespace: "Schema".to_owned(),
+            schema_source: "".to_owned(),
+            graphql_source: "graphql".to_owned(),
+            parent_types: HashMap::new(),
+            context_type: "unknown".to_owned(),
+        }
+    }
+}
+
+/// Name of the generated type that maps type names to parent types.
+const PARENTS_TYPE: &str = "ResolverParents";
+
+pub struct ResolverTypePrinter<'a, Writer: SourceMapWriter> {
+    options: ResolverTypePrinterOptions,
+    writer: &'a mut Writer,
+}
+
+impl<'a, Writer> ResolverTypePrinter<'a, Writer>
+where
+    Writer: SourceMapWriter,
+{
+    pub fn new(options: ResolverTypePrinterOptions, writer: &'a mut Writer) -> Self {
+        ResolverTypePrinter { options, writer }
+    }
+
+    pub fn print_document(&mut self, document: &TypeSystemDocument) {
+        let schema = ast_to_type_system(document);
+        let type_definitions = document
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                TypeSystemDefinition::TypeDefinition(def) => Some(def),
+                _ => None,
+            })
+            // Introspection types are resolved by GraphQL implementations.
+            .filter(|def| !def.name().name.starts_with("__"))
+            .collect::<Vec<_>>();
+
+        self.writer.write(&format!(
+            "import type {{ GraphQLResolveInfo }} from \"{}\";\n",
+            self.options.graphql_source
+        ));
+        self.writer.write(&format!(
+            "import type * as {} from \"{}\";\n\n",
+            self.options.schema_root_namespace, self.options.schema_source,
+        ));
+        self.writer.write("export type ResolverContext = ");
+        self.writer.write(&self.options.context_type);
+        self.writer.write(
+            ";
+
+type __Resolver<Parent, Args, Result> =
+  (parent: Parent, args: Args, context: ResolverContext, info: GraphQLResolveInfo) => Result | Promise<Result>;
+type __TypeResolver<Obj, Result> =
+  (object: Obj, context: ResolverContext, info: GraphQLResolveInfo) => Result | Promise<Result>;
+
+",
+        );
+
+        let parents_type = TSType::object(type_definitions.iter().filter_map(|def| {
+            self.parent_type(&schema, def)
+                .map(|ty| (def.name(), ty, None))
+        }));
+        self.writer.write("export type ");
+        self.writer.write(PARENTS_TYPE);
+        self.writer.write(" = ");
+        parents_type.print_type(self.writer);
+        self.writer.write(";\n\n");
+
+        let resolvers_type = TSType::Object(
+            type_definitions
+                .iter()
+                .filter_map(|def| self.type_resolvers(&schema, def))
+                .collect(),
+        );
+        self.writer.write("export type Resolvers = ");
+        resolvers_type.print_type(self.writer);
+        self.writer.write(";\n");
+    }
+
+    /// Generates the entry of `Resolvers` for given type.
+    /// Returns None for types that do not have resolvers.
+    /// The entry is optional so that default resolvers are used for types without one.
+    fn type_resolvers(
+        &self,
+        schema: &Schema<Cow<str>, Pos>,
+        def: &TypeDefinition,
+    ) -> Option<ObjectField> {
+        let resolvers = match def {
+            TypeDefinition::Object(def) => TSType::Object(
+                def.fields
+                    .iter()
+                    .map(|field| self.field_resolver(schema, def.name.name, field))
+                    .collect(),
+            ),
+            TypeDefinition::Interface(def) => type_resolver(
+                def.name.name,
+                interface_implementers(schema, def.name.name).map(|obj| {
+                    let name: &str = obj.name.inner_ref().borrow();
+                    name.to_owned()
+                }),
+            ),
+            TypeDefinition::Union(def) => type_resolver(
+                def.name.name,
+                def.members.iter().map(|member| member.name.to_owned()),
+            ),
+            _ => return None,
+        };
+        Some(ObjectField {
+            key: def.name().into(),
+            r#type: resolvers,
+            readonly: false,
+            optional: true,
+            description: None,
+        })
+    }
+
+    /// Calculates the type of parent value for given type.
+    /// Returns None for types that do not have resolvers.
+    fn parent_type(&self, schema: &Schema<Cow<str>, Pos>, def: &TypeDefinition) -> Option<TSType> {
+        let name = def.name().name;
+        match def {
+            TypeDefinition::Object(_) => {
+                if let Some(parent_type) = self.options.parent_types.get(name) {
+                    return Some(TSType::TypeVariable(parent_type.as_str().into()));
+                }
+                let root_types = schema.root_types().unwrap_or_default();
+                let is_root_type = [
+                    &root_types.query_type,
+                    &root_types.mutation_type,
+                    &root_types.subscription_type,
+                ]
+                .into_iter()
+                .any(|root_type| {
+                    let root_type: &str = root_type.inner_ref().borrow();
+                    root_type == name
+                });
+                if is_root_type {
+                    // Root values are not part of the schema.
+                    Some(TSType::empty_object())
+                } else {
+                    Some(TSType::TypeFunc(
+                        Box::new(TSType::TypeVariable("Omit".into())),
+                        vec![
+                            TSType::NamespaceMember(
+                                self.options.schema_root_namespace.clone(),
+                                name.to_owned(),
+                            ),
+                            TSType::StringLiteral("__typename".to_owned()),
+                        ],
+                    ))
+                }
+            }
+            TypeDefinition::Interface(_) => {
+                Some(ts_union(interface_implementers(schema, name).map(|obj| {
+                    let name: &str = obj.name.inner_ref().borrow();
+                    parent_of(name)
+                })))
+            }
+            TypeDefinition::Union(def) => Some(ts_union(
+                def.members.iter().map(|member| parent_of(member.name)),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Generates a resolver type for an object field.
+    /// Resolvers of fields are optional as the default resolver reads the field of the parent value.
+    fn field_resolver(
+        &self,
+        schema: &Schema<Cow<str>, Pos>,
+        parent_name: &str,
+        field: &FieldDefinition,
+    ) -> ObjectField {
+        let args_type = self.arguments_type(&field.arguments);
+        let result_type = self.result_type(schema, &field.r#type);
+        ObjectField {
+            key: (&field.name).into(),
+            r#type: TSType::TypeFunc(
+                Box::new(TSType::TypeVariable("__Resolver".into())),
+                vec![parent_of(parent_name), args_type, result_type],
+            ),
+            readonly: false,
+            optional: true,
+            description: field.description.as_ref().map(|d| d.value.clone()),
+        }
+    }
+
+    fn arguments_type(&self, arguments: &Option<ArgumentsDefinition>) -> TSType {
+        let Some(arguments) = arguments else {
+            return TSType::empty_object();
+        };
+        TSType::Object(
+            arguments
+                .input_values
+                .iter()
+                .map(|arg| ObjectField {
+                    key: (&arg.name).into(),
+                    r#type: get_ts_type_of_type(&arg.r#type, |name| {
+                        TSType::NamespaceMember(
+                            self.options.schema_root_namespace.clone(),
+                            name.name.name.to_owned(),
+                        )
+                    })
+                    .into_readonly(),
+                    readonly: true,
+                    optional: !arg.r#type.is_nonnull(),
+                    description: arg.description.as_ref().map(|d| d.value.clone()),
+                })
+                .collect(),
+        )
+    }
+
+    /// Type of resolver result. Object types are resolved to their parent types.
+    fn result_type(&self, schema: &Schema<Cow<str>, Pos>, ty: &Type) -> TSType {
+        get_ts_type_of_type(ty, |name: &NamedType| {
+            let has_parent = schema.get_type(name.name.name).is_some_and(|def| {
+                def.as_object().is_some()
+                    || def.as_union().is_some()
+                    || matches!(**def, SchemaTypeDefinition::Interface(_))
+            });
+            if has_parent {
+                parent_of(name.name.name)
+            } else {
+                TSType::NamespaceMember(
+                    self.options.schema_root_namespace.clone(),
+                    name.name.name.to_owned(),
+                )
+            }
+        })
+    }
+}
+
+/// Generates `__resolveType` resolver for an abstract type.
+fn type_resolver(name: &str, possible_types: impl Iterator<Item = String>) -> TSType {
+    TSType::object([(
+        "__resolveType",
+        TSType::TypeFunc(
+            Box::new(TSType::TypeVariable("__TypeResolver".into())),
+            vec![
+                parent_of(name),
+                ts_union(possible_types.map(TSType::StringLiteral)),
+            ],
+        ),
+        None,
+    )])
+}
+
+/// Refers to the parent type of given type.
+fn parent_of(name: &str) -> TSType {
+    TSType::TypeVariable(format!("{PARENTS_TYPE}[\"{name}\"]").as_str().into())
+}
@@ -0,0 +1,96 @@
+#![cfg(test)]
+
+use insta::assert_snapshot;
+use nitrogql_ast::type_system::TypeSystemDocument;
+
+use crate::resolver_type_printer::printer::{ResolverTypePrinter, ResolverTypePrinterOptions};
+use graphql_builtins::generate_builtins;
+use nitrogql_parser::parse_type_system_document;
+use nitrogql_semantics::resolve_extensions;
+use sourcemap_writer::JustWriter;
+
+#[test]
+fn resolver_printing() {
+    let mut doc = parse_type_system_document(
+        r#"
+            type User implements HasID {
+                id: ID!
+                "Name of user."
+                name: String!
+                type: UserType!
+                posts(first: Int, after: String): [Post!]!
+            }
+            interface HasID {
+                id: ID!
+            }
+            enum UserType {
+                NormalUser
+                PremiumUser
+            }
+            type Bot implements HasID {
+                id: ID!
+            }
+            type Post {
+                id: ID!
+                title: String!
+                author: User!
+            }
+            union SearchResult = User | Post
+
+            input UserSearchQuery {
+                age: Int
+                name: String
+            }
+
+            type Query {
+                me: User!
+                node(id: ID!): HasID
+                search(query: UserSearchQuery!): [SearchResult!]!
+            }
+            "#,
+    )
+    .unwrap();
+    doc.extend(generate_builtins());
+    let doc = resolve_extensions(doc).unwrap();
+    let printed = print_document(&doc, Default::default());
+    assert_snapshot!(printed);
+}
+
+#[test]
+fn custom_parent_and_context() {
+    let doc = parse_type_system_document(
+        r#"
+            schema {
+                query: RootQuery
+            }
+            type User {
+                id: ID!
+                name: String!
+            }
+            type RootQuery {
+                me: User
+            }
+            "#,
+    )
+    .unwrap();
+    let doc = resolve_extensions(doc).unwrap();
+    let mut options = ResolverTypePrinterOptions {
+        schema_source: "./schema".to_owned(),
+        context_type: "import(\"./context\").Context".to_owned(),
+        ..ResolverTypePrinterOptions::default()
+    };
+    options.parent_types.extend(vec![(
+        "User".to_owned(),
+        "import(\"./models\").UserModel".to_owned(),
+    )]);
+    let printed = print_document(&doc, options);
+    assert_snapshot!(printed);
+}
+
+fn print_document(document: &TypeSystemDocument, options: ResolverTypePrinterOptions) -> String {
+    let mut result = String::new();
+    let mut writer = JustWriter::new(&mut result);
+    let mut printer = ResolverTypePrinter::new(options, &mut writer);
+    printer.print_document(document);
+    result
+}
@@ -163,6 +163,34 @@ fn deprecated_items() {
     assert_snapshot!(printed);
 }
 
+#[test]
+fn description_is_kept_as_authored() {
+    let doc = parse_type_system_document(
+        r#"
+        """
+        A user of the service.
+
+        Example:
+            query { me { id } }
+        Comments like */ are escaped.
+        """
+        type User {
+            id: ID!
+        }
+
+        type Query {
+            "Current user.\n\n  - indented"
+            me: User!
+        }
+        "#,
+    )
+    .unwrap();
+    let doc = resolve_extensions(doc).unwrap();
+    let options = SchemaTypePrinterOptions::default();
+    let printed = print_document(&doc, options).unwrap();
+    assert_snapshot!(printed);
+}
+
 #[test]
 fn enum_runtime() {
     let doc = parse_type_system_document(
@@ -0,0 +1,239 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+use nitrogql_ast::base::Pos;
+use nitrogql_error::PositionedError;
+
+/// How much a change affects existing clients.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeSeverity {
+    /// Change that breaks existing operations.
+    Breaking,
+    /// Change that does not break existing operations but may change their behavior.
+    Dangerous,
+    /// Change that is compatible with existing operations.
+    Safe,
+}
+
+impl ChangeSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSeverity::Breaking => "breaking",
+            ChangeSeverity::Dangerous => "dangerous",
+            ChangeSeverity::Safe => "safe",
+        }
+    }
+}
+
+impl Display for ChangeSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One change found between two schemas.
+#[derive(Debug)]
+pub struct SchemaChange {
+    pub severity: ChangeSeverity,
+    pub message: SchemaChangeMessage,
+    /// Position of the changed element in the old schema.
+    pub old_position: Option<Pos>,
+    /// Position of the changed element in the new schema.
+    pub new_position: Option<Pos>,
+}
+
+#[derive(Error, Debug)]
+pub enum SchemaChangeMessage {
+    // types
+    #[error("Type '{name}' was removed")]
+    TypeRemoved { name: String },
+    #[error("Type '{name}' was added")]
+    TypeAdded { name: String },
+    #[error("Type '{name}' changed from {old_kind} to {new_kind}")]
+    TypeKindChanged {
+        name: String,
+        old_kind: &'static str,
+        new_kind: &'static str,
+    },
+    // fields
+    #[error("Field '{type_name}.{field_name}' was removed")]
+    FieldRemoved {
+        type_name: String,
+        field_name: String,
+    },
+    #[error("Field '{type_name}.{field_name}' was added")]
+    FieldAdded {
+        type_name: String,
+        field_name: String,
+    },
+    #[error("Field '{type_name}.{field_name}' changed type from '{old_type}' to '{new_type}'")]
+    FieldTypeChanged {
+        type_name: String,
+        field_name: String,
+        old_type: String,
+        new_type: String,
+    },
+    #[error("Field '{type_name}.{field_name}' was deprecated")]
+    FieldDeprecated {
+        type_name: String,
+        field_name: String,
+    },
+    // arguments
+    #[error("Argument '{argument_name}' was removed from '{parent}'")]
+    ArgumentRemoved {
+        parent: String,
+        argument_name: String,
+    },
+    #[error("Required argument '{argument_name}' was added to '{parent}'")]
+    RequiredArgumentAdded {
+        parent: String,
+        argument_name: String,
+    },
+    #[error("Optional argument '{argument_name}' was added to '{parent}'")]
+    OptionalArgumentAdded {
+        parent: String,
+        argument_name: String,
+    },
+    #[error(
+        "Argument '{argument_name}' of '{parent}' changed type from '{old_type}' to '{new_type}'"
+    )]
+    ArgumentTypeChanged {
+        parent: String,
+        argument_name: String,
+        old_type: String,
+        new_type: String,
+    },
+    #[error("Default value of argument '{argument_name}' of '{parent}' changed")]
+    ArgumentDefaultValueChanged {
+        parent: String,
+        argument_name: String,
+    },
+    // enums
+    #[error("Value '{value}' was removed from enum '{enum_name}'")]
+    EnumValueRemoved { enum_name: String, value: String },
+    #[error("Value '{value}' was added to enum '{enum_name}'")]
+    EnumValueAdded { enum_name: String, value: String },
+    #[error("Value '{value}' of enum '{enum_name}' was deprecated")]
+    EnumValueDeprecated { enum_name: String, value: String },
+    // unions
+    #[error("Member '{member}' was removed from union '{union_name}'")]
+    UnionMemberRemoved { union_name: String, member: String },
+    #[error("Member '{member}' was added to union '{union_name}'")]
+    UnionMemberAdded { union_name: String, member: String },
+    // interfaces
+    #[error("'{type_name}' no longer implements interface '{interface_name}'")]
+    InterfaceRemoved {
+        type_name: String,
+        interface_name: String,
+    },
+    #[error("'{type_name}' now implements interface '{interface_name}'")]
+    InterfaceAdded {
+        type_name: String,
+        interface_name: String,
+    },
+    // input objects
+    #[error("Input field '{type_name}.{field_name}' was removed")]
+    InputFieldRemoved {
+        type_name: String,
+        field_name: String,
+    },
+    #[error("Required input field '{type_name}.{field_name}' was added")]
+    RequiredInputFieldAdded {
+        type_name: String,
+        field_name: String,
+    },
+    #[error("Optional input field '{type_name}.{field_name}' was added")]
+    OptionalInputFieldAdded {
+        type_name: String,
+        field_name: String,
+    },
+    #[error(
+        "Input field '{type_name}.{field_name}' changed type from '{old_type}' to '{new_type}'"
+    )]
+    InputFieldTypeChanged {
+        type_name: String,
+        field_name: String,
+        old_type: String,
+        new_type: String,
+    },
+    #[error("Default value of input field '{type_name}.{field_name}' changed")]
+    InputFieldDefaultValueChanged {
+        type_name: String,
+        field_name: String,
+    },
+    // directives
+    #[error("Directive '@{name}' was removed")]
+    DirectiveRemoved { name: String },
+    #[error("Directive '@{name}' was added")]
+    DirectiveAdded { name: String },
+    #[error("Location '{location}' was removed from directive '@{name}'")]
+    DirectiveLocationRemoved { name: String, location: String },
+    #[error("Location '{location}' was added to directive '@{name}'")]
+    DirectiveLocationAdded { name: String, location: String },
+    #[error("Directive '@{name}' is no longer repeatable")]
+    DirectiveRepeatableRemoved { name: String },
+    #[error("Directive '@{name}' is now repeatable")]
+    DirectiveRepeatableAdded { name: String },
+    // schema
+    #[error("Root {operation_type} type changed from '{old_type}' to '{new_type}'")]
+    RootTypeChanged {
+        operation_type: &'static str,
+        old_type: String,
+        new_type: String,
+    },
+    #[error("Root {operation_type} type '{name}' was removed")]
+    RootTypeRemoved {
+        operation_type: &'static str,
+        name: String,
+    },
+    #[error("Root {operation_type} type '{name}' was added")]
+    RootTypeAdded {
+        operation_type: &'static str,
+        name: String,
+    },
+    // For additional info
+    #[error("Previous definition")]
+    PreviousDefinition,
+}
+
+impl SchemaChangeMessage {
+    pub fn with_severity(self, severity: ChangeSeverity) -> SchemaChange {
+        SchemaChange {
+            severity,
+            message: self,
+            old_position: None,
+            new_position: None,
+        }
+    }
+}
+
+impl SchemaChange {
+    pub fn with_old_position(mut self, position: Pos) -> Self {
+        self.old_position = Some(position);
+        self
+    }
+    pub fn with_new_position(mut self, position: Pos) -> Self {
+        self.new_position = Some(position);
+        self
+    }
+}
+
+impl From<SchemaChange> for PositionedError {
+    /// Position in the new schema is primary. Position in the old schema is shown as additional info if both exist.
+    fn from(value: SchemaChange) -> Self {
+        match (value.old_position, value.new_position) {
+            (old_position, Some(new_position)) => PositionedError::new(
+                value.message.into(),
+                Some(new_position),
+                old_position
+                    .map(|pos| (pos, SchemaChangeMessage::PreviousDefinition.to_string()))
+                    .into_iter()
+                    .collect(),
+            ),
+            (old_position, None) => {
+                PositionedError::new(value.message.into(), old_position, vec![])
+            }
+        }
+    }
+}
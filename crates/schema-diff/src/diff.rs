@@ -0,0 +1,767 @@
+use graphql_type_system::{
+    DirectiveDefinition, EnumMember, Field, InputValue, Node, OriginalNodeRef, RootTypes, Schema,
+    Text, Type, TypeDefinition,
+};
+use nitrogql_ast::base::Pos;
+
+use crate::change::{ChangeSeverity, SchemaChange, SchemaChangeMessage};
+
+/// Compares two schemas and lists changes from `old` to `new`.
+/// Built-in definitions that exist in only one of the schemas are ignored.
+pub fn diff_schemas<'a, S: Text<'a>>(
+    old: &Schema<S, Pos>,
+    new: &Schema<S, Pos>,
+) -> Vec<SchemaChange> {
+    let mut changes = vec![];
+    diff_root_types(old, new, &mut changes);
+    diff_types(old, new, &mut changes);
+    diff_directives(old, new, &mut changes);
+    changes
+}
+
+fn diff_root_types<'a, S: Text<'a>>(
+    old: &Schema<S, Pos>,
+    new: &Schema<S, Pos>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let operation_types: [(&'static str, &'static str, RootTypeSelector<S>); 3] = [
+        ("query", "Query", |root_types| &root_types.query_type),
+        ("mutation", "Mutation", |root_types| {
+            &root_types.mutation_type
+        }),
+        ("subscription", "Subscription", |root_types| {
+            &root_types.subscription_type
+        }),
+    ];
+    for (operation_type, default_name, get) in operation_types {
+        let old_root = root_type(old, get, default_name);
+        let new_root = root_type(new, get, default_name);
+        match (old_root, new_root) {
+            (Some((old_name, old_pos)), Some((new_name, new_pos))) => {
+                if old_name != new_name {
+                    changes.push(
+                        SchemaChangeMessage::RootTypeChanged {
+                            operation_type,
+                            old_type: old_name.to_owned(),
+                            new_type: new_name.to_owned(),
+                        }
+                        .with_severity(ChangeSeverity::Breaking)
+                        .with_old_position(old_pos)
+                        .with_new_position(new_pos),
+                    );
+                }
+            }
+            (Some((old_name, old_pos)), None) => {
+                changes.push(
+                    SchemaChangeMessage::RootTypeRemoved {
+                        operation_type,
+                        name: old_name.to_owned(),
+                    }
+                    .with_severity(ChangeSeverity::Breaking)
+                    .with_old_position(old_pos),
+                );
+            }
+            (None, Some((new_name, new_pos))) => {
+                changes.push(
+                    SchemaChangeMessage::RootTypeAdded {
+                        operation_type,
+                        name: new_name.to_owned(),
+                    }
+                    .with_severity(ChangeSeverity::Safe)
+                    .with_new_position(new_pos),
+                );
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Returns name and position of a root type.
+/// Without a schema definition, root types are found by their default names.
+fn root_type<'a, 's, S: Text<'a>>(
+    schema: &'s Schema<S, Pos>,
+    get: RootTypeSelector<S>,
+    default_name: &'static str,
+) -> Option<(&'s str, Pos)> {
+    let root_types = schema.root_types();
+    let has_schema_definition = root_types.query_type.is_some()
+        || root_types.mutation_type.is_some()
+        || root_types.subscription_type.is_some();
+    if has_schema_definition {
+        get(root_types)
+            .as_ref()
+            .map(|name| (&***name, *name.original_node_ref()))
+    } else {
+        schema
+            .get_type(default_name)
+            .map(|def| (default_name, *def.original_node_ref()))
+    }
+}
+
+type RootTypeSelector<S> = fn(&RootTypes<Option<Node<S, Pos>>>) -> &Option<Node<S, Pos>>;
+
+fn diff_types<'a, S: Text<'a>>(
+    old: &Schema<S, Pos>,
+    new: &Schema<S, Pos>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, old_def) in old.iter_types() {
+        if name.starts_with("__") {
+            continue;
+        }
+        match new.get_type(name) {
+            Some(new_def) => diff_type(old_def, new_def, changes),
+            None => {
+                let old_pos = *old_def.inner_ref().original_node_ref();
+                if !old_pos.builtin {
+                    changes.push(
+                        SchemaChangeMessage::TypeRemoved {
+                            name: name.to_string(),
+                        }
+                        .with_severity(ChangeSeverity::Breaking)
+                        .with_old_position(old_pos),
+                    );
+                }
+            }
+        }
+    }
+    for (name, new_def) in new.iter_types() {
+        let new_pos = *new_def.inner_ref().original_node_ref();
+        if name.starts_with("__") || new_pos.builtin || old.get_type(name).is_some() {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::TypeAdded {
+                name: name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Safe)
+            .with_new_position(new_pos),
+        );
+    }
+}
+
+fn diff_type<'a, S: Text<'a>>(
+    old: &TypeDefinition<S, Pos>,
+    new: &TypeDefinition<S, Pos>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let parents = Parents {
+        name: old.name(),
+        old_pos: *old.original_node_ref(),
+        new_pos: *new.original_node_ref(),
+    };
+    match (old, new) {
+        (TypeDefinition::Scalar(_), TypeDefinition::Scalar(_)) => {}
+        (TypeDefinition::Object(old), TypeDefinition::Object(new)) => {
+            diff_fields(&parents, &old.fields, &new.fields, changes);
+            diff_interfaces(&parents, &old.interfaces, &new.interfaces, changes);
+        }
+        (TypeDefinition::Interface(old), TypeDefinition::Interface(new)) => {
+            diff_fields(&parents, &old.fields, &new.fields, changes);
+            diff_interfaces(&parents, &old.interfaces, &new.interfaces, changes);
+        }
+        (TypeDefinition::Union(old), TypeDefinition::Union(new)) => {
+            diff_union_members(&parents, &old.possible_types, &new.possible_types, changes);
+        }
+        (TypeDefinition::Enum(old), TypeDefinition::Enum(new)) => {
+            diff_enum_members(&parents, &old.members, &new.members, changes);
+        }
+        (TypeDefinition::InputObject(old), TypeDefinition::InputObject(new)) => {
+            diff_input_fields(&parents, &old.fields, &new.fields, changes);
+        }
+        _ => {
+            changes.push(
+                SchemaChangeMessage::TypeKindChanged {
+                    name: old.name().to_string(),
+                    old_kind: type_kind(old),
+                    new_kind: type_kind(new),
+                }
+                .with_severity(ChangeSeverity::Breaking)
+                .with_old_position(parents.old_pos)
+                .with_new_position(parents.new_pos),
+            );
+        }
+    }
+}
+
+/// Name and positions of the definition that contains compared elements.
+struct Parents<'s> {
+    name: &'s str,
+    old_pos: Pos,
+    new_pos: Pos,
+}
+
+fn diff_fields<'a, S: Text<'a>>(
+    parents: &Parents,
+    old_fields: &[Field<S, Pos>],
+    new_fields: &[Field<S, Pos>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let type_name = parents.name;
+    for old_field in old_fields {
+        let old_pos = *old_field.original_node_ref();
+        let Some(new_field) = new_fields
+            .iter()
+            .find(|field| field.name == *old_field.name)
+        else {
+            changes.push(
+                SchemaChangeMessage::FieldRemoved {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Breaking)
+                .with_old_position(old_pos)
+                .with_new_position(parents.new_pos),
+            );
+            continue;
+        };
+        let new_pos = *new_field.original_node_ref();
+        if !is_same_type(&old_field.r#type, &new_field.r#type) {
+            let severity = if is_safe_output_type_change(&old_field.r#type, &new_field.r#type) {
+                ChangeSeverity::Safe
+            } else {
+                ChangeSeverity::Breaking
+            };
+            changes.push(
+                SchemaChangeMessage::FieldTypeChanged {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.to_string(),
+                    old_type: old_field.r#type.to_string(),
+                    new_type: new_field.r#type.to_string(),
+                }
+                .with_severity(severity)
+                .with_old_position(old_pos)
+                .with_new_position(new_pos),
+            );
+        }
+        if old_field.deprecation.is_none() && new_field.deprecation.is_some() {
+            changes.push(
+                SchemaChangeMessage::FieldDeprecated {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Safe)
+                .with_old_position(old_pos)
+                .with_new_position(new_pos),
+            );
+        }
+        let field_parents = Parents {
+            name: &format!("{type_name}.{}", old_field.name),
+            old_pos,
+            new_pos,
+        };
+        diff_arguments(
+            &field_parents,
+            &old_field.arguments,
+            &new_field.arguments,
+            changes,
+        );
+    }
+    for new_field in new_fields {
+        if old_fields.iter().any(|field| field.name == *new_field.name) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::FieldAdded {
+                type_name: type_name.to_owned(),
+                field_name: new_field.name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Safe)
+            .with_old_position(parents.old_pos)
+            .with_new_position(*new_field.original_node_ref()),
+        );
+    }
+}
+
+fn diff_arguments<'a, S: Text<'a>>(
+    parents: &Parents,
+    old_arguments: &[InputValue<S, Pos>],
+    new_arguments: &[InputValue<S, Pos>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let parent = parents.name;
+    for old_argument in old_arguments {
+        let old_pos = *old_argument.original_node_ref();
+        let Some(new_argument) = new_arguments
+            .iter()
+            .find(|argument| argument.name == *old_argument.name)
+        else {
+            changes.push(
+                SchemaChangeMessage::ArgumentRemoved {
+                    parent: parent.to_owned(),
+                    argument_name: old_argument.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Breaking)
+                .with_old_position(old_pos)
+                .with_new_position(parents.new_pos),
+            );
+            continue;
+        };
+        let new_pos = *new_argument.original_node_ref();
+        if !is_same_type(&old_argument.r#type, &new_argument.r#type) {
+            let severity = if is_safe_input_type_change(&old_argument.r#type, &new_argument.r#type)
+            {
+                ChangeSeverity::Safe
+            } else {
+                ChangeSeverity::Breaking
+            };
+            changes.push(
+                SchemaChangeMessage::ArgumentTypeChanged {
+                    parent: parent.to_owned(),
+                    argument_name: old_argument.name.to_string(),
+                    old_type: old_argument.r#type.to_string(),
+                    new_type: new_argument.r#type.to_string(),
+                }
+                .with_severity(severity)
+                .with_old_position(old_pos)
+                .with_new_position(new_pos),
+            );
+        }
+        if !is_same_default_value(old_argument, new_argument) {
+            changes.push(
+                SchemaChangeMessage::ArgumentDefaultValueChanged {
+                    parent: parent.to_owned(),
+                    argument_name: old_argument.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Dangerous)
+                .with_old_position(old_pos)
+                .with_new_position(new_pos),
+            );
+        }
+    }
+    for new_argument in new_arguments {
+        if old_arguments
+            .iter()
+            .any(|argument| argument.name == *new_argument.name)
+        {
+            continue;
+        }
+        let change = if is_required(new_argument) {
+            SchemaChangeMessage::RequiredArgumentAdded {
+                parent: parent.to_owned(),
+                argument_name: new_argument.name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Breaking)
+        } else {
+            SchemaChangeMessage::OptionalArgumentAdded {
+                parent: parent.to_owned(),
+                argument_name: new_argument.name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Safe)
+        };
+        changes.push(
+            change
+                .with_old_position(parents.old_pos)
+                .with_new_position(*new_argument.original_node_ref()),
+        );
+    }
+}
+
+fn diff_interfaces<'a, S: Text<'a>>(
+    parents: &Parents,
+    old_interfaces: &[Node<S, Pos>],
+    new_interfaces: &[Node<S, Pos>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_interface in old_interfaces {
+        if new_interfaces.iter().any(|name| *name == **old_interface) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::InterfaceRemoved {
+                type_name: parents.name.to_owned(),
+                interface_name: old_interface.to_string(),
+            }
+            .with_severity(ChangeSeverity::Breaking)
+            .with_old_position(*old_interface.original_node_ref())
+            .with_new_position(parents.new_pos),
+        );
+    }
+    for new_interface in new_interfaces {
+        if old_interfaces.iter().any(|name| *name == **new_interface) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::InterfaceAdded {
+                type_name: parents.name.to_owned(),
+                interface_name: new_interface.to_string(),
+            }
+            .with_severity(ChangeSeverity::Dangerous)
+            .with_old_position(parents.old_pos)
+            .with_new_position(*new_interface.original_node_ref()),
+        );
+    }
+}
+
+fn diff_union_members<'a, S: Text<'a>>(
+    parents: &Parents,
+    old_members: &[Node<S, Pos>],
+    new_members: &[Node<S, Pos>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_member in old_members {
+        if new_members.iter().any(|name| *name == **old_member) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::UnionMemberRemoved {
+                union_name: parents.name.to_owned(),
+                member: old_member.to_string(),
+            }
+            .with_severity(ChangeSeverity::Breaking)
+            .with_old_position(*old_member.original_node_ref())
+            .with_new_position(parents.new_pos),
+        );
+    }
+    for new_member in new_members {
+        if old_members.iter().any(|name| *name == **new_member) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::UnionMemberAdded {
+                union_name: parents.name.to_owned(),
+                member: new_member.to_string(),
+            }
+            .with_severity(ChangeSeverity::Dangerous)
+            .with_old_position(parents.old_pos)
+            .with_new_position(*new_member.original_node_ref()),
+        );
+    }
+}
+
+fn diff_enum_members<'a, S: Text<'a>>(
+    parents: &Parents,
+    old_members: &[EnumMember<S, Pos>],
+    new_members: &[EnumMember<S, Pos>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let enum_name = parents.name;
+    for old_member in old_members {
+        let old_pos = *old_member.name.original_node_ref();
+        let Some(new_member) = new_members
+            .iter()
+            .find(|member| member.name == *old_member.name)
+        else {
+            changes.push(
+                SchemaChangeMessage::EnumValueRemoved {
+                    enum_name: enum_name.to_owned(),
+                    value: old_member.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Breaking)
+                .with_old_position(old_pos)
+                .with_new_position(parents.new_pos),
+            );
+            continue;
+        };
+        if old_member.deprecation.is_none() && new_member.deprecation.is_some() {
+            changes.push(
+                SchemaChangeMessage::EnumValueDeprecated {
+                    enum_name: enum_name.to_owned(),
+                    value: old_member.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Safe)
+                .with_old_position(old_pos)
+                .with_new_position(*new_member.name.original_node_ref()),
+            );
+        }
+    }
+    for new_member in new_members {
+        if old_members
+            .iter()
+            .any(|member| member.name == *new_member.name)
+        {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::EnumValueAdded {
+                enum_name: enum_name.to_owned(),
+                value: new_member.name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Dangerous)
+            .with_old_position(parents.old_pos)
+            .with_new_position(*new_member.name.original_node_ref()),
+        );
+    }
+}
+
+fn diff_input_fields<'a, S: Text<'a>>(
+    parents: &Parents,
+    old_fields: &[InputValue<S, Pos>],
+    new_fields: &[InputValue<S, Pos>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let type_name = parents.name;
+    for old_field in old_fields {
+        let old_pos = *old_field.original_node_ref();
+        let Some(new_field) = new_fields
+            .iter()
+            .find(|field| field.name == *old_field.name)
+        else {
+            changes.push(
+                SchemaChangeMessage::InputFieldRemoved {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Breaking)
+                .with_old_position(old_pos)
+                .with_new_position(parents.new_pos),
+            );
+            continue;
+        };
+        let new_pos = *new_field.original_node_ref();
+        if !is_same_type(&old_field.r#type, &new_field.r#type) {
+            let severity = if is_safe_input_type_change(&old_field.r#type, &new_field.r#type) {
+                ChangeSeverity::Safe
+            } else {
+                ChangeSeverity::Breaking
+            };
+            changes.push(
+                SchemaChangeMessage::InputFieldTypeChanged {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.to_string(),
+                    old_type: old_field.r#type.to_string(),
+                    new_type: new_field.r#type.to_string(),
+                }
+                .with_severity(severity)
+                .with_old_position(old_pos)
+                .with_new_position(new_pos),
+            );
+        }
+        if !is_same_default_value(old_field, new_field) {
+            changes.push(
+                SchemaChangeMessage::InputFieldDefaultValueChanged {
+                    type_name: type_name.to_owned(),
+                    field_name: old_field.name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Dangerous)
+                .with_old_position(old_pos)
+                .with_new_position(new_pos),
+            );
+        }
+    }
+    for new_field in new_fields {
+        if old_fields.iter().any(|field| field.name == *new_field.name) {
+            continue;
+        }
+        let change = if is_required(new_field) {
+            SchemaChangeMessage::RequiredInputFieldAdded {
+                type_name: type_name.to_owned(),
+                field_name: new_field.name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Breaking)
+        } else {
+            SchemaChangeMessage::OptionalInputFieldAdded {
+                type_name: type_name.to_owned(),
+                field_name: new_field.name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Safe)
+        };
+        changes.push(
+            change
+                .with_old_position(parents.old_pos)
+                .with_new_position(*new_field.original_node_ref()),
+        );
+    }
+}
+
+fn diff_directives<'a, S: Text<'a>>(
+    old: &Schema<S, Pos>,
+    new: &Schema<S, Pos>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, old_def) in old.iter_directives() {
+        let old_pos = *old_def.name.original_node_ref();
+        match new.get_directive(name) {
+            Some(new_def) => diff_directive(old_def, new_def, changes),
+            None => {
+                if !old_pos.builtin {
+                    changes.push(
+                        SchemaChangeMessage::DirectiveRemoved {
+                            name: name.to_string(),
+                        }
+                        .with_severity(ChangeSeverity::Breaking)
+                        .with_old_position(old_pos),
+                    );
+                }
+            }
+        }
+    }
+    for (name, new_def) in new.iter_directives() {
+        let new_pos = *new_def.name.original_node_ref();
+        if new_pos.builtin || old.get_directive(name).is_some() {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::DirectiveAdded {
+                name: name.to_string(),
+            }
+            .with_severity(ChangeSeverity::Safe)
+            .with_new_position(new_pos),
+        );
+    }
+}
+
+fn diff_directive<'a, S: Text<'a>>(
+    old: &DirectiveDefinition<S, Pos>,
+    new: &DirectiveDefinition<S, Pos>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let name = old.name();
+    let old_pos = *old.name.original_node_ref();
+    let new_pos = *new.name.original_node_ref();
+    for old_location in old.locations.iter() {
+        if new.locations.iter().any(|loc| *loc == **old_location) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::DirectiveLocationRemoved {
+                name: name.to_string(),
+                location: old_location.to_string(),
+            }
+            .with_severity(ChangeSeverity::Breaking)
+            .with_old_position(*old_location.original_node_ref())
+            .with_new_position(new_pos),
+        );
+    }
+    for new_location in new.locations.iter() {
+        if old.locations.iter().any(|loc| *loc == **new_location) {
+            continue;
+        }
+        changes.push(
+            SchemaChangeMessage::DirectiveLocationAdded {
+                name: name.to_string(),
+                location: new_location.to_string(),
+            }
+            .with_severity(ChangeSeverity::Safe)
+            .with_old_position(old_pos)
+            .with_new_position(*new_location.original_node_ref()),
+        );
+    }
+    match (&old.repeatable, &new.repeatable) {
+        (Some(old_repeatable), None) => {
+            changes.push(
+                SchemaChangeMessage::DirectiveRepeatableRemoved {
+                    name: name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Breaking)
+                .with_old_position(*old_repeatable.original_node_ref())
+                .with_new_position(new_pos),
+            );
+        }
+        (None, Some(new_repeatable)) => {
+            changes.push(
+                SchemaChangeMessage::DirectiveRepeatableAdded {
+                    name: name.to_string(),
+                }
+                .with_severity(ChangeSeverity::Safe)
+                .with_old_position(old_pos)
+                .with_new_position(*new_repeatable.original_node_ref()),
+            );
+        }
+        _ => {}
+    }
+    let parents = Parents {
+        name: &format!("@{name}"),
+        old_pos,
+        new_pos,
+    };
+    diff_arguments(&parents, &old.arguments, &new.arguments, changes);
+}
+
+fn type_kind<S, D>(def: &TypeDefinition<S, D>) -> &'static str {
+    match def {
+        TypeDefinition::Scalar(_) => "scalar",
+        TypeDefinition::Object(_) => "object",
+        TypeDefinition::Interface(_) => "interface",
+        TypeDefinition::Union(_) => "union",
+        TypeDefinition::Enum(_) => "enum",
+        TypeDefinition::InputObject(_) => "input object",
+    }
+}
+
+fn is_same_type<'a, S: Text<'a>>(old: &Type<S, Pos>, new: &Type<S, Pos>) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new)) => ***old == ***new,
+        (Type::List(old), Type::List(new)) => is_same_type(old.as_inner(), new.as_inner()),
+        (Type::NonNull(old), Type::NonNull(new)) => is_same_type(old.as_inner(), new.as_inner()),
+        _ => false,
+    }
+}
+
+/// Checks whether changing the type of an output field from `old` to `new` is safe for clients.
+/// Output types may only become stricter.
+fn is_safe_output_type_change<'a, S: Text<'a>>(old: &Type<S, Pos>, new: &Type<S, Pos>) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new)) => ***old == ***new,
+        (Type::List(old), Type::List(new)) => {
+            is_safe_output_type_change(old.as_inner(), new.as_inner())
+        }
+        (Type::NonNull(old), Type::NonNull(new)) => {
+            is_safe_output_type_change(old.as_inner(), new.as_inner())
+        }
+        (Type::Named(_) | Type::List(_), Type::NonNull(new)) => {
+            is_safe_output_type_change(old, new.as_inner())
+        }
+        _ => false,
+    }
+}
+
+/// Checks whether changing the type of an input value from `old` to `new` is safe for clients.
+/// Input types may only become looser.
+fn is_safe_input_type_change<'a, S: Text<'a>>(old: &Type<S, Pos>, new: &Type<S, Pos>) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new)) => ***old == ***new,
+        (Type::List(old), Type::List(new)) => {
+            is_safe_input_type_change(old.as_inner(), new.as_inner())
+        }
+        (Type::NonNull(old), Type::NonNull(new)) => {
+            is_safe_input_type_change(old.as_inner(), new.as_inner())
+        }
+        (Type::NonNull(old), Type::Named(_) | Type::List(_)) => {
+            is_safe_input_type_change(old.as_inner(), new)
+        }
+        _ => false,
+    }
+}
+
+/// Input value is required if it is non-null and has no default value.
+fn is_required<S, D>(input_value: &InputValue<S, D>) -> bool {
+    input_value.r#type.is_nonnull() && input_value.default_value.is_none()
+}
+
+fn is_same_default_value<'a, S: Text<'a>>(
+    old: &InputValue<S, Pos>,
+    new: &InputValue<S, Pos>,
+) -> bool {
+    match (&old.default_value, &new.default_value) {
+        (Some(old), Some(new)) => normalize_value(old) == normalize_value(new),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Removes insignificant characters outside of strings
+/// so that values printed by different tools can be compared.
+fn normalize_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in value.chars() {
+        if in_string {
+            result.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            result.push(c);
+        } else if !c.is_whitespace() && c != ',' {
+            result.push(c);
+        }
+    }
+    result
+}
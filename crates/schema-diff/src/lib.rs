@@ -0,0 +1,9 @@
+//! Module for comparing two schemas and classifying changes between them.
+
+mod change;
+mod diff;
+#[cfg(test)]
+mod tests;
+
+pub use change::{ChangeSeverity, SchemaChange, SchemaChangeMessage};
+pub use diff::diff_schemas;
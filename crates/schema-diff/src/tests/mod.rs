@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+
+use graphql_builtins::generate_builtins;
+use graphql_type_system::Schema;
+use insta::{assert_debug_snapshot, assert_snapshot};
+use nitrogql_ast::base::Pos;
+use nitrogql_parser::parse_type_system_document;
+use nitrogql_semantics::{ast_to_type_system, resolve_extensions};
+
+use crate::{diff_schemas, SchemaChange};
+
+fn schema_of(sdl: &str) -> Schema<Cow<'_, str>, Pos> {
+    let mut document = parse_type_system_document(sdl).unwrap();
+    document.extend(generate_builtins());
+    let document = resolve_extensions(document).unwrap();
+    ast_to_type_system(&document)
+}
+
+fn diff_of(old: &str, new: &str) -> Vec<SchemaChange> {
+    diff_schemas(&schema_of(old), &schema_of(new))
+}
+
+/// Prints one change per line.
+fn print_changes(changes: &[SchemaChange]) -> String {
+    changes
+        .iter()
+        .map(|change| format!("{}: {}", change.severity, change.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn no_changes() {
+    let sdl = "
+        type Query { user(id: ID!): User }
+        type User { id: ID! name: String }
+    ";
+    assert!(diff_of(sdl, sdl).is_empty());
+}
+
+#[test]
+fn types() {
+    let changes = diff_of(
+        "
+        type Query { a: Int }
+        type Removed { a: Int }
+        type KindChanged { a: Int }
+        ",
+        "
+        type Query { a: Int }
+        interface KindChanged { a: Int }
+        scalar Added
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn fields() {
+    let changes = diff_of(
+        "
+        type Query { a: Int }
+        type User {
+            removed: Int
+            nullableToNonNull: Int
+            nonNullToNullable: Int!
+            listItemToNonNull: [Int]
+            listToScalar: [Int]
+            typeChanged: Int
+            deprecated: Int
+        }
+        ",
+        "
+        type Query { a: Int }
+        type User {
+            nullableToNonNull: Int!
+            nonNullToNullable: Int
+            listItemToNonNull: [Int!]
+            listToScalar: Int
+            typeChanged: String
+            deprecated: Int @deprecated
+            added: Int
+        }
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn arguments() {
+    let changes = diff_of(
+        "
+        type Query {
+            field(
+                removed: Int
+                nullableToNonNull: Int
+                nonNullToNullable: Int!
+                defaultChanged: Int = 1
+                defaultFormat: [Int] = [1, 2]
+            ): Int
+        }
+        ",
+        "
+        type Query {
+            field(
+                nullableToNonNull: Int!
+                nonNullToNullable: Int
+                defaultChanged: Int = 2
+                defaultFormat: [Int] = [1 2]
+                addedRequired: Int!
+                addedWithDefault: Int! = 0
+                addedOptional: Int
+            ): Int
+        }
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn enums_unions_interfaces() {
+    let changes = diff_of(
+        "
+        type Query { a: Int }
+        enum Color { RED GREEN BLUE }
+        type A implements Node { id: ID! }
+        type B implements Node { id: ID! }
+        type C { id: ID! }
+        interface Node { id: ID! }
+        union AB = A | B
+        ",
+        "
+        type Query { a: Int }
+        enum Color { RED BLUE @deprecated YELLOW }
+        type A { id: ID! }
+        type B implements Node { id: ID! }
+        type C implements Node { id: ID! }
+        interface Node { id: ID! }
+        union AB = B | C
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn input_objects() {
+    let changes = diff_of(
+        "
+        type Query { a: Int }
+        input Filter {
+            removed: Int
+            nullableToNonNull: Int
+            nonNullToNullable: Int!
+            defaultChanged: String = \"a\"
+        }
+        ",
+        "
+        type Query { a: Int }
+        input Filter {
+            nullableToNonNull: Int!
+            nonNullToNullable: Int
+            defaultChanged: String = \"b\"
+            addedRequired: Int!
+            addedOptional: Int
+        }
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn directives() {
+    let changes = diff_of(
+        "
+        type Query { a: Int }
+        directive @removed on FIELD
+        directive @changed(arg: Int) repeatable on FIELD | QUERY
+        ",
+        "
+        type Query { a: Int }
+        directive @changed(arg: Int, required: Int!) on FIELD | MUTATION
+        directive @added on FIELD
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn root_types() {
+    let changes = diff_of(
+        "
+        type Query { a: Int }
+        type Mutation { a: Int }
+        ",
+        "
+        schema { query: RootQuery subscription: Subscription }
+        type RootQuery { a: Int }
+        type Subscription { a: Int }
+        ",
+    );
+    assert_snapshot!(print_changes(&changes));
+}
+
+#[test]
+fn positions_from_both_sides() {
+    let changes = diff_of(
+        "type Query { a: Int }",
+        "
+        type Query { a: String }
+        ",
+    );
+    assert_debug_snapshot!(changes);
+}
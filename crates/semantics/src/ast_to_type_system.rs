@@ -70,6 +70,7 @@ fn convert_type_definition<'src>(
                     TypeDefinition::Scalar(ScalarDefinition {
                         name: ident_to_node(&def.name),
                         description: convert_description(&def.description),
+                        specified_by_url: convert_specified_by_url(&def.directives),
                     }),
                     def.position,
                 ),
@@ -224,6 +225,22 @@ fn convert_field<'src>(field: &FieldDefinition<'src>) -> Field<Cow<'src, str>, P
     }
 }
 
+fn convert_specified_by_url<'src>(directives: &[Directive<'src>]) -> Option<Cow<'src, str>> {
+    directives
+        .iter()
+        .find(|dir| dir.name.name == "specifiedBy")
+        .and_then(|dir| {
+            dir.arguments
+                .iter()
+                .flat_map(|args| args.arguments.iter())
+                .find(|(name, _)| name.name == "url")
+        })
+        .and_then(|(_, value)| match value {
+            Value::StringValue(string) => Some(Cow::Owned(string.value.clone())),
+            _ => None,
+        })
+}
+
 fn convert_deprecation<'src>(directives: &[Directive<'src>]) -> Option<Cow<'src, str>> {
     directives
         .iter()
@@ -42,30 +42,30 @@ pub enum ExtensionErrorMessage {
 
 #[derive(Debug)]
 pub struct ExtensionError {
-    pub message: ExtensionErrorMessage,
+    pub message: Box<ExtensionErrorMessage>,
 }
 
 impl From<ExtensionError> for PositionedError {
     fn from(value: ExtensionError) -> Self {
-        let position = match &value.message {
+        let position = match value.message.as_ref() {
             ExtensionErrorMessage::DuplicateOriginal { first, .. } => *first,
             ExtensionErrorMessage::NoOriginal {
                 first_extension, ..
             } => *first_extension,
         };
-        let additional_info = match &value.message {
+        let additional_info = match value.message.as_ref() {
             ExtensionErrorMessage::DuplicateOriginal { name, second, .. } => {
                 vec![(*second, format!("Another declaration of '{name}'"))]
             }
             ExtensionErrorMessage::NoOriginal { .. } => vec![],
         };
 
-        PositionedError::new(value.message.into(), Some(position), additional_info)
+        PositionedError::new((*value.message).into(), Some(position), additional_info)
     }
 }
 
 impl<OriginalType: HasPos, ExtensionType: HasPos> ExtensionList<'_, OriginalType, ExtensionType> {
-    pub fn new(name_of_elem: &str) -> ExtensionList<OriginalType, ExtensionType> {
+    pub fn new(name_of_elem: &str) -> ExtensionList<'_, OriginalType, ExtensionType> {
         ExtensionList {
             name_of_elem,
             items: HashMap::new(),
@@ -74,15 +74,15 @@ impl<OriginalType: HasPos, ExtensionType: HasPos> ExtensionList<'_, OriginalType
 
     pub fn set_original(&mut self, original: OriginalType) -> Result<(), ExtensionError> {
         let name = original.name().map(|str| str.to_owned());
-        let mut item = self.items.entry(name.clone()).or_default();
+        let item = self.items.entry(name.clone()).or_default();
         if let Some(ref first) = item.original {
             return Err(ExtensionError {
-                message: ExtensionErrorMessage::DuplicateOriginal {
+                message: Box::new(ExtensionErrorMessage::DuplicateOriginal {
                     name_of_elem: self.name_of_elem.to_owned(),
                     name: name.unwrap_or_default(),
                     first: *first.position(),
                     second: *original.position(),
-                },
+                }),
             });
         }
         item.original = Some(original);
@@ -99,15 +99,15 @@ impl<OriginalType: HasPos, ExtensionType: HasPos> ExtensionList<'_, OriginalType
     ) -> Result<Vec<(OriginalType, Vec<ExtensionType>)>, ExtensionError> {
         let result: Result<Vec<_>, _> = self
             .items
-            .into_iter()
-            .filter_map(|(_, item)| match item.original {
+            .into_values()
+            .filter_map(|item| match item.original {
                 None => match item.extensions.into_iter().next() {
                     None => None,
                     Some(first) => Some(Err(ExtensionError {
-                        message: ExtensionErrorMessage::NoOriginal {
+                        message: Box::new(ExtensionErrorMessage::NoOriginal {
                             name_of_elem: self.name_of_elem.to_owned(),
                             first_extension: *first.position(),
-                        },
+                        }),
                     })),
                 },
                 Some(orig) => Some(Ok((orig, item.extensions))),
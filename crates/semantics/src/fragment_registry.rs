@@ -0,0 +1,133 @@
+use std::collections::{HashMap, HashSet};
+
+use nitrogql_ast::{
+    operation::{ExecutableDefinition, FragmentDefinition, OperationDocument},
+    selection_set::{Selection, SelectionSet},
+};
+
+/// Registry of fragment definitions that can be spread from operation documents.
+/// A registry may contain fragments from multiple documents of a project.
+#[derive(Debug, Clone, Default)]
+pub struct FragmentRegistry<'a, 'src> {
+    fragments: HashMap<&'a str, &'a FragmentDefinition<'src>>,
+    /// Names of fragments that are spread somewhere in registered documents.
+    spread_fragments: HashSet<&'a str>,
+}
+
+impl<'a, 'src> FragmentRegistry<'a, 'src> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry that contains fragments defined in given document.
+    pub fn from_document(document: &'a OperationDocument<'src>) -> Self {
+        let mut registry = Self::new();
+        registry.add_document(document);
+        registry
+    }
+
+    /// Registers all fragments defined in given document.
+    /// When a fragment of the same name is already registered, the existing one is kept.
+    pub fn add_document(&mut self, document: &'a OperationDocument<'src>) {
+        for def in document.definitions.iter() {
+            match def {
+                ExecutableDefinition::OperationDefinition(op) => {
+                    self.add_spread_fragments(&op.selection_set);
+                }
+                ExecutableDefinition::FragmentDefinition(fragment) => {
+                    self.fragments.entry(fragment.name.name).or_insert(fragment);
+                    self.add_spread_fragments(&fragment.selection_set);
+                }
+            }
+        }
+    }
+
+    /// Registers fragments from another registry that are not registered in this one.
+    pub fn extend(&mut self, other: &FragmentRegistry<'a, 'src>) {
+        for (name, fragment) in other.fragments.iter() {
+            self.fragments.entry(name).or_insert(fragment);
+        }
+        self.spread_fragments
+            .extend(other.spread_fragments.iter().copied());
+    }
+
+    /// Returns the fragment definition of given name.
+    pub fn get(&self, name: &str) -> Option<&'a FragmentDefinition<'src>> {
+        self.fragments.get(name).copied()
+    }
+
+    /// Returns whether given fragment is spread somewhere in registered documents.
+    pub fn is_spread(&self, name: &str) -> bool {
+        self.spread_fragments.contains(name)
+    }
+
+    /// Iterates over names of fragments spread somewhere in registered documents.
+    pub fn spread_fragment_names(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.spread_fragments.iter().copied()
+    }
+
+    /// Iterates over registered fragments.
+    pub fn iter(&self) -> impl Iterator<Item = &'a FragmentDefinition<'src>> + '_ {
+        self.fragments.values().copied()
+    }
+
+    /// Returns fragments that are (directly or indirectly) spread from given selection set,
+    /// in the order of first appearance.
+    pub fn fragments_used_by(
+        &self,
+        selection_set: &'a SelectionSet<'src>,
+    ) -> Vec<&'a FragmentDefinition<'src>> {
+        let mut result = vec![];
+        self.fragments_used_by_impl(selection_set, &mut result);
+        result
+    }
+
+    fn fragments_used_by_impl(
+        &self,
+        selection_set: &'a SelectionSet<'src>,
+        result: &mut Vec<&'a FragmentDefinition<'src>>,
+    ) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    if let Some(ref selection_set) = field.selection_set {
+                        self.fragments_used_by_impl(selection_set, result);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let name = fragment_spread.fragment_name.name;
+                    if result.iter().any(|fragment| fragment.name.name == name) {
+                        // prevent infinite recursions
+                        continue;
+                    }
+                    if let Some(fragment) = self.get(name) {
+                        result.push(fragment);
+                        self.fragments_used_by_impl(&fragment.selection_set, result);
+                    }
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    self.fragments_used_by_impl(&inline_fragment.selection_set, result);
+                }
+            }
+        }
+    }
+
+    fn add_spread_fragments(&mut self, selection_set: &'a SelectionSet<'src>) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    if let Some(ref selection_set) = field.selection_set {
+                        self.add_spread_fragments(selection_set);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    self.spread_fragments
+                        .insert(fragment_spread.fragment_name.name);
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    self.add_spread_fragments(&inline_fragment.selection_set);
+                }
+            }
+        }
+    }
+}
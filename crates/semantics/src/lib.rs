@@ -2,6 +2,7 @@ mod ast_to_type_system;
 mod definition_map;
 mod direct_fields_of_output_type;
 mod extension_resolver;
+mod fragment_registry;
 #[cfg(test)]
 mod tests;
 mod type_system_to_ast;
@@ -11,4 +12,5 @@ pub use ast_to_type_system::ast_to_type_system;
 pub use definition_map::{generate_definition_map, DefinitionMap};
 pub use direct_fields_of_output_type::direct_fields_of_output_type;
 pub use extension_resolver::resolve_extensions;
+pub use fragment_registry::FragmentRegistry;
 pub use type_system_to_ast::type_system_to_ast;
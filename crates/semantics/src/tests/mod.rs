@@ -344,6 +344,90 @@ fn introspection_to_ast() {
     assert_snapshot!(print_ast(&ast));
 }
 
+#[test]
+fn introspection_default_values_to_ast() {
+    let json = r#"{
+    "__schema": {
+      "queryType": {
+        "name": "Query"
+      },
+      "mutationType": null,
+      "subscriptionType": null,
+      "types": [
+        {
+          "kind": "OBJECT",
+          "name": "Query",
+          "description": null,
+          "fields": [
+            {
+              "name": "items",
+              "description": null,
+              "args": [
+                {
+                  "name": "first",
+                  "description": null,
+                  "type": {
+                    "kind": "SCALAR",
+                    "name": "Int",
+                    "ofType": null
+                  },
+                  "defaultValue": "10"
+                },
+                {
+                  "name": "filter",
+                  "description": null,
+                  "type": {
+                    "kind": "INPUT_OBJECT",
+                    "name": "ItemFilter",
+                    "ofType": null
+                  },
+                  "defaultValue": "{kind: BOOK, tags: [\"new\", \"sale\"], archived: false}"
+                }
+              ],
+              "type": {
+                "kind": "SCALAR",
+                "name": "Int",
+                "ofType": null
+              },
+              "isDeprecated": false,
+              "deprecationReason": null
+            }
+          ],
+          "inputFields": null,
+          "interfaces": [],
+          "enumValues": null,
+          "possibleTypes": null
+        },
+        {
+          "kind": "INPUT_OBJECT",
+          "name": "ItemFilter",
+          "description": null,
+          "fields": null,
+          "inputFields": [
+            {
+              "name": "kind",
+              "description": null,
+              "type": {
+                "kind": "SCALAR",
+                "name": "String",
+                "ofType": null
+              },
+              "defaultValue": "null"
+            }
+          ],
+          "interfaces": null,
+          "enumValues": null,
+          "possibleTypes": null
+        }
+      ],
+      "directives": []
+    }
+}"#;
+    let schema = schema_from_introspection_json::<()>(json).unwrap();
+    let ast = type_system_to_ast(&schema);
+    assert_snapshot!(print_ast(&ast));
+}
+
 fn print_ast(ast: &TypeSystemDocument) -> String {
     let mut buf = String::new();
     let mut writer = JustWriter::new(&mut buf);
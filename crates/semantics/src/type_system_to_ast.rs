@@ -11,12 +11,13 @@ use nitrogql_ast::{
         ObjectTypeDefinition, ScalarTypeDefinition, SchemaDefinition, TypeDefinition,
         TypeSystemDefinition, UnionTypeDefinition,
     },
-    value::{NullValue, StringValue, Value},
+    value::StringValue,
     TypeSystemDocument,
 };
+use nitrogql_parser::parse_value;
 
 /// Convert Schema to TypeSystemDocument. For type definition generation purpose.
-pub fn type_system_to_ast<'src, S: Text<'src>, D>(schema: &Schema<S, D>) -> TypeSystemDocument {
+pub fn type_system_to_ast<'src, S: Text<'src>, D>(schema: &Schema<S, D>) -> TypeSystemDocument<'_> {
     let mut result = TypeSystemDocument::new();
     let schema_definition = {
         let mut schema_definition = SchemaDefinition {
@@ -62,7 +63,7 @@ pub fn type_system_to_ast<'src, S: Text<'src>, D>(schema: &Schema<S, D>) -> Type
 
 fn convert_type_definition<S: Deref<Target = str>, D>(
     type_def: &graphql_type_system::TypeDefinition<S, D>,
-) -> TypeDefinition {
+) -> TypeDefinition<'_> {
     match type_def {
         graphql_type_system::TypeDefinition::Scalar(scalar) => {
             TypeDefinition::Scalar(ScalarTypeDefinition {
@@ -152,7 +153,7 @@ fn convert_type_definition<S: Deref<Target = str>, D>(
 
 fn convert_field<S: Deref<Target = str>, D>(
     field: &graphql_type_system::Field<S, D>,
-) -> FieldDefinition {
+) -> FieldDefinition<'_> {
     FieldDefinition {
         description: convert_description(&field.description),
         name: convert_node_to_ident(&field.name),
@@ -162,7 +163,7 @@ fn convert_field<S: Deref<Target = str>, D>(
     }
 }
 
-fn convert_type<S: Deref<Target = str>, D>(ty: &graphql_type_system::Type<S, D>) -> Type {
+fn convert_type<S: Deref<Target = str>, D>(ty: &graphql_type_system::Type<S, D>) -> Type<'_> {
     match ty {
         graphql_type_system::Type::Named(named) => Type::Named(NamedType {
             name: convert_node_to_ident(named),
@@ -178,8 +179,8 @@ fn convert_type<S: Deref<Target = str>, D>(ty: &graphql_type_system::Type<S, D>)
 }
 
 fn convert_arguments<S: Deref<Target = str>, D>(
-    arguments: &Vec<graphql_type_system::InputValue<S, D>>,
-) -> Option<ArgumentsDefinition> {
+    arguments: &[graphql_type_system::InputValue<S, D>],
+) -> Option<ArgumentsDefinition<'_>> {
     if arguments.is_empty() {
         None
     } else {
@@ -191,19 +192,18 @@ fn convert_arguments<S: Deref<Target = str>, D>(
 
 fn convert_input_value<S: Deref<Target = str>, D>(
     input_value: &graphql_type_system::InputValue<S, D>,
-) -> InputValueDefinition {
+) -> InputValueDefinition<'_> {
     InputValueDefinition {
         description: convert_description(&input_value.description),
         position: Pos::default(),
         name: convert_node_to_ident(&input_value.name),
         r#type: convert_type(&input_value.r#type),
-        // TODO: cannot convert default value
-        default_value: input_value.default_value.as_ref().map(|_| {
-            Value::NullValue(NullValue {
-                position: Pos::default(),
-                keyword: "null",
-            })
-        }),
+        // Default values are kept as GraphQL literals.
+        // Ones that fail to parse (e.g. from a broken introspection result) are dropped.
+        default_value: input_value
+            .default_value
+            .as_ref()
+            .and_then(|default_value| parse_value(default_value).ok()),
         directives: vec![],
     }
 }
@@ -217,14 +217,14 @@ fn convert_description<S: Deref<Target = str>, D>(
     })
 }
 
-fn convert_node_to_ident<S: Deref<Target = str>, D>(node: &Node<S, D>) -> Ident {
+fn convert_node_to_ident<S: Deref<Target = str>, D>(node: &Node<S, D>) -> Ident<'_> {
     Ident {
         name: node,
         position: Pos::default(),
     }
 }
 
-fn keyword(name: &str) -> Keyword {
+fn keyword(name: &str) -> Keyword<'_> {
     Keyword {
         name,
         position: Pos::builtin(),
@@ -118,11 +118,22 @@ impl SourceMapWriter for SourceWriter {
                 Some(original_name_idx),
             );
             self.write(chunk);
+            // The end of generated chunk corresponds to the end of the node.
+            // Fall back to the end of the name if the node does not have a span.
+            let (original_end_line, original_end_column) =
+                if original_pos.end_offset > original_pos.offset {
+                    (original_pos.end_line, original_pos.end_column)
+                } else {
+                    (
+                        original_pos.line,
+                        original_pos.column + utf16_len(original_name),
+                    )
+                };
             self.mapping.add_entry(
                 self.current_line,
                 self.current_column,
-                original_pos.line,
-                original_pos.column + utf16_len(original_name),
+                original_end_line,
+                original_end_column,
                 file_index,
                 None,
             );
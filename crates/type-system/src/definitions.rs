@@ -84,6 +84,8 @@ pub struct ScalarDefinition<Str, OriginalNode> {
     pub name: Node<Str, OriginalNode>,
     /// Description of scalar.
     pub description: Option<Node<Str, OriginalNode>>,
+    /// URL given by the @specifiedBy directive.
+    pub specified_by_url: Option<Str>,
 }
 
 /// Definition of an (output) object type.
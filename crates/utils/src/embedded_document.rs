@@ -0,0 +1,21 @@
+use std::borrow::Cow;
+
+/// GraphQL document found in a tagged template.
+#[derive(Debug)]
+pub struct EmbeddedDocument<'src> {
+    /// Content of the template.
+    /// Interpolations are replaced with spaces so that offsets in the file are kept.
+    pub source: Cow<'src, str>,
+    /// 0-based line, column and byte offset in the file at which the content begins.
+    pub start: (usize, usize, usize),
+}
+
+impl EmbeddedDocument<'_> {
+    /// Converts into a document that owns its content.
+    pub fn into_owned(self) -> EmbeddedDocument<'static> {
+        EmbeddedDocument {
+            source: Cow::Owned(self.source.into_owned()),
+            start: self.start,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::Index,
+    path::{Path, PathBuf},
+};
+
+use crate::EmbeddedDocument;
+
+/// Struct that holds loaded files.
+/// Contents of files are either owned by the store or borrowed from the caller.
+/// Documents parsed from owned contents borrow the store, so files should be added before parsing.
+#[derive(Debug)]
+pub struct FileStore<'src> {
+    schema_files: Vec<(PathBuf, Cow<'src, str>, FileKind)>,
+    /// Files other than schema files. Operation files and next schema files are mixed here.
+    other_files: Vec<(PathBuf, Cow<'src, str>, FileKind)>,
+    /// Documents embedded in files, keyed by file index.
+    embedded_documents: HashMap<usize, Vec<EmbeddedDocument<'static>>>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FileKind {
+    Schema,
+    Operation,
+    /// Schema to compare with the current schema.
+    NextSchema,
+}
+
+impl<'src> FileStore<'src> {
+    /// Create a new file store.
+    pub fn new() -> Self {
+        Self {
+            schema_files: vec![],
+            other_files: vec![],
+            embedded_documents: HashMap::new(),
+        }
+    }
+
+    /// Add a file to the store.
+    /// Returns the index of the file in the store.
+    /// Once the index is issued, it will not change.
+    /// After an operation or a next schema is added, schema cannot be added.
+    pub fn add_file(&mut self, path: PathBuf, content: String, kind: FileKind) -> usize {
+        self.add_file_content(path, Cow::Owned(content), kind)
+    }
+
+    /// Add a file whose content is owned by the caller.
+    /// Otherwise same as `add_file`.
+    pub fn add_borrowed_file(
+        &mut self,
+        path: PathBuf,
+        content: &'src str,
+        kind: FileKind,
+    ) -> usize {
+        self.add_file_content(path, Cow::Borrowed(content), kind)
+    }
+
+    fn add_file_content(
+        &mut self,
+        path: PathBuf,
+        content: Cow<'src, str>,
+        kind: FileKind,
+    ) -> usize {
+        if !self.other_files.is_empty() && kind == FileKind::Schema {
+            panic!("Cannot add schema file after other files are added");
+        }
+        let schema_len = self.schema_files.len();
+        match kind {
+            FileKind::Schema => {
+                self.schema_files.push((path, content, kind));
+                schema_len
+            }
+            FileKind::Operation | FileKind::NextSchema => {
+                self.other_files.push((path, content, kind));
+                schema_len + self.other_files.len() - 1
+            }
+        }
+    }
+
+    /// Replace the content of a file with keeping its index.
+    /// Previous content and documents embedded in it are freed.
+    pub fn replace_file(&mut self, index: usize, content: String) {
+        let schema_len = self.schema_files.len();
+        let file = if index < schema_len {
+            self.schema_files.get_mut(index)
+        } else {
+            self.other_files.get_mut(index - schema_len)
+        };
+        let file = file.expect("File index out of range");
+        file.1 = Cow::Owned(content);
+        self.embedded_documents.remove(&index);
+    }
+
+    /// Set documents embedded in a file.
+    /// They are kept with the file content until the file is replaced.
+    pub fn set_embedded_documents(
+        &mut self,
+        index: usize,
+        documents: Vec<EmbeddedDocument<'static>>,
+    ) {
+        self.embedded_documents.insert(index, documents);
+    }
+
+    /// Get documents embedded in a file.
+    /// Returns an empty slice if none are set.
+    pub fn embedded_documents(&self, index: usize) -> &[EmbeddedDocument<'static>] {
+        self.embedded_documents
+            .get(&index)
+            .map_or(&[], |documents| documents.as_slice())
+    }
+
+    /// Get a file by index.
+    pub fn get_file(&self, index: usize) -> Option<&(PathBuf, Cow<'src, str>, FileKind)> {
+        if index < self.schema_files.len() {
+            self.schema_files.get(index)
+        } else {
+            self.other_files.get(index - self.schema_files.len())
+        }
+    }
+
+    /// Iterate over all files.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, (&Path, &str, FileKind))> {
+        self.schema_files
+            .iter()
+            .chain(self.other_files.iter())
+            .map(|(path, content, kind)| {
+                let path = path.as_path();
+                (path, content.as_ref(), *kind)
+            })
+            .enumerate()
+    }
+}
+
+impl Default for FileStore<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'src> Index<usize> for FileStore<'src> {
+    type Output = (PathBuf, Cow<'src, str>, FileKind);
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get_file(index).expect("File index out of range")
+    }
+}
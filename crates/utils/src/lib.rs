@@ -2,10 +2,14 @@ mod capitalize;
 mod chars;
 mod clone_into;
 mod cwd;
+mod embedded_document;
+mod file_store;
 mod relative_path;
 
 pub use capitalize::capitalize;
 pub use chars::{first_non_space_byte_index, skip_chars};
 pub use clone_into::clone_into;
 pub use cwd::get_cwd;
+pub use embedded_document::EmbeddedDocument;
+pub use file_store::{FileKind, FileStore};
 pub use relative_path::{normalize_path, relative_path};
@@ -87,6 +87,44 @@ export type NitrogqlConfig = {
                      * @default false
                      */
                     emitSchemaRuntime?: boolean | undefined;
+                    /**
+                     * File path to which resolver types are emitted.
+                     */
+                    resolversOutput?: string | undefined;
+                    /**
+                     * Mapping from GraphQL object types to TypeScript types of parent values passed to resolvers.
+                     * Defaults to the schema types.
+                     */
+                    resolverParentTypes?: Record<string, string> | undefined;
+                    /**
+                     * TypeScript type of context passed to resolvers.
+                     * @default "unknown"
+                     */
+                    resolverContextType?: string | undefined;
+                  }
+                | undefined;
+              /**
+               * Config related to the 'print-schema' command.
+               */
+              printSchema?:
+                | {
+                    /**
+                     * Path to the output schema file.
+                     * If not specified, the schema is printed to stdout.
+                     */
+                    output?: string | undefined;
+                  }
+                | undefined;
+              /**
+               * Config related to the 'print-introspection' command.
+               */
+              printIntrospection?:
+                | {
+                    /**
+                     * Path to the output introspection JSON file.
+                     * If not specified, the result is printed to stdout.
+                     */
+                    output?: string | undefined;
                   }
                 | undefined;
             }
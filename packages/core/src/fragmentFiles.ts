@@ -0,0 +1,230 @@
+/**
+ * @file Registration of fragment files to the graphql-loader wasm module.
+ */
+
+import path from "node:path";
+import { readFile, readdir, stat } from "node:fs/promises";
+
+/**
+ * Exports of the graphql-loader wasm module used for registering fragment files.
+ */
+type GraphQLLoaderExports = {
+  is_operation_file(path_ptr: number, path_len: number): boolean;
+  register_fragment_file(
+    path_ptr: number,
+    path_len: number,
+    source_ptr: number,
+    source_len: number
+  ): void;
+  unregister_fragment_file(path_ptr: number, path_len: number): void;
+  list_imports(source_ptr: number, source_len: number): boolean;
+  get_result_ptr(): number;
+  get_result_size(): number;
+};
+
+/**
+ * String allocator for the graphql-loader wasm module.
+ */
+type StringAllocator = {
+  allocString(content: string): {
+    ptr: number;
+    size: number;
+    free(): void;
+  };
+  readString(ptr: number, size: number): string;
+};
+
+type RegisteredFile = {
+  mtimeMs: number;
+  /**
+   * Absolute paths of files imported by `#import` comments.
+   */
+  imports: string[];
+};
+
+/**
+ * Keeps operation documents of a project registered to the graphql-loader wasm module
+ * so that fragments defined in them can be used from converted documents.
+ *
+ * Files matching the `documents` globs of the config are listed for each conversion
+ * so that newly added files are found; conversions that start while files are being listed
+ * share the result.
+ * Registered files are read and parsed again only when their mtime changes.
+ */
+export class FragmentFileRegistry {
+  readonly #exports: GraphQLLoaderExports;
+  readonly #alloc: StringAllocator;
+  #project: { root: string; useConfigGlobs: boolean } | undefined;
+  #projectFiles: Promise<string[]> | undefined;
+  readonly #registeredFiles = new Map<string, RegisteredFile>();
+
+  constructor(instance: WebAssembly.Instance, alloc: StringAllocator) {
+    this.#exports = instance.exports as unknown as GraphQLLoaderExports;
+    this.#alloc = alloc;
+  }
+
+  /**
+   * Sets how project files are found.
+   * Should be called after a config file is loaded.
+   * @param root directory of the config file, or the project root if no config file is used.
+   * @param useConfigGlobs whether files are matched by the globs of loaded config.
+   * Otherwise, all `.graphql` files under `root` are used.
+   */
+  reset(root: string, useConfigGlobs: boolean): void {
+    this.#project = { root, useConfigGlobs };
+    this.#projectFiles = undefined;
+  }
+
+  /**
+   * Registers files needed to convert given document:
+   * project files and files imported from the document (transitively).
+   * @returns paths of registered files.
+   */
+  async prepare(filePath: string, source: string): Promise<string[]> {
+    const pending = [
+      ...(await this.#listProjectFiles()),
+      ...this.#listImports(filePath, source),
+    ];
+    const visited = new Set<string>([filePath]);
+    while (pending.length > 0) {
+      const file = pending.pop()!;
+      if (visited.has(file)) {
+        continue;
+      }
+      visited.add(file);
+      const registered = await this.#update(file);
+      if (registered !== undefined) {
+        pending.push(...registered.imports);
+      }
+    }
+    visited.delete(filePath);
+    return [...visited].filter((file) => this.#registeredFiles.has(file));
+  }
+
+  /**
+   * Lists project files, reusing the listing in progress if any.
+   */
+  #listProjectFiles(): Promise<string[]> {
+    if (this.#project === undefined) {
+      throw new Error("FragmentFileRegistry is used before reset");
+    }
+    if (this.#projectFiles !== undefined) {
+      return this.#projectFiles;
+    }
+    const { root, useConfigGlobs } = this.#project;
+    const projectFiles = findFiles(root, (filePath) =>
+      useConfigGlobs
+        ? this.#isOperationFile(
+            path.relative(root, filePath).split(path.sep).join("/")
+          )
+        : filePath.endsWith(".graphql")
+    );
+    this.#projectFiles = projectFiles;
+    const done = () => {
+      if (this.#projectFiles === projectFiles) {
+        this.#projectFiles = undefined;
+      }
+    };
+    projectFiles.then(done, done);
+    return projectFiles;
+  }
+
+  /**
+   * Registers given file again if it has changed since last registration.
+   * Files that no longer exist are unregistered.
+   */
+  async #update(file: string): Promise<RegisteredFile | undefined> {
+    const current = this.#registeredFiles.get(file);
+    let mtimeMs;
+    try {
+      mtimeMs = (await stat(file)).mtimeMs;
+    } catch {
+      if (current !== undefined) {
+        this.#registeredFiles.delete(file);
+        const pathString = this.#alloc.allocString(file);
+        this.#exports.unregister_fragment_file(
+          pathString.ptr,
+          pathString.size
+        );
+        pathString.free();
+      }
+      return undefined;
+    }
+    if (current !== undefined && current.mtimeMs === mtimeMs) {
+      return current;
+    }
+    const source = await readFile(file, "utf-8");
+    const pathString = this.#alloc.allocString(file);
+    const sourceString = this.#alloc.allocString(source);
+    this.#exports.register_fragment_file(
+      pathString.ptr,
+      pathString.size,
+      sourceString.ptr,
+      sourceString.size
+    );
+    pathString.free();
+    sourceString.free();
+    const registered = {
+      mtimeMs,
+      imports: this.#listImports(file, source),
+    };
+    this.#registeredFiles.set(file, registered);
+    return registered;
+  }
+
+  #isOperationFile(relativePath: string): boolean {
+    const pathString = this.#alloc.allocString(relativePath);
+    const result = this.#exports.is_operation_file(
+      pathString.ptr,
+      pathString.size
+    );
+    pathString.free();
+    return result;
+  }
+
+  /**
+   * Lists absolute paths of files imported by `#import` comments in given document.
+   */
+  #listImports(filePath: string, source: string): string[] {
+    const sourceString = this.#alloc.allocString(source);
+    const result = this.#exports.list_imports(
+      sourceString.ptr,
+      sourceString.size
+    );
+    sourceString.free();
+    if (!result) {
+      return [];
+    }
+    const imports = this.#alloc.readString(
+      this.#exports.get_result_ptr(),
+      this.#exports.get_result_size()
+    );
+    return imports
+      .split("\n")
+      .filter((importPath) => importPath !== "")
+      .map((importPath) => path.resolve(path.dirname(filePath), importPath));
+  }
+}
+
+/**
+ * Recursively finds files under given directory that satisfy given predicate.
+ */
+async function findFiles(
+  dir: string,
+  predicate: (filePath: string) => boolean
+): Promise<string[]> {
+  const entries = await readdir(dir, { withFileTypes: true });
+  const result: string[] = [];
+  for (const entry of entries) {
+    if (entry.name === "node_modules" || entry.name.startsWith(".")) {
+      continue;
+    }
+    const entryPath = path.join(dir, entry.name);
+    if (entry.isDirectory()) {
+      result.push(...(await findFiles(entryPath, predicate)));
+    } else if (entry.isFile() && predicate(entryPath)) {
+      result.push(entryPath);
+    }
+  }
+  return result;
+}
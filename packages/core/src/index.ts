@@ -1,5 +1,6 @@
 import { config } from "./config.js";
 import { NitrogqlConfig } from "./configFormat.js";
+import { FragmentFileRegistry } from "./fragmentFiles.js";
 import { setMemory } from "./memory.js";
 
 export {
@@ -11,6 +12,10 @@ export {
    * `nitrogql_helper/config` namespace
    */
   config,
+  /**
+   * Registers fragment files to the graphql-loader wasm module.
+   */
+  FragmentFileRegistry,
 };
 
 export type { NitrogqlConfig };
@@ -2,6 +2,7 @@
 
 import path from "node:path";
 import { readFile } from "node:fs/promises";
+import { FragmentFileRegistry } from "@nitrogql/core";
 import { StringAllocator } from "./alloc.mjs";
 
 const wasm = await WebAssembly.compile(
@@ -10,10 +11,13 @@ const wasm = await WebAssembly.compile(
 const instance = await WebAssembly.instantiate(wasm);
 
 const alloc = new StringAllocator(instance);
+const fragmentFiles = new FragmentFileRegistry(instance, alloc);
 
 instance.exports.init();
 
 let lastLoadedConfigPath = undefined;
+/** Directory from which fragment files are listed. */
+let fragmentFilesRoot = undefined;
 
 /**
  * @type {import('webpack').LoaderDefinitionFunction}
@@ -42,6 +46,21 @@ export default function graphQLLoader(source) {
     }
     lastLoadedConfigPath = configFilePath;
 
+    const nextFragmentFilesRoot = configFilePath
+      ? path.dirname(configFilePath)
+      : this.rootContext;
+    if (fragmentFilesRoot !== nextFragmentFilesRoot) {
+      fragmentFiles.reset(nextFragmentFilesRoot, !!configFilePath);
+      fragmentFilesRoot = nextFragmentFilesRoot;
+    }
+    const registeredFiles = await fragmentFiles.prepare(
+      this.resourcePath,
+      source
+    );
+    for (const file of registeredFiles) {
+      this.addDependency(file);
+    }
+
     const inputString = alloc.allocString(source);
 
     const convertResult = instance.exports.convert_to_js(
@@ -2,6 +2,7 @@
 
 import path from "node:path";
 import { readFile } from "node:fs/promises";
+import { FragmentFileRegistry } from "@nitrogql/core";
 import { createFilter } from "@rollup/pluginutils";
 import { StringAllocator } from "./alloc.mjs";
 
@@ -11,10 +12,13 @@ const wasm = await WebAssembly.compile(
 const instance = await WebAssembly.instantiate(wasm);
 
 const alloc = new StringAllocator(instance);
+const fragmentFiles = new FragmentFileRegistry(instance, alloc);
 
 instance.exports.init();
 
 let lastLoadedConfigPath = undefined;
+/** Directory from which fragment files are listed. */
+let fragmentFilesRoot = undefined;
 
 /**
  * @type {import('rollup').PluginImpl<{
@@ -58,6 +62,18 @@ export default function NitrogqlRollupPlugin(options) {
       }
       lastLoadedConfigPath = configFilePath;
 
+      const nextFragmentFilesRoot = configFilePath
+        ? path.dirname(configFilePath)
+        : root;
+      if (fragmentFilesRoot !== nextFragmentFilesRoot) {
+        fragmentFiles.reset(nextFragmentFilesRoot, !!configFilePath);
+        fragmentFilesRoot = nextFragmentFilesRoot;
+      }
+      const registeredFiles = await fragmentFiles.prepare(id, source);
+      for (const file of registeredFiles) {
+        this.addWatchFile(file);
+      }
+
       const inputString = alloc.allocString(source);
 
       const convertResult = instance.exports.convert_to_js(
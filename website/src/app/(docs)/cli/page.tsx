@@ -1,3 +1,4 @@
+import Link from "next/link";
 import { Hint } from "@/app/(utils)/Hint";
 import { Highlight } from "@/app/(utils)/Highlight";
 
@@ -20,11 +21,40 @@ export default function GettingStarted() {
       <p>The following commands are available.</p>
       <ul>
         <li>
-          <code>check</code>: Check your GraphQL code.
+          <code>check</code>: Check your GraphQL code. Use of deprecated
+          fields, arguments and enum values is reported as a warning, which
+          does not make the check fail by itself. Lint rules configured with{" "}
+          <Link href="/configuration#lint.rules">
+            <code>lint.rules</code>
+          </Link>{" "}
+          are also run.
         </li>
         <li>
           <code>generate</code>: Generate types from your schema and operations.
         </li>
+        <li>
+          <code>print-schema</code>: Print your schema as a single GraphQL
+          document, with all extensions merged. An introspection JSON schema is
+          also converted to a GraphQL document.
+        </li>
+        <li>
+          <code>print-introspection</code>: Print your schema as a result of
+          the standard introspection query (JSON).
+        </li>
+        <li>
+          <code>diff</code>: Compare your schema with the schema given by{" "}
+          <code>--next-schema</code> and report changes as breaking, dangerous
+          or safe. Exits with a non-zero status if any breaking change is
+          found.
+        </li>
+        <li>
+          <code>format</code>: Format your schema and operation files in place.
+          Comments are preserved. Formatting is configured with{" "}
+          <Link href="/configuration#format">
+            <code>format</code>
+          </Link>{" "}
+          options. Introspection JSON files are not formatted.
+        </li>
       </ul>
       <Hint>
         💡 <code>generate</code> also implies <code>check</code>. GraphQL code
@@ -58,6 +88,63 @@ export default function GettingStarted() {
         path specified in the configuration file.
       </p>
 
+      <h4>
+        <code>--next-schema</code>
+      </h4>
+      <p>
+        Specify the path to the schema file(s) to compare with for the{" "}
+        <code>diff</code> command. Either GraphQL documents or an introspection
+        JSON can be specified, just like <code>--schema</code>.
+      </p>
+      <Highlight language="bash">{`npx nitrogql diff --schema ./main/schema.graphql --next-schema ./schema.graphql`}</Highlight>
+      <p>
+        When specified, the <code>check</code> command also checks your
+        operations against the next schema and reports errors that only occur
+        with the next schema, grouped by operation. This is useful for finding
+        which operations a schema change would break.
+      </p>
+      <Highlight language="bash">{`npx nitrogql check --next-schema ./next/schema.graphql`}</Highlight>
+
+      <h4>
+        <code>--max-warnings</code>
+      </h4>
+      <p>
+        Specify the maximum number of warnings allowed. If the{" "}
+        <code>check</code> command finds more warnings than this, it exits with
+        a non-zero status. By default, warnings do not affect the exit status.
+      </p>
+      <Highlight language="bash">{`npx nitrogql check --max-warnings 0`}</Highlight>
+
+      <h4>
+        <code>--check</code>
+      </h4>
+      <p>
+        Makes the <code>format</code> command report files that are not
+        formatted instead of rewriting them. It exits with a non-zero status if
+        any file is not formatted, which is useful in CI.
+      </p>
+      <Highlight language="bash">{`npx nitrogql format --check`}</Highlight>
+
+      <h4>
+        <code>--print-schema-output</code>
+      </h4>
+      <p>
+        Specify the path to which the <code>print-schema</code> command writes
+        the schema. This overrides <code>printSchema.output</code> in the
+        configuration file. If neither is specified, the schema is printed to
+        stdout.
+      </p>
+
+      <h4>
+        <code>--print-introspection-output</code>
+      </h4>
+      <p>
+        Specify the path to which the <code>print-introspection</code> command
+        writes the introspection JSON. This overrides{" "}
+        <code>printIntrospection.output</code> in the configuration file. If
+        neither is specified, the JSON is printed to stdout.
+      </p>
+
       <h4>
         <code>--output-format</code>
       </h4>
@@ -75,7 +162,13 @@ export default function GettingStarted() {
             rdjson
           </a>{" "}
           format to stdout. This is useful for integrating with reviewdog. Makes
-          sense only when the <code>check</code> command is run.
+          sense only when the <code>check</code> or <code>diff</code> command
+          is run. Errors and warnings from <code>check</code> are reported with{" "}
+          <code>ERROR</code> and <code>WARNING</code> severity respectively,
+          and results of lint rules have the rule id as <code>code</code>.
+          Breaking, dangerous and safe changes are reported with{" "}
+          <code>ERROR</code>, <code>WARNING</code> and <code>INFO</code>{" "}
+          severity respectively.
         </li>
         <li>
           <code>json</code>: prints nitrogql specific JSON output to stdout.
@@ -99,15 +192,16 @@ export default function GettingStarted() {
     message: string;
   }
   /**
-   * Exists when the 'check' command is run.
+   * Exists when the 'check' command (or another command that checks the schema) is run.
    */
   check?: {
     /**
-     * List of errors.
-     * Empty when the check is successful.
+     * List of errors and warnings.
+     * Has no errors when the check is successful.
      */
     errors: {
       fileType: "schema" | "operation";
+      severity: "error" | "warning";
       file?: {
         path: string;
         // line and column are 0-indexed
@@ -116,6 +210,43 @@ export default function GettingStarted() {
       }
       message: string;
     }[]
+    /**
+     * Exists when --next-schema is specified.
+     * Errors that only occur with the next schema, grouped by operation.
+     */
+    /**
+     * Problems found by lint rules.
+     */
+    lintErrors: {
+      fileType: "schema" | "operation";
+      severity: "error" | "warning";
+      // Id of the lint rule
+      rule: string;
+      file: {
+        path: string;
+        line: number;
+        column: number;
+      } | null;
+      message: string;
+    }[]
+    nextSchemaErrors?: {
+      // Label of operation, such as "query 'GetUser'"
+      operation: string;
+      // Position of operation
+      file: {
+        path: string;
+        line: number;
+        column: number;
+      } | null;
+      errors: {
+        file: {
+          path: string;
+          line: number;
+          column: number;
+        } | null;
+        message: string;
+      }[];
+    }[]
   }
   /**
    * Exists when the 'generate' command is run.
@@ -129,7 +260,62 @@ export default function GettingStarted() {
         | "schemaTypeDefinition"
         | "schemaTypeDefinitionSourceMap"
         | "operationTypeDefinition"
-        | "operationTypeDefinitionSourceMap";
+        | "operationTypeDefinitionSourceMap"
+        | "persistedDocuments";
+      path: string;
+    }[];
+  }
+  /**
+   * Exists when the 'print-schema' or 'print-introspection' command is run.
+   */
+  print?: {
+    /**
+     * List of output files.
+     * Does not include outputs printed to stdout.
+     */
+    files: {
+      fileType: "schemaDefinition" | "introspection";
+      path: string;
+    }[];
+  }
+  /**
+   * Exists when the 'diff' command is run.
+   */
+  diff?: {
+    /**
+     * List of changes from the schema to the next schema.
+     */
+    changes: {
+      severity: "breaking" | "dangerous" | "safe";
+      message: string;
+      // Position in the schema. line and column are 0-indexed
+      oldFile: {
+        path: string;
+        line: number;
+        column: number;
+      } | null;
+      // Position in the next schema. line and column are 0-indexed
+      newFile: {
+        path: string;
+        line: number;
+        column: number;
+      } | null;
+    }[];
+  }
+  /**
+   * Exists when the 'format' command is run.
+   */
+  format?: {
+    /**
+     * Whether --check is specified.
+     */
+    check: boolean;
+    /**
+     * List of files that were rewritten,
+     * or files that are not formatted if check is true.
+     */
+    files: {
+      fileType: "schema" | "operation";
       path: string;
     }[];
   }
@@ -156,6 +156,9 @@ extensions:
         <li>
           <code>standalone-ts-4.0</code>
         </li>
+        <li>
+          <code>types-only-ts-4.0</code>
+        </li>
       </ul>
       <p>Example:</p>
       <Highlight language="yaml">
@@ -215,6 +218,20 @@ extensions:
         runtime code so you do not need to configure your bundler.
       </p>
 
+      <h4>types-only-ts-4.0</h4>
+      <p>
+        Generates only types, compatible with TypeScript 4.x. This mode is meant
+        for operations written in tagged templates of TypeScript files (see{" "}
+        <Link href="#embedded">embedded</Link>).
+      </p>
+      <p>
+        This mode generates <code>foo.graphql-types.ts</code> next to{" "}
+        <code>foo.tsx</code> (or <code>foo.graphql</code>). The generated file
+        exports the result and variables types of each operation and the types
+        of fragments. Operations in TypeScript files get no types in other
+        modes.
+      </p>
+
       <h3 id="generate.schemaModuleSpecifier">
         generate.schemaModuleSpecifier
       </h3>
@@ -314,6 +331,90 @@ export const UserType = {
 } as const;`}
       </Highlight>
 
+      <h3 id="generate.resolversOutput">generate.resolversOutput</h3>
+      <p>
+        File path to which type definitions for resolvers are emitted. If
+        omitted, resolver types are not generated.
+      </p>
+      <p>
+        The generated file exports a <code>Resolvers</code> type that has a
+        resolver for every field of object types and a{" "}
+        <code>__resolveType</code> function for every interface and union.
+        Types and fields can be omitted, in which case default resolvers are
+        used.
+      </p>
+      <p>Example:</p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    generate:
+      schemaOutput: "./app/generated/schema.d.ts"
+      resolversOutput: "./app/generated/resolvers.d.ts"`}
+      </Highlight>
+      <Highlight language="typescript">
+        {`import type { Resolvers } from "./app/generated/resolvers";
+
+const resolvers: Resolvers = {
+  Query: {
+    me: async (parent, args, context) => { /* ... */ },
+  },
+  // ...
+};`}
+      </Highlight>
+
+      <h3 id="generate.resolverParentTypes">generate.resolverParentTypes</h3>
+      <p>
+        Mapping from GraphQL object types to TypeScript types of parent values
+        passed to their resolvers. Types are written as raw TypeScript code.
+        By default, parent types are the schema types (without{" "}
+        <code>__typename</code>) and an empty object for root operation types.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    generate:
+      resolverParentTypes:
+        User: 'import("../models").UserModel'`}
+      </Highlight>
+
+      <h3 id="generate.resolverContextType">generate.resolverContextType</h3>
+      <p>
+        TypeScript type of the context passed to resolvers, written as raw
+        TypeScript code. Default is <code>unknown</code>.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    generate:
+      resolverContextType: 'import("../context").Context'`}
+      </Highlight>
+
+      <h3 id="generate.persistedDocumentsOutput">
+        generate.persistedDocumentsOutput
+      </h3>
+      <p>
+        Path to the persisted documents manifest. If specified, the{" "}
+        <code>generate</code> command writes a JSON object that maps the hash
+        of each operation to its document text. The document consists of the
+        operation and the fragments reachable from it, sorted by name, printed
+        in a canonical form. The hash is a hex-encoded SHA-256 of the document
+        text, so it does not change with whitespace, comments or the order of
+        fragments in source files.
+      </p>
+      <p>
+        When this option is set, generated document nodes (by the webpack
+        loader, or by the <code>standalone-ts-4.0</code> mode) have the hash
+        in <code>__meta__.hash</code>. Clients can send the hash instead of
+        the document, and the server can look up the document from the
+        manifest.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    generate:
+      persistedDocumentsOutput: ./generated/persisted-documents.json`}
+      </Highlight>
+
       <h3 id="generate.name">generate.name</h3>
       <p>Set of configurations about names of generated variables and types.</p>
       <p>Default settings are:</p>
@@ -424,6 +525,227 @@ export const UserType = {
         schema, the generated subscription variable will be{" "}
         <code>OnUserCreatedSubscription</code>.
       </p>
+
+      <h3 id="printSchema.output">printSchema.output</h3>
+      <p>
+        Path to the file to which the <code>print-schema</code> command writes
+        the resolved schema. If not specified, the schema is printed to stdout.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    printSchema:
+      output: ./generated/schema.graphql`}
+      </Highlight>
+
+      <h3 id="printIntrospection.output">printIntrospection.output</h3>
+      <p>
+        Path to the file to which the <code>print-introspection</code> command
+        writes the schema as a result of the standard introspection query. If
+        not specified, the result is printed to stdout.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    printIntrospection:
+      output: ./generated/introspection.json`}
+      </Highlight>
+
+      <h3 id="lint.rules">lint.rules</h3>
+      <p>
+        Lint rules run by the <code>check</code> command, keyed by rule id.
+        Each rule is set to a severity (<code>error</code>,{" "}
+        <code>warning</code> or <code>off</code>), or to an object with{" "}
+        <code>severity</code> and <code>options</code>. Rules that are not
+        listed are off. Errors make the check fail, while warnings are only
+        reported (see also <code>--max-warnings</code>).
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    lint:
+      rules:
+        named-operations: error
+        operation-name-suffix: warning
+        max-selection-depth:
+          severity: error
+          options:
+            maxDepth: 5`}
+      </Highlight>
+      <p>The following rules are available for operations.</p>
+      <ul>
+        <li>
+          <code>named-operations</code>: operations must be named.
+        </li>
+        <li>
+          <code>operation-name-suffix</code>: operation names must end with
+          their operation type, such as <code>GetUserQuery</code> and{" "}
+          <code>UpdateUserMutation</code>.
+        </li>
+        <li>
+          <code>no-mutation-literal-arguments</code>: arguments in mutations
+          must be passed as variables.
+        </li>
+        <li>
+          <code>max-selection-depth</code>: fields must not be nested deeper
+          than <code>maxDepth</code> (default: 10). Root fields are at depth 1,
+          and fragment spreads are followed.
+        </li>
+        <li>
+          <code>require-id-field</code>: a field named <code>fieldName</code>{" "}
+          (default: <code>id</code>) must be selected whenever the selected
+          type has one.
+        </li>
+      </ul>
+      <p>
+        The following rules are available for schemas. They only apply to
+        schema files written in the GraphQL language; schemas loaded from an
+        introspection result are not linted.
+      </p>
+      <ul>
+        <li>
+          <code>require-description</code>: types, fields, input fields and
+          arguments must have a description.
+        </li>
+        <li>
+          <code>enum-values-upper-case</code>: enum values must be{" "}
+          <code>UPPER_CASE</code>.
+        </li>
+        <li>
+          <code>input-type-suffix</code>: names of input types must end with{" "}
+          <code>Input</code>.
+        </li>
+        <li>
+          <code>no-nullable-list-of-nullable-items</code>: a nullable list type
+          such as <code>[String]</code> must not have nullable items.
+        </li>
+        <li>
+          <code>deprecation-reason</code>: <code>@deprecated</code> must be
+          given a non-empty <code>reason</code>.
+        </li>
+        <li>
+          <code>relay-connection</code>: object types whose name ends with{" "}
+          <code>Connection</code> must follow the{" "}
+          <a href="https://relay.dev/graphql/connections.htm">
+            Relay Cursor Connections Specification
+          </a>
+          . They must have <code>edges</code> of a list of edge types and{" "}
+          <code>pageInfo: PageInfo!</code>. Edge types must have{" "}
+          <code>node</code> and a scalar <code>cursor</code>, and{" "}
+          <code>PageInfo</code> must have <code>hasPreviousPage</code>,{" "}
+          <code>hasNextPage</code>, <code>startCursor</code> and{" "}
+          <code>endCursor</code>.
+        </li>
+      </ul>
+
+      <h3 id="customScalars">customScalars</h3>
+      <p>
+        Declares which literals each custom scalar accepts. The{" "}
+        <code>check</code> command and the language server report values
+        passed to a configured scalar that are not one of the listed kinds.
+        Values passed to custom scalars not listed here are not checked.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    customScalars:
+      DateTime:
+        literals: [string]
+        pattern: '\\d{4}-\\d{2}-\\d{2}T.+'
+      JSON:
+        literals: [string, int, float, boolean, list, object]`}
+      </Highlight>
+      <ul>
+        <li>
+          <code>literals</code>: kinds of literals the scalar accepts. Each is
+          one of <code>int</code>, <code>float</code>, <code>string</code>,{" "}
+          <code>boolean</code>, <code>enum</code>, <code>list</code> and{" "}
+          <code>object</code>. <code>null</code> and variables are always
+          allowed.
+        </li>
+        <li>
+          <code>pattern</code>: optional regular expression that string literals
+          must match as a whole.
+        </li>
+      </ul>
+      <p>
+        Built-in scalars are always checked as defined in the GraphQL
+        specification. For example, <code>Int</code> must fit in a 32-bit
+        signed integer, and <code>Float</code> and <code>ID</code> also accept
+        integer literals.
+      </p>
+
+      <h3 id="embedded">embedded</h3>
+      <p>
+        Operations can also be written in tagged templates such as{" "}
+        <code>{"gql`...`"}</code> in TypeScript and JavaScript files (
+        <code>.ts</code>, <code>.tsx</code>, <code>.js</code>,{" "}
+        <code>.jsx</code> and their <code>.m*</code> and <code>.c*</code>{" "}
+        variants) matched by <code>documents</code>. All tagged templates in one
+        file are treated as one document. Interpolations such as{" "}
+        <code>{"${Fragment}"}</code> are ignored, so fragments are referenced by
+        their names as in <code>.graphql</code> files.
+      </p>
+      <Highlight language="yaml">
+        {`documents: "./app/**/*.tsx"
+extensions:
+  nitrogql:
+    embedded:
+      tagNames: [gql, graphql]
+      moduleSources: ["@apollo/client", "graphql-tag"]`}
+      </Highlight>
+      <ul>
+        <li>
+          <code>tagNames</code>: names of tags whose templates are GraphQL
+          documents (default: <code>[gql, graphql]</code>).
+        </li>
+        <li>
+          <code>moduleSources</code>: modules from which tags must be imported
+          (default: none). If specified, a template is only recognized when its
+          tag is imported from one of these modules, possibly under another
+          name. Otherwise, tags are recognized by name.
+        </li>
+      </ul>
+      <p>
+        The <code>check</code> command reports errors at their locations in the
+        TypeScript files. The <code>format</code> command and the language
+        server skip these files.
+      </p>
+
+      <h3 id="format">format</h3>
+      <p>
+        Options for the <code>format</code> command. All options are optional.
+      </p>
+      <Highlight language="yaml">
+        {`extensions:
+  nitrogql:
+    format:
+      indentWidth: 2
+      lineWidth: 80
+      sortDefinitions: false`}
+      </Highlight>
+      <ul>
+        <li>
+          <code>indentWidth</code>: number of spaces for one level of
+          indentation (default: 2).
+        </li>
+        <li>
+          <code>lineWidth</code>: width of lines that the formatter tries to
+          fit the output in (default: 80). Arguments, lists, objects, union
+          members and directive locations are broken into multiple lines when
+          they do not fit.
+        </li>
+        <li>
+          <code>sortDefinitions</code>: if true, top-level definitions are
+          sorted by kind and then by name (default: false). Comments before a
+          definition move together with the definition. Otherwise, the original
+          order is kept.
+        </li>
+      </ul>
+      <p>
+        Block strings are always printed with the opening and closing quotes on
+        their own lines and the content indented to match the definition.
+      </p>
     </main>
   );
 }